@@ -0,0 +1,487 @@
+
+use std::ops::{Add, Div, Mul, Neg, Not, Rem, Sub};
+
+use rincon_core::api::types::Value;
+
+use query::QueryBuilder;
+use render::{is_function_name, Renderer};
+
+/// Constructs an expression that references the variable with the given name.
+pub fn var<N>(name: N) -> Expr
+    where N: Into<String>
+{
+    Expr::Variable(name.into())
+}
+
+/// Constructs an expression that references the collection with the given
+/// name.
+///
+/// The name of the collection is passed to the server as a collection bind
+/// parameter.
+pub fn collection<N>(name: N) -> Expr
+    where N: Into<String>
+{
+    Expr::Collection(name.into())
+}
+
+/// Constructs a literal expression from the given value.
+///
+/// Literal values are never written into the query string. They are passed
+/// to the server as bind parameters.
+pub fn lit<V>(value: V) -> Expr
+    where V: Into<Value>
+{
+    Expr::Literal(value.into())
+}
+
+/// Constructs the `null` literal.
+pub fn null() -> Expr {
+    Expr::Null
+}
+
+/// Constructs a call of the AQL function with the given name.
+///
+/// The name is written into the query string as is, so it must consist of
+/// letters, digits and underscores, optionally separated into namespaces by
+/// `::`, and must not start with a digit.
+///
+/// # Panics
+///
+/// Panics if the name is not a valid function name.
+pub fn function<N, A>(name: N, arguments: A) -> Expr
+    where N: Into<String>, A: IntoIterator<Item=Expr>
+{
+    let name = name.into();
+    assert!(is_function_name(&name), "invalid AQL function name: {:?}", name);
+    Expr::Function(name, arguments.into_iter().collect())
+}
+
+/// Constructs an array expression from the given elements.
+pub fn array<A>(elements: A) -> Expr
+    where A: IntoIterator<Item=Expr>
+{
+    Expr::Array(elements.into_iter().collect())
+}
+
+/// Constructs an object expression from the given attributes.
+pub fn object<N, A>(attributes: A) -> Expr
+    where N: Into<String>, A: IntoIterator<Item=(N, Expr)>
+{
+    Expr::Object(attributes.into_iter().map(|(name, value)| (name.into(), value)).collect())
+}
+
+/// Constructs a range expression `from..to`.
+pub fn range<F, T>(from: F, to: T) -> Expr
+    where F: Into<Expr>, T: Into<Expr>
+{
+    Expr::Range(Box::new(from.into()), Box::new(to.into()))
+}
+
+/// Constructs a subquery expression.
+pub fn subquery(query: QueryBuilder) -> Expr {
+    Expr::Subquery(Box::new(query))
+}
+
+/// Defines the unary operators of AQL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOperator {
+    /// Logical negation `NOT`
+    Not,
+    /// Arithmetic negation `-`
+    Negate,
+}
+
+impl UnaryOperator {
+    /// Returns the AQL representation of this operator.
+    pub fn as_str(&self) -> &str {
+        match *self {
+            UnaryOperator::Not => "NOT ",
+            UnaryOperator::Negate => "-",
+        }
+    }
+}
+
+/// Defines the binary operators of AQL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOperator {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    In,
+    NotIn,
+    Like,
+    RegexMatch,
+    And,
+    Or,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulo,
+}
+
+impl BinaryOperator {
+    /// Returns the AQL representation of this operator.
+    pub fn as_str(&self) -> &str {
+        match *self {
+            BinaryOperator::Equal => "==",
+            BinaryOperator::NotEqual => "!=",
+            BinaryOperator::Less => "<",
+            BinaryOperator::LessOrEqual => "<=",
+            BinaryOperator::Greater => ">",
+            BinaryOperator::GreaterOrEqual => ">=",
+            BinaryOperator::In => "IN",
+            BinaryOperator::NotIn => "NOT IN",
+            BinaryOperator::Like => "LIKE",
+            BinaryOperator::RegexMatch => "=~",
+            BinaryOperator::And => "AND",
+            BinaryOperator::Or => "OR",
+            BinaryOperator::Add => "+",
+            BinaryOperator::Subtract => "-",
+            BinaryOperator::Multiply => "*",
+            BinaryOperator::Divide => "/",
+            BinaryOperator::Modulo => "%",
+        }
+    }
+}
+
+/// An AQL expression.
+///
+/// Expressions are composed by the functions of this module and the methods
+/// of this type. Literal values contained in an expression are rendered as
+/// bind parameters.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// The `null` literal
+    Null,
+    /// A literal value that is passed as bind parameter
+    Literal(Value),
+    /// A reference to a variable
+    Variable(String),
+    /// A reference to a collection that is passed as collection bind
+    /// parameter
+    Collection(String),
+    /// Access to an attribute of an object
+    Attribute(Box<Expr>, String),
+    /// Access to an element of an array or an attribute by a computed name
+    Index(Box<Expr>, Box<Expr>),
+    /// An unary operation
+    Unary(UnaryOperator, Box<Expr>),
+    /// A binary operation
+    Binary(Box<Expr>, BinaryOperator, Box<Expr>),
+    /// The ternary operator `condition ? then : else`
+    Ternary(Box<Expr>, Box<Expr>, Box<Expr>),
+    /// A call of an AQL function. Rendering panics if the name is not a
+    /// valid function name.
+    Function(String, Vec<Expr>),
+    /// An array of expressions
+    Array(Vec<Expr>),
+    /// An object with named attributes
+    Object(Vec<(String, Expr)>),
+    /// A range of integers `from..to`
+    Range(Box<Expr>, Box<Expr>),
+    /// A subquery
+    Subquery(Box<QueryBuilder>),
+}
+
+impl Expr {
+    /// Constructs an expression that accesses the attribute with the given
+    /// name of this expression.
+    pub fn attr<N>(self, name: N) -> Self
+        where N: Into<String>
+    {
+        Expr::Attribute(Box::new(self), name.into())
+    }
+
+    /// Constructs an expression that accesses the element at the given index
+    /// of this expression.
+    pub fn at<I>(self, index: I) -> Self
+        where I: Into<Expr>
+    {
+        Expr::Index(Box::new(self), Box::new(index.into()))
+    }
+
+    /// Constructs a ternary expression with this expression as condition.
+    pub fn then_else<T, E>(self, then: T, otherwise: E) -> Self
+        where T: Into<Expr>, E: Into<Expr>
+    {
+        Expr::Ternary(Box::new(self), Box::new(then.into()), Box::new(otherwise.into()))
+    }
+
+    fn binary<R>(self, operator: BinaryOperator, right: R) -> Self
+        where R: Into<Expr>
+    {
+        Expr::Binary(Box::new(self), operator, Box::new(right.into()))
+    }
+
+    /// Constructs the comparison `self == other`.
+    pub fn equals<R>(self, other: R) -> Self
+        where R: Into<Expr>
+    {
+        self.binary(BinaryOperator::Equal, other)
+    }
+
+    /// Constructs the comparison `self != other`.
+    pub fn not_equals<R>(self, other: R) -> Self
+        where R: Into<Expr>
+    {
+        self.binary(BinaryOperator::NotEqual, other)
+    }
+
+    /// Constructs the comparison `self < other`.
+    pub fn less_than<R>(self, other: R) -> Self
+        where R: Into<Expr>
+    {
+        self.binary(BinaryOperator::Less, other)
+    }
+
+    /// Constructs the comparison `self <= other`.
+    pub fn less_or_equal<R>(self, other: R) -> Self
+        where R: Into<Expr>
+    {
+        self.binary(BinaryOperator::LessOrEqual, other)
+    }
+
+    /// Constructs the comparison `self > other`.
+    pub fn greater_than<R>(self, other: R) -> Self
+        where R: Into<Expr>
+    {
+        self.binary(BinaryOperator::Greater, other)
+    }
+
+    /// Constructs the comparison `self >= other`.
+    pub fn greater_or_equal<R>(self, other: R) -> Self
+        where R: Into<Expr>
+    {
+        self.binary(BinaryOperator::GreaterOrEqual, other)
+    }
+
+    /// Constructs the comparison `self IN other`.
+    pub fn in_<R>(self, other: R) -> Self
+        where R: Into<Expr>
+    {
+        self.binary(BinaryOperator::In, other)
+    }
+
+    /// Constructs the comparison `self NOT IN other`.
+    pub fn not_in<R>(self, other: R) -> Self
+        where R: Into<Expr>
+    {
+        self.binary(BinaryOperator::NotIn, other)
+    }
+
+    /// Constructs the comparison `self LIKE pattern`.
+    pub fn like<R>(self, pattern: R) -> Self
+        where R: Into<Expr>
+    {
+        self.binary(BinaryOperator::Like, pattern)
+    }
+
+    /// Constructs the comparison `self =~ regex`.
+    pub fn matches<R>(self, regex: R) -> Self
+        where R: Into<Expr>
+    {
+        self.binary(BinaryOperator::RegexMatch, regex)
+    }
+
+    /// Constructs the logical conjunction `self AND other`.
+    pub fn and<R>(self, other: R) -> Self
+        where R: Into<Expr>
+    {
+        self.binary(BinaryOperator::And, other)
+    }
+
+    /// Constructs the logical disjunction `self OR other`.
+    pub fn or<R>(self, other: R) -> Self
+        where R: Into<Expr>
+    {
+        self.binary(BinaryOperator::Or, other)
+    }
+
+    pub(crate) fn render(&self, renderer: &mut Renderer) {
+        match *self {
+            Expr::Null => renderer.write("null"),
+            Expr::Literal(ref value) => renderer.write_value(value.clone()),
+            Expr::Variable(ref name) => renderer.write_name(name),
+            Expr::Collection(ref name) => renderer.write_collection(name),
+            Expr::Attribute(ref expr, ref name) => {
+                render_operand(expr, renderer);
+                renderer.write(".");
+                renderer.write_name(name);
+            },
+            Expr::Index(ref expr, ref index) => {
+                render_operand(expr, renderer);
+                renderer.write("[");
+                index.render(renderer);
+                renderer.write("]");
+            },
+            Expr::Unary(ref operator, ref expr) => {
+                renderer.write(operator.as_str());
+                render_operand(expr, renderer);
+            },
+            Expr::Binary(ref left, ref operator, ref right) => {
+                render_operand(left, renderer);
+                renderer.write(" ");
+                renderer.write(operator.as_str());
+                renderer.write(" ");
+                render_operand(right, renderer);
+            },
+            Expr::Ternary(ref condition, ref then, ref otherwise) => {
+                render_operand(condition, renderer);
+                renderer.write(" ? ");
+                render_operand(then, renderer);
+                renderer.write(" : ");
+                render_operand(otherwise, renderer);
+            },
+            Expr::Function(ref name, ref arguments) => {
+                renderer.write_function_name(name);
+                renderer.write("(");
+                render_list(arguments, renderer);
+                renderer.write(")");
+            },
+            Expr::Array(ref elements) => {
+                renderer.write("[");
+                render_list(elements, renderer);
+                renderer.write("]");
+            },
+            Expr::Object(ref attributes) => {
+                renderer.write("{ ");
+                for (index, (name, value)) in attributes.iter().enumerate() {
+                    if index > 0 {
+                        renderer.write(", ");
+                    }
+                    renderer.write_object_key(name);
+                    renderer.write(": ");
+                    value.render(renderer);
+                }
+                renderer.write(" }");
+            },
+            Expr::Range(ref from, ref to) => {
+                render_operand(from, renderer);
+                renderer.write("..");
+                render_operand(to, renderer);
+            },
+            Expr::Subquery(ref query) => {
+                renderer.write("(");
+                query.render(renderer);
+                renderer.write(")");
+            },
+        }
+    }
+}
+
+impl Not for Expr {
+    type Output = Expr;
+
+    fn not(self) -> Self::Output {
+        Expr::Unary(UnaryOperator::Not, Box::new(self))
+    }
+}
+
+impl Neg for Expr {
+    type Output = Expr;
+
+    fn neg(self) -> Self::Output {
+        Expr::Unary(UnaryOperator::Negate, Box::new(self))
+    }
+}
+
+macro_rules! impl_binary_operator {
+    ( $( $op_trait:ident, $op_fn:ident, $operator:expr );* ) => {
+        $(
+            impl<R> $op_trait<R> for Expr
+                where R: Into<Expr>
+            {
+                type Output = Expr;
+
+                fn $op_fn(self, other: R) -> Self::Output {
+                    self.binary($operator, other)
+                }
+            }
+        )*
+    }
+}
+
+impl_binary_operator!(
+    Add, add, BinaryOperator::Add;
+    Sub, sub, BinaryOperator::Subtract;
+    Mul, mul, BinaryOperator::Multiply;
+    Div, div, BinaryOperator::Divide;
+    Rem, rem, BinaryOperator::Modulo
+);
+
+/// Renders an operand of an operator and puts it into parenthesis if it is
+/// an operation itself.
+fn render_operand(expr: &Expr, renderer: &mut Renderer) {
+    match *expr {
+        Expr::Unary(..) | Expr::Binary(..) | Expr::Ternary(..) | Expr::Range(..) => {
+            renderer.write("(");
+            expr.render(renderer);
+            renderer.write(")");
+        },
+        _ => expr.render(renderer),
+    }
+}
+
+fn render_list(exprs: &[Expr], renderer: &mut Renderer) {
+    for (index, expr) in exprs.iter().enumerate() {
+        if index > 0 {
+            renderer.write(", ");
+        }
+        expr.render(renderer);
+    }
+}
+
+macro_rules! impl_from_literal {
+    ( $( $ty:ty ),* ) => {
+        $(
+            impl From<$ty> for Expr {
+                fn from(value: $ty) -> Self {
+                    Expr::Literal(value.into())
+                }
+            }
+        )*
+    }
+}
+
+impl_from_literal!(String, bool, f64, f32, isize, i64, i32, i16, i8, usize, u64, u32, u16, u8,
+    Vec<String>, Vec<bool>, Vec<f64>, Vec<f32>, Vec<isize>, Vec<i64>, Vec<i32>, Vec<i16>,
+    Vec<i8>, Vec<usize>, Vec<u64>, Vec<u32>, Vec<u16>, Vec<u8>);
+
+impl From<&str> for Expr {
+    fn from(value: &str) -> Self {
+        Expr::Literal(value.into())
+    }
+}
+
+impl From<Vec<&str>> for Expr {
+    fn from(value: Vec<&str>) -> Self {
+        Expr::Literal(value.into())
+    }
+}
+
+impl From<Value> for Expr {
+    fn from(value: Value) -> Self {
+        Expr::Literal(value)
+    }
+}
+
+impl<T> From<Option<T>> for Expr
+    where T: Into<Expr>
+{
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => value.into(),
+            None => Expr::Null,
+        }
+    }
+}
+
+impl From<QueryBuilder> for Expr {
+    fn from(query: QueryBuilder) -> Self {
+        subquery(query)
+    }
+}
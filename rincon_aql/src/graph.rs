@@ -0,0 +1,462 @@
+//! Graph traversals and shortest path searches of AQL.
+//!
+//! A `Traversal` renders into `FOR v, e, p IN min..max OUTBOUND start GRAPH g`,
+//! a `ShortestPath` into `FOR v, e IN OUTBOUND SHORTEST_PATH start TO target
+//! GRAPH g` and a `KShortestPaths` into `FOR p IN OUTBOUND K_SHORTEST_PATHS
+//! start TO target GRAPH g`. The paths returned by a traversal or a k shortest
+//! paths search can be deserialized into a `Path<V, E>`.
+
+use rincon_client::document::types::DocumentId;
+use rincon_client::graph::types::{EdgeDefinition, Graph};
+pub use rincon_client::graph::types::{Direction, Path, TraversalStrategy, Uniqueness};
+
+use expression::Expr;
+use render::Renderer;
+
+const DEFAULT_VERTEX_VARIABLE: &str = "v";
+const DEFAULT_EDGE_VARIABLE: &str = "e";
+const DEFAULT_PATH_VARIABLE: &str = "p";
+
+/// Constructs a reference to the named graph with the given name.
+pub fn named_graph<N>(name: N) -> GraphSource
+    where N: Into<String>
+{
+    GraphSource::Named(name.into())
+}
+
+/// Constructs a list of edge collections that are followed in the direction
+/// of the traversal or search.
+pub fn edge_collections<N, I>(names: I) -> GraphSource
+    where N: Into<String>, I: IntoIterator<Item=N>
+{
+    GraphSource::EdgeCollections(names.into_iter().map(|name| (None, name.into())).collect())
+}
+
+/// Defines the edges that are followed by a traversal or a shortest path
+/// search.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraphSource {
+    /// The edges of the named graph with the given name.
+    Named(String),
+    /// The edges of the given edge collections. A direction given for an edge
+    /// collection overrides the direction of the traversal or search.
+    EdgeCollections(Vec<(Option<Direction>, String)>),
+}
+
+impl GraphSource {
+    /// Adds an edge collection that is followed in the given direction.
+    ///
+    /// A reference to a named graph is turned into an empty list of edge
+    /// collections first.
+    pub fn with_edge_collection<N>(self, direction: Direction, name: N) -> Self
+        where N: Into<String>
+    {
+        let mut collections = match self {
+            GraphSource::Named(_) => Vec::new(),
+            GraphSource::EdgeCollections(collections) => collections,
+        };
+        collections.push((Some(direction), name.into()));
+        GraphSource::EdgeCollections(collections)
+    }
+
+    fn render(&self, renderer: &mut Renderer) {
+        match *self {
+            GraphSource::Named(ref name) => {
+                renderer.write("GRAPH ");
+                renderer.write_value(name.to_owned().into());
+            },
+            GraphSource::EdgeCollections(ref collections) => {
+                for (index, (direction, name)) in collections.iter().enumerate() {
+                    if index > 0 {
+                        renderer.write(", ");
+                    }
+                    if let Some(direction) = *direction {
+                        renderer.write(direction_keyword(direction));
+                        renderer.write(" ");
+                    }
+                    renderer.write_collection(name);
+                }
+            },
+        }
+    }
+}
+
+impl<'a> From<&'a Graph> for GraphSource {
+    fn from(graph: &'a Graph) -> Self {
+        named_graph(graph.name())
+    }
+}
+
+impl<'a> From<&'a EdgeDefinition> for GraphSource {
+    fn from(edge_definition: &'a EdgeDefinition) -> Self {
+        edge_collections(vec![edge_definition.collection()])
+    }
+}
+
+impl<'a> From<&'a [EdgeDefinition]> for GraphSource {
+    fn from(edge_definitions: &'a [EdgeDefinition]) -> Self {
+        edge_collections(edge_definitions.iter().map(EdgeDefinition::collection))
+    }
+}
+
+impl From<DocumentId> for Expr {
+    fn from(document_id: DocumentId) -> Self {
+        Expr::Literal(document_id.into_string().into())
+    }
+}
+
+impl<'a> From<&'a DocumentId> for Expr {
+    fn from(document_id: &'a DocumentId) -> Self {
+        Expr::Literal(document_id.to_string().into())
+    }
+}
+
+/// A graph traversal of the form `FOR v, e, p IN min..max OUTBOUND start
+/// GRAPH g`.
+///
+/// The vertex, edge and path variables default to `v`, `e` and `p`.
+///
+/// # Example
+///
+/// ```
+/// # extern crate rincon_aql;
+/// # use rincon_aql::*;
+/// # fn main() {
+/// let query = QueryBuilder::new()
+///     .for_traversal(Traversal::new(Direction::Outbound, "persons/alice", named_graph("social"))
+///         .with_depth(1, 3)
+///         .with_prune(var("v").attr("age").less_than(18))
+///         .with_unique_vertices(Uniqueness::Global)
+///         .with_strategy(TraversalStrategy::BreadthFirst))
+///     .return_(var("p"))
+///     .build();
+///
+/// assert_eq!("FOR v, e, p IN 1..3 OUTBOUND @value0 GRAPH @value1 PRUNE v.age < @value2 \
+///     OPTIONS { bfs: true, uniqueVertices: \"global\" } RETURN p", query.str());
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Traversal {
+    vertex: String,
+    edge: String,
+    path: String,
+    depth: Option<(u32, u32)>,
+    direction: Direction,
+    start: Expr,
+    graph: GraphSource,
+    prune: Option<Expr>,
+    strategy: Option<TraversalStrategy>,
+    unique_vertices: Option<Uniqueness>,
+    unique_edges: Option<Uniqueness>,
+}
+
+impl Traversal {
+    /// Constructs a new `Traversal` that starts at the given vertex and
+    /// follows the edges of the given graph in the given direction.
+    pub fn new<S, G>(direction: Direction, start: S, graph: G) -> Self
+        where S: Into<Expr>, G: Into<GraphSource>
+    {
+        Traversal {
+            vertex: DEFAULT_VERTEX_VARIABLE.to_owned(),
+            edge: DEFAULT_EDGE_VARIABLE.to_owned(),
+            path: DEFAULT_PATH_VARIABLE.to_owned(),
+            depth: None,
+            direction,
+            start: start.into(),
+            graph: graph.into(),
+            prune: None,
+            strategy: None,
+            unique_vertices: None,
+            unique_edges: None,
+        }
+    }
+
+    /// Sets the names of the vertex, edge and path variables.
+    pub fn with_variables<V, E, P>(mut self, vertex: V, edge: E, path: P) -> Self
+        where V: Into<String>, E: Into<String>, P: Into<String>
+    {
+        self.vertex = vertex.into();
+        self.edge = edge.into();
+        self.path = path.into();
+        self
+    }
+
+    /// Sets the minimal and maximal depth of the traversal.
+    ///
+    /// Without a depth the server traverses exactly one step.
+    pub fn with_depth(mut self, min: u32, max: u32) -> Self {
+        self.depth = Some((min, max));
+        self
+    }
+
+    /// Sets the condition on which the traversal stops to follow a path.
+    ///
+    /// The vertex, edge and path on which the condition is met are still
+    /// part of the result.
+    pub fn with_prune<E>(mut self, condition: E) -> Self
+        where E: Into<Expr>
+    {
+        self.prune = Some(condition.into());
+        self
+    }
+
+    /// Sets whether the traversal follows the paths depth first or breadth
+    /// first.
+    pub fn with_strategy(mut self, strategy: TraversalStrategy) -> Self {
+        self.strategy = Some(strategy);
+        self
+    }
+
+    /// Sets the uniqueness rule for the vertices of the traversal.
+    pub fn with_unique_vertices(mut self, uniqueness: Uniqueness) -> Self {
+        self.unique_vertices = Some(uniqueness);
+        self
+    }
+
+    /// Sets the uniqueness rule for the edges of the traversal.
+    ///
+    /// The server does not support global uniqueness for edges.
+    pub fn with_unique_edges(mut self, uniqueness: Uniqueness) -> Self {
+        self.unique_edges = Some(uniqueness);
+        self
+    }
+
+    pub(crate) fn render(&self, renderer: &mut Renderer) {
+        renderer.write("FOR ");
+        renderer.write_name(&self.vertex);
+        renderer.write(", ");
+        renderer.write_name(&self.edge);
+        renderer.write(", ");
+        renderer.write_name(&self.path);
+        renderer.write(" IN ");
+        if let Some((min, max)) = self.depth {
+            renderer.write(&format!("{}..{} ", min, max));
+        }
+        renderer.write(direction_keyword(self.direction));
+        renderer.write(" ");
+        self.start.render(renderer);
+        renderer.write(" ");
+        self.graph.render(renderer);
+        if let Some(ref prune) = self.prune {
+            renderer.write(" PRUNE ");
+            prune.render(renderer);
+        }
+        let mut options = Vec::new();
+        if let Some(strategy) = self.strategy {
+            options.push(("bfs", OptionValue::Constant(match strategy {
+                TraversalStrategy::DepthFirst => "false",
+                TraversalStrategy::BreadthFirst => "true",
+            })));
+        }
+        if let Some(uniqueness) = self.unique_vertices {
+            options.push(("uniqueVertices", OptionValue::Constant(uniqueness_constant(uniqueness))));
+        }
+        if let Some(uniqueness) = self.unique_edges {
+            options.push(("uniqueEdges", OptionValue::Constant(uniqueness_constant(uniqueness))));
+        }
+        render_options(&options, renderer);
+    }
+}
+
+/// A shortest path search of the form `FOR v, e IN OUTBOUND SHORTEST_PATH
+/// start TO target GRAPH g`.
+///
+/// The search yields one row per vertex on the shortest path. The edge of
+/// the first row is `null`. The vertex and edge variables default to `v` and
+/// `e`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShortestPath {
+    vertex: String,
+    edge: String,
+    search: PathSearch,
+}
+
+impl ShortestPath {
+    /// Constructs a new `ShortestPath` search from the start vertex to the
+    /// target vertex, that follows the edges of the given graph in the given
+    /// direction.
+    pub fn new<S, T, G>(direction: Direction, start: S, target: T, graph: G) -> Self
+        where S: Into<Expr>, T: Into<Expr>, G: Into<GraphSource>
+    {
+        ShortestPath {
+            vertex: DEFAULT_VERTEX_VARIABLE.to_owned(),
+            edge: DEFAULT_EDGE_VARIABLE.to_owned(),
+            search: PathSearch::new(direction, start.into(), target.into(), graph.into()),
+        }
+    }
+
+    /// Sets the names of the vertex and edge variables.
+    pub fn with_variables<V, E>(mut self, vertex: V, edge: E) -> Self
+        where V: Into<String>, E: Into<String>
+    {
+        self.vertex = vertex.into();
+        self.edge = edge.into();
+        self
+    }
+
+    /// Sets the edge attribute that holds the weight of an edge.
+    pub fn with_weight_attribute<N>(mut self, name: N) -> Self
+        where N: Into<String>
+    {
+        self.search.weight_attribute = Some(name.into());
+        self
+    }
+
+    /// Sets the weight of edges that do not have the weight attribute.
+    pub fn with_default_weight(mut self, weight: f64) -> Self {
+        self.search.default_weight = Some(weight);
+        self
+    }
+
+    pub(crate) fn render(&self, renderer: &mut Renderer) {
+        renderer.write("FOR ");
+        renderer.write_name(&self.vertex);
+        renderer.write(", ");
+        renderer.write_name(&self.edge);
+        renderer.write(" IN ");
+        self.search.render("SHORTEST_PATH", renderer);
+    }
+}
+
+/// A search for the k shortest paths of the form `FOR p IN OUTBOUND
+/// K_SHORTEST_PATHS start TO target GRAPH g`.
+///
+/// The search yields the paths in the order of their length or weight. It
+/// should be combined with a `LIMIT` operation. The path variable defaults
+/// to `p`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KShortestPaths {
+    path: String,
+    search: PathSearch,
+}
+
+impl KShortestPaths {
+    /// Constructs a new `KShortestPaths` search from the start vertex to the
+    /// target vertex, that follows the edges of the given graph in the given
+    /// direction.
+    pub fn new<S, T, G>(direction: Direction, start: S, target: T, graph: G) -> Self
+        where S: Into<Expr>, T: Into<Expr>, G: Into<GraphSource>
+    {
+        KShortestPaths {
+            path: DEFAULT_PATH_VARIABLE.to_owned(),
+            search: PathSearch::new(direction, start.into(), target.into(), graph.into()),
+        }
+    }
+
+    /// Sets the name of the path variable.
+    pub fn with_variable<P>(mut self, path: P) -> Self
+        where P: Into<String>
+    {
+        self.path = path.into();
+        self
+    }
+
+    /// Sets the edge attribute that holds the weight of an edge.
+    pub fn with_weight_attribute<N>(mut self, name: N) -> Self
+        where N: Into<String>
+    {
+        self.search.weight_attribute = Some(name.into());
+        self
+    }
+
+    /// Sets the weight of edges that do not have the weight attribute.
+    pub fn with_default_weight(mut self, weight: f64) -> Self {
+        self.search.default_weight = Some(weight);
+        self
+    }
+
+    pub(crate) fn render(&self, renderer: &mut Renderer) {
+        renderer.write("FOR ");
+        renderer.write_name(&self.path);
+        renderer.write(" IN ");
+        self.search.render("K_SHORTEST_PATHS", renderer);
+    }
+}
+
+/// The common parts of the shortest path searches.
+#[derive(Debug, Clone, PartialEq)]
+struct PathSearch {
+    direction: Direction,
+    start: Expr,
+    target: Expr,
+    graph: GraphSource,
+    weight_attribute: Option<String>,
+    default_weight: Option<f64>,
+}
+
+impl PathSearch {
+    fn new(direction: Direction, start: Expr, target: Expr, graph: GraphSource) -> Self {
+        PathSearch {
+            direction,
+            start,
+            target,
+            graph,
+            weight_attribute: None,
+            default_weight: None,
+        }
+    }
+
+    fn render(&self, keyword: &str, renderer: &mut Renderer) {
+        renderer.write(direction_keyword(self.direction));
+        renderer.write(" ");
+        renderer.write(keyword);
+        renderer.write(" ");
+        self.start.render(renderer);
+        renderer.write(" TO ");
+        self.target.render(renderer);
+        renderer.write(" ");
+        self.graph.render(renderer);
+        let weight_attribute = self.weight_attribute.clone().map(Expr::from);
+        let default_weight = self.default_weight.map(Expr::from);
+        let mut options = Vec::new();
+        if let Some(ref weight_attribute) = weight_attribute {
+            options.push(("weightAttribute", OptionValue::Value(weight_attribute)));
+        }
+        if let Some(ref default_weight) = default_weight {
+            options.push(("defaultWeight", OptionValue::Value(default_weight)));
+        }
+        render_options(&options, renderer);
+    }
+}
+
+/// The value of an option. Constants are keywords of AQL while values are
+/// passed as bind parameters.
+enum OptionValue<'a> {
+    Constant(&'static str),
+    Value(&'a Expr),
+}
+
+fn render_options(options: &[(&str, OptionValue)], renderer: &mut Renderer) {
+    if options.is_empty() {
+        return;
+    }
+    renderer.write(" OPTIONS { ");
+    for (index, (name, value)) in options.iter().enumerate() {
+        if index > 0 {
+            renderer.write(", ");
+        }
+        renderer.write_object_key(name);
+        renderer.write(": ");
+        match *value {
+            OptionValue::Constant(constant) => renderer.write(constant),
+            OptionValue::Value(expr) => expr.render(renderer),
+        }
+    }
+    renderer.write(" }");
+}
+
+fn direction_keyword(direction: Direction) -> &'static str {
+    match direction {
+        Direction::Outbound => "OUTBOUND",
+        Direction::Inbound => "INBOUND",
+        Direction::Any => "ANY",
+    }
+}
+
+fn uniqueness_constant(uniqueness: Uniqueness) -> &'static str {
+    match uniqueness {
+        Uniqueness::None => "\"none\"",
+        Uniqueness::Global => "\"global\"",
+        Uniqueness::Path => "\"path\"",
+    }
+}
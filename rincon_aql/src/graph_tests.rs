@@ -0,0 +1,99 @@
+use rincon_client::document::types::DocumentId;
+use rincon_client::graph::types::EdgeDefinition;
+
+use super::expression::*;
+use super::graph::*;
+use super::query::*;
+
+#[test]
+fn build_traversal_query_with_default_variables() {
+    let query = QueryBuilder::new()
+        .for_traversal(Traversal::new(Direction::Inbound, DocumentId::new("persons", "alice"),
+            named_graph("social")))
+        .return_(var("v"))
+        .build();
+
+    assert_eq!("FOR v, e, p IN INBOUND @value0 GRAPH @value1 RETURN v", query.str());
+    assert_eq!(Some(&"persons/alice".to_owned()), query.parameter("value0"));
+    assert_eq!(Some(&"social".to_owned()), query.parameter("value1"));
+}
+
+#[test]
+fn build_traversal_query_with_depth_prune_and_options() {
+    let query = QueryBuilder::new()
+        .for_traversal(Traversal::new(Direction::Any, "persons/alice", named_graph("social"))
+            .with_variables("person", "knows", "path")
+            .with_depth(2, 5)
+            .with_prune(var("person").attr("name").equals("bob"))
+            .with_strategy(TraversalStrategy::DepthFirst)
+            .with_unique_vertices(Uniqueness::Path)
+            .with_unique_edges(Uniqueness::None))
+        .return_(var("path"))
+        .build();
+
+    assert_eq!("FOR person, knows, path IN 2..5 ANY @value0 GRAPH @value1 \
+        PRUNE person.name == @value2 \
+        OPTIONS { bfs: false, uniqueVertices: \"path\", uniqueEdges: \"none\" } RETURN path",
+        query.str());
+}
+
+#[test]
+fn build_traversal_query_over_edge_definitions() {
+    let edge_definitions = [
+        EdgeDefinition::new("knows", vec!["persons".to_owned()], vec!["persons".to_owned()]),
+        EdgeDefinition::new("lives_in", vec!["persons".to_owned()], vec!["cities".to_owned()]),
+    ];
+    let query = QueryBuilder::new()
+        .for_traversal(Traversal::new(Direction::Outbound, "persons/alice", &edge_definitions[..])
+            .with_depth(1, 2))
+        .return_(var("v"))
+        .build();
+
+    assert_eq!("FOR v, e, p IN 1..2 OUTBOUND @value0 @@coll0, @@coll1 RETURN v", query.str());
+    assert_eq!(Some(&"knows".to_owned()), query.parameter("@coll0"));
+    assert_eq!(Some(&"lives_in".to_owned()), query.parameter("@coll1"));
+}
+
+#[test]
+fn build_traversal_query_with_directed_edge_collections() {
+    let query = QueryBuilder::new()
+        .for_traversal(Traversal::new(Direction::Outbound, "persons/alice",
+            edge_collections(vec!["knows"]).with_edge_collection(Direction::Inbound, "lives_in")))
+        .return_(var("v"))
+        .build();
+
+    assert_eq!("FOR v, e, p IN OUTBOUND @value0 @@coll0, INBOUND @@coll1 RETURN v", query.str());
+}
+
+#[test]
+fn build_shortest_path_query() {
+    let query = QueryBuilder::new()
+        .for_shortest_path(ShortestPath::new(Direction::Any,
+            DocumentId::new("cities", "berlin"), DocumentId::new("cities", "rome"),
+            edge_collections(vec!["routes"]))
+            .with_weight_attribute("distance")
+            .with_default_weight(1.0))
+        .return_(object(vec![("city", var("v").attr("name")), ("route", var("e"))]))
+        .build();
+
+    assert_eq!("FOR v, e IN ANY SHORTEST_PATH @value0 TO @value1 @@coll0 \
+        OPTIONS { weightAttribute: @value2, defaultWeight: @value3 } \
+        RETURN { city: v.name, route: e }", query.str());
+    assert_eq!(Some(&"cities/rome".to_owned()), query.parameter("value1"));
+    assert_eq!(Some(&"distance".to_owned()), query.parameter("value2"));
+    assert_eq!(Some(&1.0f64), query.parameter("value3"));
+}
+
+#[test]
+fn build_k_shortest_paths_query() {
+    let query = QueryBuilder::new()
+        .for_k_shortest_paths(KShortestPaths::new(Direction::Outbound,
+            "cities/berlin", "cities/rome", named_graph("routes"))
+            .with_variable("route"))
+        .limit(3)
+        .return_(var("route"))
+        .build();
+
+    assert_eq!("FOR route IN OUTBOUND K_SHORTEST_PATHS @value0 TO @value1 GRAPH @value2 \
+        LIMIT @value3 RETURN route", query.str());
+}
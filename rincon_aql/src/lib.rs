@@ -14,3 +14,18 @@
 )]
 
 extern crate rincon_core;
+extern crate rincon_client;
+
+mod render;
+
+pub mod expression;
+pub mod graph;
+#[cfg(test)]
+mod graph_tests;
+pub mod query;
+#[cfg(test)]
+mod query_tests;
+
+pub use expression::*;
+pub use graph::*;
+pub use query::*;
@@ -0,0 +1,496 @@
+
+use rincon_core::api::query::Query;
+
+use expression::Expr;
+use graph::{KShortestPaths, ShortestPath, Traversal};
+use render::Renderer;
+
+/// Defines the direction of a sort criterion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+impl SortDirection {
+    /// Returns the AQL representation of this sort direction.
+    pub fn as_str(&self) -> &str {
+        match *self {
+            SortDirection::Ascending => "ASC",
+            SortDirection::Descending => "DESC",
+        }
+    }
+}
+
+/// The `COLLECT` operation of a query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Collect {
+    groups: Vec<(String, Expr)>,
+    aggregates: Vec<(String, Expr)>,
+    into: Option<(String, Option<Expr>)>,
+    count_into: Option<String>,
+}
+
+impl Collect {
+    /// Constructs a new `Collect` operation without any groups.
+    ///
+    /// Without groups all documents are collected into one group.
+    pub fn new() -> Self {
+        Collect {
+            groups: Vec::new(),
+            aggregates: Vec::new(),
+            into: None,
+            count_into: None,
+        }
+    }
+
+    /// Adds a group criterion, that is assigned to the variable of the given
+    /// name.
+    pub fn group<N, E>(mut self, variable: N, expr: E) -> Self
+        where N: Into<String>, E: Into<Expr>
+    {
+        self.groups.push((variable.into(), expr.into()));
+        self
+    }
+
+    /// Adds an aggregation, that is assigned to the variable of the given
+    /// name.
+    pub fn aggregate<N, E>(mut self, variable: N, expr: E) -> Self
+        where N: Into<String>, E: Into<Expr>
+    {
+        self.aggregates.push((variable.into(), expr.into()));
+        self
+    }
+
+    /// Collects the documents of each group into the variable of the given
+    /// name.
+    pub fn into_group<N>(mut self, variable: N) -> Self
+        where N: Into<String>
+    {
+        self.into = Some((variable.into(), None));
+        self
+    }
+
+    /// Collects the result of the given projection for each document of a
+    /// group into the variable of the given name.
+    pub fn into_group_projection<N, E>(mut self, variable: N, projection: E) -> Self
+        where N: Into<String>, E: Into<Expr>
+    {
+        self.into = Some((variable.into(), Some(projection.into())));
+        self
+    }
+
+    /// Counts the documents of each group into the variable of the given
+    /// name.
+    pub fn with_count_into<N>(mut self, variable: N) -> Self
+        where N: Into<String>
+    {
+        self.count_into = Some(variable.into());
+        self
+    }
+
+    fn render(&self, renderer: &mut Renderer) {
+        renderer.write("COLLECT");
+        render_assignments(&self.groups, renderer);
+        if !self.aggregates.is_empty() {
+            renderer.write(" AGGREGATE");
+            render_assignments(&self.aggregates, renderer);
+        }
+        if let Some((ref variable, ref projection)) = self.into {
+            renderer.write(" INTO ");
+            renderer.write_name(variable);
+            if let Some(ref projection) = *projection {
+                renderer.write(" = ");
+                projection.render(renderer);
+            }
+        }
+        if let Some(ref variable) = self.count_into {
+            renderer.write(" WITH COUNT INTO ");
+            renderer.write_name(variable);
+        }
+    }
+}
+
+impl Default for Collect {
+    fn default() -> Self {
+        Collect::new()
+    }
+}
+
+/// The `UPSERT` operation of a query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Upsert {
+    search: Expr,
+    insert: Expr,
+    update: Expr,
+    replace: bool,
+}
+
+impl Upsert {
+    /// Constructs a new `Upsert` operation that updates the document found
+    /// by the search expression or inserts the given document if none is
+    /// found.
+    pub fn update<S, I, U>(search: S, insert: I, update: U) -> Self
+        where S: Into<Expr>, I: Into<Expr>, U: Into<Expr>
+    {
+        Upsert {
+            search: search.into(),
+            insert: insert.into(),
+            update: update.into(),
+            replace: false,
+        }
+    }
+
+    /// Constructs a new `Upsert` operation that replaces the document found
+    /// by the search expression or inserts the given document if none is
+    /// found.
+    pub fn replace<S, I, R>(search: S, insert: I, replace: R) -> Self
+        where S: Into<Expr>, I: Into<Expr>, R: Into<Expr>
+    {
+        Upsert {
+            search: search.into(),
+            insert: insert.into(),
+            update: replace.into(),
+            replace: true,
+        }
+    }
+}
+
+/// The operations a query is composed of.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operation {
+    For(String, Expr),
+    Traversal(Traversal),
+    ShortestPath(ShortestPath),
+    KShortestPaths(KShortestPaths),
+    Filter(Expr),
+    Sort(Vec<(Expr, SortDirection)>),
+    Limit(Option<Expr>, Expr),
+    Let(String, Expr),
+    Collect(Collect),
+    Return(bool, Expr),
+    Insert(Expr, String),
+    Update(Expr, Option<Expr>, String),
+    Replace(Expr, Option<Expr>, String),
+    Remove(Expr, String),
+    Upsert(Upsert, String),
+}
+
+/// A builder for AQL queries.
+///
+/// The operations of the query are added in the order they shall appear in
+/// the query. The `build` function renders the query into a `Query` that
+/// holds all literal values and collection names as bind parameters.
+///
+/// # Example
+///
+/// ```
+/// # extern crate rincon_aql;
+/// # use rincon_aql::*;
+/// # fn main() {
+/// let query = QueryBuilder::new()
+///     .for_in("u", collection("users"))
+///     .filter(var("u").attr("age").greater_or_equal(18))
+///     .sort(var("u").attr("name"), SortDirection::Ascending)
+///     .limit(10)
+///     .return_(var("u").attr("name"))
+///     .build();
+///
+/// assert_eq!("FOR u IN @@coll0 FILTER u.age >= @value0 SORT u.name ASC \
+///     LIMIT @value1 RETURN u.name", query.str());
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryBuilder {
+    operations: Vec<Operation>,
+}
+
+impl QueryBuilder {
+    /// Constructs a new and empty `QueryBuilder`.
+    pub fn new() -> Self {
+        QueryBuilder {
+            operations: Vec::new(),
+        }
+    }
+
+    /// Returns the operations added to this builder so far.
+    pub fn operations(&self) -> &[Operation] {
+        &self.operations
+    }
+
+    fn push(mut self, operation: Operation) -> Self {
+        self.operations.push(operation);
+        self
+    }
+
+    /// Adds a `FOR variable IN source` operation.
+    pub fn for_in<N, E>(self, variable: N, source: E) -> Self
+        where N: Into<String>, E: Into<Expr>
+    {
+        self.push(Operation::For(variable.into(), source.into()))
+    }
+
+    /// Adds a `FOR v, e, p IN min..max DIRECTION start GRAPH g` operation.
+    pub fn for_traversal(self, traversal: Traversal) -> Self {
+        self.push(Operation::Traversal(traversal))
+    }
+
+    /// Adds a `FOR v, e IN DIRECTION SHORTEST_PATH start TO target GRAPH g`
+    /// operation.
+    pub fn for_shortest_path(self, shortest_path: ShortestPath) -> Self {
+        self.push(Operation::ShortestPath(shortest_path))
+    }
+
+    /// Adds a `FOR p IN DIRECTION K_SHORTEST_PATHS start TO target GRAPH g`
+    /// operation.
+    pub fn for_k_shortest_paths(self, k_shortest_paths: KShortestPaths) -> Self {
+        self.push(Operation::KShortestPaths(k_shortest_paths))
+    }
+
+    /// Adds a `FILTER condition` operation.
+    pub fn filter<E>(self, condition: E) -> Self
+        where E: Into<Expr>
+    {
+        self.push(Operation::Filter(condition.into()))
+    }
+
+    /// Adds a sort criterion.
+    ///
+    /// Consecutive calls of this function add the criteria to the same
+    /// `SORT` operation.
+    pub fn sort<E>(mut self, expr: E, direction: SortDirection) -> Self
+        where E: Into<Expr>
+    {
+        if let Some(&mut Operation::Sort(ref mut criteria)) = self.operations.last_mut() {
+            criteria.push((expr.into(), direction));
+            return self;
+        }
+        self.push(Operation::Sort(vec![(expr.into(), direction)]))
+    }
+
+    /// Adds a `LIMIT count` operation.
+    pub fn limit<C>(self, count: C) -> Self
+        where C: Into<Expr>
+    {
+        self.push(Operation::Limit(None, count.into()))
+    }
+
+    /// Adds a `LIMIT offset, count` operation.
+    pub fn limit_with_offset<O, C>(self, offset: O, count: C) -> Self
+        where O: Into<Expr>, C: Into<Expr>
+    {
+        self.push(Operation::Limit(Some(offset.into()), count.into()))
+    }
+
+    /// Adds a `LET variable = expr` operation.
+    pub fn let_<N, E>(self, variable: N, expr: E) -> Self
+        where N: Into<String>, E: Into<Expr>
+    {
+        self.push(Operation::Let(variable.into(), expr.into()))
+    }
+
+    /// Adds a `COLLECT` operation.
+    pub fn collect(self, collect: Collect) -> Self {
+        self.push(Operation::Collect(collect))
+    }
+
+    /// Adds a `RETURN expr` operation.
+    pub fn return_<E>(self, expr: E) -> Self
+        where E: Into<Expr>
+    {
+        self.push(Operation::Return(false, expr.into()))
+    }
+
+    /// Adds a `RETURN DISTINCT expr` operation.
+    pub fn return_distinct<E>(self, expr: E) -> Self
+        where E: Into<Expr>
+    {
+        self.push(Operation::Return(true, expr.into()))
+    }
+
+    /// Adds an `INSERT document INTO collection` operation.
+    ///
+    /// The name of the collection is passed to the server as a collection
+    /// bind parameter, like in all other modifying operations.
+    pub fn insert<D, N>(self, document: D, collection: N) -> Self
+        where D: Into<Expr>, N: Into<String>
+    {
+        self.push(Operation::Insert(document.into(), collection.into()))
+    }
+
+    /// Adds an `UPDATE document IN collection` operation.
+    pub fn update<D, N>(self, document: D, collection: N) -> Self
+        where D: Into<Expr>, N: Into<String>
+    {
+        self.push(Operation::Update(document.into(), None, collection.into()))
+    }
+
+    /// Adds an `UPDATE key WITH changes IN collection` operation.
+    pub fn update_with<K, U, N>(self, key: K, changes: U, collection: N) -> Self
+        where K: Into<Expr>, U: Into<Expr>, N: Into<String>
+    {
+        self.push(Operation::Update(key.into(), Some(changes.into()), collection.into()))
+    }
+
+    /// Adds a `REPLACE document IN collection` operation.
+    pub fn replace<D, N>(self, document: D, collection: N) -> Self
+        where D: Into<Expr>, N: Into<String>
+    {
+        self.push(Operation::Replace(document.into(), None, collection.into()))
+    }
+
+    /// Adds a `REPLACE key WITH document IN collection` operation.
+    pub fn replace_with<K, D, N>(self, key: K, document: D, collection: N) -> Self
+        where K: Into<Expr>, D: Into<Expr>, N: Into<String>
+    {
+        self.push(Operation::Replace(key.into(), Some(document.into()), collection.into()))
+    }
+
+    /// Adds a `REMOVE key IN collection` operation.
+    pub fn remove<K, N>(self, key: K, collection: N) -> Self
+        where K: Into<Expr>, N: Into<String>
+    {
+        self.push(Operation::Remove(key.into(), collection.into()))
+    }
+
+    /// Adds an `UPSERT search INSERT document UPDATE|REPLACE document IN
+    /// collection` operation.
+    pub fn upsert<N>(self, upsert: Upsert, collection: N) -> Self
+        where N: Into<String>
+    {
+        self.push(Operation::Upsert(upsert, collection.into()))
+    }
+
+    /// Renders this builder into a `Query`.
+    pub fn build(&self) -> Query {
+        let mut renderer = Renderer::new();
+        self.render(&mut renderer);
+        renderer.into_query()
+    }
+
+    pub(crate) fn render(&self, renderer: &mut Renderer) {
+        for (index, operation) in self.operations.iter().enumerate() {
+            if index > 0 {
+                renderer.write(" ");
+            }
+            render_operation(operation, renderer);
+        }
+    }
+}
+
+impl Default for QueryBuilder {
+    fn default() -> Self {
+        QueryBuilder::new()
+    }
+}
+
+impl From<QueryBuilder> for Query {
+    fn from(builder: QueryBuilder) -> Self {
+        builder.build()
+    }
+}
+
+fn render_operation(operation: &Operation, renderer: &mut Renderer) {
+    match *operation {
+        Operation::For(ref variable, ref source) => {
+            renderer.write("FOR ");
+            renderer.write_name(variable);
+            renderer.write(" IN ");
+            source.render(renderer);
+        },
+        Operation::Traversal(ref traversal) => {
+            traversal.render(renderer);
+        },
+        Operation::ShortestPath(ref shortest_path) => {
+            shortest_path.render(renderer);
+        },
+        Operation::KShortestPaths(ref k_shortest_paths) => {
+            k_shortest_paths.render(renderer);
+        },
+        Operation::Filter(ref condition) => {
+            renderer.write("FILTER ");
+            condition.render(renderer);
+        },
+        Operation::Sort(ref criteria) => {
+            renderer.write("SORT ");
+            for (index, (expr, direction)) in criteria.iter().enumerate() {
+                if index > 0 {
+                    renderer.write(", ");
+                }
+                expr.render(renderer);
+                renderer.write(" ");
+                renderer.write(direction.as_str());
+            }
+        },
+        Operation::Limit(ref offset, ref count) => {
+            renderer.write("LIMIT ");
+            if let Some(ref offset) = *offset {
+                offset.render(renderer);
+                renderer.write(", ");
+            }
+            count.render(renderer);
+        },
+        Operation::Let(ref variable, ref expr) => {
+            renderer.write("LET ");
+            renderer.write_name(variable);
+            renderer.write(" = ");
+            expr.render(renderer);
+        },
+        Operation::Collect(ref collect) => {
+            collect.render(renderer);
+        },
+        Operation::Return(distinct, ref expr) => {
+            renderer.write(if distinct { "RETURN DISTINCT " } else { "RETURN " });
+            expr.render(renderer);
+        },
+        Operation::Insert(ref document, ref collection) => {
+            renderer.write("INSERT ");
+            document.render(renderer);
+            renderer.write(" INTO ");
+            renderer.write_collection(collection);
+        },
+        Operation::Update(ref key, ref changes, ref collection) => {
+            renderer.write("UPDATE ");
+            render_modification(key, changes, collection, renderer);
+        },
+        Operation::Replace(ref key, ref document, ref collection) => {
+            renderer.write("REPLACE ");
+            render_modification(key, document, collection, renderer);
+        },
+        Operation::Remove(ref key, ref collection) => {
+            renderer.write("REMOVE ");
+            key.render(renderer);
+            renderer.write(" IN ");
+            renderer.write_collection(collection);
+        },
+        Operation::Upsert(ref upsert, ref collection) => {
+            renderer.write("UPSERT ");
+            upsert.search.render(renderer);
+            renderer.write(" INSERT ");
+            upsert.insert.render(renderer);
+            renderer.write(if upsert.replace { " REPLACE " } else { " UPDATE " });
+            upsert.update.render(renderer);
+            renderer.write(" IN ");
+            renderer.write_collection(collection);
+        },
+    }
+}
+
+fn render_modification(key: &Expr, with: &Option<Expr>, collection: &str, renderer: &mut Renderer) {
+    key.render(renderer);
+    if let Some(ref with) = *with {
+        renderer.write(" WITH ");
+        with.render(renderer);
+    }
+    renderer.write(" IN ");
+    renderer.write_collection(collection);
+}
+
+fn render_assignments(assignments: &[(String, Expr)], renderer: &mut Renderer) {
+    for (index, (variable, expr)) in assignments.iter().enumerate() {
+        renderer.write(if index > 0 { ", " } else { " " });
+        renderer.write_name(variable);
+        renderer.write(" = ");
+        expr.render(renderer);
+    }
+}
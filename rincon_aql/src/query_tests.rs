@@ -0,0 +1,235 @@
+
+use rincon_core::api::types::Value;
+
+use super::expression::*;
+use super::query::*;
+
+#[test]
+fn build_for_filter_return_query_with_bind_parameters() {
+    let query = QueryBuilder::new()
+        .for_in("u", collection("users"))
+        .filter(var("u").attr("name").equals("simone"))
+        .return_(var("u"))
+        .build();
+
+    assert_eq!("FOR u IN @@coll0 FILTER u.name == @value0 RETURN u", query.str());
+    assert_eq!(Some(&"users".to_owned()), query.parameter("@coll0"));
+    assert_eq!(Some(&"simone".to_owned()), query.parameter("value0"));
+}
+
+#[test]
+fn build_query_with_sort_and_limit() {
+    let query = QueryBuilder::new()
+        .for_in("u", collection("users"))
+        .sort(var("u").attr("age"), SortDirection::Descending)
+        .sort(var("u").attr("name"), SortDirection::Ascending)
+        .limit_with_offset(20, 10)
+        .return_distinct(var("u").attr("name"))
+        .build();
+
+    assert_eq!("FOR u IN @@coll0 SORT u.age DESC, u.name ASC LIMIT @value0, @value1 \
+        RETURN DISTINCT u.name", query.str());
+    assert_eq!(Some(&20i32), query.parameter("value0"));
+    assert_eq!(Some(&10i32), query.parameter("value1"));
+}
+
+#[test]
+fn build_query_with_let_and_subquery() {
+    let query = QueryBuilder::new()
+        .for_in("u", collection("users"))
+        .let_("friends", QueryBuilder::new()
+            .for_in("f", collection("friends"))
+            .filter(var("f").attr("userId").equals(var("u").attr("_key")))
+            .return_(var("f").attr("name")))
+        .return_(object(vec![("user", var("u")), ("friends", var("friends"))]))
+        .build();
+
+    assert_eq!("FOR u IN @@coll0 LET friends = (FOR f IN @@coll1 FILTER f.userId == u._key \
+        RETURN f.name) RETURN { user: u, friends: friends }", query.str());
+    assert_eq!(Some(&"friends".to_owned()), query.parameter("@coll1"));
+}
+
+#[test]
+fn build_query_with_collect_aggregate_and_count() {
+    let query = QueryBuilder::new()
+        .for_in("u", collection("users"))
+        .collect(Collect::new()
+            .group("city", var("u").attr("city"))
+            .aggregate("maxAge", function("MAX", vec![var("u").attr("age")]))
+            .with_count_into("count"))
+        .return_(object(vec![("city", var("city")), ("maxAge", var("maxAge")), ("count", var("count"))]))
+        .build();
+
+    assert_eq!("FOR u IN @@coll0 COLLECT city = u.city AGGREGATE maxAge = MAX(u.age) \
+        WITH COUNT INTO count RETURN { city: city, maxAge: maxAge, count: count }", query.str());
+}
+
+#[test]
+fn build_query_calling_user_defined_function_in_namespace() {
+    let query = QueryBuilder::new()
+        .for_in("u", collection("users"))
+        .return_(function("MYFUNCTIONS::TEXT::CAPITALIZE_2", vec![var("u").attr("name")]))
+        .build();
+
+    assert_eq!("FOR u IN @@coll0 RETURN MYFUNCTIONS::TEXT::CAPITALIZE_2(u.name)", query.str());
+}
+
+#[test]
+#[should_panic(expected = "invalid AQL function name")]
+fn function_name_with_query_fragment_is_rejected() {
+    function("LENGTH(@@coll0)) REMOVE u IN users RETURN LENGTH", vec![]);
+}
+
+#[test]
+#[should_panic(expected = "invalid AQL function name")]
+fn function_name_with_empty_namespace_is_rejected() {
+    function("MYFUNCTIONS::::CAPITALIZE", vec![]);
+}
+
+#[test]
+#[should_panic(expected = "invalid AQL function name")]
+fn rendering_function_expression_with_invalid_name_panics() {
+    QueryBuilder::new()
+        .return_(Expr::Function("1_INVALID".to_owned(), vec![]))
+        .build();
+}
+
+#[test]
+fn build_query_with_collect_into_projection() {
+    let query = QueryBuilder::new()
+        .for_in("u", collection("users"))
+        .collect(Collect::new()
+            .group("city", var("u").attr("city"))
+            .into_group_projection("names", var("u").attr("name")))
+        .return_(var("names"))
+        .build();
+
+    assert_eq!("FOR u IN @@coll0 COLLECT city = u.city INTO names = u.name RETURN names",
+        query.str());
+}
+
+#[test]
+fn build_insert_query_with_object_literal() {
+    let query = QueryBuilder::new()
+        .insert(object(vec![("name", lit("simone")), ("age", lit(42))]), "users")
+        .return_(var("NEW"))
+        .build();
+
+    assert_eq!("INSERT { name: @value0, age: @value1 } INTO @@coll0 RETURN NEW", query.str());
+    assert_eq!(Some(&"simone".to_owned()), query.parameter("value0"));
+    assert_eq!(Some(&42i32), query.parameter("value1"));
+}
+
+#[test]
+fn build_insert_query_into_collection_given_by_name() {
+    let query = QueryBuilder::new()
+        .insert(object(vec![("name", lit("simone"))]), "customers")
+        .build();
+
+    assert_eq!("INSERT { name: @value0 } INTO @@coll0", query.str());
+    assert_eq!(Some(&"customers".to_owned()), query.parameter("@coll0"));
+}
+
+#[test]
+fn build_update_with_query_reusing_collection_parameter() {
+    let query = QueryBuilder::new()
+        .for_in("u", collection("users"))
+        .filter(var("u").attr("active").equals(false))
+        .update_with(var("u"), object(vec![("active", lit(true))]), "users")
+        .build();
+
+    assert_eq!("FOR u IN @@coll0 FILTER u.active == @value0 \
+        UPDATE u WITH { active: @value1 } IN @@coll0", query.str());
+    assert_eq!(Some(&false), query.parameter("value0"));
+    assert_eq!(Some(&true), query.parameter("value1"));
+}
+
+#[test]
+fn build_replace_and_remove_queries() {
+    let replace = QueryBuilder::new()
+        .replace_with("1234", object(vec![("name", lit("cesar"))]), "users")
+        .build();
+    assert_eq!("REPLACE @value0 WITH { name: @value1 } IN @@coll0", replace.str());
+
+    let remove = QueryBuilder::new()
+        .for_in("u", collection("users"))
+        .remove(var("u"), "users")
+        .return_(var("OLD"))
+        .build();
+    assert_eq!("FOR u IN @@coll0 REMOVE u IN @@coll0 RETURN OLD", remove.str());
+}
+
+#[test]
+fn build_upsert_query() {
+    let query = QueryBuilder::new()
+        .upsert(Upsert::update(
+            object(vec![("name", lit("simone"))]),
+            object(vec![("name", lit("simone")), ("logins", lit(1))]),
+            object(vec![("logins", var("OLD").attr("logins") + 1)]),
+        ), "users")
+        .build();
+
+    assert_eq!("UPSERT { name: @value0 } INSERT { name: @value1, logins: @value2 } \
+        UPDATE { logins: OLD.logins + @value3 } IN @@coll0", query.str());
+}
+
+#[test]
+fn nested_operations_are_put_into_parenthesis() {
+    let query = QueryBuilder::new()
+        .for_in("u", collection("users"))
+        .filter(var("u").attr("age").greater_or_equal(18).and(!var("u").attr("name").like("A%")))
+        .return_(var("u").attr("age") * 2 + 1)
+        .build();
+
+    assert_eq!("FOR u IN @@coll0 FILTER (u.age >= @value0) AND (NOT (u.name LIKE @value1)) \
+        RETURN (u.age * @value2) + @value3", query.str());
+}
+
+#[test]
+fn names_that_are_no_plain_identifiers_are_quoted() {
+    let query = QueryBuilder::new()
+        .for_in("doc", collection("docs"))
+        .return_(object(vec![("first name", var("doc").attr("first name").attr("filter"))]))
+        .build();
+
+    assert_eq!("FOR doc IN @@coll0 RETURN { \"first name\": doc.`first name`.`filter` }",
+        query.str());
+}
+
+#[test]
+fn variables_named_like_keywords_are_quoted() {
+    let query = QueryBuilder::new()
+        .for_in("prune", collection("docs"))
+        .let_("search", var("prune").attr("options"))
+        .return_(var("search"))
+        .build();
+
+    assert_eq!("FOR `prune` IN @@coll0 LET `search` = `prune`.`options` RETURN `search`",
+        query.str());
+}
+
+#[test]
+fn literal_strings_are_never_written_into_the_query_string() {
+    let malicious = "x\" RETURN 1 //";
+    let query = QueryBuilder::new()
+        .for_in("u", collection("users"))
+        .filter(var("u").attr("name").equals(malicious))
+        .return_(var("u"))
+        .build();
+
+    assert!(!query.str().contains(malicious));
+    assert_eq!(Some(&malicious.to_owned()), query.parameter("value0"));
+}
+
+#[test]
+fn build_query_with_range_array_and_null() {
+    let query = QueryBuilder::new()
+        .for_in("i", range(1, 10))
+        .filter(var("i").in_(vec![2, 4, 6]).or(var("i").equals(null())))
+        .return_(array(vec![var("i"), lit(Value::Bool(true))]))
+        .build();
+
+    assert_eq!("FOR i IN @value0..@value1 FILTER (i IN @value2) OR (i == null) \
+        RETURN [i, @value3]", query.str());
+    assert_eq!(Some(&vec![2i32, 4, 6]), query.parameter("value2"));
+}
@@ -0,0 +1,146 @@
+
+use rincon_core::api::query::Query;
+use rincon_core::api::types::Value;
+
+/// Reserved words of AQL that must be quoted when used as names.
+const KEYWORDS: &[&str] = &[
+    "AGGREGATE", "ALL", "AND", "ANY", "ASC", "COLLECT", "DESC", "DISTINCT", "FALSE", "FILTER",
+    "FOR", "GRAPH", "IN", "INBOUND", "INSERT", "INTO", "K_SHORTEST_PATHS", "LET", "LIKE", "LIMIT",
+    "NONE", "NOT", "NULL", "OPTIONS", "OR", "OUTBOUND", "PRUNE", "REMOVE", "REPLACE", "RETURN",
+    "SEARCH", "SHORTEST_PATH", "SORT", "TRUE", "UPDATE", "UPSERT", "WITH",
+];
+
+/// Prefix of the names of generated value bind parameters.
+const VALUE_PARAM_PREFIX: &str = "value";
+
+/// Prefix of the names of generated collection bind parameters.
+const COLLECTION_PARAM_PREFIX: &str = "@coll";
+
+/// Collects the query string and the bind parameters while rendering a
+/// query.
+#[derive(Debug)]
+pub(crate) struct Renderer {
+    string: String,
+    values: Vec<Value>,
+    collections: Vec<String>,
+}
+
+impl Renderer {
+    pub(crate) fn new() -> Self {
+        Renderer {
+            string: String::new(),
+            values: Vec::new(),
+            collections: Vec::new(),
+        }
+    }
+
+    /// Writes the given fragment of AQL as is.
+    pub(crate) fn write(&mut self, fragment: &str) {
+        self.string.push_str(fragment);
+    }
+
+    /// Writes a variable or attribute name and quotes it if necessary.
+    pub(crate) fn write_name(&mut self, name: &str) {
+        if is_plain_name(name) {
+            self.string.push_str(name);
+        } else {
+            self.string.push('`');
+            for c in name.chars() {
+                if c == '`' || c == '\\' {
+                    self.string.push('\\');
+                }
+                self.string.push(c);
+            }
+            self.string.push('`');
+        }
+    }
+
+    /// Writes the name of a function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name is not a valid function name, as function names
+    /// can not be quoted.
+    pub(crate) fn write_function_name(&mut self, name: &str) {
+        assert!(is_function_name(name), "invalid AQL function name: {:?}", name);
+        self.string.push_str(name);
+    }
+
+    /// Writes the name of an attribute in an object literal and quotes it if
+    /// necessary.
+    pub(crate) fn write_object_key(&mut self, name: &str) {
+        if is_plain_name(name) {
+            self.string.push_str(name);
+        } else {
+            self.string.push('"');
+            for c in name.chars() {
+                if c == '"' || c == '\\' {
+                    self.string.push('\\');
+                }
+                self.string.push(c);
+            }
+            self.string.push('"');
+        }
+    }
+
+    /// Writes a reference to a new bind parameter holding the given value.
+    pub(crate) fn write_value(&mut self, value: Value) {
+        self.values.push(value);
+        let param = format!("@{}{}", VALUE_PARAM_PREFIX, self.values.len() - 1);
+        self.string.push_str(&param);
+    }
+
+    /// Writes a reference to a collection bind parameter for the given
+    /// collection name.
+    ///
+    /// The same bind parameter is used for all occurrences of a collection.
+    pub(crate) fn write_collection(&mut self, name: &str) {
+        let index = match self.collections.iter().position(|coll| coll == name) {
+            Some(index) => index,
+            None => {
+                self.collections.push(name.to_owned());
+                self.collections.len() - 1
+            },
+        };
+        let param = format!("@{}{}", COLLECTION_PARAM_PREFIX, index);
+        self.string.push_str(&param);
+    }
+
+    /// Converts the rendered query string and the collected bind parameters
+    /// into a `Query`.
+    pub(crate) fn into_query(self) -> Query {
+        let mut query = Query::new(self.string);
+        for (index, value) in self.values.into_iter().enumerate() {
+            query.set_parameter(format!("{}{}", VALUE_PARAM_PREFIX, index), value);
+        }
+        for (index, name) in self.collections.into_iter().enumerate() {
+            query.set_parameter(format!("{}{}", COLLECTION_PARAM_PREFIX, index), name);
+        }
+        query
+    }
+}
+
+/// Returns whether the given name is a valid name of a builtin function or
+/// of a user defined function in a namespace, like `LENGTH` or
+/// `MYFUNCTIONS::GEO::DISTANCE`.
+pub(crate) fn is_function_name(name: &str) -> bool {
+    name.split("::").all(|part| {
+        let mut chars = part.chars();
+        match chars.next() {
+            Some(c) if c.is_ascii_alphabetic() || c == '_' =>
+                chars.all(|c| c.is_ascii_alphanumeric() || c == '_'),
+            _ => false,
+        }
+    })
+}
+
+fn is_plain_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    let starts_valid = match chars.next() {
+        Some(c) => c.is_ascii_alphabetic() || c == '_',
+        None => false,
+    };
+    starts_valid
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !KEYWORDS.iter().any(|keyword| keyword.eq_ignore_ascii_case(name))
+}
@@ -1,7 +1,8 @@
 
 use rincon_core::api::method::{Method, Operation, Parameters, Prepare, RpcReturnType};
-use rincon_core::arango::protocol::{FIELD_CODE, PARAM_DETAILS, PATH_ADMIN,
-    PATH_API_VERSION, PATH_DATABASE, PATH_TARGET_VERSION};
+use rincon_core::arango::protocol::{FIELD_CODE, FIELD_ID, FIELD_ROLE, PARAM_DETAILS,
+    PATH_ADMIN, PATH_API_ENGINE, PATH_API_VERSION, PATH_DATABASE, PATH_ID, PATH_ROLE,
+    PATH_SERVER, PATH_TARGET_VERSION};
 use super::types::*;
 
 #[allow(missing_copy_implementations)]
@@ -105,3 +106,155 @@ impl Prepare for GetServerVersion {
         None
     }
 }
+
+/// Returns the storage engine the server is configured to use.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct GetEngine {}
+
+impl GetEngine {
+    pub fn new() -> Self {
+        GetEngine {}
+    }
+}
+
+impl Default for GetEngine {
+    fn default() -> Self {
+        GetEngine::new()
+    }
+}
+
+impl Method for GetEngine {
+    type Result = Engine;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: None,
+    };
+}
+
+impl Prepare for GetEngine {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Read
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_ENGINE)
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Returns the role of the server.
+///
+/// The role tells whether the server runs as single server or which role it
+/// has in a cluster.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct GetServerRole {}
+
+impl GetServerRole {
+    pub fn new() -> Self {
+        GetServerRole {}
+    }
+}
+
+impl Default for GetServerRole {
+    fn default() -> Self {
+        GetServerRole::new()
+    }
+}
+
+impl Method for GetServerRole {
+    type Result = ServerRole;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: Some(FIELD_ROLE),
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for GetServerRole {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Read
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_ADMIN) + PATH_SERVER + PATH_ROLE
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Returns the id of a server in a cluster.
+///
+/// The request fails if the server is not running in a cluster.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct GetServerId {}
+
+impl GetServerId {
+    pub fn new() -> Self {
+        GetServerId {}
+    }
+}
+
+impl Default for GetServerId {
+    fn default() -> Self {
+        GetServerId::new()
+    }
+}
+
+impl Method for GetServerId {
+    type Result = String;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: Some(FIELD_ID),
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for GetServerId {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Read
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_ADMIN) + PATH_SERVER + PATH_ID
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
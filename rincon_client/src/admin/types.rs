@@ -1,6 +1,21 @@
 
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
 use rincon_core::api::types::JsonValue;
 
+const ENGINE_TYPE_MMFILES: &str = "mmfiles";
+const ENGINE_TYPE_ROCKSDB: &str = "rocksdb";
+
+const LICENSE_ENTERPRISE: &str = "enterprise";
+
+const SERVER_ROLE_SINGLE: &str = "SINGLE";
+const SERVER_ROLE_COORDINATOR: &str = "COORDINATOR";
+const SERVER_ROLE_PRIMARY: &str = "PRIMARY";
+const SERVER_ROLE_SECONDARY: &str = "SECONDARY";
+const SERVER_ROLE_AGENT: &str = "AGENT";
+const SERVER_ROLE_UNDEFINED: &str = "UNDEFINED";
+
 /// Represents the database-version that this server requires.
 #[derive(Clone, Debug, PartialEq, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -75,6 +90,11 @@ impl ServerVersion {
         &self.license
     }
 
+    /// Returns whether the server is an enterprise edition.
+    pub fn is_enterprise(&self) -> bool {
+        self.license == LICENSE_ENTERPRISE
+    }
+
     /// Returns additional details.
     ///
     /// The details are present only if the details method parameter was set
@@ -83,3 +103,246 @@ impl ServerVersion {
         self.details.as_ref()
     }
 }
+
+/// Represents the storage engine used by the server.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Engine {
+    /// The type of the storage engine.
+    name: EngineType,
+    /// The features supported by the storage engine.
+    supports: EngineSupports,
+}
+
+impl Engine {
+    /// Returns the type of the storage engine.
+    pub fn kind(&self) -> EngineType {
+        self.name
+    }
+
+    /// Returns the features supported by the storage engine.
+    pub fn supports(&self) -> &EngineSupports {
+        &self.supports
+    }
+}
+
+/// Represents the features supported by a storage engine.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineSupports {
+    /// Whether the storage engine supports the database file debugger.
+    #[serde(default)]
+    dfdb: bool,
+    /// The types of indexes supported by the storage engine.
+    #[serde(default)]
+    indexes: Vec<String>,
+}
+
+impl EngineSupports {
+    /// Returns whether the storage engine supports the database file
+    /// debugger.
+    pub fn is_dfdb(&self) -> bool {
+        self.dfdb
+    }
+
+    /// Returns the types of indexes supported by the storage engine.
+    pub fn indexes(&self) -> &[String] {
+        &self.indexes
+    }
+}
+
+/// The storage engines an ArangoDB server can be configured with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EngineType {
+    /// The MMFiles storage engine
+    MmFiles,
+    /// The RocksDB storage engine
+    RocksDb,
+}
+
+impl EngineType {
+    /// Returns the name of the storage engine as used by the ArangoDB server.
+    pub fn as_str(&self) -> &str {
+        match *self {
+            EngineType::MmFiles => ENGINE_TYPE_MMFILES,
+            EngineType::RocksDb => ENGINE_TYPE_ROCKSDB,
+        }
+    }
+}
+
+impl Serialize for EngineType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for EngineType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        use serde::de::Error;
+        let value = String::deserialize(deserializer)?;
+        match &value[..] {
+            ENGINE_TYPE_MMFILES => Ok(EngineType::MmFiles),
+            ENGINE_TYPE_ROCKSDB => Ok(EngineType::RocksDb),
+            _ => Err(D::Error::custom(format!("Unknown storage engine: {:?}", value))),
+        }
+    }
+}
+
+/// Holds values whose shape depends on the storage engine of the server.
+///
+/// When deserialized the shape of the value decides which variant is
+/// chosen, so that one client works with servers of both storage engines.
+/// The MMFiles shape is tried first.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum EngineSpecific<M, R> {
+    /// The value as provided by the MMFiles storage engine
+    MmFiles(M),
+    /// The value as provided by the RocksDB storage engine
+    RocksDb(R),
+}
+
+impl<M, R> EngineSpecific<M, R> {
+    /// Returns the type of the storage engine that provided the value.
+    pub fn engine(&self) -> EngineType {
+        match *self {
+            EngineSpecific::MmFiles(_) => EngineType::MmFiles,
+            EngineSpecific::RocksDb(_) => EngineType::RocksDb,
+        }
+    }
+
+    /// Returns the value if it has been provided by the MMFiles storage
+    /// engine.
+    pub fn mmfiles(&self) -> Option<&M> {
+        match *self {
+            EngineSpecific::MmFiles(ref value) => Some(value),
+            EngineSpecific::RocksDb(_) => None,
+        }
+    }
+
+    /// Returns the value if it has been provided by the RocksDB storage
+    /// engine.
+    pub fn rocksdb(&self) -> Option<&R> {
+        match *self {
+            EngineSpecific::MmFiles(_) => None,
+            EngineSpecific::RocksDb(ref value) => Some(value),
+        }
+    }
+}
+
+/// The roles a server can have.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ServerRole {
+    /// The server is running as single server and is not part of a cluster
+    Single,
+    /// The server is a coordinator in a cluster
+    Coordinator,
+    /// The server is a primary database server in a cluster
+    Primary,
+    /// The server is a secondary database server in a cluster
+    Secondary,
+    /// The server is an agent in a cluster
+    Agent,
+    /// The role of the server is not known, e.g. because it is still starting
+    Undefined,
+}
+
+impl ServerRole {
+    /// Returns the name of the role as used by the ArangoDB server.
+    pub fn as_str(&self) -> &str {
+        match *self {
+            ServerRole::Single => SERVER_ROLE_SINGLE,
+            ServerRole::Coordinator => SERVER_ROLE_COORDINATOR,
+            ServerRole::Primary => SERVER_ROLE_PRIMARY,
+            ServerRole::Secondary => SERVER_ROLE_SECONDARY,
+            ServerRole::Agent => SERVER_ROLE_AGENT,
+            ServerRole::Undefined => SERVER_ROLE_UNDEFINED,
+        }
+    }
+
+    /// Returns whether a server with this role is part of a cluster.
+    pub fn is_cluster(&self) -> bool {
+        !matches!(*self, ServerRole::Single | ServerRole::Undefined)
+    }
+}
+
+impl Serialize for ServerRole {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ServerRole {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        use serde::de::Error;
+        let value = String::deserialize(deserializer)?;
+        match &value[..] {
+            SERVER_ROLE_SINGLE => Ok(ServerRole::Single),
+            SERVER_ROLE_COORDINATOR => Ok(ServerRole::Coordinator),
+            SERVER_ROLE_PRIMARY => Ok(ServerRole::Primary),
+            SERVER_ROLE_SECONDARY => Ok(ServerRole::Secondary),
+            SERVER_ROLE_AGENT => Ok(ServerRole::Agent),
+            SERVER_ROLE_UNDEFINED => Ok(ServerRole::Undefined),
+            _ => Err(D::Error::custom(format!("Unknown server role: {:?}", value))),
+        }
+    }
+}
+
+/// Describes the capabilities of a server as detected when connecting to it.
+///
+/// It combines the role of the server, its id within a cluster and its
+/// version including the license.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ServerInfo {
+    role: ServerRole,
+    id: Option<String>,
+    version: ServerVersion,
+}
+
+impl ServerInfo {
+    /// Constructs a new instance of `ServerInfo` from the detected values.
+    pub fn new<Id>(role: ServerRole, id: Id, version: ServerVersion) -> Self
+        where Id: Into<Option<String>>
+    {
+        ServerInfo {
+            role,
+            id: id.into(),
+            version,
+        }
+    }
+
+    /// Returns the role of the server.
+    pub fn role(&self) -> ServerRole {
+        self.role
+    }
+
+    /// Returns the id of the server.
+    ///
+    /// Only servers that are part of a cluster have an id.
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_ref().map(|id| &id[..])
+    }
+
+    /// Returns the name and version of the server.
+    pub fn version(&self) -> &ServerVersion {
+        &self.version
+    }
+
+    /// Returns whether the server is part of a cluster.
+    pub fn is_cluster(&self) -> bool {
+        self.role.is_cluster()
+    }
+
+    /// Returns whether the server is an enterprise edition.
+    pub fn is_enterprise(&self) -> bool {
+        self.version.is_enterprise()
+    }
+}
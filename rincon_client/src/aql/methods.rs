@@ -1,7 +1,10 @@
 
 use rincon_core::api::method::{Method, Operation, Parameters, Prepare, RpcReturnType};
 use rincon_core::api::query::Query;
-use rincon_core::arango::protocol::{FIELD_CODE, PATH_API_EXPLAIN, PATH_API_QUERY};
+use rincon_core::api::types::Empty;
+use rincon_core::arango::protocol::{FIELD_CODE, FIELD_DELETED_COUNT, FIELD_IS_NEWLY_CREATED,
+    FIELD_RESULT, PARAM_GROUP, PARAM_NAMESPACE, PATH_API_AQLFUNCTION, PATH_API_EXPLAIN,
+    PATH_API_QUERY, PATH_CURRENT, PATH_PROPERTIES, PATH_SLOW};
 use super::types::*;
 
 #[derive(Clone, Debug, PartialEq)]
@@ -109,3 +112,510 @@ impl Prepare for ExplainQuery {
         Some(&self.query_options)
     }
 }
+
+/// Returns the AQL user functions registered in the database.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ListAqlFunctions {
+    namespace: Option<String>,
+}
+
+impl ListAqlFunctions {
+    /// Constructs a new `ListAqlFunctions` method that returns all
+    /// registered functions.
+    pub fn new() -> Self {
+        ListAqlFunctions {
+            namespace: None,
+        }
+    }
+
+    /// Constructs a new `ListAqlFunctions` method that returns only the
+    /// functions within the given namespace, e.g. `myfunctions::scoring`.
+    pub fn with_namespace<N>(namespace: N) -> Self
+        where N: Into<String>
+    {
+        ListAqlFunctions {
+            namespace: Some(namespace.into()),
+        }
+    }
+
+    pub fn namespace(&self) -> Option<&String> {
+        self.namespace.as_ref()
+    }
+}
+
+impl Default for ListAqlFunctions {
+    fn default() -> Self {
+        ListAqlFunctions::new()
+    }
+}
+
+impl Method for ListAqlFunctions {
+    type Result = Vec<AqlFunction>;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: Some(FIELD_RESULT),
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for ListAqlFunctions {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Read
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_AQLFUNCTION)
+    }
+
+    fn parameters(&self) -> Parameters {
+        let mut params = Parameters::with_capacity(1);
+        if let Some(ref namespace) = self.namespace {
+            params.insert(PARAM_NAMESPACE, namespace.to_owned());
+        }
+        params
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Registers an AQL user function.
+///
+/// If a function with the same name is already registered it is replaced.
+/// The result is `true` if the function has been newly created and `false`
+/// if an existing function has been replaced.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CreateAqlFunction {
+    function: NewAqlFunction,
+}
+
+impl CreateAqlFunction {
+    pub fn new(function: NewAqlFunction) -> Self {
+        CreateAqlFunction {
+            function,
+        }
+    }
+
+    pub fn function(&self) -> &NewAqlFunction {
+        &self.function
+    }
+}
+
+impl Method for CreateAqlFunction {
+    type Result = bool;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: Some(FIELD_IS_NEWLY_CREATED),
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for CreateAqlFunction {
+    type Content = NewAqlFunction;
+
+    fn operation(&self) -> Operation {
+        Operation::Create
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_AQLFUNCTION)
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        Some(&self.function)
+    }
+}
+
+/// Removes an AQL user function or a group of functions.
+///
+/// The result is the number of removed functions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeleteAqlFunction {
+    name: String,
+    group: bool,
+}
+
+impl DeleteAqlFunction {
+    /// Constructs a new `DeleteAqlFunction` method that removes the function
+    /// with the given fully qualified name.
+    pub fn new<N>(name: N) -> Self
+        where N: Into<String>
+    {
+        DeleteAqlFunction {
+            name: name.into(),
+            group: false,
+        }
+    }
+
+    /// Constructs a new `DeleteAqlFunction` method that removes all functions
+    /// within the given namespace.
+    pub fn group<N>(namespace: N) -> Self
+        where N: Into<String>
+    {
+        DeleteAqlFunction {
+            name: namespace.into(),
+            group: true,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn is_group(&self) -> bool {
+        self.group
+    }
+}
+
+impl Method for DeleteAqlFunction {
+    type Result = u32;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: Some(FIELD_DELETED_COUNT),
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for DeleteAqlFunction {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Delete
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_AQLFUNCTION) + "/" + &self.name
+    }
+
+    fn parameters(&self) -> Parameters {
+        let mut params = Parameters::with_capacity(1);
+        if self.group {
+            params.insert(PARAM_GROUP, true);
+        }
+        params
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Returns the currently running queries of the database.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct GetCurrentQueries {}
+
+impl GetCurrentQueries {
+    pub fn new() -> Self {
+        GetCurrentQueries {}
+    }
+}
+
+impl Default for GetCurrentQueries {
+    fn default() -> Self {
+        GetCurrentQueries::new()
+    }
+}
+
+impl Method for GetCurrentQueries {
+    type Result = Vec<RunningQuery>;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: None,
+    };
+}
+
+impl Prepare for GetCurrentQueries {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Read
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_QUERY) + PATH_CURRENT
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Returns the slow queries of the database.
+///
+/// A query is considered slow if its run time exceeds the slow query
+/// threshold of the query tracking properties.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct GetSlowQueries {}
+
+impl GetSlowQueries {
+    pub fn new() -> Self {
+        GetSlowQueries {}
+    }
+}
+
+impl Default for GetSlowQueries {
+    fn default() -> Self {
+        GetSlowQueries::new()
+    }
+}
+
+impl Method for GetSlowQueries {
+    type Result = Vec<RunningQuery>;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: None,
+    };
+}
+
+impl Prepare for GetSlowQueries {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Read
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_QUERY) + PATH_SLOW
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Clears the list of slow queries of the database.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClearSlowQueries {}
+
+impl ClearSlowQueries {
+    pub fn new() -> Self {
+        ClearSlowQueries {}
+    }
+}
+
+impl Default for ClearSlowQueries {
+    fn default() -> Self {
+        ClearSlowQueries::new()
+    }
+}
+
+impl Method for ClearSlowQueries {
+    type Result = Empty;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for ClearSlowQueries {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Delete
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_QUERY) + PATH_SLOW
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Kills a running query.
+///
+/// The query is terminated at the next cancelation point.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KillQuery {
+    id: String,
+}
+
+impl KillQuery {
+    pub fn new<Id>(query_id: Id) -> Self
+        where Id: Into<String>
+    {
+        KillQuery {
+            id: query_id.into(),
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl Method for KillQuery {
+    type Result = Empty;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for KillQuery {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Delete
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_QUERY) + "/" + &self.id
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Returns the configuration of the query tracking of the database.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct GetQueryTrackingProperties {}
+
+impl GetQueryTrackingProperties {
+    pub fn new() -> Self {
+        GetQueryTrackingProperties {}
+    }
+}
+
+impl Default for GetQueryTrackingProperties {
+    fn default() -> Self {
+        GetQueryTrackingProperties::new()
+    }
+}
+
+impl Method for GetQueryTrackingProperties {
+    type Result = QueryTrackingProperties;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: None,
+    };
+}
+
+impl Prepare for GetQueryTrackingProperties {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Read
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_QUERY) + PATH_PROPERTIES
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Changes the configuration of the query tracking of the database and
+/// returns the resulting configuration.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChangeQueryTrackingProperties {
+    updates: QueryTrackingPropertiesUpdate,
+}
+
+impl ChangeQueryTrackingProperties {
+    pub fn new(updates: QueryTrackingPropertiesUpdate) -> Self {
+        ChangeQueryTrackingProperties {
+            updates,
+        }
+    }
+
+    pub fn updates(&self) -> &QueryTrackingPropertiesUpdate {
+        &self.updates
+    }
+}
+
+impl Method for ChangeQueryTrackingProperties {
+    type Result = QueryTrackingProperties;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: None,
+    };
+}
+
+impl Prepare for ChangeQueryTrackingProperties {
+    type Content = QueryTrackingPropertiesUpdate;
+
+    fn operation(&self) -> Operation {
+        Operation::Replace
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_QUERY) + PATH_PROPERTIES
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        Some(&self.updates)
+    }
+}
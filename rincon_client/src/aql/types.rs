@@ -12,6 +12,18 @@ use rincon_core::api::query::Query;
 use rincon_core::api::types::{JsonValue, Value};
 use index::types::Index;
 
+const QUERY_STATE_INITIALIZING: &str = "initializing";
+const QUERY_STATE_PARSING: &str = "parsing";
+const QUERY_STATE_OPTIMIZING_AST: &str = "optimizing ast";
+const QUERY_STATE_LOADING_COLLECTIONS: &str = "loading collections";
+const QUERY_STATE_INSTANTIATING_PLAN: &str = "instantiating plan";
+const QUERY_STATE_OPTIMIZING_PLAN: &str = "optimizing plan";
+const QUERY_STATE_EXECUTING: &str = "executing";
+const QUERY_STATE_FINALIZING: &str = "finalizing";
+const QUERY_STATE_FINISHED: &str = "finished";
+const QUERY_STATE_KILLED: &str = "killed";
+const QUERY_STATE_INVALID: &str = "invalid";
+
 const COLLECT_METHOD_SORTED: &str = "sorted";
 const COLLECT_METHOD_HASH: &str = "hash";
 
@@ -32,13 +44,9 @@ const EXECUTION_NODE_TYPE_REPLACE_NODE: &str = "ReplaceNode";
 const EXECUTION_NODE_TYPE_UPDATE_NODE: &str = "UpdateNode";
 const EXECUTION_NODE_TYPE_UPSERT_NODE: &str = "UpsertNode";
 const EXECUTION_NODE_TYPE_NO_RESULTS_NODE: &str = "NoResultsNode";
-#[cfg(feature = "cluster")]
 const EXECUTION_NODE_TYPE_SCATTER_NODE: &str = "ScatterNode";
-#[cfg(feature = "cluster")]
 const EXECUTION_NODE_TYPE_GATHER_NODE: &str = "GatherNode";
-#[cfg(feature = "cluster")]
 const EXECUTION_NODE_TYPE_DISTRIBUTE_NODE: &str = "DistributeNode";
-#[cfg(feature = "cluster")]
 const EXECUTION_NODE_TYPE_REMOTE_NODE: &str = "RemoteNode";
 
 const OPTIMIZER_RULE_ALL: &str = "all";
@@ -65,19 +73,367 @@ const OPTIMIZER_RULE_INLINE_SUBQUERIES: &str = "inline-subqueries";
 const OPTIMIZER_RULE_GEO_INDEX_OPTIMIZER: &str = "geo-index-optimizer";
 const OPTIMIZER_RULE_REMOVE_SORT_RAND: &str = "remove-sort-rand";
 const OPTIMIZER_RULE_REDUCE_EXTRACTION_TO_PROJECTION: &str = "reduce-extraction-to-projection";
-#[cfg(feature = "cluster")]
 const OPTIMIZER_RULE_DISTRIBUTE_IN_CLUSTER: &str = "distribute-in-cluster";
-#[cfg(feature = "cluster")]
 const OPTIMIZER_RULE_SCATTER_IN_CLUSTER: &str = "scatter-in-cluster";
-#[cfg(feature = "cluster")]
 const OPTIMIZER_RULE_DISTRIBUTE_FILTERCALC_TO_CLUSTER: &str = "distribute-filtercalc-to-cluster";
-#[cfg(feature = "cluster")]
 const OPTIMIZER_RULE_DISTRIBUTE_SORT_TO_CLUSTER: &str = "distribute-sort-to-cluster";
-#[cfg(feature = "cluster")]
 const OPTIMIZER_RULE_REMOVE_UNNECESSARY_REMOTE_SCATTER: &str = "remove-unnecessary-remote-scatter";
-#[cfg(feature = "cluster")]
 const OPTIMIZER_RULE_UNDISTRIBUTE_REMOVE_AFTER_ENUM_COLL: &str = "undistribute-remove-after-enum-coll";
 
+/// This struct holds the properties of an AQL user function as returned by
+/// the server.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AqlFunction {
+    name: String,
+    code: String,
+    #[serde(default)]
+    is_deterministic: bool,
+}
+
+impl AqlFunction {
+    /// Returns the fully qualified name of the function, e.g.
+    /// `myfunctions::scoring::bm25`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the stringified JavaScript code of the function.
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// Returns whether the function always returns the same result for the
+    /// same arguments.
+    pub fn is_deterministic(&self) -> bool {
+        self.is_deterministic
+    }
+}
+
+/// This struct defines an AQL user function to be registered on the server.
+///
+/// If a function with the same name is already registered it is replaced.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewAqlFunction {
+    name: String,
+    code: String,
+    is_deterministic: bool,
+}
+
+impl NewAqlFunction {
+    /// Constructs a new non-deterministic AQL user function with the given
+    /// fully qualified name and stringified JavaScript code.
+    pub fn new<N, C>(name: N, code: C) -> Self
+        where N: Into<String>, C: Into<String>
+    {
+        NewAqlFunction {
+            name: name.into(),
+            code: code.into(),
+            is_deterministic: false,
+        }
+    }
+
+    /// Marks the function as deterministic, which allows the query optimizer
+    /// to evaluate calls with constant arguments only once.
+    pub fn with_deterministic(mut self, is_deterministic: bool) -> Self {
+        self.is_deterministic = is_deterministic;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn is_deterministic(&self) -> bool {
+        self.is_deterministic
+    }
+
+    /// Returns whether the given registered function has the same name, code
+    /// and determinism as this function.
+    ///
+    /// The names of AQL user functions are case-insensitive. Leading and
+    /// trailing whitespace of the code is ignored, as the server may not
+    /// return the code exactly as it has been registered.
+    pub fn is_registered_as(&self, function: &AqlFunction) -> bool {
+        self.name.eq_ignore_ascii_case(function.name())
+            && self.code.trim() == function.code().trim()
+            && self.is_deterministic == function.is_deterministic()
+    }
+}
+
+/// This enum defines the states of a running query.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum QueryState {
+    Initializing,
+    Parsing,
+    OptimizingAst,
+    LoadingCollections,
+    InstantiatingPlan,
+    OptimizingPlan,
+    Executing,
+    Finalizing,
+    Finished,
+    Killed,
+    Invalid,
+    /// A state that is not known to this version of the driver.
+    Other(String),
+}
+
+impl QueryState {
+    /// Returns the name of this state as reported by the server.
+    pub fn as_str(&self) -> &str {
+        use self::QueryState::*;
+        match *self {
+            Initializing => QUERY_STATE_INITIALIZING,
+            Parsing => QUERY_STATE_PARSING,
+            OptimizingAst => QUERY_STATE_OPTIMIZING_AST,
+            LoadingCollections => QUERY_STATE_LOADING_COLLECTIONS,
+            InstantiatingPlan => QUERY_STATE_INSTANTIATING_PLAN,
+            OptimizingPlan => QUERY_STATE_OPTIMIZING_PLAN,
+            Executing => QUERY_STATE_EXECUTING,
+            Finalizing => QUERY_STATE_FINALIZING,
+            Finished => QUERY_STATE_FINISHED,
+            Killed => QUERY_STATE_KILLED,
+            Invalid => QUERY_STATE_INVALID,
+            Other(ref state) => state,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for QueryState {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        use self::QueryState::*;
+        let value = String::deserialize(deserializer)?;
+        Ok(match &value[..] {
+            QUERY_STATE_INITIALIZING => Initializing,
+            QUERY_STATE_PARSING => Parsing,
+            QUERY_STATE_OPTIMIZING_AST => OptimizingAst,
+            QUERY_STATE_LOADING_COLLECTIONS => LoadingCollections,
+            QUERY_STATE_INSTANTIATING_PLAN => InstantiatingPlan,
+            QUERY_STATE_OPTIMIZING_PLAN => OptimizingPlan,
+            QUERY_STATE_EXECUTING => Executing,
+            QUERY_STATE_FINALIZING => Finalizing,
+            QUERY_STATE_FINISHED => Finished,
+            QUERY_STATE_KILLED => Killed,
+            QUERY_STATE_INVALID => Invalid,
+            _ => Other(value),
+        })
+    }
+}
+
+/// This struct holds the information about a currently running or a slow
+/// query.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunningQuery {
+    id: String,
+    query: String,
+    #[serde(default)]
+    bind_vars: HashMap<String, JsonValue>,
+    run_time: f64,
+    started: String,
+    state: QueryState,
+}
+
+impl RunningQuery {
+    /// Returns the id of the query, which is used to kill the query.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the query string, which may be truncated by the server.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Returns the bind parameters of the query.
+    ///
+    /// The bind parameters are only reported if the tracking of bind
+    /// parameters is enabled.
+    pub fn bind_vars(&self) -> &HashMap<String, JsonValue> {
+        &self.bind_vars
+    }
+
+    /// Returns the run time of the query in seconds.
+    pub fn run_time(&self) -> f64 {
+        self.run_time
+    }
+
+    /// Returns the date and time when the query was started in ISO 8601
+    /// format.
+    pub fn started(&self) -> &str {
+        &self.started
+    }
+
+    /// Returns the state of the query.
+    pub fn state(&self) -> &QueryState {
+        &self.state
+    }
+}
+
+/// This struct holds the configuration of the tracking of running and slow
+/// queries.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryTrackingProperties {
+    enabled: bool,
+    track_slow_queries: bool,
+    #[serde(default)]
+    track_bind_vars: bool,
+    max_slow_queries: u32,
+    slow_query_threshold: f64,
+    max_query_string_length: u32,
+}
+
+impl QueryTrackingProperties {
+    /// Returns whether queries are tracked at all.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Returns whether slow queries are tracked.
+    pub fn is_track_slow_queries(&self) -> bool {
+        self.track_slow_queries
+    }
+
+    /// Returns whether the bind parameters of queries are tracked.
+    pub fn is_track_bind_vars(&self) -> bool {
+        self.track_bind_vars
+    }
+
+    /// Returns the maximal number of slow queries that are kept.
+    pub fn max_slow_queries(&self) -> u32 {
+        self.max_slow_queries
+    }
+
+    /// Returns the run time in seconds after which a query is considered
+    /// slow.
+    pub fn slow_query_threshold(&self) -> f64 {
+        self.slow_query_threshold
+    }
+
+    /// Returns the maximal length of tracked query strings.
+    pub fn max_query_string_length(&self) -> u32 {
+        self.max_query_string_length
+    }
+}
+
+/// This struct holds the changes to the configuration of the query tracking.
+///
+/// Only the properties that are set are changed on the server.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryTrackingPropertiesUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    track_slow_queries: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    track_bind_vars: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_slow_queries: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    slow_query_threshold: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_query_string_length: Option<u32>,
+}
+
+impl QueryTrackingPropertiesUpdate {
+    /// Constructs a new instance of `QueryTrackingPropertiesUpdate` with no
+    /// properties set.
+    pub fn new() -> Self {
+        QueryTrackingPropertiesUpdate {
+            enabled: None,
+            track_slow_queries: None,
+            track_bind_vars: None,
+            max_slow_queries: None,
+            slow_query_threshold: None,
+            max_query_string_length: None,
+        }
+    }
+
+    /// Sets whether queries are tracked at all.
+    pub fn set_enabled<E>(&mut self, enabled: E)
+        where E: Into<Option<bool>>
+    {
+        self.enabled = enabled.into();
+    }
+
+    pub fn is_enabled(&self) -> Option<bool> {
+        self.enabled
+    }
+
+    /// Sets whether slow queries are tracked.
+    pub fn set_track_slow_queries<T>(&mut self, track_slow_queries: T)
+        where T: Into<Option<bool>>
+    {
+        self.track_slow_queries = track_slow_queries.into();
+    }
+
+    pub fn is_track_slow_queries(&self) -> Option<bool> {
+        self.track_slow_queries
+    }
+
+    /// Sets whether the bind parameters of queries are tracked.
+    pub fn set_track_bind_vars<T>(&mut self, track_bind_vars: T)
+        where T: Into<Option<bool>>
+    {
+        self.track_bind_vars = track_bind_vars.into();
+    }
+
+    pub fn is_track_bind_vars(&self) -> Option<bool> {
+        self.track_bind_vars
+    }
+
+    /// Sets the maximal number of slow queries that are kept.
+    pub fn set_max_slow_queries<M>(&mut self, max_slow_queries: M)
+        where M: Into<Option<u32>>
+    {
+        self.max_slow_queries = max_slow_queries.into();
+    }
+
+    pub fn max_slow_queries(&self) -> Option<u32> {
+        self.max_slow_queries
+    }
+
+    /// Sets the run time in seconds after which a query is considered slow.
+    pub fn set_slow_query_threshold<S>(&mut self, slow_query_threshold: S)
+        where S: Into<Option<f64>>
+    {
+        self.slow_query_threshold = slow_query_threshold.into();
+    }
+
+    pub fn slow_query_threshold(&self) -> Option<f64> {
+        self.slow_query_threshold
+    }
+
+    /// Sets the maximal length of tracked query strings.
+    pub fn set_max_query_string_length<M>(&mut self, max_query_string_length: M)
+        where M: Into<Option<u32>>
+    {
+        self.max_query_string_length = max_query_string_length.into();
+    }
+
+    pub fn max_query_string_length(&self) -> Option<u32> {
+        self.max_query_string_length
+    }
+}
+
+impl Default for QueryTrackingPropertiesUpdate {
+    fn default() -> Self {
+        QueryTrackingPropertiesUpdate::new()
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ParsedQuery {
@@ -376,18 +732,14 @@ pub enum ExecutionNode {
     /// The NoResultsNode will pass an empty result set into the processing
     /// pipeline.
     NoResults(NoResultsNode),
-    #[cfg(feature = "cluster")]
     /// Used on a coordinator to fan-out data to one or multiple shards.
     Scatter(ScatterNode),
-    #[cfg(feature = "cluster")]
     /// Used on a coordinator to aggregate results from one or many shards into
     /// a combined stream of results.
     Gather(GatherNode),
-    #[cfg(feature = "cluster")]
     /// Used on a coordinator to fan-out data to one or multiple shards, taking
     /// into account a collection's shard key.
     Distribute(DistributeNode),
-    #[cfg(feature = "cluster")]
     /// A RemoteNode will perform communication with another ArangoDB instances
     /// in the cluster. For example, the cluster coordinator will need to
     /// communicate with other servers to fetch the actual data from the shards.
@@ -1104,7 +1456,6 @@ impl NoResultsNode {
     }
 }
 
-#[cfg(feature = "cluster")]
 //TODO add node specific fields
 /// Used on a coordinator to fan-out data to one or multiple shards.
 #[derive(Clone, Debug, PartialEq)]
@@ -1116,7 +1467,6 @@ pub struct ScatterNode {
 
 }
 
-#[cfg(feature = "cluster")]
 impl ScatterNode {
     pub fn new<Deps>(
         id: ExecutionNodeId,
@@ -1129,14 +1479,13 @@ impl ScatterNode {
     {
         ScatterNode {
             id,
-            dependencies: Vec::from_iter(dependencies.into_iter()),
+            dependencies: Vec::from_iter(dependencies),
             estimated_cost,
             estimated_nr_items,
         }
     }
 }
 
-#[cfg(feature = "cluster")]
 //TODO add node specific fields
 /// Used on a coordinator to aggregate results from one or many shards into a
 /// combined stream of results.
@@ -1149,7 +1498,6 @@ pub struct GatherNode {
 
 }
 
-#[cfg(feature = "cluster")]
 impl GatherNode {
     pub fn new<Deps>(
         id: ExecutionNodeId,
@@ -1162,14 +1510,13 @@ impl GatherNode {
     {
         GatherNode {
             id,
-            dependencies: Vec::from_iter(dependencies.into_iter()),
+            dependencies: Vec::from_iter(dependencies),
             estimated_cost,
             estimated_nr_items,
         }
     }
 }
 
-#[cfg(feature = "cluster")]
 //TODO add node specific fields
 /// Used on a coordinator to fan-out data to one or multiple shards, taking
 /// into account a collection's shard key.
@@ -1182,7 +1529,6 @@ pub struct DistributeNode {
 
 }
 
-#[cfg(feature = "cluster")]
 impl DistributeNode {
     pub fn new<Deps>(
         id: ExecutionNodeId,
@@ -1195,14 +1541,13 @@ impl DistributeNode {
     {
         DistributeNode {
             id,
-            dependencies: Vec::from_iter(dependencies.into_iter()),
+            dependencies: Vec::from_iter(dependencies),
             estimated_cost,
             estimated_nr_items,
         }
     }
 }
 
-#[cfg(feature = "cluster")]
 //TODO add node specific fields
 /// A RemoteNode will perform communication with another ArangoDB instances in
 /// the cluster. For example, the cluster coordinator will need to communicate
@@ -1219,7 +1564,6 @@ pub struct RemoteNode {
 
 }
 
-#[cfg(feature = "cluster")]
 impl RemoteNode {
     pub fn new<Deps>(
         id: ExecutionNodeId,
@@ -1232,7 +1576,7 @@ impl RemoteNode {
     {
         RemoteNode {
             id,
-            dependencies: Vec::from_iter(dependencies.into_iter()),
+            dependencies: Vec::from_iter(dependencies),
             estimated_cost,
             estimated_nr_items,
         }
@@ -1843,16 +2187,12 @@ pub enum ExecutionNodeType {
     UpsertNode,
     /// will be inserted if FILTER statements turn out to be never satisfiable. The NoResultsNode will pass an empty result set into the processing pipeline.
     NoResultsNode,
-    #[cfg(feature = "cluster")]
     /// used on a coordinator to fan-out data to one or multiple shards.
     ScatterNode,
-    #[cfg(feature = "cluster")]
     /// used on a coordinator to aggregate results from one or many shards into a combined stream of results.
     GatherNode,
-    #[cfg(feature = "cluster")]
     /// used on a coordinator to fan-out data to one or multiple shards, taking into account a collection's shard key.
     DistributeNode,
-    #[cfg(feature = "cluster")]
     /// a RemoteNode will perform communication with another ArangoDB instances in the cluster. For example, the cluster coordinator will need to communicate with other servers to fetch the actual data from the shards. It will do so via RemoteNodes. The data servers themselves might again pull further data from the coordinator, and thus might also employ RemoteNodes. So, all of the above cluster relevant nodes will be accompanied by a RemoteNode.
     RemoteNode,
     /// Can be used to specify a execution node that has not been added to this enum yet.
@@ -1882,13 +2222,9 @@ impl ExecutionNodeType {
             EXECUTION_NODE_TYPE_UPDATE_NODE => UpdateNode,
             EXECUTION_NODE_TYPE_UPSERT_NODE => UpsertNode,
             EXECUTION_NODE_TYPE_NO_RESULTS_NODE => NoResultsNode,
-            #[cfg(feature = "cluster")]
             EXECUTION_NODE_TYPE_SCATTER_NODE => ScatterNode,
-            #[cfg(feature = "cluster")]
             EXECUTION_NODE_TYPE_GATHER_NODE => GatherNode,
-            #[cfg(feature = "cluster")]
             EXECUTION_NODE_TYPE_DISTRIBUTE_NODE => DistributeNode,
-            #[cfg(feature = "cluster")]
             EXECUTION_NODE_TYPE_REMOTE_NODE => RemoteNode,
             node => Unlisted(node.to_owned()),
         }
@@ -1916,13 +2252,9 @@ impl ExecutionNodeType {
             UpdateNode => EXECUTION_NODE_TYPE_UPDATE_NODE,
             UpsertNode => EXECUTION_NODE_TYPE_UPSERT_NODE,
             NoResultsNode => EXECUTION_NODE_TYPE_NO_RESULTS_NODE,
-            #[cfg(feature = "cluster")]
             ScatterNode => EXECUTION_NODE_TYPE_SCATTER_NODE,
-            #[cfg(feature = "cluster")]
             GatherNode => EXECUTION_NODE_TYPE_GATHER_NODE,
-            #[cfg(feature = "cluster")]
             DistributeNode => EXECUTION_NODE_TYPE_DISTRIBUTE_NODE,
-            #[cfg(feature = "cluster")]
             RemoteNode => EXECUTION_NODE_TYPE_REMOTE_NODE,
             Unlisted(ref node) => node,
         }
@@ -2688,22 +3020,16 @@ pub enum OptimizerRule {
     RemoveSortRand,
     /// will appear when an EnumerationCollectionNode that would have extracted an entire document was modified to return only a projection of each document. This optimizer rule is specific for the RocksDB storage engine.
     ReduceExtractionToProjection,
-    #[cfg(feature = "cluster")]
     /// will appear when query parts get distributed in a cluster. This is not an optimization rule, and it cannot be turned off.
     DistributeInCluster,
-    #[cfg(feature = "cluster")]
     /// will appear when scatter, gather, and remote nodes are inserted into a distributed query. This is not an optimization rule, and it cannot be turned off.
     ScatterInCluster,
-    #[cfg(feature = "cluster")]
     /// will appear when filters are moved up in a distributed execution plan. Filters are moved as far up in the plan as possible to make result sets as small as possible as early as possible.
     DistributeFilterCalcToCluster,
-    #[cfg(feature = "cluster")]
     /// will appear if sorts are moved up in a distributed query. Sorts are moved as far up in the plan as possible to make result sets as small as possible as early as possible.
     DistributeSortToCluster,
-    #[cfg(feature = "cluster")]
     /// will appear if a RemoteNode is followed by a ScatterNode, and the ScatterNode is only followed by calculations or the SingletonNode. In this case, there is no need to distribute the calculation, and it will be handled centrally.
     RemoveUnnecessaryRemoteScatter,
-    #[cfg(feature = "cluster")]
     /// will appear if a RemoveNode can be pushed into the same query part that enumerates over the documents of a collection. This saves inter-cluster round-trips between the EnumerateCollectionNode and the RemoveNode.
     UnDistributeRemoveAfterEnumColl,
     /// Can be used to specify a rule that has not been added to this enum yet.
@@ -2740,17 +3066,11 @@ impl OptimizerRule {
             OPTIMIZER_RULE_GEO_INDEX_OPTIMIZER => GeoIndexOptimizer,
             OPTIMIZER_RULE_REMOVE_SORT_RAND => RemoveSortRand,
             OPTIMIZER_RULE_REDUCE_EXTRACTION_TO_PROJECTION => ReduceExtractionToProjection,
-            #[cfg(feature = "cluster")]
             OPTIMIZER_RULE_DISTRIBUTE_IN_CLUSTER => DistributeInCluster,
-            #[cfg(feature = "cluster")]
             OPTIMIZER_RULE_SCATTER_IN_CLUSTER => ScatterInCluster,
-            #[cfg(feature = "cluster")]
             OPTIMIZER_RULE_DISTRIBUTE_FILTERCALC_TO_CLUSTER => DistributeFilterCalcToCluster,
-            #[cfg(feature = "cluster")]
             OPTIMIZER_RULE_DISTRIBUTE_SORT_TO_CLUSTER => DistributeSortToCluster,
-            #[cfg(feature = "cluster")]
             OPTIMIZER_RULE_REMOVE_UNNECESSARY_REMOTE_SCATTER => RemoveUnnecessaryRemoteScatter,
-            #[cfg(feature = "cluster")]
             OPTIMIZER_RULE_UNDISTRIBUTE_REMOVE_AFTER_ENUM_COLL => UnDistributeRemoveAfterEnumColl,
             rule => Custom(rule.to_owned()),
         }
@@ -2785,17 +3105,11 @@ impl OptimizerRule {
             GeoIndexOptimizer => OPTIMIZER_RULE_GEO_INDEX_OPTIMIZER,
             RemoveSortRand => OPTIMIZER_RULE_REMOVE_SORT_RAND,
             ReduceExtractionToProjection => OPTIMIZER_RULE_REDUCE_EXTRACTION_TO_PROJECTION,
-            #[cfg(feature = "cluster")]
             DistributeInCluster => OPTIMIZER_RULE_DISTRIBUTE_IN_CLUSTER,
-            #[cfg(feature = "cluster")]
             ScatterInCluster => OPTIMIZER_RULE_SCATTER_IN_CLUSTER,
-            #[cfg(feature = "cluster")]
             DistributeFilterCalcToCluster => OPTIMIZER_RULE_DISTRIBUTE_FILTERCALC_TO_CLUSTER,
-            #[cfg(feature = "cluster")]
             DistributeSortToCluster => OPTIMIZER_RULE_DISTRIBUTE_SORT_TO_CLUSTER,
-            #[cfg(feature = "cluster")]
             RemoveUnnecessaryRemoteScatter => OPTIMIZER_RULE_REMOVE_UNNECESSARY_REMOTE_SCATTER,
-            #[cfg(feature = "cluster")]
             UnDistributeRemoveAfterEnumColl => OPTIMIZER_RULE_UNDISTRIBUTE_REMOVE_AFTER_ENUM_COLL,
             Custom(ref rule) => rule,
         }
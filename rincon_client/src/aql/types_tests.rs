@@ -0,0 +1,56 @@
+
+use serde_json;
+use super::types::*;
+
+#[test]
+fn deserialize_running_query() {
+    let json_str = r#"{
+        "id": "1894",
+        "query": "FOR c IN customers FILTER c.age > @age RETURN c",
+        "bindVars": { "age": 42 },
+        "started": "2018-03-14T09:12:45Z",
+        "runTime": 12.75,
+        "state": "executing"
+    }"#;
+
+    let running_query: RunningQuery = serde_json::from_str(json_str).unwrap();
+
+    assert_eq!("1894", running_query.id());
+    assert_eq!("FOR c IN customers FILTER c.age > @age RETURN c", running_query.query());
+    assert_eq!(Some(&json!(42)), running_query.bind_vars().get("age"));
+    assert_eq!("2018-03-14T09:12:45Z", running_query.started());
+    assert_eq!(12.75, running_query.run_time());
+    assert_eq!(&QueryState::Executing, running_query.state());
+}
+
+#[test]
+fn deserialize_running_query_without_bind_vars_and_unknown_state() {
+    let json_str = r#"{
+        "id": "1895",
+        "query": "RETURN SLEEP(60)",
+        "started": "2018-03-14T09:12:45Z",
+        "runTime": 61.5,
+        "state": "waiting for the moon"
+    }"#;
+
+    let running_query: RunningQuery = serde_json::from_str(json_str).unwrap();
+
+    assert!(running_query.bind_vars().is_empty());
+    assert_eq!(&QueryState::Other("waiting for the moon".to_owned()), running_query.state());
+    assert_eq!("waiting for the moon", running_query.state().as_str());
+}
+
+#[test]
+fn new_function_is_registered_as_function_with_untrimmed_code() {
+    let registered: AqlFunction = serde_json::from_str(r#"{
+        "name": "SCORING::BM25",
+        "code": "\n  function (tf, idf) { return tf * idf; }\n",
+        "isDeterministic": true
+    }"#).unwrap();
+
+    let function = NewAqlFunction::new("scoring::bm25", "function (tf, idf) { return tf * idf; }")
+        .with_deterministic(true);
+
+    assert!(function.is_registered_as(&registered));
+    assert!(!function.with_deterministic(false).is_registered_as(&registered));
+}
@@ -0,0 +1,263 @@
+
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+
+use rincon_core::api::connector::Error;
+use rincon_core::api::method::{ContentFormat, Method, Operation, Parameters, Prepare,
+    RpcReturnType};
+use rincon_core::arango::protocol::PATH_API_BATCH;
+use super::types::*;
+
+/// Executes several methods with one request to the server.
+///
+/// The methods are added one by one with the `with_method` function. Each
+/// method may be of a different type. The result of a batch is a
+/// `BatchResponse` which holds the response of each method. The responses are
+/// converted into the results of the methods by calling `into_results` on the
+/// `BatchResponse`. For a batch of methods of the same type `into_results`
+/// takes the number of methods in the batch.
+///
+/// All methods of a batch are executed in the database of the connection the
+/// batch is executed on. The methods are executed independently of each
+/// other, so a failing method does not stop the execution of the others.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let batch = Batch::new()
+///     .with_method(GetServerVersion::new())
+///     .with_method(ListCollections::new());
+///
+/// let response = core.run(conn.execute(batch)).unwrap();
+/// let (version, collections) = response.into_results();
+/// ```
+///
+/// To execute many methods of the same type a batch can be collected from an
+/// iterator. The results of such a batch are returned as a `Vec`.
+///
+/// ```rust,ignore
+/// let batch: Batch<Vec<_>> = keys.iter()
+///     .map(|key| GetDocument::<Customer>::new("customers", DocumentKey::new(key)))
+///     .collect();
+/// let number_of_methods = batch.len();
+///
+/// let documents: Vec<Result<Document<Customer>, Error>> =
+///     core.run(conn.execute(batch)).unwrap().into_results(number_of_methods);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Batch<P> {
+    parts_type: PhantomData<P>,
+    content: BatchContent,
+}
+
+impl Batch<()> {
+    /// Constructs a new instance of the `Batch` method without any methods.
+    pub fn new() -> Self {
+        Batch {
+            parts_type: PhantomData,
+            content: BatchContent::new(),
+        }
+    }
+}
+
+impl Default for Batch<()> {
+    fn default() -> Self {
+        Batch::new()
+    }
+}
+
+impl<M> Batch<Vec<M>>
+    where M: Method + Prepare
+{
+    /// Constructs a new instance of the `Batch` method for the given methods
+    /// of the same type.
+    pub fn from_methods<I>(methods: I) -> Self
+        where I: IntoIterator<Item=M>
+    {
+        methods.into_iter().collect()
+    }
+}
+
+impl<M> FromIterator<M> for Batch<Vec<M>>
+    where M: Method + Prepare
+{
+    fn from_iter<I>(iter: I) -> Self
+        where I: IntoIterator<Item=M>
+    {
+        let mut content = BatchContent::new();
+        for method in iter {
+            content.push(&method);
+        }
+        Batch {
+            parts_type: PhantomData,
+            content,
+        }
+    }
+}
+
+impl<P> Batch<P> {
+    /// Adds the given method to this batch.
+    ///
+    /// The method is serialized immediately. If the method can not be
+    /// serialized the execution of the batch fails with a serialization
+    /// error.
+    pub fn with_method<M>(self, method: M) -> Batch<<P as Append<M>>::Output>
+        where P: Append<M>, M: Method + Prepare
+    {
+        let mut content = self.content;
+        content.push(&method);
+        Batch {
+            parts_type: PhantomData,
+            content,
+        }
+    }
+
+    /// Returns the number of methods in this batch.
+    pub fn len(&self) -> usize {
+        self.content.len()
+    }
+
+    /// Returns whether this batch does not contain any method.
+    pub fn is_empty(&self) -> bool {
+        self.content.is_empty()
+    }
+
+    /// Returns the serialized requests of the methods in this batch.
+    pub fn content(&self) -> &BatchContent {
+        &self.content
+    }
+}
+
+impl<P> Method for Batch<P>
+    where P: BatchParts
+{
+    type Result = BatchResponse<P>;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: None,
+    };
+}
+
+impl<P> Prepare for Batch<P> {
+    type Content = BatchContent;
+
+    fn operation(&self) -> Operation {
+        Operation::Create
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_BATCH)
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        Some(&self.content)
+    }
+
+    fn content_format(&self) -> ContentFormat {
+        ContentFormat::Multipart
+    }
+}
+
+/// Defines the type of a batch after another method has been added.
+///
+/// The methods of a batch are kept in a tuple, which grows by one element
+/// for each added method. Batches of methods of the same type are kept in a
+/// `Vec`.
+pub trait Append<M> {
+    /// The type of the methods after the method `M` has been added.
+    type Output;
+}
+
+impl<M> Append<M> for Vec<M> {
+    type Output = Vec<M>;
+}
+
+/// Converts the responses to the parts of a batch into the results of the
+/// methods in the batch.
+pub trait BatchParts {
+    /// The type of the results of the methods in the batch.
+    type Results;
+
+    /// Converts the given responses into the results of the given number
+    /// of methods.
+    ///
+    /// The responses are assigned to the methods by their content id. A
+    /// method for which no response has been received gets a
+    /// deserialization error as its result.
+    fn demultiplex(responses: Vec<PartResponse>, number_of_methods: usize) -> Self::Results;
+}
+
+impl BatchParts for () {
+    type Results = ();
+
+    fn demultiplex(_responses: Vec<PartResponse>, _number_of_methods: usize) -> Self::Results {}
+}
+
+impl<M> BatchParts for Vec<M>
+    where M: Method
+{
+    type Results = Vec<Result<<M as Method>::Result, Error>>;
+
+    fn demultiplex(responses: Vec<PartResponse>, number_of_methods: usize) -> Self::Results {
+        let mut parts = Demultiplexer::new(responses);
+        (0..number_of_methods).map(|_| parts.next::<M>()).collect()
+    }
+}
+
+macro_rules! batch_parts_tuple {
+    ( $( $name:ident ),* => $last:ident ) => {
+        impl<$( $name, )* $last> Append<$last> for ( $( $name, )* ) {
+            type Output = ( $( $name, )* $last, );
+        }
+
+        impl<$( $name, )* $last> BatchParts for ( $( $name, )* $last, )
+            where $( $name: Method, )* $last: Method
+        {
+            type Results = (
+                $( Result<<$name as Method>::Result, Error>, )*
+                Result<<$last as Method>::Result, Error>,
+            );
+
+            fn demultiplex(responses: Vec<PartResponse>, _number_of_methods: usize) -> Self::Results {
+                let mut parts = Demultiplexer::new(responses);
+                ( $( parts.next::<$name>(), )* parts.next::<$last>(), )
+            }
+        }
+
+        impl<$( $name, )* $last> BatchResponse<( $( $name, )* $last, )>
+            where $( $name: Method, )* $last: Method
+        {
+            /// Converts the responses into the results of the methods in the
+            /// batch.
+            ///
+            /// The results are returned as a tuple with one element for each
+            /// method. Each result is either the result of the method or the
+            /// error returned for the method.
+            pub fn into_results(self) -> <( $( $name, )* $last, ) as BatchParts>::Results {
+                let number_of_methods = [$( stringify!($name), )* stringify!($last)].len();
+                self.demultiplex(number_of_methods)
+            }
+        }
+    }
+}
+
+batch_parts_tuple!(=> M1);
+batch_parts_tuple!(M1 => M2);
+batch_parts_tuple!(M1, M2 => M3);
+batch_parts_tuple!(M1, M2, M3 => M4);
+batch_parts_tuple!(M1, M2, M3, M4 => M5);
+batch_parts_tuple!(M1, M2, M3, M4, M5 => M6);
+batch_parts_tuple!(M1, M2, M3, M4, M5, M6 => M7);
+batch_parts_tuple!(M1, M2, M3, M4, M5, M6, M7 => M8);
+batch_parts_tuple!(M1, M2, M3, M4, M5, M6, M7, M8 => M9);
+batch_parts_tuple!(M1, M2, M3, M4, M5, M6, M7, M8, M9 => M10);
+batch_parts_tuple!(M1, M2, M3, M4, M5, M6, M7, M8, M9, M10 => M11);
+batch_parts_tuple!(M1, M2, M3, M4, M5, M6, M7, M8, M9, M10, M11 => M12);
@@ -0,0 +1,364 @@
+
+use std::marker::PhantomData;
+
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+use serde_json::{self, Value};
+
+use rincon_core::api;
+use rincon_core::api::connector::Error;
+use rincon_core::api::json_lines;
+use rincon_core::api::method::{request_path, ContentFormat, Method, Prepare};
+use rincon_core::arango::ErrorCode;
+use rincon_core::arango::protocol::{BATCH_BOUNDARY, CONTENT_TYPE_BATCH_PART,
+    HEADER_CONTENT_ID, HEADER_CONTENT_TYPE};
+use super::methods::BatchParts;
+
+const HEADER_CONTENT_LENGTH: &str = "Content-Length";
+
+/// The content of a batch request.
+///
+/// It holds the serialized HTTP request of each method in the batch. The
+/// content serializes to the multipart body of the batch request. Each part
+/// gets a content id that is equal to the position of the method in the
+/// batch starting with 1.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BatchContent {
+    parts: Vec<Result<String, String>>,
+}
+
+impl BatchContent {
+    /// Constructs new `BatchContent` without any parts.
+    pub fn new() -> Self {
+        BatchContent {
+            parts: Vec::new(),
+        }
+    }
+
+    /// Serializes the request of the given method and appends it as a new
+    /// part.
+    pub fn push<P>(&mut self, prepare: &P)
+        where P: Prepare
+    {
+        self.parts.push(serialize_request(prepare));
+    }
+
+    /// Returns the number of parts.
+    pub fn len(&self) -> usize {
+        self.parts.len()
+    }
+
+    /// Returns whether there are no parts.
+    pub fn is_empty(&self) -> bool {
+        self.parts.is_empty()
+    }
+}
+
+impl Serialize for BatchContent {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        use serde::ser::Error;
+        let mut body = String::new();
+        for (index, part) in self.parts.iter().enumerate() {
+            let request = part.as_ref().map_err(|cause|
+                S::Error::custom(format!("Part {} of batch: {}", index + 1, cause)))?;
+            body.push_str(&format!("--{}\r\n", BATCH_BOUNDARY));
+            body.push_str(&format!("{}: {}\r\n", HEADER_CONTENT_TYPE, CONTENT_TYPE_BATCH_PART));
+            body.push_str(&format!("{}: {}\r\n\r\n", HEADER_CONTENT_ID, index + 1));
+            body.push_str(request);
+            body.push_str("\r\n");
+        }
+        body.push_str(&format!("--{}--\r\n", BATCH_BOUNDARY));
+        serializer.serialize_str(&body)
+    }
+}
+
+fn serialize_request<P>(prepare: &P) -> Result<String, String>
+    where P: Prepare
+{
+    let mut request = String::new();
+    request.push_str(prepare.operation().http_method());
+    request.push(' ');
+    request.push_str(&request_path(None, prepare));
+    request.push_str(" HTTP/1.1\r\n");
+    for (name, value) in prepare.header().iter() {
+        request.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    match prepare.content() {
+        Some(content) => {
+            let body = match prepare.content_format() {
+                ContentFormat::Json => serde_json::to_string(content)
+                    .map_err(|cause| cause.to_string())?,
+                ContentFormat::JsonLines => json_lines::to_vec(content)
+                    .map_err(|cause| cause.to_string())
+                    .and_then(|body| String::from_utf8(body).map_err(|cause| cause.to_string()))?,
+                ContentFormat::Multipart =>
+                    return Err("a batch can not contain another batch".into()),
+            };
+            request.push_str(&format!("{}: {}\r\n\r\n", HEADER_CONTENT_LENGTH, body.len()));
+            request.push_str(&body);
+        },
+        None => request.push_str("\r\n"),
+    }
+    Ok(request)
+}
+
+/// The response to a batch request.
+///
+/// It holds the responses to the parts of the batch as they have been
+/// returned by the server. The responses are converted into the results of
+/// the methods in the batch by the `into_results` function.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchResponse<P> {
+    parts_type: PhantomData<P>,
+    parts: Vec<PartResponse>,
+}
+
+impl<P> BatchResponse<P> {
+    /// Constructs a new `BatchResponse` with the given responses to the
+    /// parts of the batch.
+    pub fn new(parts: Vec<PartResponse>) -> Self {
+        BatchResponse {
+            parts_type: PhantomData,
+            parts,
+        }
+    }
+
+    /// Returns the responses to the parts of the batch.
+    pub fn parts(&self) -> &[PartResponse] {
+        &self.parts
+    }
+
+    /// Converts the responses into the results of the given number of
+    /// methods.
+    pub(crate) fn demultiplex(self, number_of_methods: usize) -> <P as BatchParts>::Results
+        where P: BatchParts
+    {
+        P::demultiplex(self.parts, number_of_methods)
+    }
+}
+
+impl BatchResponse<()> {
+    /// Converts the responses into the results of the methods in the batch.
+    ///
+    /// A batch without any methods has no results.
+    pub fn into_results(self) {}
+}
+
+impl<M> BatchResponse<Vec<M>>
+    where M: Method
+{
+    /// Converts the responses into the results of the methods in the batch.
+    ///
+    /// The number of methods must be the length of the batch as returned by
+    /// `Batch::len` before it has been executed. The returned `Vec` holds
+    /// one result for each method. Each result is either the result of the
+    /// method or the error returned for the method. A method for which no
+    /// response has been received gets a deserialization error as its
+    /// result.
+    pub fn into_results(self, number_of_methods: usize) -> Vec<Result<<M as Method>::Result, Error>> {
+        self.demultiplex(number_of_methods)
+    }
+}
+
+impl<'de, P> Deserialize<'de> for BatchResponse<P> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        use serde::de::Error;
+        let body = String::deserialize(deserializer)?;
+        parse_multipart(&body).map(BatchResponse::new).map_err(D::Error::custom)
+    }
+}
+
+/// The response to one part of a batch request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartResponse {
+    content_id: Option<String>,
+    status_code: u16,
+    body: String,
+}
+
+impl PartResponse {
+    /// Constructs a new `PartResponse` with the given values.
+    pub fn new<I, B>(content_id: I, status_code: u16, body: B) -> Self
+        where I: Into<Option<String>>, B: Into<String>
+    {
+        PartResponse {
+            content_id: content_id.into(),
+            status_code,
+            body: body.into(),
+        }
+    }
+
+    /// Returns the content id of the part this response belongs to.
+    pub fn content_id(&self) -> Option<&str> {
+        self.content_id.as_ref().map(|id| &id[..])
+    }
+
+    /// Returns the HTTP status code of this response.
+    pub fn status_code(&self) -> u16 {
+        self.status_code
+    }
+
+    /// Returns the body of this response.
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    /// Returns whether the method of this part has been executed
+    /// successfully.
+    pub fn is_success(&self) -> bool {
+        self.status_code >= 200 && self.status_code < 300
+    }
+
+    /// Converts this response into the result of the method `M`.
+    ///
+    /// If the method has failed the error returned by the server is
+    /// returned as `Error::Method`.
+    pub fn into_result<M>(self) -> Result<<M as Method>::Result, Error>
+        where M: Method
+    {
+        if self.is_success() {
+            let result = match M::RETURN_TYPE.result_field {
+                Some(result_field) => match serde_json::from_str(&self.body) {
+                    Ok(Value::Object(ref mut obj)) => match obj.remove(result_field) {
+                        Some(result_value) => serde_json::from_value(result_value),
+                        None => serde_json::from_str(&self.body),
+                    },
+                    _ => serde_json::from_str(&self.body),
+                },
+                None => serde_json::from_str(&self.body),
+            };
+            result.map_err(|cause| Error::Deserialization(cause.to_string()))
+        } else {
+            let api_error = serde_json::from_str(&self.body).unwrap_or_else(|_| {
+                let error_code = ErrorCode::from_u16(self.status_code);
+                let message = if self.body.is_empty() {
+                    error_code.description().to_owned()
+                } else {
+                    self.body.clone()
+                };
+                api::Error::new(self.status_code, error_code, message)
+            });
+            Err(Error::Method(api_error))
+        }
+    }
+}
+
+/// Assigns the responses to the parts of a batch to the methods in the
+/// batch.
+#[derive(Debug)]
+pub(crate) struct Demultiplexer {
+    parts: Vec<Option<PartResponse>>,
+    position: usize,
+}
+
+impl Demultiplexer {
+    /// Orders the given responses by their content id. Responses without a
+    /// valid content id are assigned to the first position not taken yet.
+    pub(crate) fn new(responses: Vec<PartResponse>) -> Self {
+        let mut parts: Vec<Option<PartResponse>> = Vec::with_capacity(responses.len());
+        let mut unassigned = Vec::new();
+        for response in responses {
+            match response.content_id().and_then(|id| id.trim().parse::<usize>().ok()) {
+                Some(content_id) if content_id > 0 => {
+                    if parts.len() < content_id {
+                        parts.resize(content_id, None);
+                    }
+                    parts[content_id - 1] = Some(response);
+                },
+                _ => unassigned.push(response),
+            }
+        }
+        let mut unassigned = unassigned.into_iter();
+        for part in &mut parts {
+            if part.is_none() {
+                *part = unassigned.next();
+            }
+        }
+        parts.extend(unassigned.map(Some));
+        Demultiplexer {
+            parts,
+            position: 0,
+        }
+    }
+
+    /// Returns the result of the method of the next part.
+    pub(crate) fn next<M>(&mut self) -> Result<<M as Method>::Result, Error>
+        where M: Method
+    {
+        self.position += 1;
+        match self.parts.get_mut(self.position - 1).and_then(Option::take) {
+            Some(response) => response.into_result::<M>(),
+            None => Err(Error::Deserialization(
+                format!("No response received for part {} of batch", self.position))),
+        }
+    }
+}
+
+/// Parses the multipart body of the response to a batch request.
+///
+/// The boundary is taken from the first line of the body.
+fn parse_multipart(body: &str) -> Result<Vec<PartResponse>, String> {
+    let start = match body.find("--") {
+        Some(start) => start,
+        None if body.trim().is_empty() => return Ok(Vec::new()),
+        None => return Err("No boundary found in batch response".into()),
+    };
+    let delimiter = body[start..].lines().next().unwrap_or_default().trim_end();
+    let mut parts = Vec::new();
+    for chunk in body[start + delimiter.len()..].split(delimiter) {
+        if chunk.starts_with("--") {
+            break;
+        }
+        if !chunk.trim().is_empty() {
+            parts.push(parse_part(chunk)?);
+        }
+    }
+    Ok(parts)
+}
+
+fn parse_part(chunk: &str) -> Result<PartResponse, String> {
+    let chunk = chunk.trim_start_matches(&['\r', '\n'][..]);
+    let (part_header, response) = split_head(chunk);
+    let content_id = header_value(part_header, HEADER_CONTENT_ID);
+    let (head, body) = split_head(response);
+    let status_code = head.lines().next()
+        .and_then(|status_line| status_line.split_whitespace().nth(1))
+        .and_then(|code| code.parse::<u16>().ok())
+        .ok_or_else(|| format!("Invalid status line in batch response: {:?}", head))?;
+    let body = match header_value(head, HEADER_CONTENT_LENGTH)
+        .and_then(|length| length.parse::<usize>().ok())
+    {
+        Some(length) if length <= body.len() && body.is_char_boundary(length) => &body[..length],
+        _ => body.trim_end_matches('\n').trim_end_matches('\r'),
+    };
+    Ok(PartResponse::new(content_id, status_code, body))
+}
+
+/// Splits the given text at the first empty line into the header and the
+/// remaining body.
+fn split_head(text: &str) -> (&str, &str) {
+    match text.find("\r\n\r\n") {
+        Some(index) => (&text[..index], &text[index + 4..]),
+        None => match text.find("\n\n") {
+            Some(index) => (&text[..index], &text[index + 2..]),
+            None => (text, ""),
+        },
+    }
+}
+
+fn header_value(head: &str, name: &str) -> Option<String> {
+    head.lines()
+        .filter_map(|line| {
+            let mut split = line.splitn(2, ':');
+            match (split.next(), split.next()) {
+                (Some(key), Some(value)) if key.trim().eq_ignore_ascii_case(name) =>
+                    Some(value.trim().to_owned()),
+                _ => None,
+            }
+        })
+        .next()
+}
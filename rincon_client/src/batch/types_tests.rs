@@ -0,0 +1,173 @@
+
+use serde_json;
+
+use rincon_core::api::connector::Error;
+use rincon_core::api::query::Query;
+use rincon_core::arango::ErrorCode;
+use admin::methods::GetServerVersion;
+use collection::methods::GetCollection;
+use cursor::methods::CreateCursor;
+use super::methods::*;
+use super::types::*;
+
+#[test]
+fn serialize_batch_content_as_multipart_body() {
+    let mut content = BatchContent::new();
+    content.push(&GetServerVersion::with_details());
+    content.push(&CreateCursor::<i32>::from_query(Query::new("RETURN 1")));
+
+    let cursor_body = serde_json::to_string(
+        CreateCursor::<i32>::from_query(Query::new("RETURN 1")).query()).unwrap();
+    let expected = format!("--XXXrinconBatchBoundaryXXX\r\n\
+        Content-Type: application/x-arango-batchpart\r\n\
+        Content-Id: 1\r\n\
+        \r\n\
+        GET /_api/version?details=true HTTP/1.1\r\n\
+        \r\n\
+        \r\n\
+        --XXXrinconBatchBoundaryXXX\r\n\
+        Content-Type: application/x-arango-batchpart\r\n\
+        Content-Id: 2\r\n\
+        \r\n\
+        POST /_api/cursor HTTP/1.1\r\n\
+        Content-Length: {}\r\n\
+        \r\n\
+        {}\r\n\
+        --XXXrinconBatchBoundaryXXX--\r\n", cursor_body.len(), cursor_body);
+
+    assert_eq!(json!(expected), serde_json::to_value(&content).unwrap());
+}
+
+#[test]
+fn serialize_batch_content_containing_another_batch_fails() {
+    let mut content = BatchContent::new();
+    content.push(&Batch::new().with_method(GetServerVersion::new()));
+
+    let result = serde_json::to_value(&content);
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn batch_counts_the_added_methods() {
+    let batch = Batch::new()
+        .with_method(GetServerVersion::new())
+        .with_method(GetCollection::with_name("customers"));
+
+    assert_eq!(2, batch.len());
+    assert!(!batch.is_empty());
+    assert!(Batch::new().is_empty());
+}
+
+#[test]
+fn deserialize_batch_response_into_tuple_of_results() {
+    let body = "--XXXrinconBatchBoundaryXXX\r\n\
+        Content-Type: application/x-arango-batchpart\r\n\
+        Content-Id: 1\r\n\
+        \r\n\
+        HTTP/1.1 200 OK\r\n\
+        Content-Type: application/json; charset=utf-8\r\n\
+        Content-Length: 60\r\n\
+        \r\n\
+        {\"server\":\"arango\",\"version\":\"3.2.10\",\"license\":\"community\"}\r\n\
+        --XXXrinconBatchBoundaryXXX\r\n\
+        Content-Type: application/x-arango-batchpart\r\n\
+        Content-Id: 2\r\n\
+        \r\n\
+        HTTP/1.1 404 Not Found\r\n\
+        Content-Type: application/json; charset=utf-8\r\n\
+        \r\n\
+        {\"error\":true,\"code\":404,\"errorNum\":1203,\
+        \"errorMessage\":\"collection not found\"}\r\n\
+        --XXXrinconBatchBoundaryXXX--\r\n";
+
+    let response: BatchResponse<(GetServerVersion, GetCollection)> =
+        serde_json::from_value(json!(body)).unwrap();
+
+    assert_eq!(2, response.parts().len());
+    assert_eq!(Some("1"), response.parts()[0].content_id());
+    assert_eq!(404, response.parts()[1].status_code());
+
+    let (version, collection) = response.into_results();
+    let version = version.unwrap();
+    assert_eq!("arango", version.server());
+    assert_eq!("3.2.10", version.version());
+    match collection {
+        Err(Error::Method(error)) => {
+            assert_eq!(404, error.status_code());
+            assert_eq!(ErrorCode::ArangoCollectionNotFound, error.error_code());
+        },
+        _ => panic!("Error::Method expected but got {:?}", collection),
+    }
+}
+
+#[test]
+fn responses_are_assigned_to_methods_by_content_id() {
+    let response: BatchResponse<Vec<GetServerVersion>> = BatchResponse::new(vec![
+        PartResponse::new("3".to_owned(), 200,
+            r#"{"server":"arango","version":"3.2.3","license":"community"}"#),
+        PartResponse::new("1".to_owned(), 200,
+            r#"{"server":"arango","version":"3.2.1","license":"community"}"#),
+    ]);
+
+    let results = response.into_results(3);
+
+    assert_eq!(3, results.len());
+    assert_eq!("3.2.1", results[0].as_ref().unwrap().version());
+    assert_eq!(Err(Error::Deserialization("No response received for part 2 of batch".to_owned())),
+        results[1]);
+    assert_eq!("3.2.3", results[2].as_ref().unwrap().version());
+}
+
+#[test]
+fn missing_responses_at_the_end_of_truncated_response_are_errors() {
+    let body = "--XXXrinconBatchBoundaryXXX\r\n\
+        Content-Type: application/x-arango-batchpart\r\n\
+        Content-Id: 1\r\n\
+        \r\n\
+        HTTP/1.1 200 OK\r\n\
+        Content-Type: application/json; charset=utf-8\r\n\
+        \r\n\
+        {\"server\":\"arango\",\"version\":\"3.2.10\",\"license\":\"community\"}\r\n\
+        --XXXrinconBatchBoundaryXXX--\r\n";
+    let response: BatchResponse<Vec<GetServerVersion>> =
+        serde_json::from_value(json!(body)).unwrap();
+
+    let results = response.into_results(3);
+
+    assert_eq!(3, results.len());
+    assert_eq!("3.2.10", results[0].as_ref().unwrap().version());
+    assert_eq!(Err(Error::Deserialization("No response received for part 2 of batch".to_owned())),
+        results[1]);
+    assert_eq!(Err(Error::Deserialization("No response received for part 3 of batch".to_owned())),
+        results[2]);
+}
+
+#[test]
+fn missing_responses_of_tuple_batch_are_errors() {
+    let response: BatchResponse<(GetServerVersion, GetServerVersion)> = BatchResponse::new(vec![
+        PartResponse::new(None, 200,
+            r#"{"server":"arango","version":"3.2.10","license":"community"}"#),
+    ]);
+
+    let (first, second) = response.into_results();
+
+    assert_eq!("3.2.10", first.unwrap().version());
+    assert_eq!(Err(Error::Deserialization("No response received for part 2 of batch".to_owned())),
+        second);
+}
+
+#[test]
+fn error_of_part_without_json_body_is_derived_from_status_code() {
+    let response = PartResponse::new("1".to_owned(), 500, "");
+
+    let result = response.into_result::<GetServerVersion>();
+
+    match result {
+        Err(Error::Method(error)) => {
+            assert_eq!(500, error.status_code());
+            assert_eq!(ErrorCode::HttpServerError, error.error_code());
+        },
+        _ => panic!("Error::Method expected but got {:?}", result),
+    }
+}
@@ -1,8 +1,10 @@
 
 use rincon_core::api::method::{Method, Operation, Parameters, Prepare, RpcReturnType};
-use rincon_core::arango::protocol::{FIELD_CODE, FIELD_ID, FIELD_RESULT,
-    PARAM_EXCLUDE_SYSTEM, PATH_API_COLLECTION, PATH_PROPERTIES, PATH_RENAME};
-#[cfg(feature = "cluster")]
+use rincon_core::arango::protocol::{FIELD_CODE, FIELD_COUNT, FIELD_ID,
+    FIELD_RESULT, PARAM_EXCLUDE_SYSTEM, PARAM_WITH_DATA, PARAM_WITH_REVISIONS,
+    PATH_API_COLLECTION, PATH_CHECKSUM, PATH_COUNT, PATH_FIGURES, PATH_LOAD,
+    PATH_LOAD_INDEXES_INTO_MEMORY, PATH_PROPERTIES, PATH_RENAME, PATH_REVISION,
+    PATH_ROTATE, PATH_TRUNCATE, PATH_UNLOAD};
 use rincon_core::arango::protocol::PARAM_WAIT_FOR_SYNC_REPLICATION;
 use super::types::*;
 
@@ -83,7 +85,6 @@ impl Prepare for ListCollections {
 #[derive(Clone, Debug, PartialEq)]
 pub struct CreateCollection {
     collection: NewCollection,
-    #[cfg(feature = "cluster")]
     wait_for_sync_replication: bool,
 }
 
@@ -93,7 +94,6 @@ impl CreateCollection {
     pub fn new(collection: NewCollection) -> Self {
         CreateCollection {
             collection,
-            #[cfg(feature = "cluster")]
             wait_for_sync_replication: true,
         }
     }
@@ -108,7 +108,6 @@ impl CreateCollection {
     {
         CreateCollection {
             collection: NewCollection::with_name(name),
-            #[cfg(feature = "cluster")]
             wait_for_sync_replication: true,
         }
     }
@@ -122,7 +121,6 @@ impl CreateCollection {
     {
         CreateCollection {
             collection: NewCollection::documents_with_name(name),
-            #[cfg(feature = "cluster")]
             wait_for_sync_replication: true,
         }
     }
@@ -136,12 +134,10 @@ impl CreateCollection {
     {
         CreateCollection {
             collection: NewCollection::edges_with_name(name),
-            #[cfg(feature = "cluster")]
             wait_for_sync_replication: true,
         }
     }
 
-    #[cfg(feature = "cluster")]
     /// Set whether the server shall wait until the new collection has been
     /// created at all replications before it returns the response.
     pub fn set_wait_for_sync_replication(&mut self, wait_for_sync_replication: bool) {
@@ -154,7 +150,6 @@ impl CreateCollection {
         &self.collection
     }
 
-    #[cfg(feature = "cluster")]
     /// Returns whether the request will wait until the new collection has
     /// been created at all replications.
     pub fn is_wait_for_sync_replication(&self) -> bool {
@@ -181,12 +176,6 @@ impl Prepare for CreateCollection {
         String::from(PATH_API_COLLECTION)
     }
 
-    #[cfg(not(feature = "cluster"))]
-    fn parameters(&self) -> Parameters {
-        Parameters::empty()
-    }
-
-    #[cfg(feature = "cluster")]
     fn parameters(&self) -> Parameters {
         let mut params = Parameters::with_capacity(1);
         if !self.wait_for_sync_replication {
@@ -583,3 +572,630 @@ impl RenameCollectionBuilder {
         }
     }
 }
+
+/// Counts the documents in the collection identified by the given name.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GetCollectionCount {
+    name: String,
+}
+
+impl GetCollectionCount {
+    /// Constructs a new instance of the `GetCollectionCount` method.
+    pub fn new(name: String) -> Self {
+        GetCollectionCount {
+            name,
+        }
+    }
+
+    /// Constructs a new instance of the `GetCollectionCount` method to
+    /// count the documents in the collection with the given name.
+    pub fn with_name<N>(name: N) -> Self
+        where N: Into<String>
+    {
+        GetCollectionCount {
+            name: name.into(),
+        }
+    }
+
+    /// Returns the name of the collection for which the documents shall be
+    /// counted.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Method for GetCollectionCount {
+    type Result = u64;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: Some(FIELD_COUNT),
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for GetCollectionCount {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Read
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_COLLECTION)
+            + "/" + &self.name
+            + PATH_COUNT
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Removes all documents from the collection identified by the given name,
+/// but leaves the indexes intact.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TruncateCollection {
+    name: String,
+}
+
+impl TruncateCollection {
+    /// Constructs a new instance of the `TruncateCollection` method.
+    pub fn new(name: String) -> Self {
+        TruncateCollection {
+            name,
+        }
+    }
+
+    /// Constructs a new instance of the `TruncateCollection` method to
+    /// remove all documents from the collection with the given name.
+    pub fn with_name<N>(name: N) -> Self
+        where N: Into<String>
+    {
+        TruncateCollection {
+            name: name.into(),
+        }
+    }
+
+    /// Returns the name of the collection that shall be truncated.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Method for TruncateCollection {
+    type Result = Collection;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for TruncateCollection {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Replace
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_COLLECTION)
+            + "/" + &self.name
+            + PATH_TRUNCATE
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Fetches the statistical figures of the collection identified by the given
+/// name.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GetCollectionFigures {
+    name: String,
+}
+
+impl GetCollectionFigures {
+    /// Constructs a new instance of the `GetCollectionFigures` method.
+    pub fn new(name: String) -> Self {
+        GetCollectionFigures {
+            name,
+        }
+    }
+
+    /// Constructs a new instance of the `GetCollectionFigures` method to
+    /// fetch the figures of the collection with the given name.
+    pub fn with_name<N>(name: N) -> Self
+        where N: Into<String>
+    {
+        GetCollectionFigures {
+            name: name.into(),
+        }
+    }
+
+    /// Returns the name of the collection for which the figures shall be
+    /// fetched.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Method for GetCollectionFigures {
+    type Result = CollectionFigures;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for GetCollectionFigures {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Read
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_COLLECTION)
+            + "/" + &self.name
+            + PATH_FIGURES
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Calculates a checksum for the collection identified by the given name.
+///
+/// By default the checksum is calculated over the keys of the documents only.
+/// The revision ids and the document data can be included in the calculation
+/// by setting the `with_revisions` and `with_data` parameters.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GetCollectionChecksum {
+    name: String,
+    with_revisions: bool,
+    with_data: bool,
+}
+
+impl GetCollectionChecksum {
+    /// Constructs a new instance of the `GetCollectionChecksum` method.
+    pub fn new(name: String) -> Self {
+        GetCollectionChecksum {
+            name,
+            with_revisions: false,
+            with_data: false,
+        }
+    }
+
+    /// Constructs a new instance of the `GetCollectionChecksum` method to
+    /// calculate the checksum of the collection with the given name.
+    pub fn with_name<N>(name: N) -> Self
+        where N: Into<String>
+    {
+        GetCollectionChecksum::new(name.into())
+    }
+
+    /// Returns the name of the collection for which the checksum shall be
+    /// calculated.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Sets whether the revision ids of the documents shall be included in
+    /// the checksum calculation.
+    pub fn set_with_revisions(&mut self, with_revisions: bool) {
+        self.with_revisions = with_revisions;
+    }
+
+    /// Returns whether the revision ids of the documents are going to be
+    /// included in the checksum calculation.
+    pub fn is_with_revisions(&self) -> bool {
+        self.with_revisions
+    }
+
+    /// Sets whether the data of the documents shall be included in the
+    /// checksum calculation.
+    pub fn set_with_data(&mut self, with_data: bool) {
+        self.with_data = with_data;
+    }
+
+    /// Returns whether the data of the documents is going to be included in
+    /// the checksum calculation.
+    pub fn is_with_data(&self) -> bool {
+        self.with_data
+    }
+}
+
+impl Method for GetCollectionChecksum {
+    type Result = CollectionChecksum;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for GetCollectionChecksum {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Read
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_COLLECTION)
+            + "/" + &self.name
+            + PATH_CHECKSUM
+    }
+
+    fn parameters(&self) -> Parameters {
+        let mut params = Parameters::with_capacity(2);
+        if self.with_revisions {
+            params.insert(PARAM_WITH_REVISIONS, true);
+        }
+        if self.with_data {
+            params.insert(PARAM_WITH_DATA, true);
+        }
+        params
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Fetches the revision id of the collection identified by the given name.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GetCollectionRevision {
+    name: String,
+}
+
+impl GetCollectionRevision {
+    /// Constructs a new instance of the `GetCollectionRevision` method.
+    pub fn new(name: String) -> Self {
+        GetCollectionRevision {
+            name,
+        }
+    }
+
+    /// Constructs a new instance of the `GetCollectionRevision` method to
+    /// fetch the revision id of the collection with the given name.
+    pub fn with_name<N>(name: N) -> Self
+        where N: Into<String>
+    {
+        GetCollectionRevision {
+            name: name.into(),
+        }
+    }
+
+    /// Returns the name of the collection for which the revision id shall
+    /// be fetched.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Method for GetCollectionRevision {
+    type Result = CollectionRevision;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for GetCollectionRevision {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Read
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_COLLECTION)
+            + "/" + &self.name
+            + PATH_REVISION
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Loads the collection identified by the given name into memory.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoadCollection {
+    name: String,
+}
+
+impl LoadCollection {
+    /// Constructs a new instance of the `LoadCollection` method.
+    pub fn new(name: String) -> Self {
+        LoadCollection {
+            name,
+        }
+    }
+
+    /// Constructs a new instance of the `LoadCollection` method to
+    /// load the collection with the given name into memory.
+    pub fn with_name<N>(name: N) -> Self
+        where N: Into<String>
+    {
+        LoadCollection {
+            name: name.into(),
+        }
+    }
+
+    /// Returns the name of the collection that shall be loaded.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Method for LoadCollection {
+    type Result = Collection;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for LoadCollection {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Replace
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_COLLECTION)
+            + "/" + &self.name
+            + PATH_LOAD
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Removes the collection identified by the given name from memory.
+///
+/// The collection is loaded again by the server on the next access.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnloadCollection {
+    name: String,
+}
+
+impl UnloadCollection {
+    /// Constructs a new instance of the `UnloadCollection` method.
+    pub fn new(name: String) -> Self {
+        UnloadCollection {
+            name,
+        }
+    }
+
+    /// Constructs a new instance of the `UnloadCollection` method to
+    /// unload the collection with the given name from memory.
+    pub fn with_name<N>(name: N) -> Self
+        where N: Into<String>
+    {
+        UnloadCollection {
+            name: name.into(),
+        }
+    }
+
+    /// Returns the name of the collection that shall be unloaded.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Method for UnloadCollection {
+    type Result = Collection;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for UnloadCollection {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Replace
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_COLLECTION)
+            + "/" + &self.name
+            + PATH_UNLOAD
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Loads the indexes of the collection identified by the given name into
+/// memory.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoadCollectionIndexes {
+    name: String,
+}
+
+impl LoadCollectionIndexes {
+    /// Constructs a new instance of the `LoadCollectionIndexes` method.
+    pub fn new(name: String) -> Self {
+        LoadCollectionIndexes {
+            name,
+        }
+    }
+
+    /// Constructs a new instance of the `LoadCollectionIndexes` method to
+    /// load the indexes of the collection with the given name into memory.
+    pub fn with_name<N>(name: N) -> Self
+        where N: Into<String>
+    {
+        LoadCollectionIndexes {
+            name: name.into(),
+        }
+    }
+
+    /// Returns the name of the collection of which the indexes shall be
+    /// loaded.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Method for LoadCollectionIndexes {
+    type Result = bool;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: Some(FIELD_RESULT),
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for LoadCollectionIndexes {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Replace
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_COLLECTION)
+            + "/" + &self.name
+            + PATH_LOAD_INDEXES_INTO_MEMORY
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Rotates the journal of the collection identified by the given name.
+///
+/// The current journal of the collection is closed and made a read-only
+/// datafile. This method is specific to the MMFiles storage engine.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RotateCollectionJournal {
+    name: String,
+}
+
+impl RotateCollectionJournal {
+    /// Constructs a new instance of the `RotateCollectionJournal` method.
+    pub fn new(name: String) -> Self {
+        RotateCollectionJournal {
+            name,
+        }
+    }
+
+    /// Constructs a new instance of the `RotateCollectionJournal` method to
+    /// rotate the journal of the collection with the given name.
+    pub fn with_name<N>(name: N) -> Self
+        where N: Into<String>
+    {
+        RotateCollectionJournal {
+            name: name.into(),
+        }
+    }
+
+    /// Returns the name of the collection of which the journal shall be
+    /// rotated.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Method for RotateCollectionJournal {
+    type Result = bool;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: Some(FIELD_RESULT),
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for RotateCollectionJournal {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Replace
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_COLLECTION)
+            + "/" + &self.name
+            + PATH_ROTATE
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
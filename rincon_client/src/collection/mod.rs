@@ -1,5 +1,6 @@
 
 pub mod methods;
 pub mod types;
+#[cfg(test)] mod types_tests;
 
 pub mod prelude;
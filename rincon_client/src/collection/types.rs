@@ -5,6 +5,8 @@ use std::i32;
 use serde::de::{Deserialize, Deserializer};
 use serde::ser::{Serialize, Serializer};
 
+use admin::types::EngineSpecific;
+
 const COLLECTION_TYPE_DOCUMENTS: i32 = 2;
 const COLLECTION_TYPE_EDGES: i32 = 3;
 
@@ -108,7 +110,6 @@ pub struct NewCollection {
     #[serde(skip_serializing_if = "Option::is_none")]
     wait_for_sync: Option<bool>,
 
-    #[cfg(feature = "cluster")]
     /// In a cluster, this value determines the number of shards to create for
     /// the collection.
     /// (The default is 1)
@@ -117,7 +118,6 @@ pub struct NewCollection {
     #[serde(skip_serializing_if = "Option::is_none")]
     number_of_shards: Option<u16>,
 
-    #[cfg(feature = "cluster")]
     /// (The default is [ "_key" ])
     ///
     /// In a cluster, this attribute determines which document attributes are
@@ -130,9 +130,8 @@ pub struct NewCollection {
     ///
     /// In a single server setup, this option is meaningless.
     #[serde(skip_serializing_if = "Option::is_none")]
-    shard_keys: Option<String>,
+    shard_keys: Option<Vec<String>>,
 
-    #[cfg(feature = "cluster")]
     /// The replication factor.
     /// (The default is 1)
     ///
@@ -150,7 +149,6 @@ pub struct NewCollection {
     #[serde(skip_serializing_if = "Option::is_none")]
     replication_factor: Option<u16>,
 
-    #[cfg(feature = "mmfiles")]
     /// If true then the collection data is kept in-memory only and not made
     /// persistent.
     /// (The default is false)
@@ -168,7 +166,6 @@ pub struct NewCollection {
     #[serde(skip_serializing_if = "Option::is_none")]
     is_volatile: Option<bool>,
 
-    #[cfg(feature = "mmfiles")]
     /// Whether or not the collection will be compacted.
     /// (default is true)
     ///
@@ -176,7 +173,6 @@ pub struct NewCollection {
     #[serde(skip_serializing_if = "Option::is_none")]
     do_compact: Option<bool>,
 
-    #[cfg(feature = "mmfiles")]
     /// The number of buckets into which indexes using a hash table are split.
     /// (The default is 16)
     ///
@@ -195,7 +191,6 @@ pub struct NewCollection {
     #[serde(skip_serializing_if = "Option::is_none")]
     index_buckets: Option<u16>,
 
-    #[cfg(feature = "mmfiles")]
     /// The maximal size of a journal or datafile in bytes.
     /// (The default is a configuration parameter)
     ///
@@ -216,19 +211,12 @@ impl NewCollection {
             is_system: is_system.into(),
             key_options: None,
             wait_for_sync: None,
-            #[cfg(feature = "cluster")]
             number_of_shards: None,
-            #[cfg(feature = "cluster")]
             shard_keys: None,
-            #[cfg(feature = "cluster")]
             replication_factor: None,
-            #[cfg(feature = "mmfiles")]
             is_volatile: None,
-            #[cfg(feature = "mmfiles")]
             do_compact: None,
-            #[cfg(feature = "mmfiles")]
             index_buckets: None,
-            #[cfg(feature = "mmfiles")]
             journal_size: None,
         }
     }
@@ -339,7 +327,6 @@ impl NewCollection {
         self.wait_for_sync
     }
 
-    #[cfg(feature = "cluster")]
     /// Sets the number of shards that shall be created for this collection.
     pub fn set_number_of_shards<S>(&mut self, number_of_shards: S)
         where S: Into<Option<u16>>
@@ -347,27 +334,23 @@ impl NewCollection {
         self.number_of_shards = number_of_shards.into();
     }
 
-    #[cfg(feature = "cluster")]
     /// Returns the number of shards that shall be created for this collection.
     pub fn number_of_shards(&self) -> Option<u16> {
         self.number_of_shards
     }
 
-    #[cfg(feature = "cluster")]
     /// Sets the keys to determine the shard for a collection.
     pub fn set_shard_keys<K>(&mut self, shard_keys: K)
-        where K: Into<Option<String>>
+        where K: Into<Option<Vec<String>>>
     {
         self.shard_keys = shard_keys.into();
     }
 
-    #[cfg(feature = "cluster")]
     /// Returns the keys to determine the shard for a collection.
-    pub fn shard_keys(&self) -> Option<&String> {
-        self.shard_keys.as_ref()
+    pub fn shard_keys(&self) -> Option<&[String]> {
+        self.shard_keys.as_ref().map(|keys| &keys[..])
     }
 
-    #[cfg(feature = "cluster")]
     /// Sets the number of copies that are kept of each shard.
     pub fn set_replication_factor<R>(&mut self, replication_factor: R)
         where R: Into<Option<u16>>
@@ -375,13 +358,11 @@ impl NewCollection {
         self.replication_factor = replication_factor.into();
     }
 
-    #[cfg(feature = "cluster")]
     /// Returns the number of copies that are kept of each shard.
     pub fn replication_factor(&self) -> Option<u16> {
         self.replication_factor
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Sets whether this collection is going to be a volatile collection.
     pub fn set_volatile<V>(&mut self, volatile: V)
         where V: Into<Option<bool>>
@@ -389,13 +370,11 @@ impl NewCollection {
         self.is_volatile = volatile.into();
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Returns whether this collection is going to be a volatile collection.
     pub fn is_volatile(&self) -> Option<bool> {
         self.is_volatile
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Sets whether this collection is going to be compacted.
     pub fn set_do_compact<C>(&mut self, do_compact: C)
         where C: Into<Option<bool>>
@@ -403,13 +382,11 @@ impl NewCollection {
         self.do_compact = do_compact.into();
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Returns whether this collection is going to be compacted.
     pub fn is_do_compact(&self) -> Option<bool> {
         self.do_compact
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Sets the number of buckets into which indexes using a hash table
     /// are split.
     pub fn set_index_buckets<B>(&mut self, index_buckets: B)
@@ -418,14 +395,12 @@ impl NewCollection {
         self.index_buckets = index_buckets.into();
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Returns the number of buckets into which indexes using a hash table
     /// are split.
     pub fn index_buckets(&self) -> Option<u16> {
         self.index_buckets
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Sets the maximal size of a journal or datafile in bytes.
     pub fn set_journal_size<J>(&mut self, journal_size: J)
         where J: Into<Option<u64>>
@@ -433,7 +408,6 @@ impl NewCollection {
         self.journal_size = journal_size.into();
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Returns the maximal size of a journal or datafile in bytes.
     pub fn journal_size(&self) -> Option<u64> {
         self.journal_size
@@ -568,9 +542,10 @@ pub struct BasicCollectionProperties {
     /// the file system before returning the response.
     wait_for_sync: bool,
 
-    #[cfg(feature = "mmfiles")]
     /// Whether this collection is volatile.
-    is_volatile: bool,
+    ///
+    /// Only returned by servers using the MMFiles storage engine.
+    is_volatile: Option<bool>,
 }
 
 impl BasicCollectionProperties {
@@ -606,9 +581,10 @@ impl BasicCollectionProperties {
         self.wait_for_sync
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Returns whether this collection is a volatile collection.
-    pub fn is_volatile(&self) -> bool {
+    ///
+    /// Returns `None` if the server does not use the MMFiles storage engine.
+    pub fn is_volatile(&self) -> Option<bool> {
         self.is_volatile
     }
 }
@@ -642,33 +618,40 @@ pub struct CollectionProperties {
     /// the file system before returning the response.
     wait_for_sync: bool,
 
-    #[cfg(feature = "cluster")]
     /// The number of shards of the collection.
-    number_of_shards: u16,
+    ///
+    /// Only returned by servers that are part of a cluster.
+    number_of_shards: Option<u16>,
 
-    #[cfg(feature = "cluster")]
     /// The keys used to identify the shards of a collection.
-    shard_keys: String,
+    ///
+    /// Only returned by servers that are part of a cluster.
+    shard_keys: Option<Vec<String>>,
 
-    #[cfg(feature = "cluster")]
     /// The number of copies that are kept of each shard.
-    replication_factor: u64,
+    ///
+    /// Only returned by servers that are part of a cluster.
+    replication_factor: Option<u64>,
 
-    #[cfg(feature = "mmfiles")]
     /// Whether this collection is volatile.
-    is_volatile: bool,
+    ///
+    /// Only returned by servers using the MMFiles storage engine.
+    is_volatile: Option<bool>,
 
-    #[cfg(feature = "mmfiles")]
     /// Whether this collection is compacted.
-    do_compact: bool,
+    ///
+    /// Only returned by servers using the MMFiles storage engine.
+    do_compact: Option<bool>,
 
-    #[cfg(feature = "mmfiles")]
     /// The number of buckets into which indexes using a hash table are split.
-    index_buckets: u16,
+    ///
+    /// Only returned by servers using the MMFiles storage engine.
+    index_buckets: Option<u16>,
 
-    #[cfg(feature = "mmfiles")]
     /// The maximal size of a journal or datafile in bytes.
-    journal_size: u64,
+    ///
+    /// Only returned by servers using the MMFiles storage engine.
+    journal_size: Option<u64>,
 }
 
 impl CollectionProperties {
@@ -709,46 +692,53 @@ impl CollectionProperties {
         self.wait_for_sync
     }
 
-    #[cfg(feature = "cluster")]
     /// Returns the number of shards for this collection.
-    pub fn number_of_shards(&self) -> u16 {
+    ///
+    /// Returns `None` if the server is not part of a cluster.
+    pub fn number_of_shards(&self) -> Option<u16> {
         self.number_of_shards
     }
 
-    #[cfg(feature = "cluster")]
     /// Returns the keys to determine the shard for a collection.
-    pub fn shard_keys(&self) -> &str {
-        &self.shard_keys
+    ///
+    /// Returns `None` if the server is not part of a cluster.
+    pub fn shard_keys(&self) -> Option<&[String]> {
+        self.shard_keys.as_ref().map(|keys| &keys[..])
     }
 
-    #[cfg(feature = "cluster")]
     /// Returns the number of copies that are kept of each shard.
-    pub fn replication_factor(&self) -> u64 {
+    ///
+    /// Returns `None` if the server is not part of a cluster.
+    pub fn replication_factor(&self) -> Option<u64> {
         self.replication_factor
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Returns whether this collection is a volatile collection.
-    pub fn is_volatile(&self) -> bool {
+    ///
+    /// Returns `None` if the server does not use the MMFiles storage engine.
+    pub fn is_volatile(&self) -> Option<bool> {
         self.is_volatile
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Returns whether this collection is compacted.
-    pub fn is_do_compact(&self) -> bool {
+    ///
+    /// Returns `None` if the server does not use the MMFiles storage engine.
+    pub fn is_do_compact(&self) -> Option<bool> {
         self.do_compact
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Returns the number of buckets into which indexes using a hash table
     /// are split.
-    pub fn index_buckets(&self) -> u16 {
+    ///
+    /// Returns `None` if the server does not use the MMFiles storage engine.
+    pub fn index_buckets(&self) -> Option<u16> {
         self.index_buckets
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Returns the maximal size of a journal or datafile in bytes.
-    pub fn journal_size(&self) -> u64 {
+    ///
+    /// Returns `None` if the server does not use the MMFiles storage engine.
+    pub fn journal_size(&self) -> Option<u64> {
         self.journal_size
     }
 }
@@ -763,7 +753,6 @@ pub struct CollectionPropertiesUpdate {
     /// the file system before returning the response.
     wait_for_sync: Option<bool>,
 
-    #[cfg(feature = "mmfiles")]
     /// The maximal size of a journal or datafile in bytes.
     journal_size: Option<u64>,
 }
@@ -774,7 +763,6 @@ impl CollectionPropertiesUpdate {
     pub fn new() -> Self {
         CollectionPropertiesUpdate {
             wait_for_sync: None,
-            #[cfg(feature = "mmfiles")]
             journal_size: None,
         }
     }
@@ -793,7 +781,6 @@ impl CollectionPropertiesUpdate {
         self.wait_for_sync
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Sets the maximal size of a journal or datafile in bytes.
     pub fn set_journal_size<J>(&mut self, journal_size: J)
         where J: Into<Option<u64>>
@@ -801,7 +788,6 @@ impl CollectionPropertiesUpdate {
         self.journal_size = journal_size.into();
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Returns the maximal size of a journal or datafile in bytes.
     pub fn journal_size(&self) -> Option<u64> {
         self.journal_size
@@ -832,6 +818,375 @@ impl RenameTo {
     }
 }
 
+/// This struct holds the statistical figures of a collection.
+///
+/// It is returned by the `GetCollectionFigures` method.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionFigures {
+    /// The id of the collection.
+    id: String,
+
+    /// The name of the collection.
+    name: String,
+
+    /// The type of the collection.
+    #[serde(rename = "type")]
+    kind: CollectionType,
+
+    /// The status of the collection.
+    status: CollectionStatus,
+
+    /// Whether the collection is a system collection or regular collection.
+    is_system: bool,
+
+    /// The number of documents in the collection.
+    count: u64,
+
+    /// The figures of the collection.
+    figures: Figures,
+}
+
+impl CollectionFigures {
+    /// Returns the id of the collection.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the name of the collection.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the type of the collection.
+    pub fn kind(&self) -> CollectionType {
+        self.kind
+    }
+
+    /// Returns the status of the collection.
+    pub fn status(&self) -> CollectionStatus {
+        self.status
+    }
+
+    /// Returns whether the collection is a system or regular collection.
+    pub fn is_system(&self) -> bool {
+        self.is_system
+    }
+
+    /// Returns the number of documents in the collection.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the figures of the collection.
+    pub fn figures(&self) -> &Figures {
+        &self.figures
+    }
+}
+
+/// This struct holds the figures about the storage of a collection as
+/// reported by the storage engine.
+///
+/// Apart from the indexes the figures depend on the storage engine used by
+/// the server. They are provided as `EngineSpecific` value.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Figures {
+    /// The number and total size of the indexes of the collection.
+    indexes: FigureCount,
+
+    /// The figures specific to the storage engine of the server.
+    #[serde(flatten)]
+    engine_specific: EngineSpecific<MmFilesFigures, RocksDbFigures>,
+}
+
+impl Figures {
+    /// Returns the number and total size of the indexes.
+    pub fn indexes(&self) -> &FigureCount {
+        &self.indexes
+    }
+
+    /// Returns the figures specific to the storage engine of the server.
+    pub fn engine_specific(&self) -> &EngineSpecific<MmFilesFigures, RocksDbFigures> {
+        &self.engine_specific
+    }
+
+    /// Returns the figures specific to the MMFiles storage engine or `None`
+    /// if the server uses another storage engine.
+    pub fn mmfiles(&self) -> Option<&MmFilesFigures> {
+        self.engine_specific.mmfiles()
+    }
+
+    /// Returns the figures specific to the RocksDB storage engine or `None`
+    /// if the server uses another storage engine.
+    pub fn rocksdb(&self) -> Option<&RocksDbFigures> {
+        self.engine_specific.rocksdb()
+    }
+}
+
+/// This struct holds the figures of a collection that are reported by
+/// servers using the MMFiles storage engine.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MmFilesFigures {
+    /// The number and total size of all living documents.
+    alive: FigureCount,
+
+    /// The number and total size of all dead documents.
+    dead: FigureCount,
+
+    /// The number and total file size of the datafiles.
+    datafiles: FileFigures,
+
+    /// The number and total file size of the journal files.
+    journals: FileFigures,
+
+    /// The number and total file size of the compactor files.
+    compactors: FileFigures,
+
+    /// The number of markers in the write-ahead log for this collection that
+    /// have not been transferred to journals or datafiles.
+    uncollected_logfile_entries: u64,
+}
+
+impl MmFilesFigures {
+    /// Returns the number and total size of all living documents.
+    pub fn alive(&self) -> &FigureCount {
+        &self.alive
+    }
+
+    /// Returns the number and total size of all dead documents.
+    pub fn dead(&self) -> &FigureCount {
+        &self.dead
+    }
+
+    /// Returns the number and total file size of the datafiles.
+    pub fn datafiles(&self) -> &FileFigures {
+        &self.datafiles
+    }
+
+    /// Returns the number and total file size of the journal files.
+    pub fn journals(&self) -> &FileFigures {
+        &self.journals
+    }
+
+    /// Returns the number and total file size of the compactor files.
+    pub fn compactors(&self) -> &FileFigures {
+        &self.compactors
+    }
+
+    /// Returns the number of markers in the write-ahead log that have not
+    /// been transferred to journals or datafiles yet.
+    pub fn uncollected_logfile_entries(&self) -> u64 {
+        self.uncollected_logfile_entries
+    }
+}
+
+/// This struct holds the figures of a collection that are reported by
+/// servers using the RocksDB storage engine.
+///
+/// Older server versions do not report any of these figures.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RocksDbFigures {
+    /// The total size of all documents in bytes.
+    documents_size: Option<u64>,
+
+    /// Whether the document cache is enabled for this collection.
+    cache_in_use: Option<bool>,
+
+    /// The size of the document cache in bytes.
+    cache_size: Option<u64>,
+}
+
+impl RocksDbFigures {
+    /// Returns the total size of all documents in bytes.
+    pub fn documents_size(&self) -> Option<u64> {
+        self.documents_size
+    }
+
+    /// Returns whether the document cache is enabled for this collection.
+    pub fn is_cache_in_use(&self) -> Option<bool> {
+        self.cache_in_use
+    }
+
+    /// Returns the size of the document cache in bytes.
+    pub fn cache_size(&self) -> Option<u64> {
+        self.cache_size
+    }
+}
+
+/// This struct holds the number and total size in bytes of some kind of
+/// entries, e.g. documents or indexes.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+pub struct FigureCount {
+    /// The number of entries.
+    count: u64,
+
+    /// The total size of the entries in bytes.
+    size: u64,
+}
+
+impl FigureCount {
+    /// Returns the number of entries.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the total size of the entries in bytes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+/// This struct holds the number and total size in bytes of some kind of
+/// files, e.g. datafiles or journals.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileFigures {
+    /// The number of files.
+    count: u64,
+
+    /// The total size of the files in bytes.
+    file_size: u64,
+}
+
+impl FileFigures {
+    /// Returns the number of files.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the total size of the files in bytes.
+    pub fn file_size(&self) -> u64 {
+        self.file_size
+    }
+}
+
+/// This struct holds the checksum of a collection.
+///
+/// It is returned by the `GetCollectionChecksum` method.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionChecksum {
+    /// The id of the collection.
+    id: String,
+
+    /// The name of the collection.
+    name: String,
+
+    /// The type of the collection.
+    #[serde(rename = "type")]
+    kind: CollectionType,
+
+    /// The status of the collection.
+    status: CollectionStatus,
+
+    /// Whether the collection is a system collection or regular collection.
+    is_system: bool,
+
+    /// The calculated checksum.
+    checksum: String,
+
+    /// The revision id of the collection.
+    revision: String,
+}
+
+impl CollectionChecksum {
+    /// Returns the id of the collection.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the name of the collection.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the type of the collection.
+    pub fn kind(&self) -> CollectionType {
+        self.kind
+    }
+
+    /// Returns the status of the collection.
+    pub fn status(&self) -> CollectionStatus {
+        self.status
+    }
+
+    /// Returns whether the collection is a system or regular collection.
+    pub fn is_system(&self) -> bool {
+        self.is_system
+    }
+
+    /// Returns the calculated checksum.
+    pub fn checksum(&self) -> &str {
+        &self.checksum
+    }
+
+    /// Returns the revision id of the collection at the time the checksum
+    /// has been calculated.
+    pub fn revision(&self) -> &str {
+        &self.revision
+    }
+}
+
+/// This struct holds the revision id of a collection.
+///
+/// It is returned by the `GetCollectionRevision` method.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionRevision {
+    /// The id of the collection.
+    id: String,
+
+    /// The name of the collection.
+    name: String,
+
+    /// The type of the collection.
+    #[serde(rename = "type")]
+    kind: CollectionType,
+
+    /// The status of the collection.
+    status: CollectionStatus,
+
+    /// Whether the collection is a system collection or regular collection.
+    is_system: bool,
+
+    /// The revision id of the collection.
+    revision: String,
+}
+
+impl CollectionRevision {
+    /// Returns the id of the collection.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the name of the collection.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the type of the collection.
+    pub fn kind(&self) -> CollectionType {
+        self.kind
+    }
+
+    /// Returns the status of the collection.
+    pub fn status(&self) -> CollectionStatus {
+        self.status
+    }
+
+    /// Returns whether the collection is a system or regular collection.
+    pub fn is_system(&self) -> bool {
+        self.is_system
+    }
+
+    /// Returns the revision id of the collection.
+    pub fn revision(&self) -> &str {
+        &self.revision
+    }
+}
+
 /// This struct holds the key related properties.
 #[allow(missing_copy_implementations)]
 #[derive(Clone, Debug, Deserialize)]
@@ -0,0 +1,83 @@
+
+use serde_json;
+use admin::types::EngineType;
+use super::types::*;
+
+#[test]
+fn deserialize_collection_properties_of_rocksdb_server() {
+    let json_str = r#"{
+        "id": "10145",
+        "name": "customers",
+        "type": 2,
+        "status": 3,
+        "isSystem": false,
+        "keyOptions": { "allowUserKeys": true, "type": "traditional", "lastValue": 0 },
+        "waitForSync": false
+    }"#;
+
+    let properties: CollectionProperties = serde_json::from_str(json_str).unwrap();
+
+    assert_eq!("customers", properties.name());
+    assert_eq!(None, properties.is_volatile());
+    assert_eq!(None, properties.journal_size());
+}
+
+#[test]
+fn deserialize_collection_figures_of_mmfiles_server() {
+    let json_str = r#"{
+        "id": "10145",
+        "name": "customers",
+        "type": 2,
+        "status": 3,
+        "isSystem": false,
+        "count": 2,
+        "figures": {
+            "alive": { "count": 2, "size": 104 },
+            "dead": { "count": 1, "size": 52, "deletion": 0 },
+            "datafiles": { "count": 0, "fileSize": 0 },
+            "journals": { "count": 1, "fileSize": 33554432 },
+            "compactors": { "count": 0, "fileSize": 0 },
+            "indexes": { "count": 1, "size": 32128 },
+            "uncollectedLogfileEntries": 0,
+            "lastTick": "10158"
+        }
+    }"#;
+
+    let figures: CollectionFigures = serde_json::from_str(json_str).unwrap();
+
+    assert_eq!(2, figures.count());
+    assert_eq!(EngineType::MmFiles, figures.figures().engine_specific().engine());
+    assert_eq!(1, figures.figures().indexes().count());
+    let mmfiles = figures.figures().mmfiles().unwrap();
+    assert_eq!(104, mmfiles.alive().size());
+    assert_eq!(33_554_432, mmfiles.journals().file_size());
+    assert!(figures.figures().rocksdb().is_none());
+}
+
+#[test]
+fn deserialize_collection_figures_of_rocksdb_server() {
+    let json_str = r#"{
+        "id": "10145",
+        "name": "customers",
+        "type": 2,
+        "status": 3,
+        "isSystem": false,
+        "count": 2,
+        "figures": {
+            "indexes": { "count": 1, "size": 1024 },
+            "documentsSize": 412,
+            "cacheInUse": false,
+            "cacheSize": 0,
+            "cacheUsage": 0
+        }
+    }"#;
+
+    let figures: CollectionFigures = serde_json::from_str(json_str).unwrap();
+
+    assert_eq!(EngineType::RocksDb, figures.figures().engine_specific().engine());
+    assert_eq!(1024, figures.figures().indexes().size());
+    let rocksdb = figures.figures().rocksdb().unwrap();
+    assert_eq!(Some(412), rocksdb.documents_size());
+    assert_eq!(Some(false), rocksdb.is_cache_in_use());
+    assert!(figures.figures().mmfiles().is_none());
+}
@@ -6,7 +6,8 @@ use serde::de::DeserializeOwned;
 use rincon_core::api::method::{Method, Operation, Parameters, Prepare, RpcReturnType};
 use rincon_core::api::query::Query;
 use rincon_core::api::types::Empty;
-use rincon_core::arango::protocol::{FIELD_CODE, PATH_API_CURSOR};
+use rincon_core::arango::protocol::{FIELD_CODE, PARAM_COLLECTION, PATH_API_CURSOR,
+    PATH_API_EXPORT};
 use super::types::*;
 
 /// Executes a query and returns a cursor with the first result set.
@@ -75,6 +76,92 @@ impl<T> Prepare for CreateCursor<T> {
     }
 }
 
+/// Exports the documents of a collection and returns a cursor with the first
+/// result set.
+///
+/// The documents are read directly from the collection without executing a
+/// query, which makes the export much faster than a query for dumping whole
+/// collections. Further result sets are read with the
+/// `ReadNextBatchFromCursor` method.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CreateExportCursor<T> {
+    result_type: PhantomData<T>,
+    collection_name: String,
+    export: NewExport,
+}
+
+impl<T> CreateExportCursor<T> {
+    /// Constructs a new instance of the `CreateExportCursor` method for the
+    /// collection with the given name and the given `NewExport` parameters.
+    pub fn new(collection_name: String, export: NewExport) -> Self {
+        CreateExportCursor {
+            result_type: PhantomData,
+            collection_name,
+            export,
+        }
+    }
+
+    /// Constructs a new instance of the `CreateExportCursor` method that
+    /// exports all documents of the collection with the given name.
+    ///
+    /// All export parameters are left to their default values.
+    pub fn with_name<N>(collection_name: N) -> Self
+        where N: Into<String>
+    {
+        CreateExportCursor {
+            result_type: PhantomData,
+            collection_name: collection_name.into(),
+            export: NewExport::new(),
+        }
+    }
+
+    /// Returns the name of the collection to be exported.
+    pub fn collection_name(&self) -> &str {
+        &self.collection_name
+    }
+
+    /// Returns the parameters of the export.
+    pub fn export(&self) -> &NewExport {
+        &self.export
+    }
+}
+
+impl<T> Method for CreateExportCursor<T>
+    where T: DeserializeOwned
+{
+    type Result = Cursor<T>;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl<T> Prepare for CreateExportCursor<T> {
+    type Content = NewExport;
+
+    fn operation(&self) -> Operation {
+        Operation::Create
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_EXPORT)
+    }
+
+    fn parameters(&self) -> Parameters {
+        let mut params = Parameters::with_capacity(1);
+        params.insert(PARAM_COLLECTION, self.collection_name.to_owned());
+        params
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        Some(&self.export)
+    }
+}
+
 /// Deletes the cursor and frees the resources associated with it.
 #[derive(Clone, Debug, PartialEq)]
 pub struct DeleteCursor {
@@ -2,10 +2,16 @@
 use std::collections::HashMap;
 use std::mem;
 
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
 use rincon_core::api::query::Query;
 use rincon_core::api::types::{JsonValue, Value};
 use aql::types::Optimizer;
 
+const RESTRICTION_TYPE_INCLUDE: &str = "include";
+const RESTRICTION_TYPE_EXCLUDE: &str = "exclude";
+
 /// A temporary cursor for retrieving query results.
 ///
 /// If the result set contains more documents than should be transferred in a
@@ -44,6 +50,9 @@ pub struct Cursor<T> {
     /// query cache or not. If the query result is served from the query cache,
     /// the extra return attribute will not contain any stats sub-attribute and
     /// no profile sub-attribute.
+    ///
+    /// Cursors created by an export do not return this flag.
+    #[serde(default)]
     cached: bool,
 
     /// An optional JSON object with extra information about the query result
@@ -66,6 +75,15 @@ impl<T> Cursor<T> {
         &self.result
     }
 
+    /// Moves the result documents out of this cursor.
+    ///
+    /// After calling this function this `Cursor` instance is consumed. The
+    /// id and the `has_more` flag must be read before if they are still
+    /// needed.
+    pub fn into_result(self) -> Vec<T> {
+        self.result
+    }
+
     /// Returns whether there are more results available for this cursor on
     /// the server.
     pub fn has_more(&self) -> bool {
@@ -427,7 +445,6 @@ pub struct CursorOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
     optimizer: Option<Optimizer>,
 
-    #[cfg(feature = "rocksdb")]
     /// Maximum number of operations after which an intermediate commit is
     /// performed automatically.
     ///
@@ -435,7 +452,6 @@ pub struct CursorOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
     intermediate_commit_count: Option<u32>,
 
-    #[cfg(feature = "rocksdb")]
     /// Maximum total size of operations after which an intermediate commit is
     /// performed automatically.
     ///
@@ -443,14 +459,12 @@ pub struct CursorOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
     intermediate_commit_size: Option<u32>,
 
-    #[cfg(feature = "rocksdb")]
     /// Transaction size limit in bytes.
     ///
     /// Honored by the RocksDB storage engine only.
     #[serde(skip_serializing_if = "Option::is_none")]
     max_transaction_size: Option<u32>,
 
-    #[cfg(feature = "enterprise")]
     /// This enterprise parameter allows to configure how long a DBServer will
     /// have time to bring the satellite collections involved in the query into
     /// sync. The default value is 60.0 (seconds). When the max time has been
@@ -471,13 +485,9 @@ impl CursorOptions {
             full_count: None,
             max_plans: None,
             optimizer: None,
-            #[cfg(feature = "rocksdb")]
             intermediate_commit_count: None,
-            #[cfg(feature = "rocksdb")]
             intermediate_commit_size: None,
-            #[cfg(feature = "rocksdb")]
             max_transaction_size: None,
-            #[cfg(feature = "enterprise")]
             satellite_sync_wait: None,
         }
     }
@@ -590,7 +600,6 @@ impl CursorOptions {
         self.optimizer.as_ref()
     }
 
-    #[cfg(feature = "rocksdb")]
     /// Sets the maximum number of operations after which an intermediate
     /// commit is performed automatically.
     ///
@@ -601,7 +610,6 @@ impl CursorOptions {
         self.intermediate_commit_count = intermediate_commit_count.into();
     }
 
-    #[cfg(feature = "rocksdb")]
     /// Returns the maximum number of operations after which an intermediate
     /// commit is performed automatically.
     ///
@@ -610,7 +618,6 @@ impl CursorOptions {
         self.intermediate_commit_count
     }
 
-    #[cfg(feature = "rocksdb")]
     /// Sets the maximum total size of operations after which an intermediate
     /// commit is performed automatically.
     ///
@@ -621,7 +628,6 @@ impl CursorOptions {
         self.intermediate_commit_size = intermediate_commit_size.into();
     }
 
-    #[cfg(feature = "rocksdb")]
     /// Returns the maximum total size of operations after which an intermediate
     /// commit is performed automatically.
     ///
@@ -630,7 +636,6 @@ impl CursorOptions {
         self.intermediate_commit_size
     }
 
-    #[cfg(feature = "rocksdb")]
     /// Sets the transaction size limit in bytes.
     ///
     /// Honored by the RocksDB storage engine only.
@@ -640,7 +645,6 @@ impl CursorOptions {
         self.max_transaction_size = max_transaction_size.into();
     }
 
-    #[cfg(feature = "rocksdb")]
     /// Returns the transaction size limit in bytes.
     ///
     /// Honored by the RocksDB storage engine only.
@@ -648,7 +652,6 @@ impl CursorOptions {
         self.max_transaction_size
     }
 
-    #[cfg(feature = "enterprise")]
     /// Sets the enterprise parameter that configures how long a DBServer will
     /// have time to bring the satellite collections involved in the query into
     /// sync.
@@ -661,7 +664,6 @@ impl CursorOptions {
         self.satellite_sync_wait = satellite_sync_wait.into();
     }
 
-    #[cfg(feature = "enterprise")]
     /// Returns the enterprise parameter that configures how long a DBServer
     /// will have time to bring the satellite collections involved in the query
     /// into sync.
@@ -669,3 +671,257 @@ impl CursorOptions {
         self.satellite_sync_wait
     }
 }
+
+/// This struct defines the parameters of an export of the documents of a
+/// collection.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewExport {
+    /// If set to true, a WAL flush operation will be executed prior to the
+    /// export. The flush operation will start copying documents from the WAL
+    /// to the collection's datafiles. There will be an additional wait time
+    /// of up to `flush_wait` seconds after the flush to allow the WAL
+    /// collector to change the adjusted document meta-data to point into the
+    /// datafiles, too. The default value is false (i.e. no flush) so most
+    /// recently inserted or updated documents from the collection might be
+    /// missing in the export.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    flush: Option<bool>,
+
+    /// Maximum wait time in seconds after a flush operation. The default
+    /// value is 10. This option only has an effect when `flush` is set to
+    /// true.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    flush_wait: Option<u32>,
+
+    /// Indicates whether the number of documents in the result set should be
+    /// returned in the "count" attribute of the result.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    count: Option<bool>,
+
+    /// Maximum number of result documents to be transferred from the server
+    /// to the client in one round-trip. If this attribute is not set, a
+    /// server-controlled default value will be used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    batch_size: Option<u32>,
+
+    /// An optional limit value, determining the maximum number of documents
+    /// to be included in the cursor. Omitting the limit attribute or setting
+    /// it to 0 will lead to no limit being used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    limit: Option<u64>,
+
+    /// The time-to-live for the cursor (in seconds). The cursor will be
+    /// removed on the server automatically after the specified amount of
+    /// time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ttl: Option<u32>,
+
+    /// An optional restriction of the attributes that are included in the
+    /// exported documents.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    restrict: Option<ExportRestriction>,
+}
+
+impl NewExport {
+    /// Constructs a new instance of `NewExport` with all parameters set to
+    /// their default values.
+    pub fn new() -> Self {
+        NewExport {
+            flush: None,
+            flush_wait: None,
+            count: None,
+            batch_size: None,
+            limit: None,
+            ttl: None,
+            restrict: None,
+        }
+    }
+
+    /// Sets whether a WAL flush operation shall be executed prior to the
+    /// export.
+    pub fn set_flush<F>(&mut self, flush: F)
+        where F: Into<Option<bool>>
+    {
+        self.flush = flush.into();
+    }
+
+    /// Returns whether a WAL flush operation shall be executed prior to the
+    /// export.
+    pub fn is_flush(&self) -> Option<bool> {
+        self.flush
+    }
+
+    /// Sets the maximum wait time in seconds after a flush operation.
+    pub fn set_flush_wait<W>(&mut self, flush_wait: W)
+        where W: Into<Option<u32>>
+    {
+        self.flush_wait = flush_wait.into();
+    }
+
+    /// Returns the maximum wait time in seconds after a flush operation.
+    pub fn flush_wait(&self) -> Option<u32> {
+        self.flush_wait
+    }
+
+    /// Sets the flag whether the number of documents in the result set
+    /// should be returned.
+    pub fn set_count<C>(&mut self, count: C)
+        where C: Into<Option<bool>>
+    {
+        self.count = count.into();
+    }
+
+    /// Returns whether the number of documents in the result set should be
+    /// returned in the "count" attribute of the result.
+    pub fn is_count(&self) -> Option<bool> {
+        self.count
+    }
+
+    /// Sets the maximum number of result documents to be transferred from the
+    /// server to the client in one round-trip.
+    pub fn set_batch_size<S>(&mut self, batch_size: S)
+        where S: Into<Option<u32>>
+    {
+        self.batch_size = batch_size.into();
+    }
+
+    /// Returns the maximum number of result documents to be transferred from
+    /// the server to the client in one round-trip.
+    pub fn batch_size(&self) -> Option<u32> {
+        self.batch_size
+    }
+
+    /// Sets the maximum number of documents to be included in the cursor.
+    ///
+    /// A limit of 0 means that no limit is used.
+    pub fn set_limit<L>(&mut self, limit: L)
+        where L: Into<Option<u64>>
+    {
+        self.limit = limit.into();
+    }
+
+    /// Returns the maximum number of documents to be included in the cursor.
+    pub fn limit(&self) -> Option<u64> {
+        self.limit
+    }
+
+    /// Sets the time-to-live for the cursor (in seconds).
+    pub fn set_ttl<T>(&mut self, ttl: T)
+        where T: Into<Option<u32>>
+    {
+        self.ttl = ttl.into();
+    }
+
+    /// Returns the time-to-live for the cursor (in seconds).
+    pub fn ttl(&self) -> Option<u32> {
+        self.ttl
+    }
+
+    /// Sets the restriction of the attributes that are included in the
+    /// exported documents.
+    pub fn set_restrict<R>(&mut self, restrict: R)
+        where R: Into<Option<ExportRestriction>>
+    {
+        self.restrict = restrict.into();
+    }
+
+    /// Returns the restriction of the attributes that are included in the
+    /// exported documents.
+    pub fn restrict(&self) -> Option<&ExportRestriction> {
+        self.restrict.as_ref()
+    }
+}
+
+impl Default for NewExport {
+    fn default() -> Self {
+        NewExport::new()
+    }
+}
+
+/// Restricts the attributes that are included in the documents of an
+/// export.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ExportRestriction {
+    /// Whether the fields are included or excluded.
+    #[serde(rename = "type")]
+    kind: RestrictionType,
+    /// The names of the attributes to be included or excluded.
+    fields: Vec<String>,
+}
+
+impl ExportRestriction {
+    /// Constructs a new `ExportRestriction` that includes only the given
+    /// attributes in the exported documents.
+    pub fn include<F>(fields: F) -> Self
+        where F: IntoIterator, F::Item: Into<String>
+    {
+        ExportRestriction {
+            kind: RestrictionType::Include,
+            fields: fields.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Constructs a new `ExportRestriction` that excludes the given
+    /// attributes from the exported documents.
+    pub fn exclude<F>(fields: F) -> Self
+        where F: IntoIterator, F::Item: Into<String>
+    {
+        ExportRestriction {
+            kind: RestrictionType::Exclude,
+            fields: fields.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Returns whether the fields are included or excluded.
+    pub fn kind(&self) -> RestrictionType {
+        self.kind
+    }
+
+    /// Returns the names of the attributes to be included or excluded.
+    pub fn fields(&self) -> &[String] {
+        &self.fields
+    }
+}
+
+/// The kinds of restrictions of the attributes of exported documents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RestrictionType {
+    /// Only the listed attributes are included
+    Include,
+    /// The listed attributes are excluded
+    Exclude,
+}
+
+impl RestrictionType {
+    /// Returns the name of the restriction type as used by the ArangoDB
+    /// server.
+    pub fn as_str(&self) -> &str {
+        match *self {
+            RestrictionType::Include => RESTRICTION_TYPE_INCLUDE,
+            RestrictionType::Exclude => RESTRICTION_TYPE_EXCLUDE,
+        }
+    }
+}
+
+impl Serialize for RestrictionType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for RestrictionType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        use serde::de::Error;
+        let value = String::deserialize(deserializer)?;
+        match &value[..] {
+            RESTRICTION_TYPE_INCLUDE => Ok(RestrictionType::Include),
+            RESTRICTION_TYPE_EXCLUDE => Ok(RestrictionType::Exclude),
+            _ => Err(D::Error::custom(format!("Unknown restriction type: {:?}", value))),
+        }
+    }
+}
@@ -2,7 +2,7 @@
 use serde_json;
 
 use rincon_core::api::query::Query;
-use rincon_core::api::types::Value;
+use rincon_core::api::types::{JsonValue, Value};
 use aql::types::OptimizerRule;
 use super::types::*;
 
@@ -55,13 +55,9 @@ fn set_cursor_options_on_newly_initialized_new_cursor() {
         cursor_options.set_full_count(Some(false));
         cursor_options.set_max_warning_count(None);
         cursor_options.set_max_plans(5);
+        cursor_options.set_intermediate_commit_count(1);
 
-        #[cfg(feature = "rocksdb")] {
-            cursor_options.set_intermediate_commit_count(1);
-        }
-        #[cfg(feature = "enterprise")] {
-            cursor_options.set_satellite_sync_wait(false);
-        }
+        cursor_options.set_satellite_sync_wait(false);
     }
     let new_cursor = new_cursor;
     assert!(new_cursor.options().is_some());
@@ -73,14 +69,10 @@ fn set_cursor_options_on_newly_initialized_new_cursor() {
     assert_eq!(Some(false), cursor_options.is_full_count());
     assert_eq!(Some(5), cursor_options.max_plans());
 
-    #[cfg(feature = "rocksdb")] {
-        assert_eq!(Some(1), cursor_options.intermediate_commit_count());
-        assert_eq!(None, cursor_options.intermediate_commit_size());
-        assert_eq!(None, cursor_options.max_transaction_size());
-    }
-    #[cfg(feature = "enterprise")] {
-        assert_eq!(Some(false), cursor_options.satellite_sync_wait());
-    }
+    assert_eq!(Some(1), cursor_options.intermediate_commit_count());
+    assert_eq!(None, cursor_options.intermediate_commit_size());
+    assert_eq!(None, cursor_options.max_transaction_size());
+    assert_eq!(Some(false), cursor_options.satellite_sync_wait());
 }
 
 #[test]
@@ -119,30 +111,15 @@ fn serialize_new_cursor_with_cursor_options_set() {
         cursor_options.set_full_count(Some(false));
         cursor_options.set_max_warning_count(None);
         cursor_options.set_max_plans(5);
+        cursor_options.set_intermediate_commit_count(1);
 
-        #[cfg(feature = "rocksdb")] {
-            cursor_options.set_intermediate_commit_count(1);
-        }
-        #[cfg(feature = "enterprise")] {
-            cursor_options.set_satellite_sync_wait(false);
-        }
+        cursor_options.set_satellite_sync_wait(false);
     }
     let new_cursor = new_cursor;
 
     let json_cursor = serde_json::to_string(&new_cursor).unwrap();
 
-    #[cfg(all(not(feature = "rocksdb"), not(feature = "enterprise")))] {
-        assert_eq!(r#"{"query":"FOR u IN users FILTER u.name = @name RETURN u.name","bindVars":{"name":"simone"},"options":{"failOnWarning":true,"fullCount":false,"maxPlans":5}}"#, &json_cursor);
-    }
-    #[cfg(all(feature = "rocksdb", not(feature = "enterprise")))] {
-        assert_eq!(r#"{"query":"FOR u IN users FILTER u.name = @name RETURN u.name","bindVars":{"name":"simone"},"options":{"failOnWarning":true,"fullCount":false,"maxPlans":5,"intermediateCommitCount":1}}"#, &json_cursor);
-    }
-    #[cfg(all(not(feature = "rocksdb"), feature = "enterprise"))] {
-        assert_eq!(r#"{"query":"FOR u IN users FILTER u.name = @name RETURN u.name","bindVars":{"name":"simone"},"options":{"failOnWarning":true,"fullCount":false,"maxPlans":5,"satelliteSyncWait":false}}"#, &json_cursor);
-    }
-    #[cfg(all(feature = "rocksdb", feature = "enterprise"))] {
-        assert_eq!(r#"{"query":"FOR u IN users FILTER u.name = @name RETURN u.name","bindVars":{"name":"simone"},"options":{"failOnWarning":true,"fullCount":false,"maxPlans":5,"intermediateCommitCount":1,"satelliteSyncWait":false}}"#, &json_cursor);
-    }
+    assert_eq!(r#"{"query":"FOR u IN users FILTER u.name = @name RETURN u.name","bindVars":{"name":"simone"},"options":{"failOnWarning":true,"fullCount":false,"maxPlans":5,"intermediateCommitCount":1,"satelliteSyncWait":false}}"#, &json_cursor);
 }
 
 #[test]
@@ -171,3 +148,30 @@ fn serialize_new_cursor_with_optimizer_rules_set() {
     assert!(json_cursor.contains("+use-index-for-sort"));
     assert!(json_cursor.contains("+inline-subqueries"));
 }
+
+#[test]
+fn serialize_new_export_with_restriction() {
+    let mut new_export = NewExport::new();
+    new_export.set_flush(true);
+    new_export.set_batch_size(100);
+    new_export.set_limit(1000);
+    new_export.set_restrict(ExportRestriction::include(vec!["_key", "name"]));
+    let new_export = new_export;
+
+    let json_export = serde_json::to_string(&new_export).unwrap();
+
+    assert_eq!(r#"{"flush":true,"batchSize":100,"limit":1000,"restrict":{"type":"include","fields":["_key","name"]}}"#, &json_export);
+}
+
+#[test]
+fn deserialize_cursor_of_export() {
+    let json_cursor = r#"{"result":[{"name":"simone"}],"hasMore":true,"id":"1234","count":2,"error":false,"code":201}"#;
+
+    let cursor: Cursor<JsonValue> = serde_json::from_str(json_cursor).unwrap();
+
+    assert_eq!(Some(&"1234".to_owned()), cursor.id());
+    assert_eq!(1, cursor.result().len());
+    assert!(cursor.has_more());
+    assert_eq!(Some(2), cursor.count());
+    assert!(!cursor.is_cached());
+}
@@ -81,6 +81,14 @@ impl<T> GetDocument<T> {
     }
 }
 
+impl<T> GetDocument<Entity<T>>
+    where T: ArangoDocument
+{
+    pub fn with_entity_key(document_key: DocumentKey) -> Self {
+        GetDocument::with_key(T::COLLECTION_NAME, document_key)
+    }
+}
+
 impl<T> Method for GetDocument<T>
     where T: DeserializeOwned
 {
@@ -266,6 +274,14 @@ impl<T> InsertDocument<T> {
     }
 }
 
+impl<T> InsertDocument<Entity<T>>
+    where T: ArangoDocument
+{
+    pub fn with_entity(entity: T) -> Self {
+        InsertDocument::new(T::COLLECTION_NAME, NewDocument::from_content(Entity::new(entity)))
+    }
+}
+
 impl<T> Method for InsertDocument<T>
     where T: DeserializeOwned
 {
@@ -634,6 +650,28 @@ impl<Old, New> ReplaceDocument<Old, New> {
     }
 }
 
+impl<T> ReplaceDocument<Entity<T>, Entity<T>>
+    where T: ArangoDocument
+{
+    /// Replaces the stored document with the given entity.
+    ///
+    /// If the entity holds a revision the document is only replaced if the
+    /// revision matches the current revision of the stored document.
+    ///
+    /// Returns an error if the entity does not hold a document key.
+    pub fn with_entity(entity: T) -> Result<Self, String> {
+        let key = entity.document_key().ok_or_else(||
+            format!("An entity must hold a document key to be replaced in collection '{}'",
+                T::COLLECTION_NAME))?;
+        let revision = entity.document_revision();
+        let ignore_revisions = revision.as_ref().map(|_| false);
+        let document_id = DocumentId::new(T::COLLECTION_NAME, key.as_str());
+        let update = DocumentUpdate::new(key, Entity::new(entity)).with_revision(revision);
+        Ok(ReplaceDocument::new(document_id, update)
+            .with_ignore_revisions(ignore_revisions))
+    }
+}
+
 impl<Old, New> Method for ReplaceDocument<Old, New>
     where Old: DeserializeOwned, New: DeserializeOwned
 {
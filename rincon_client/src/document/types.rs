@@ -5,9 +5,9 @@ use std::marker::PhantomData;
 use serde::de::{Deserialize, DeserializeOwned, Deserializer, MapAccess, Visitor};
 use serde::ser::{Serialize, Serializer};
 
-use rincon_core::arango::protocol::{FIELD_ENTITY_ID, FIELD_ENTITY_KEY,
-    FIELD_ENTITY_REVISION, FIELD_ENTITY_NEW, FIELD_ENTITY_OLD,
-    FIELD_ENTITY_OLD_REVISION, Handle, HandleOption};
+use rincon_core::arango::protocol::{FIELD_ENTITY_FROM, FIELD_ENTITY_ID,
+    FIELD_ENTITY_KEY, FIELD_ENTITY_REVISION, FIELD_ENTITY_NEW, FIELD_ENTITY_OLD,
+    FIELD_ENTITY_OLD_REVISION, FIELD_ENTITY_TO, Handle, HandleOption};
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum DocumentIdOption {
@@ -767,3 +767,211 @@ impl<Upd> Serialize for DocumentUpdate<Upd>
         json_value_with_header_fields.serialize(serializer)
     }
 }
+
+/// A type whose values are stored as documents in a collection.
+///
+/// The trait tells which fields of the type hold the system attributes
+/// `_key`, `_id`, `_rev`, `_from` and `_to`. It is usually implemented with
+/// `#[derive(ArangoDocument)]` from the `rincon_derive` crate. Values of the
+/// type are sent to and received from the server wrapped in an `Entity`.
+pub trait ArangoDocument: Serialize + DeserializeOwned {
+    /// The name of the collection the documents are stored in.
+    const COLLECTION_NAME: &'static str;
+
+    /// The name of the field holding the `_key` attribute.
+    const KEY_FIELD: Option<&'static str> = None;
+
+    /// The name of the field holding the `_id` attribute.
+    const ID_FIELD: Option<&'static str> = None;
+
+    /// The name of the field holding the `_rev` attribute.
+    const REVISION_FIELD: Option<&'static str> = None;
+
+    /// The name of the field holding the `_from` attribute of an edge.
+    const FROM_FIELD: Option<&'static str> = None;
+
+    /// The name of the field holding the `_to` attribute of an edge.
+    const TO_FIELD: Option<&'static str> = None;
+
+    /// Returns the document key of this value if it has one.
+    fn document_key(&self) -> Option<DocumentKey> {
+        None
+    }
+
+    /// Returns the revision of this value if it has one.
+    fn document_revision(&self) -> Option<Revision> {
+        None
+    }
+
+    /// Assigns the system attributes of a stored document to this value.
+    fn set_document_header(&mut self, _id: &DocumentId, _key: &DocumentKey, _revision: &Revision) {}
+}
+
+/// Conversion between the type of a field and a system attribute.
+///
+/// This trait is used by the code generated for `#[derive(ArangoDocument)]`.
+/// It is implemented for the attribute type itself, for `String` and for the
+/// `Option`s of both.
+pub trait SystemAttribute<A>: Sized {
+    /// Converts the given attribute into the type of the field.
+    fn from_attribute(attribute: &A) -> Self;
+
+    /// Returns the attribute held by the field.
+    fn to_attribute(&self) -> Option<A>;
+}
+
+macro_rules! impl_system_attribute {
+    ($attribute:ty, $to_string:expr, $from_str:expr) => {
+        impl SystemAttribute<$attribute> for $attribute {
+            fn from_attribute(attribute: &$attribute) -> Self {
+                attribute.clone()
+            }
+
+            fn to_attribute(&self) -> Option<$attribute> {
+                Some(self.clone())
+            }
+        }
+
+        impl SystemAttribute<$attribute> for Option<$attribute> {
+            fn from_attribute(attribute: &$attribute) -> Self {
+                Some(attribute.clone())
+            }
+
+            fn to_attribute(&self) -> Option<$attribute> {
+                self.clone()
+            }
+        }
+
+        impl SystemAttribute<$attribute> for String {
+            fn from_attribute(attribute: &$attribute) -> Self {
+                $to_string(attribute)
+            }
+
+            fn to_attribute(&self) -> Option<$attribute> {
+                $from_str(self)
+            }
+        }
+
+        impl SystemAttribute<$attribute> for Option<String> {
+            fn from_attribute(attribute: &$attribute) -> Self {
+                Some($to_string(attribute))
+            }
+
+            fn to_attribute(&self) -> Option<$attribute> {
+                self.as_ref().and_then(|value| $from_str(value))
+            }
+        }
+    }
+}
+
+impl_system_attribute!(DocumentId,
+    |id: &DocumentId| id.to_string(),
+    |value: &str| DocumentId::from_str(value).ok());
+impl_system_attribute!(DocumentKey,
+    |key: &DocumentKey| key.as_str().to_owned(),
+    |value: &str| DocumentKey::from_str(value).ok());
+impl_system_attribute!(Revision,
+    |revision: &Revision| revision.as_str().to_owned(),
+    |value: &str| Some(Revision::from_str(value)));
+
+/// Wraps a value of an `ArangoDocument` type for sending it to or receiving
+/// it from the server.
+///
+/// When serialized the fields holding system attributes are renamed to the
+/// attribute names. The `_id` attribute and attributes without a value are
+/// left out as they are assigned by the server. When deserialized the
+/// attributes are renamed back to the field names.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entity<T>(T);
+
+impl<T> Entity<T> {
+    pub fn new(entity: T) -> Self {
+        Entity(entity)
+    }
+
+    pub fn entity(&self) -> &T {
+        &self.0
+    }
+
+    pub fn unwrap(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Entity<T> {
+    fn from(entity: T) -> Self {
+        Entity(entity)
+    }
+}
+
+fn system_fields<T>() -> [(Option<&'static str>, &'static str); 5]
+    where T: ArangoDocument
+{
+    [
+        (T::KEY_FIELD, FIELD_ENTITY_KEY),
+        (T::ID_FIELD, FIELD_ENTITY_ID),
+        (T::REVISION_FIELD, FIELD_ENTITY_REVISION),
+        (T::FROM_FIELD, FIELD_ENTITY_FROM),
+        (T::TO_FIELD, FIELD_ENTITY_TO),
+    ]
+}
+
+impl<T> Serialize for Entity<T>
+    where T: ArangoDocument + Debug
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        use serde::ser::Error;
+        use serde_json::{self, Value};
+        let mut json_value = serde_json::to_value(&self.0).map_err(S::Error::custom)?;
+        match json_value {
+            Value::Object(ref mut fields) => {
+                for &(field, attribute) in &system_fields::<T>() {
+                    if let Some(value) = field.and_then(|field| fields.remove(field)) {
+                        if attribute != FIELD_ENTITY_ID && !value.is_null() {
+                            fields.insert(attribute.to_owned(), value);
+                        }
+                    }
+                }
+            },
+            _ => return Err(S::Error::custom(format!("Invalid document content! Only types that serialize into valid Json objects are supported. But got: {:?}", &self.0))),
+        }
+        let json_value_with_attributes = json_value;
+        json_value_with_attributes.serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Entity<T>
+    where T: ArangoDocument
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        use serde::de::Error;
+        use serde_json::{Value, from_value};
+        let mut json_value = Value::deserialize(deserializer)?;
+        if let Value::Object(ref mut fields) = json_value {
+            for &(field, attribute) in &system_fields::<T>() {
+                if let Some(field) = field {
+                    if let Some(value) = fields.remove(attribute) {
+                        fields.insert(field.to_owned(), value);
+                    }
+                }
+            }
+        }
+        from_value(json_value).map(Entity).map_err(D::Error::custom)
+    }
+}
+
+impl<T> Document<Entity<T>>
+    where T: ArangoDocument
+{
+    /// Unwraps the entity and assigns the system attributes of this document
+    /// to it.
+    pub fn into_entity(self) -> T {
+        let mut entity = self.content.0;
+        entity.set_document_header(&self.id, &self.key, &self.revision);
+        entity
+    }
+}
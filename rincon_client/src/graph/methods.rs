@@ -7,7 +7,8 @@ use serde::ser::Serialize;
 
 use rincon_core::api::method::{Method, Operation, Parameters, Prepare, RpcReturnType};
 use rincon_core::arango::protocol::{FIELD_CODE, FIELD_COLLECTIONS, FIELD_EDGE,
-    FIELD_GRAPH, FIELD_GRAPHS, FIELD_REMOVED, FIELD_VERTEX, PATH_API_GHARIAL,
+    FIELD_EDGES, FIELD_GRAPH, FIELD_GRAPHS, FIELD_REMOVED, FIELD_RESULT, FIELD_VERTEX,
+    PARAM_DIRECTION, PARAM_VERTEX, PATH_API_EDGES, PATH_API_GHARIAL, PATH_API_TRAVERSAL,
     PATH_EDGE, PATH_VERTEX};
 use document::types::{Document, DocumentHeader, DocumentId, DocumentKey, NewDocument,
     UpdatedDocumentHeader};
@@ -577,7 +578,7 @@ impl<T> Method for GetVertex<T>
 {
     type Result = Document<T>;
     const RETURN_TYPE: RpcReturnType = RpcReturnType {
-        result_field: Some(FIELD_REMOVED),
+        result_field: Some(FIELD_VERTEX),
         code_field: Some(FIELD_CODE),
     };
 }
@@ -586,7 +587,7 @@ impl<T> Prepare for GetVertex<T> {
     type Content = ();
 
     fn operation(&self) -> Operation {
-        Operation::Delete
+        Operation::Read
     }
 
     fn path(&self) -> String {
@@ -682,6 +683,30 @@ pub struct ModifyVertex<Upd> {
     update: Upd,
 }
 
+impl<Upd> ModifyVertex<Upd> {
+    pub fn new<G>(graph_name: G, vertex_id: DocumentId, update: Upd) -> Self
+        where G: Into<String>
+    {
+        ModifyVertex {
+            graph_name: graph_name.into(),
+            vertex_id,
+            update,
+        }
+    }
+
+    pub fn graph_name(&self) -> &str {
+        &self.graph_name
+    }
+
+    pub fn vertex_id(&self) -> &DocumentId {
+        &self.vertex_id
+    }
+
+    pub fn update(&self) -> &Upd {
+        &self.update
+    }
+}
+
 impl<Upd> Method for ModifyVertex<Upd> {
     type Result = UpdatedDocumentHeader;
     const RETURN_TYPE: RpcReturnType = RpcReturnType {
@@ -1181,7 +1206,7 @@ pub struct ReplaceEdge<T> {
 }
 
 impl<T> ReplaceEdge<T> {
-    pub fn new<G, Coll>(graph_name: G, edge_id: DocumentId, new_edge: NewEdge<T>) -> Self
+    pub fn new<G>(graph_name: G, edge_id: DocumentId, new_edge: NewEdge<T>) -> Self
         where G: Into<String>
     {
         ReplaceEdge {
@@ -1306,3 +1331,231 @@ impl<Upd> Prepare for ModifyEdge<Upd>
         Some(&self.update)
     }
 }
+
+/// Fetches the edges of an edge collection that are connected to a vertex.
+///
+/// The type parameter `T` specifies the type of the content of the edges.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GetEdges<T> {
+    content: PhantomData<T>,
+    collection_name: String,
+    vertex_id: DocumentId,
+    direction: Direction,
+}
+
+impl<T> GetEdges<T> {
+    /// Constructs a new instance of the `GetEdges` method for the edges of
+    /// the given collection that are connected to the given vertex in the
+    /// given direction.
+    pub fn new<Coll>(collection_name: Coll, vertex_id: DocumentId, direction: Direction) -> Self
+        where Coll: Into<String>
+    {
+        GetEdges {
+            content: PhantomData,
+            collection_name: collection_name.into(),
+            vertex_id,
+            direction,
+        }
+    }
+
+    /// Constructs a new instance of the `GetEdges` method for the edges
+    /// ending in the given vertex.
+    pub fn inbound<Coll>(collection_name: Coll, vertex_id: DocumentId) -> Self
+        where Coll: Into<String>
+    {
+        GetEdges::new(collection_name, vertex_id, Direction::Inbound)
+    }
+
+    /// Constructs a new instance of the `GetEdges` method for the edges
+    /// starting at the given vertex.
+    pub fn outbound<Coll>(collection_name: Coll, vertex_id: DocumentId) -> Self
+        where Coll: Into<String>
+    {
+        GetEdges::new(collection_name, vertex_id, Direction::Outbound)
+    }
+
+    /// Constructs a new instance of the `GetEdges` method for all edges
+    /// connected to the given vertex.
+    pub fn any<Coll>(collection_name: Coll, vertex_id: DocumentId) -> Self
+        where Coll: Into<String>
+    {
+        GetEdges::new(collection_name, vertex_id, Direction::Any)
+    }
+
+    pub fn collection_name(&self) -> &str {
+        &self.collection_name
+    }
+
+    pub fn vertex_id(&self) -> &DocumentId {
+        &self.vertex_id
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+}
+
+impl<T> Method for GetEdges<T>
+    where T: DeserializeOwned
+{
+    type Result = Vec<Edge<T>>;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: Some(FIELD_EDGES),
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl<T> Prepare for GetEdges<T> {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Read
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_EDGES) + "/" + &self.collection_name
+    }
+
+    fn parameters(&self) -> Parameters {
+        let mut params = Parameters::with_capacity(2);
+        params.insert(PARAM_VERTEX, self.vertex_id.to_string());
+        match self.direction {
+            Direction::Inbound => params.insert(PARAM_DIRECTION, "in"),
+            Direction::Outbound => params.insert(PARAM_DIRECTION, "out"),
+            Direction::Any => {},
+        }
+        params
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Executes a traversal on the server.
+///
+/// The traversal starts at a vertex and follows the edges of a graph or of
+/// an edge collection. The type parameters `V` and `E` specify the type of
+/// the content of the visited vertices and edges respectively.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExecuteTraversal<V, E> {
+    content: PhantomData<(V, E)>,
+    traversal: NewTraversal,
+}
+
+impl<V, E> ExecuteTraversal<V, E> {
+    /// Constructs a new instance of the `ExecuteTraversal` method for the
+    /// given traversal.
+    pub fn new(traversal: NewTraversal) -> Self {
+        ExecuteTraversal {
+            content: PhantomData,
+            traversal,
+        }
+    }
+
+    /// Returns the traversal that shall be executed.
+    pub fn traversal(&self) -> &NewTraversal {
+        &self.traversal
+    }
+}
+
+impl<V, E> Method for ExecuteTraversal<V, E>
+    where V: DeserializeOwned, E: DeserializeOwned
+{
+    type Result = TraversalResult<V, E>;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: Some(FIELD_RESULT),
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl<V, E> Prepare for ExecuteTraversal<V, E> {
+    type Content = NewTraversal;
+
+    fn operation(&self) -> Operation {
+        Operation::Create
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_TRAVERSAL)
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        Some(&self.traversal)
+    }
+}
+
+/// Executes a traversal with custom JavaScript functions on the server.
+///
+/// A traversal with a custom `init` or `visitor` function builds its own
+/// result object, which does not necessarily hold the visited vertices and
+/// paths like the `TraversalResult` returned by the `ExecuteTraversal`
+/// method. The type parameter `R` specifies the type the result object
+/// built by the custom functions is deserialized into.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExecuteCustomTraversal<R> {
+    result: PhantomData<R>,
+    traversal: NewTraversal,
+}
+
+impl<R> ExecuteCustomTraversal<R> {
+    /// Constructs a new instance of the `ExecuteCustomTraversal` method for
+    /// the given traversal.
+    pub fn new(traversal: NewTraversal) -> Self {
+        ExecuteCustomTraversal {
+            result: PhantomData,
+            traversal,
+        }
+    }
+
+    /// Returns the traversal that shall be executed.
+    pub fn traversal(&self) -> &NewTraversal {
+        &self.traversal
+    }
+}
+
+impl<R> Method for ExecuteCustomTraversal<R>
+    where R: DeserializeOwned
+{
+    type Result = R;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: Some(FIELD_RESULT),
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl<R> Prepare for ExecuteCustomTraversal<R> {
+    type Content = NewTraversal;
+
+    fn operation(&self) -> Operation {
+        Operation::Create
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_TRAVERSAL)
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        Some(&self.traversal)
+    }
+}
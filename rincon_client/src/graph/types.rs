@@ -12,7 +12,25 @@ use rincon_core::arango::protocol::{FIELD_EDGE_DEFINITIONS, FIELD_ENTITY_FROM,
     FIELD_ORPHAN_COLLECTIONS};
 use rincon_core::arango::protocol::{FIELD_NUMBER_OF_SHARDS, FIELD_REPLICATION_FACTOR};
 use rincon_core::arango::protocol::{FIELD_IS_SMART, FIELD_SMART_GRAPH_ATTRIBUTE};
-use document::types::{DocumentId, DocumentKey, Revision};
+use document::types::{Document, DocumentId, DocumentKey, Revision};
+
+const DIRECTION_OUTBOUND: &str = "outbound";
+const DIRECTION_INBOUND: &str = "inbound";
+const DIRECTION_ANY: &str = "any";
+
+const UNIQUENESS_NONE: &str = "none";
+const UNIQUENESS_GLOBAL: &str = "global";
+const UNIQUENESS_PATH: &str = "path";
+
+const ORDER_PREORDER: &str = "preorder";
+const ORDER_POSTORDER: &str = "postorder";
+const ORDER_PREORDER_EXPANDER: &str = "preorder-expander";
+
+const ITEM_ORDER_FORWARD: &str = "forward";
+const ITEM_ORDER_BACKWARD: &str = "backward";
+
+const STRATEGY_DEPTH_FIRST: &str = "depthfirst";
+const STRATEGY_BREADTH_FIRST: &str = "breadthfirst";
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Graph {
@@ -22,14 +40,10 @@ pub struct Graph {
     name: String,
     edge_definitions: Vec<EdgeDefinition>,
     orphan_collections: Vec<String>,
-    #[cfg(feature = "enterprise")]
-    smart: bool,
-    #[cfg(feature = "enterprise")]
-    smart_graph_attribute: String,
-    #[cfg(feature = "cluster")]
-    number_of_shards: u16,
-    #[cfg(feature = "cluster")]
-    replication_factor: u64,
+    smart: Option<bool>,
+    smart_graph_attribute: Option<String>,
+    number_of_shards: Option<u16>,
+    replication_factor: Option<u64>,
 }
 
 impl Graph {
@@ -57,23 +71,31 @@ impl Graph {
         &self.orphan_collections
     }
 
-    #[cfg(feature = "enterprise")]
-    pub fn is_smart(&self) -> bool {
+    /// Returns whether this graph is a smart graph.
+    ///
+    /// Only returned by the enterprise edition of the server.
+    pub fn is_smart(&self) -> Option<bool> {
         self.smart
     }
 
-    #[cfg(feature = "enterprise")]
-    pub fn smart_graph_attribute(&self) -> &str {
-        &self.smart_graph_attribute
+    /// Returns the attribute used to shard the vertices of a smart graph.
+    ///
+    /// Only returned by the enterprise edition of the server.
+    pub fn smart_graph_attribute(&self) -> Option<&str> {
+        self.smart_graph_attribute.as_deref()
     }
 
-    #[cfg(feature = "cluster")]
-    pub fn number_of_shards(&self) -> u16 {
+    /// Returns the number of shards of the collections of this graph.
+    ///
+    /// Only returned by servers in a cluster.
+    pub fn number_of_shards(&self) -> Option<u16> {
         self.number_of_shards
     }
 
-    #[cfg(feature = "cluster")]
-    pub fn replication_factor(&self) -> u64 {
+    /// Returns the replication factor of the collections of this graph.
+    ///
+    /// Only returned by servers in a cluster.
+    pub fn replication_factor(&self) -> Option<u64> {
         self.replication_factor
     }
 }
@@ -155,13 +177,9 @@ impl<'de> Deserialize<'de> for Graph {
                 let mut name: Option<String> = None;
                 let mut edge_definitions: Option<Vec<EdgeDefinition>> = None;
                 let mut orphan_collections: Option<Vec<String>> = None;
-                #[cfg(feature = "enterprise")]
                 let mut smart: Option<bool> = None;
-                #[cfg(feature = "enterprise")]
                 let mut smart_graph_attribute: Option<String> = None;
-                #[cfg(feature = "cluster")]
                 let mut number_of_shards: Option<u16> = None;
-                #[cfg(feature = "cluster")]
                 let mut replication_factor: Option<u64> = None;
 
                 let mut fields = map;
@@ -185,38 +203,18 @@ impl<'de> Deserialize<'de> for Graph {
                         GraphField::OrphanCollections => {
                             orphan_collections = fields.next_value()?;
                         },
-                        #[cfg(feature = "enterprise")]
                         GraphField::Smart => {
                             smart = fields.next_value()?;
                         },
-                        #[cfg(not(feature = "enterprise"))]
-                        GraphField::Smart => {
-                            let _: Option<bool> = fields.next_value()?;
-                        },
-                        #[cfg(feature = "enterprise")]
                         GraphField::SmartGraphAttribute => {
                             smart_graph_attribute = fields.next_value()?;
                         },
-                        #[cfg(not(feature = "enterprise"))]
-                        GraphField::SmartGraphAttribute => {
-                            let _: Option<String> = fields.next_value()?;
-                        },
-                        #[cfg(feature = "cluster")]
                         GraphField::NumberOfShards => {
                             number_of_shards = fields.next_value()?;
                         },
-                        #[cfg(not(feature = "cluster"))]
-                        GraphField::NumberOfShards => {
-                            let _: Option<u16> = fields.next_value()?;
-                        },
-                        #[cfg(feature = "cluster")]
                         GraphField::ReplicationFactor => {
                             replication_factor = fields.next_value()?;
                         },
-                        #[cfg(not(feature = "cluster"))]
-                        GraphField::ReplicationFactor => {
-                            let _: Option<u64> = fields.next_value()?;
-                        },
                         GraphField::Other(_) => {
                             //ignore
                             let _: Value = fields.next_value()?;
@@ -224,16 +222,11 @@ impl<'de> Deserialize<'de> for Graph {
                     }
                 }
 
-                #[cfg(all(feature = "enterprise", feature = "cluster"))]
                 match (id, key, revision, name,
                     edge_definitions, orphan_collections,
-                    smart, smart_graph_attribute,
-                    number_of_shards, replication_factor,
                 ) {
                     (Some(id), _key, Some(revision), _name,
                         Some(edge_definitions), Some(orphan_collections),
-                        Some(smart), Some(smart_graph_attribute),
-                        Some(number_of_shards), Some(replication_factor),
                     ) => {
                         let (key, name) = match (_key, _name) {
                             (Some(_key), Some(_name)) => (_key, _name),
@@ -257,120 +250,11 @@ impl<'de> Deserialize<'de> for Graph {
                             replication_factor,
                         })
                     },
-                    (None, _, _, _, _, _, _, _, _, _) => Err(A::Error::missing_field(FIELD_ENTITY_ID)),
-                    (_, _, None, _, _, _, _, _, _, _) => Err(A::Error::missing_field(FIELD_ENTITY_REVISION)),
-                    (_, _, _, _, None, _, _, _, _, _) => Err(A::Error::missing_field(FIELD_EDGE_DEFINITIONS)),
-                    (_, _, _, _, _, None, _, _, _, _) => Err(A::Error::missing_field(FIELD_ORPHAN_COLLECTIONS)),
-                    (_, _, _, _, _, _, None, _, _, _) => Err(A::Error::missing_field(FIELD_IS_SMART)),
-                    (_, _, _, _, _, _, _, None, _, _) => Err(A::Error::missing_field(FIELD_SMART_GRAPH_ATTRIBUTE)),
-                    (_, _, _, _, _, _, _, _, None, _) => Err(A::Error::missing_field(FIELD_NUMBER_OF_SHARDS)),
-                    (_, _, _, _, _, _, _, _, _, None) => Err(A::Error::missing_field(FIELD_REPLICATION_FACTOR)),
-                }
-
-                #[cfg(not(any(feature = "enterprise", feature = "cluster")))]
-                match (id, key, revision, name,
-                    edge_definitions, orphan_collections,
-                ) {
-                    (Some(id), _key, Some(revision), _name,
-                        Some(edge_definitions), Some(orphan_collections),
-                    ) => {
-                        let (key, name) = match (_key, _name) {
-                            (Some(_key), Some(_name)) => (_key, _name),
-                            (Some(_key), None) => (_key.clone(), _key),
-                            (None, Some(_name)) => (_name.clone(), _name),
-                            (None, None) => return Err(A::Error::missing_field("name or _key")),
-                        };
-                        let id = DocumentId::from_str(&id).map_err(A::Error::custom)?;
-                        let key = DocumentKey::from_string(key).map_err(A::Error::custom)?;
-                        let revision = Revision::from_string(revision);
-                        Ok(Graph {
-                            id,
-                            key,
-                            revision,
-                            name,
-                            edge_definitions,
-                            orphan_collections,
-                        })
-                    },
                     (None, _, _, _, _, _) => Err(A::Error::missing_field(FIELD_ENTITY_ID)),
                     (_, _, None, _, _, _) => Err(A::Error::missing_field(FIELD_ENTITY_REVISION)),
                     (_, _, _, _, None, _) => Err(A::Error::missing_field(FIELD_EDGE_DEFINITIONS)),
                     (_, _, _, _, _, None) => Err(A::Error::missing_field(FIELD_ORPHAN_COLLECTIONS)),
                 }
-
-                #[cfg(all(feature = "enterprise", not(feature = "cluster")))]
-                    match (id, key, revision, name,
-                    edge_definitions, orphan_collections,
-                    smart, smart_graph_attribute,
-                ) {
-                    (Some(id), _key, Some(revision), _name,
-                        Some(edge_definitions), Some(orphan_collections),
-                        Some(smart), Some(smart_graph_attribute),
-                    ) => {
-                        let (key, name) = match (_key, _name) {
-                            (Some(_key), Some(_name)) => (_key, _name),
-                            (Some(_key), None) => (_key.clone(), _key),
-                            (None, Some(_name)) => (_name.clone(), _name),
-                            (None, None) => return Err(A::Error::missing_field("name or _key")),
-                        };
-                        let id = DocumentId::from_str(&id).map_err(A::Error::custom)?;
-                        let key = DocumentKey::from_string(key).map_err(A::Error::custom)?;
-                        let revision = Revision::from_string(revision);
-                        Ok(Graph {
-                            id,
-                            key,
-                            revision,
-                            name,
-                            edge_definitions,
-                            orphan_collections,
-                            smart,
-                            smart_graph_attribute,
-                        })
-                    },
-                    (None, _, _, _, _, _, _, _) => Err(A::Error::missing_field(FIELD_ENTITY_ID)),
-                    (_, _, None, _, _, _, _, _) => Err(A::Error::missing_field(FIELD_ENTITY_REVISION)),
-                    (_, _, _, _, None, _, _, _) => Err(A::Error::missing_field(FIELD_EDGE_DEFINITIONS)),
-                    (_, _, _, _, _, None, _, _) => Err(A::Error::missing_field(FIELD_ORPHAN_COLLECTIONS)),
-                    (_, _, _, _, _, _, None, _) => Err(A::Error::missing_field(FIELD_IS_SMART)),
-                    (_, _, _, _, _, _, _, None) => Err(A::Error::missing_field(FIELD_SMART_GRAPH_ATTRIBUTE)),
-                }
-
-                #[cfg(all(not(feature = "enterprise"), feature = "cluster"))]
-                    match (id, key, revision, name,
-                    edge_definitions, orphan_collections,
-                    number_of_shards, replication_factor,
-                ) {
-                    (Some(id), _key, Some(revision), _name,
-                        Some(edge_definitions), Some(orphan_collections),
-                        Some(number_of_shards), Some(replication_factor),
-                    ) => {
-                        let (key, name) = match (_key, _name) {
-                            (Some(_key), Some(_name)) => (_key, _name),
-                            (Some(_key), None) => (_key.clone(), _key),
-                            (None, Some(_name)) => (_name.clone(), _name),
-                            (None, None) => return Err(A::Error::missing_field("name or _key")),
-                        };
-                        let id = DocumentId::from_str(&id).map_err(A::Error::custom)?;
-                        let key = DocumentKey::from_string(key).map_err(A::Error::custom)?;
-                        let revision = Revision::from_string(revision);
-                        Ok(Graph {
-                            id,
-                            key,
-                            revision,
-                            name,
-                            edge_definitions,
-                            orphan_collections,
-                            number_of_shards,
-                            replication_factor,
-                        })
-                    },
-                    (None, _, _, _, _, _, _, _) => Err(A::Error::missing_field(FIELD_ENTITY_ID)),
-                    (_, _, None, _, _, _, _, _) => Err(A::Error::missing_field(FIELD_ENTITY_REVISION)),
-                    (_, _, _, _, None, _, _, _) => Err(A::Error::missing_field(FIELD_EDGE_DEFINITIONS)),
-                    (_, _, _, _, _, None, _, _) => Err(A::Error::missing_field(FIELD_ORPHAN_COLLECTIONS)),
-                    (_, _, _, _, _, _, None, _) => Err(A::Error::missing_field(FIELD_NUMBER_OF_SHARDS)),
-                    (_, _, _, _, _, _, _, None) => Err(A::Error::missing_field(FIELD_REPLICATION_FACTOR)),
-                }
             }
         }
 
@@ -385,16 +269,14 @@ pub struct NewGraph {
     edge_definitions: Vec<EdgeDefinition>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     orphan_collections: Vec<String>,
-    #[cfg(feature = "enterprise")]
     #[serde(rename = "isSmart")]
-    smart: bool,
-    #[cfg(any(feature = "enterprise", feature = "cluster"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    smart: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     options: Option<GraphOptions>,
 }
 
 impl NewGraph {
-    #[cfg(not(feature = "enterprise"))]
     pub fn new<Name, Edges>(name: Name, edges: Edges) -> Self
         where
             Name: Into<String>,
@@ -404,22 +286,7 @@ impl NewGraph {
             name: name.into(),
             edge_definitions: Vec::from_iter(edges.into_iter()),
             orphan_collections: Vec::new(),
-            #[cfg(any(feature = "enterprise", feature = "cluster"))]
-            options: None,
-        }
-    }
-
-    #[cfg(feature = "enterprise")]
-    pub fn new<Name, Edges>(name: Name, edges: Edges, smart: bool) -> Self
-        where
-            Name: Into<String>,
-            Edges: IntoIterator<Item=EdgeDefinition>,
-    {
-        NewGraph {
-            name: name.into(),
-            edge_definitions: Vec::from_iter(edges.into_iter()),
-            orphan_collections: Vec::new(),
-            smart,
+            smart: None,
             options: None,
         }
     }
@@ -427,12 +294,7 @@ impl NewGraph {
     pub fn with_name<Name>(name: Name) -> Self
         where Name: Into<String>
     {
-        #[cfg(not(feature = "enterprise"))] {
-            NewGraph::new(name, Vec::new())
-        }
-        #[cfg(feature = "enterprise")] {
-            NewGraph::new(name, Vec::new(), false)
-        }
+        NewGraph::new(name, Vec::new())
     }
 
     pub fn with_edge_definitions<Edges>(mut self, edges: Edges) -> Self
@@ -457,14 +319,18 @@ impl NewGraph {
         &mut self.orphan_collections
     }
 
-    #[cfg(feature = "enterprise")]
-    pub fn set_smart(&mut self, smart: bool) {
-        self.smart = smart;
+    /// Sets whether the graph shall be created as smart graph.
+    ///
+    /// Smart graphs are supported by the enterprise edition of the server
+    /// only.
+    pub fn set_smart<S>(&mut self, smart: S)
+        where S: Into<Option<bool>>
+    {
+        self.smart = smart.into();
     }
 
-    #[cfg(any(feature = "enterprise", feature = "cluster"))]
     pub fn options_mut(&mut self) -> &mut GraphOptions {
-        self.options.get_or_insert_with(|| GraphOptions::default())
+        self.options.get_or_insert_with(GraphOptions::default)
     }
 
     pub fn name(&self) -> &str {
@@ -479,84 +345,67 @@ impl NewGraph {
         &self.orphan_collections
     }
 
-    #[cfg(feature = "enterprise")]
-    pub fn is_smart(&self) -> bool {
+    pub fn is_smart(&self) -> Option<bool> {
         self.smart
     }
 
-    #[cfg(any(feature = "enterprise", feature = "cluster"))]
     pub fn options(&self) -> Option<&GraphOptions> {
         self.options.as_ref()
     }
 }
 
-#[cfg(any(feature = "enterprise", feature = "cluster"))]
 #[allow(missing_copy_implementations)]
 #[derive(Clone, Debug, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GraphOptions {
-    #[cfg(feature = "enterprise")]
     #[serde(skip_serializing_if = "Option::is_none")]
     smart_graph_attribute: Option<String>,
-    #[cfg(feature = "cluster")]
     #[serde(skip_serializing_if = "Option::is_none")]
     number_of_shards: Option<u16>,
-    #[cfg(feature = "cluster")]
     #[serde(skip_serializing_if = "Option::is_none")]
     replication_factor: Option<u64>,
 }
 
-#[cfg(any(feature = "enterprise", feature = "cluster"))]
 impl GraphOptions {
     fn empty() -> Self {
         GraphOptions {
-            #[cfg(feature = "enterprise")]
             smart_graph_attribute: None,
-            #[cfg(feature = "cluster")]
             number_of_shards: None,
-            #[cfg(feature = "cluster")]
             replication_factor: None,
         }
     }
 
-    #[cfg(feature = "enterprise")]
     pub fn set_smart_graph_attribute<Attr>(&mut self, smart_graph_attribute: Attr)
         where Attr: Into<Option<String>>
     {
         self.smart_graph_attribute = smart_graph_attribute.into();
     }
 
-    #[cfg(feature = "cluster")]
     pub fn set_number_of_shards<S>(&mut self, number_of_shards: S)
         where S: Into<Option<u16>>
     {
         self.number_of_shards = number_of_shards.into();
     }
 
-    #[cfg(feature = "cluster")]
     pub fn set_replication_factor<R>(&mut self, replication_factor: R)
         where R: Into<Option<u64>>
     {
         self.replication_factor = replication_factor.into();
     }
 
-    #[cfg(feature = "enterprise")]
     pub fn smart_graph_attribute(&self) -> Option<&String> {
         self.smart_graph_attribute.as_ref()
     }
 
-    #[cfg(feature = "cluster")]
     pub fn number_of_shards(&self) -> Option<u16> {
         self.number_of_shards
     }
 
-    #[cfg(feature = "cluster")]
     pub fn replication_factor(&self) -> Option<u64> {
         self.replication_factor
     }
 }
 
-#[cfg(any(feature = "enterprise", feature = "cluster"))]
 impl Default for GraphOptions {
     fn default() -> Self {
         GraphOptions::empty()
@@ -841,18 +690,471 @@ impl<T> Serialize for NewEdge<T>
         use serde::ser::Error;
         use serde_json::{self, Value};
 
-        if let Some(ref key) = self.key {
-            let mut json_value = serde_json::to_value(&self.content).map_err(S::Error::custom)?;
-            match json_value {
-                Value::Object(ref mut fields) => {
+        let mut json_value = serde_json::to_value(&self.content).map_err(S::Error::custom)?;
+        match json_value {
+            Value::Object(ref mut fields) => {
+                if let Some(ref key) = self.key {
                     fields.insert(FIELD_ENTITY_KEY.to_owned(), Value::String(key.as_str().to_owned()));
-                },
-                _ => return Err(S::Error::custom(format!("Invalid edge content! Only types that serialize into valid Json objects are supported. But got: {:?}", &self.content))),
-            }
-            let json_value_with_key = json_value;
-            json_value_with_key.serialize(serializer)
-        } else {
-            self.content.serialize(serializer)
+                }
+                fields.insert(FIELD_ENTITY_FROM.to_owned(), Value::String(self.from.to_string()));
+                fields.insert(FIELD_ENTITY_TO.to_owned(), Value::String(self.to.to_string()));
+            },
+            _ => return Err(S::Error::custom(format!("Invalid edge content! Only types that serialize into valid Json objects are supported. But got: {:?}", &self.content))),
+        }
+        json_value.serialize(serializer)
+    }
+}
+
+/// This enum defines the directions in which edges are followed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum Direction {
+    /// Follow edges from their `_from` vertex to their `_to` vertex.
+    #[default]
+    Outbound,
+    /// Follow edges from their `_to` vertex to their `_from` vertex.
+    Inbound,
+    /// Follow edges in both directions.
+    Any,
+}
+
+impl Direction {
+    /// Returns the name of this direction as used by the traversal API.
+    pub fn as_str(&self) -> &str {
+        match *self {
+            Direction::Outbound => DIRECTION_OUTBOUND,
+            Direction::Inbound => DIRECTION_INBOUND,
+            Direction::Any => DIRECTION_ANY,
+        }
+    }
+}
+
+impl Serialize for Direction {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// This enum defines how often a vertex or edge may be visited during a
+/// traversal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Uniqueness {
+    /// No uniqueness check is performed.
+    None,
+    /// A vertex or edge is visited at most once during the whole traversal.
+    Global,
+    /// A vertex or edge is visited at most once on each path.
+    Path,
+}
+
+impl Serialize for Uniqueness {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.serialize_str(match *self {
+            Uniqueness::None => UNIQUENESS_NONE,
+            Uniqueness::Global => UNIQUENESS_GLOBAL,
+            Uniqueness::Path => UNIQUENESS_PATH,
+        })
+    }
+}
+
+/// This struct holds the uniqueness rules for the vertices and edges of a
+/// traversal.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct TraversalUniqueness {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    vertices: Option<Uniqueness>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    edges: Option<Uniqueness>,
+}
+
+impl TraversalUniqueness {
+    pub fn new<V, E>(vertices: V, edges: E) -> Self
+        where V: Into<Option<Uniqueness>>, E: Into<Option<Uniqueness>>
+    {
+        TraversalUniqueness {
+            vertices: vertices.into(),
+            edges: edges.into(),
+        }
+    }
+
+    pub fn vertices(&self) -> Option<Uniqueness> {
+        self.vertices
+    }
+
+    pub fn edges(&self) -> Option<Uniqueness> {
+        self.edges
+    }
+}
+
+/// This enum defines when a vertex is visited in relation to its
+/// connected vertices.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TraversalOrder {
+    /// A vertex is visited before its connected vertices.
+    Preorder,
+    /// A vertex is visited after its connected vertices.
+    Postorder,
+    /// Like `Preorder`, but the connected vertices are determined before
+    /// the vertex is visited.
+    PreorderExpander,
+}
+
+impl Serialize for TraversalOrder {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.serialize_str(match *self {
+            TraversalOrder::Preorder => ORDER_PREORDER,
+            TraversalOrder::Postorder => ORDER_POSTORDER,
+            TraversalOrder::PreorderExpander => ORDER_PREORDER_EXPANDER,
+        })
+    }
+}
+
+/// This enum defines the order in which the connected edges of a vertex
+/// are followed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ItemOrder {
+    Forward,
+    Backward,
+}
+
+impl Serialize for ItemOrder {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.serialize_str(match *self {
+            ItemOrder::Forward => ITEM_ORDER_FORWARD,
+            ItemOrder::Backward => ITEM_ORDER_BACKWARD,
+        })
+    }
+}
+
+/// This enum defines the strategies of a traversal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TraversalStrategy {
+    DepthFirst,
+    BreadthFirst,
+}
+
+impl Serialize for TraversalStrategy {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.serialize_str(match *self {
+            TraversalStrategy::DepthFirst => STRATEGY_DEPTH_FIRST,
+            TraversalStrategy::BreadthFirst => STRATEGY_BREADTH_FIRST,
+        })
+    }
+}
+
+/// This struct defines the parameters of a traversal that is going to be
+/// executed on the server.
+///
+/// A traversal starts at the given start vertex and follows either the
+/// edges of a named graph or the edges of a single edge collection. The
+/// optional `filter`, `visitor`, `init`, `expander` and `sort` functions are
+/// given as stringified JavaScript code.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewTraversal {
+    start_vertex: DocumentId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    graph_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    edge_collection: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    direction: Option<Direction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_depth: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_depth: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uniqueness: Option<TraversalUniqueness>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    order: Option<TraversalOrder>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    item_order: Option<ItemOrder>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    strategy: Option<TraversalStrategy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    filter: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    visitor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    init: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expander: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sort: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_iterations: Option<u64>,
+}
+
+impl NewTraversal {
+    fn new(start_vertex: DocumentId, graph_name: Option<String>, edge_collection: Option<String>) -> Self {
+        NewTraversal {
+            start_vertex,
+            graph_name,
+            edge_collection,
+            direction: None,
+            min_depth: None,
+            max_depth: None,
+            uniqueness: None,
+            order: None,
+            item_order: None,
+            strategy: None,
+            filter: None,
+            visitor: None,
+            init: None,
+            expander: None,
+            sort: None,
+            max_iterations: None,
         }
     }
+
+    /// Constructs a new traversal that follows the edges of the named
+    /// graph.
+    pub fn with_graph<G>(start_vertex: DocumentId, graph_name: G) -> Self
+        where G: Into<String>
+    {
+        NewTraversal::new(start_vertex, Some(graph_name.into()), None)
+    }
+
+    /// Constructs a new traversal that follows the edges of the given edge
+    /// collection.
+    pub fn with_edge_collection<E>(start_vertex: DocumentId, edge_collection: E) -> Self
+        where E: Into<String>
+    {
+        NewTraversal::new(start_vertex, None, Some(edge_collection.into()))
+    }
+
+    pub fn with_direction(mut self, direction: Direction) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+
+    pub fn with_min_depth(mut self, min_depth: u32) -> Self {
+        self.min_depth = Some(min_depth);
+        self
+    }
+
+    pub fn with_max_depth(mut self, max_depth: u32) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    pub fn with_uniqueness(mut self, uniqueness: TraversalUniqueness) -> Self {
+        self.uniqueness = Some(uniqueness);
+        self
+    }
+
+    pub fn with_order(mut self, order: TraversalOrder) -> Self {
+        self.order = Some(order);
+        self
+    }
+
+    pub fn with_item_order(mut self, item_order: ItemOrder) -> Self {
+        self.item_order = Some(item_order);
+        self
+    }
+
+    pub fn with_strategy(mut self, strategy: TraversalStrategy) -> Self {
+        self.strategy = Some(strategy);
+        self
+    }
+
+    /// Sets a JavaScript function that decides whether a vertex is
+    /// visited and/or expanded, e.g.
+    /// `if (path.vertices.length > 2) { return "prune"; }`.
+    pub fn with_filter<F>(mut self, filter: F) -> Self
+        where F: Into<String>
+    {
+        self.filter = Some(filter.into());
+        self
+    }
+
+    /// Sets a JavaScript function that is called for each visited vertex.
+    ///
+    /// The visitor must add to `result.visited.vertices` and
+    /// `result.visited.paths` for the result to be returned as
+    /// `TraversalResult`. A traversal whose visitor builds a result of
+    /// another shape must be executed with the `ExecuteCustomTraversal`
+    /// method instead of the `ExecuteTraversal` method.
+    pub fn with_visitor<V>(mut self, visitor: V) -> Self
+        where V: Into<String>
+    {
+        self.visitor = Some(visitor.into());
+        self
+    }
+
+    /// Sets a JavaScript function that initializes the result of the
+    /// traversal.
+    pub fn with_init<I>(mut self, init: I) -> Self
+        where I: Into<String>
+    {
+        self.init = Some(init.into());
+        self
+    }
+
+    /// Sets a JavaScript function that returns the connected edges of a
+    /// vertex.
+    pub fn with_expander<E>(mut self, expander: E) -> Self
+        where E: Into<String>
+    {
+        self.expander = Some(expander.into());
+        self
+    }
+
+    /// Sets a JavaScript function that sorts the connected edges of a
+    /// vertex.
+    pub fn with_sort<S>(mut self, sort: S) -> Self
+        where S: Into<String>
+    {
+        self.sort = Some(sort.into());
+        self
+    }
+
+    pub fn with_max_iterations(mut self, max_iterations: u64) -> Self {
+        self.max_iterations = Some(max_iterations);
+        self
+    }
+
+    pub fn start_vertex(&self) -> &DocumentId {
+        &self.start_vertex
+    }
+
+    pub fn graph_name(&self) -> Option<&String> {
+        self.graph_name.as_ref()
+    }
+
+    pub fn edge_collection(&self) -> Option<&String> {
+        self.edge_collection.as_ref()
+    }
+
+    pub fn direction(&self) -> Option<Direction> {
+        self.direction
+    }
+
+    pub fn min_depth(&self) -> Option<u32> {
+        self.min_depth
+    }
+
+    pub fn max_depth(&self) -> Option<u32> {
+        self.max_depth
+    }
+
+    pub fn uniqueness(&self) -> Option<&TraversalUniqueness> {
+        self.uniqueness.as_ref()
+    }
+
+    pub fn order(&self) -> Option<TraversalOrder> {
+        self.order
+    }
+
+    pub fn item_order(&self) -> Option<ItemOrder> {
+        self.item_order
+    }
+
+    pub fn strategy(&self) -> Option<TraversalStrategy> {
+        self.strategy
+    }
+
+    pub fn filter(&self) -> Option<&String> {
+        self.filter.as_ref()
+    }
+
+    pub fn visitor(&self) -> Option<&String> {
+        self.visitor.as_ref()
+    }
+
+    pub fn init(&self) -> Option<&String> {
+        self.init.as_ref()
+    }
+
+    pub fn expander(&self) -> Option<&String> {
+        self.expander.as_ref()
+    }
+
+    pub fn sort(&self) -> Option<&String> {
+        self.sort.as_ref()
+    }
+
+    pub fn max_iterations(&self) -> Option<u64> {
+        self.max_iterations
+    }
+}
+
+/// A path through a graph consisting of the visited vertices and the edges
+/// that connect them.
+///
+/// The content of the vertices is of type `V` and the content of the edges
+/// is of type `E`.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(bound(deserialize = "V: DeserializeOwned, E: DeserializeOwned"))]
+pub struct Path<V, E> {
+    #[serde(default)]
+    edges: Vec<Edge<E>>,
+    #[serde(default)]
+    vertices: Vec<Document<V>>,
+}
+
+impl<V, E> Path<V, E> {
+    pub fn edges(&self) -> &[Edge<E>] {
+        &self.edges
+    }
+
+    pub fn vertices(&self) -> &[Document<V>] {
+        &self.vertices
+    }
+
+    /// Returns the number of edges of this path.
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(bound(deserialize = "V: DeserializeOwned, E: DeserializeOwned"))]
+struct Visited<V, E> {
+    #[serde(default)]
+    vertices: Vec<Document<V>>,
+    #[serde(default)]
+    paths: Vec<Path<V, E>>,
+}
+
+/// The result of a traversal holding the visited vertices and the paths
+/// that lead to them.
+///
+/// This is the shape of the result built by the default visitor of the
+/// server. The result of a traversal with a custom visitor is returned by
+/// the `ExecuteCustomTraversal` method.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(bound(deserialize = "V: DeserializeOwned, E: DeserializeOwned"))]
+pub struct TraversalResult<V, E> {
+    visited: Visited<V, E>,
+}
+
+impl<V, E> TraversalResult<V, E> {
+    /// Returns the visited vertices in the order they have been visited.
+    pub fn vertices(&self) -> &[Document<V>] {
+        &self.visited.vertices
+    }
+
+    /// Returns the path to each visited vertex.
+    pub fn paths(&self) -> &[Path<V, E>] {
+        &self.visited.paths
+    }
+
+    /// Unwraps the visited vertices and the paths to them.
+    pub fn unwrap(self) -> (Vec<Document<V>>, Vec<Path<V, E>>) {
+        (self.visited.vertices, self.visited.paths)
+    }
 }
@@ -0,0 +1,65 @@
+
+use serde_json::{self, Value};
+
+use document::types::{DocumentId, DocumentKey};
+use super::types::*;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Relation {
+    kind: String,
+}
+
+#[test]
+fn serialize_new_edge_without_key() {
+    let new_edge = NewEdge::new(
+        DocumentId::new("persons", "alice"),
+        DocumentId::new("persons", "bob"),
+        Relation { kind: "friend".to_owned() },
+    );
+    let json: Value = serde_json::to_value(&new_edge).unwrap();
+
+    assert_eq!(json!({
+        "_from": "persons/alice",
+        "_to": "persons/bob",
+        "kind": "friend",
+    }), json);
+}
+
+#[test]
+fn serialize_new_edge_with_key() {
+    let new_edge = NewEdge::new(
+        DocumentId::new("persons", "alice"),
+        DocumentId::new("persons", "bob"),
+        Relation { kind: "friend".to_owned() },
+    ).with_key(DocumentKey::new("r1"));
+    let json: Value = serde_json::to_value(&new_edge).unwrap();
+
+    assert_eq!(json!({
+        "_key": "r1",
+        "_from": "persons/alice",
+        "_to": "persons/bob",
+        "kind": "friend",
+    }), json);
+}
+
+#[test]
+fn serialize_new_traversal_with_options() {
+    let new_traversal = NewTraversal::with_edge_collection(DocumentId::new("persons", "alice"), "knows")
+        .with_direction(Direction::Any)
+        .with_max_depth(2)
+        .with_uniqueness(TraversalUniqueness::new(Uniqueness::Global, Uniqueness::Path))
+        .with_strategy(TraversalStrategy::BreadthFirst);
+    let json: Value = serde_json::to_value(&new_traversal).unwrap();
+
+    assert_eq!(json!({
+        "startVertex": "persons/alice",
+        "edgeCollection": "knows",
+        "direction": "any",
+        "maxDepth": 2,
+        "uniqueness": {
+            "vertices": "global",
+            "edges": "path",
+        },
+        "strategy": "breadthfirst",
+    }), json);
+}
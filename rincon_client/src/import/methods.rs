@@ -0,0 +1,248 @@
+
+use serde::ser::Serialize;
+
+use rincon_core::api::method::{ContentFormat, Method, Operation, Parameters,
+    Prepare, RpcReturnType};
+use rincon_core::arango::protocol::{PARAM_COLLECTION, PARAM_COMPLETE,
+    PARAM_DETAILS, PARAM_FROM_PREFIX, PARAM_ON_DUPLICATE, PARAM_OVERWRITE,
+    PARAM_TO_PREFIX, PARAM_TYPE, PARAM_WAIT_FOR_SYNC, PATH_API_IMPORT};
+use super::types::*;
+
+/// Imports documents into a collection in one bulk operation.
+///
+/// Importing many documents with this method is considerably faster than
+/// inserting them with the `InsertDocuments` method.
+///
+/// The documents are sent as one JSON array by default. With
+/// `ImportType::Documents` each document is sent as a separate JSON document
+/// on its own line.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportDocuments<T> {
+    collection_name: String,
+    documents: Vec<T>,
+    import_type: ImportType,
+    on_duplicate: Option<OnDuplicate>,
+    complete: Option<bool>,
+    details: Option<bool>,
+    overwrite: Option<bool>,
+    from_prefix: Option<String>,
+    to_prefix: Option<String>,
+    force_wait_for_sync: Option<bool>,
+}
+
+impl<T> ImportDocuments<T> {
+    /// Constructs a new instance of the `ImportDocuments` method that shall
+    /// import the given documents into the collection with the given name.
+    pub fn new<N>(collection_name: N, documents: Vec<T>) -> Self
+        where N: Into<String>
+    {
+        ImportDocuments {
+            collection_name: collection_name.into(),
+            documents,
+            import_type: ImportType::default(),
+            on_duplicate: None,
+            complete: None,
+            details: None,
+            overwrite: None,
+            from_prefix: None,
+            to_prefix: None,
+            force_wait_for_sync: None,
+        }
+    }
+
+    /// Sets the format in which the documents are sent to the server.
+    pub fn with_import_type(mut self, import_type: ImportType) -> Self {
+        self.import_type = import_type;
+        self
+    }
+
+    /// Sets the action to be carried out in case of a unique key constraint
+    /// violation.
+    pub fn with_on_duplicate<D>(mut self, on_duplicate: D) -> Self
+        where D: Into<Option<OnDuplicate>>
+    {
+        self.on_duplicate = on_duplicate.into();
+        self
+    }
+
+    /// Sets whether the whole import shall fail if any error occurs.
+    ///
+    /// Otherwise the import will continue even if some documents cannot be
+    /// imported.
+    pub fn with_complete<C>(mut self, complete: C) -> Self
+        where C: Into<Option<bool>>
+    {
+        self.complete = complete.into();
+        self
+    }
+
+    /// Sets whether the result shall contain detailed error messages for
+    /// documents that could not be imported.
+    pub fn with_details<D>(mut self, details: D) -> Self
+        where D: Into<Option<bool>>
+    {
+        self.details = details.into();
+        self
+    }
+
+    /// Sets whether all data in the collection shall be removed prior to the
+    /// import.
+    ///
+    /// Note that any existing index definitions will be preserved.
+    pub fn with_overwrite<O>(mut self, overwrite: O) -> Self
+        where O: Into<Option<bool>>
+    {
+        self.overwrite = overwrite.into();
+        self
+    }
+
+    /// Sets an optional prefix for the values in `_from` attributes.
+    ///
+    /// If specified, the value is automatically prepended to each `_from`
+    /// input value. This allows specifying just the keys for `_from`.
+    pub fn with_from_prefix<P>(mut self, from_prefix: P) -> Self
+        where P: Into<Option<String>>
+    {
+        self.from_prefix = from_prefix.into();
+        self
+    }
+
+    /// Sets an optional prefix for the values in `_to` attributes.
+    ///
+    /// If specified, the value is automatically prepended to each `_to`
+    /// input value. This allows specifying just the keys for `_to`.
+    pub fn with_to_prefix<P>(mut self, to_prefix: P) -> Self
+        where P: Into<Option<String>>
+    {
+        self.to_prefix = to_prefix.into();
+        self
+    }
+
+    /// Sets whether the import shall wait until the documents have been
+    /// synced to disk.
+    pub fn with_force_wait_for_sync<W>(mut self, force_wait_for_sync: W) -> Self
+        where W: Into<Option<bool>>
+    {
+        self.force_wait_for_sync = force_wait_for_sync.into();
+        self
+    }
+
+    /// Returns the name of the collection into which the documents shall be
+    /// imported.
+    pub fn collection_name(&self) -> &str {
+        &self.collection_name
+    }
+
+    /// Returns the documents that shall be imported.
+    pub fn documents(&self) -> &[T] {
+        &self.documents
+    }
+
+    /// Returns the format in which the documents are sent to the server.
+    pub fn import_type(&self) -> ImportType {
+        self.import_type
+    }
+
+    /// Returns the action to be carried out in case of a unique key
+    /// constraint violation.
+    pub fn on_duplicate(&self) -> Option<OnDuplicate> {
+        self.on_duplicate
+    }
+
+    /// Returns whether the whole import shall fail if any error occurs.
+    pub fn is_complete(&self) -> Option<bool> {
+        self.complete
+    }
+
+    /// Returns whether the result shall contain detailed error messages.
+    pub fn is_details(&self) -> Option<bool> {
+        self.details
+    }
+
+    /// Returns whether all data in the collection shall be removed prior to
+    /// the import.
+    pub fn is_overwrite(&self) -> Option<bool> {
+        self.overwrite
+    }
+
+    /// Returns the prefix for the values in `_from` attributes.
+    pub fn from_prefix(&self) -> Option<&String> {
+        self.from_prefix.as_ref()
+    }
+
+    /// Returns the prefix for the values in `_to` attributes.
+    pub fn to_prefix(&self) -> Option<&String> {
+        self.to_prefix.as_ref()
+    }
+
+    /// Returns whether the import shall wait until the documents have been
+    /// synced to disk.
+    pub fn is_force_wait_for_sync(&self) -> Option<bool> {
+        self.force_wait_for_sync
+    }
+}
+
+impl<T> Method for ImportDocuments<T> {
+    type Result = ImportResult;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: None,
+    };
+}
+
+impl<T> Prepare for ImportDocuments<T>
+    where T: Serialize
+{
+    type Content = Vec<T>;
+
+    fn operation(&self) -> Operation {
+        Operation::Create
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_IMPORT)
+    }
+
+    fn parameters(&self) -> Parameters {
+        let mut params = Parameters::with_capacity(9);
+        params.insert(PARAM_COLLECTION, self.collection_name.to_owned());
+        params.insert(PARAM_TYPE, self.import_type.as_str());
+        if let Some(on_duplicate) = self.on_duplicate {
+            params.insert(PARAM_ON_DUPLICATE, on_duplicate.as_str());
+        }
+        if let Some(complete) = self.complete {
+            params.insert(PARAM_COMPLETE, complete);
+        }
+        if let Some(details) = self.details {
+            params.insert(PARAM_DETAILS, details);
+        }
+        if let Some(overwrite) = self.overwrite {
+            params.insert(PARAM_OVERWRITE, overwrite);
+        }
+        if let Some(ref from_prefix) = self.from_prefix {
+            params.insert(PARAM_FROM_PREFIX, from_prefix.to_owned());
+        }
+        if let Some(ref to_prefix) = self.to_prefix {
+            params.insert(PARAM_TO_PREFIX, to_prefix.to_owned());
+        }
+        if let Some(force_wait_for_sync) = self.force_wait_for_sync {
+            params.insert(PARAM_WAIT_FOR_SYNC, force_wait_for_sync);
+        }
+        params
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        Some(&self.documents)
+    }
+
+    fn content_format(&self) -> ContentFormat {
+        match self.import_type {
+            ImportType::Documents => ContentFormat::JsonLines,
+            ImportType::List => ContentFormat::Json,
+        }
+    }
+}
@@ -0,0 +1,3 @@
+
+pub use super::methods::*;
+pub use super::types::*;
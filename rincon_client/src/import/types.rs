@@ -0,0 +1,123 @@
+
+const IMPORT_TYPE_DOCUMENTS: &str = "documents";
+const IMPORT_TYPE_LIST: &str = "list";
+
+const ON_DUPLICATE_ERROR: &str = "error";
+const ON_DUPLICATE_UPDATE: &str = "update";
+const ON_DUPLICATE_REPLACE: &str = "replace";
+const ON_DUPLICATE_IGNORE: &str = "ignore";
+
+/// This enum defines the formats in which documents are sent to the server
+/// for being imported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ImportType {
+    /// Each document is sent as a separate JSON document on its own line.
+    Documents,
+    /// All documents are sent as one JSON array.
+    #[default]
+    List,
+}
+
+impl ImportType {
+    /// Returns the value of the `type` parameter for this import type.
+    pub fn as_str(&self) -> &str {
+        match *self {
+            ImportType::Documents => IMPORT_TYPE_DOCUMENTS,
+            ImportType::List => IMPORT_TYPE_LIST,
+        }
+    }
+}
+
+/// This enum defines the possible actions that shall be carried out in case
+/// of a unique key constraint violation during an import.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum OnDuplicate {
+    /// The document is not imported and the violation is reported as error.
+    /// This is the default.
+    #[default]
+    Error,
+    /// The existing document is updated with the attributes of the imported
+    /// document.
+    Update,
+    /// The existing document is replaced by the imported document.
+    Replace,
+    /// The imported document is ignored and the existing document is left
+    /// unchanged.
+    Ignore,
+}
+
+impl OnDuplicate {
+    /// Returns the value of the `onDuplicate` parameter for this action.
+    pub fn as_str(&self) -> &str {
+        match *self {
+            OnDuplicate::Error => ON_DUPLICATE_ERROR,
+            OnDuplicate::Update => ON_DUPLICATE_UPDATE,
+            OnDuplicate::Replace => ON_DUPLICATE_REPLACE,
+            OnDuplicate::Ignore => ON_DUPLICATE_IGNORE,
+        }
+    }
+}
+
+/// This struct holds the result of an import.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportResult {
+    /// The number of documents imported.
+    created: u64,
+    /// The number of documents that were not imported due to an error.
+    errors: u64,
+    /// The number of empty lines found in the input.
+    empty: u64,
+    /// The number of updated or replaced documents.
+    #[serde(default)]
+    updated: u64,
+    /// The number of failed but ignored insert operations.
+    #[serde(default)]
+    ignored: u64,
+    /// Detailed error messages for documents that could not be imported.
+    #[serde(default)]
+    details: Vec<String>,
+}
+
+impl ImportResult {
+    /// Returns the number of documents imported.
+    pub fn created(&self) -> u64 {
+        self.created
+    }
+
+    /// Returns the number of documents that were not imported due to an
+    /// error.
+    pub fn errors(&self) -> u64 {
+        self.errors
+    }
+
+    /// Returns the number of empty lines found in the input.
+    pub fn empty(&self) -> u64 {
+        self.empty
+    }
+
+    /// Returns the number of updated or replaced documents.
+    ///
+    /// Documents are only updated or replaced if the import is executed with
+    /// `OnDuplicate::Update` or `OnDuplicate::Replace`.
+    pub fn updated(&self) -> u64 {
+        self.updated
+    }
+
+    /// Returns the number of failed but ignored insert operations.
+    ///
+    /// Insert operations are only ignored if the import is executed with
+    /// `OnDuplicate::Ignore`.
+    pub fn ignored(&self) -> u64 {
+        self.ignored
+    }
+
+    /// Returns detailed error messages for documents that could not be
+    /// imported.
+    ///
+    /// The details are only returned if the import is executed with the
+    /// `details` option set to `true`.
+    pub fn details(&self) -> &[String] {
+        &self.details
+    }
+}
@@ -0,0 +1,29 @@
+
+use serde_json;
+
+use super::types::*;
+
+#[test]
+fn deserialize_import_result_without_details() {
+    let json_str = r#"{"error":false,"created":2,"errors":0,"empty":0,"updated":0,"ignored":0}"#;
+    let result: ImportResult = serde_json::from_str(json_str).unwrap();
+
+    assert_eq!(2, result.created());
+    assert_eq!(0, result.errors());
+    assert_eq!(0, result.empty());
+    assert_eq!(0, result.updated());
+    assert_eq!(0, result.ignored());
+    assert!(result.details().is_empty());
+}
+
+#[test]
+fn deserialize_import_result_with_details() {
+    let json_str = r#"{"error":false,"created":1,"errors":1,"empty":0,"updated":0,"ignored":0,
+        "details":["at position 1: creating document failed with error 'unique constraint violated'"]}"#;
+    let result: ImportResult = serde_json::from_str(json_str).unwrap();
+
+    assert_eq!(1, result.created());
+    assert_eq!(1, result.errors());
+    assert_eq!(&["at position 1: creating document failed with error 'unique constraint violated'".to_owned()],
+        result.details());
+}
@@ -1,11 +1,9 @@
 //!
 //! Some attributes are only meaningful in a certain server configuration, like
 //! using RocksDB instead of MMFiles or the server is setup in a cluster. Those
-//! attributes are only available in the API of this crate if the crate is
-//! compiled with the related feature enabled. The crate feature `mmfiles`
-//! enables MMFiles related attributes, the crate feature `rocksdb` enables
-//! RocksDB related attributes and the crate feature `cluster` enables cluster
-//! related attributes.
+//! attributes are optional in the API of this crate and are only present if
+//! the server returns them. The configuration of a server can be queried with
+//! the methods in the `admin` module.
 
 #![doc(html_root_url = "https://docs.rs/rincon_client/0.1.0")]
 
@@ -31,12 +29,15 @@ extern crate rincon_core;
 pub mod admin;
 pub mod aql;
 pub mod auth;
+pub mod batch;
 pub mod collection;
 pub mod cursor;
 pub mod database;
 pub mod document;
 pub mod graph;
+pub mod import;
 pub mod index;
+pub mod transaction;
 pub mod user;
 
 pub mod build;
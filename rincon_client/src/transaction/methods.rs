@@ -0,0 +1,77 @@
+
+use std::marker::PhantomData;
+
+use serde::de::DeserializeOwned;
+use serde::ser::Serialize;
+
+use rincon_core::api::method::{Method, Operation, Parameters, Prepare, RpcReturnType};
+use rincon_core::arango::protocol::{FIELD_CODE, FIELD_RESULT, PATH_API_TRANSACTION};
+use super::types::*;
+
+/// Executes a transaction on the server.
+///
+/// The transaction is defined by a JavaScript function that is executed on
+/// the server. All collections used by the transaction must be declared
+/// upfront. The transaction is committed if the function returns normally
+/// and is rolled back if the function throws an exception.
+///
+/// The type parameter `P` specifies the type of the parameters that are
+/// passed to the action function and the type parameter `R` specifies the
+/// type of the value returned by the action function.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExecuteTransaction<P, R> {
+    result_type: PhantomData<R>,
+    transaction: NewTransaction<P>,
+}
+
+impl<P, R> ExecuteTransaction<P, R> {
+    /// Constructs a new instance of the `ExecuteTransaction` method for the
+    /// given transaction.
+    pub fn new(transaction: NewTransaction<P>) -> Self {
+        ExecuteTransaction {
+            result_type: PhantomData,
+            transaction,
+        }
+    }
+
+    /// Returns the transaction that shall be executed.
+    pub fn transaction(&self) -> &NewTransaction<P> {
+        &self.transaction
+    }
+}
+
+impl<P, R> Method for ExecuteTransaction<P, R>
+    where R: DeserializeOwned
+{
+    type Result = R;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: Some(FIELD_RESULT),
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl<P, R> Prepare for ExecuteTransaction<P, R>
+    where P: Serialize
+{
+    type Content = NewTransaction<P>;
+
+    fn operation(&self) -> Operation {
+        Operation::Create
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_TRANSACTION)
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        Some(&self.transaction)
+    }
+}
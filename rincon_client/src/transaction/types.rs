@@ -0,0 +1,263 @@
+
+/// This struct holds the names of the collections that are used in a
+/// transaction.
+///
+/// All collections that are read from or written to in a transaction must be
+/// declared upfront.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionCollections {
+    /// The names of the collections that are only read from within the
+    /// transaction.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    read: Vec<String>,
+
+    /// The names of the collections that are written to within the
+    /// transaction.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    write: Vec<String>,
+
+    /// The names of the collections that are written to exclusively within
+    /// the transaction.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    exclusive: Vec<String>,
+}
+
+impl TransactionCollections {
+    /// Constructs a new instance of `TransactionCollections` with the given
+    /// collection names.
+    pub fn new(read: Vec<String>, write: Vec<String>, exclusive: Vec<String>) -> Self {
+        TransactionCollections {
+            read,
+            write,
+            exclusive,
+        }
+    }
+
+    /// Constructs a new and empty instance of `TransactionCollections`.
+    pub fn empty() -> Self {
+        TransactionCollections {
+            read: Vec::new(),
+            write: Vec::new(),
+            exclusive: Vec::new(),
+        }
+    }
+
+    /// Adds the name of a collection that is read from within the
+    /// transaction.
+    pub fn with_read<N>(mut self, collection_name: N) -> Self
+        where N: Into<String>
+    {
+        self.read.push(collection_name.into());
+        self
+    }
+
+    /// Adds the name of a collection that is written to within the
+    /// transaction.
+    pub fn with_write<N>(mut self, collection_name: N) -> Self
+        where N: Into<String>
+    {
+        self.write.push(collection_name.into());
+        self
+    }
+
+    /// Adds the name of a collection that is written to exclusively within
+    /// the transaction.
+    pub fn with_exclusive<N>(mut self, collection_name: N) -> Self
+        where N: Into<String>
+    {
+        self.exclusive.push(collection_name.into());
+        self
+    }
+
+    /// Returns the names of the collections that are read from within the
+    /// transaction.
+    pub fn read(&self) -> &[String] {
+        &self.read
+    }
+
+    /// Returns the names of the collections that are written to within the
+    /// transaction.
+    pub fn write(&self) -> &[String] {
+        &self.write
+    }
+
+    /// Returns the names of the collections that are written to exclusively
+    /// within the transaction.
+    pub fn exclusive(&self) -> &[String] {
+        &self.exclusive
+    }
+}
+
+impl Default for TransactionCollections {
+    fn default() -> Self {
+        TransactionCollections::empty()
+    }
+}
+
+/// This struct defines the parameters of a transaction that is going to be
+/// executed on the server.
+///
+/// The type parameter `P` specifies the type of the parameters that are
+/// passed to the action of the transaction. If the action does not need any
+/// parameters one can use the provided `Empty` type.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewTransaction<P> {
+    /// The collections that are used in the transaction.
+    collections: TransactionCollections,
+
+    /// The actual transaction operations to be executed, in the form of
+    /// stringified JavaScript code.
+    action: String,
+
+    /// Optional parameters that are passed to the action function.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    params: Option<P>,
+
+    /// An optional boolean flag that, if set, will force the transaction to
+    /// write all data to disk before returning.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    wait_for_sync: Option<bool>,
+
+    /// An optional numeric value that can be used to set a timeout for
+    /// waiting on collection locks. If not specified, a default value will be
+    /// used. Setting `lock_timeout` to 0 will make ArangoDB not time out
+    /// waiting for a lock.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lock_timeout: Option<u32>,
+
+    /// Transaction size limit in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_transaction_size: Option<u64>,
+
+    /// Maximum total size of operations after which an intermediate commit is
+    /// performed automatically.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    intermediate_commit_size: Option<u64>,
+
+    /// Maximum number of operations after which an intermediate commit is
+    /// performed automatically.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    intermediate_commit_count: Option<u64>,
+}
+
+impl<P> NewTransaction<P> {
+    /// Constructs a new instance of `NewTransaction` for the given
+    /// collections and action without any parameters.
+    pub fn new<A>(collections: TransactionCollections, action: A) -> Self
+        where A: Into<String>
+    {
+        NewTransaction {
+            collections,
+            action: action.into(),
+            params: None,
+            wait_for_sync: None,
+            lock_timeout: None,
+            max_transaction_size: None,
+            intermediate_commit_size: None,
+            intermediate_commit_count: None,
+        }
+    }
+
+    /// Constructs a new instance of `NewTransaction` for the given
+    /// collections, action and parameters.
+    pub fn with_params<A>(collections: TransactionCollections, action: A, params: P) -> Self
+        where A: Into<String>
+    {
+        let mut transaction = NewTransaction::new(collections, action);
+        transaction.params = Some(params);
+        transaction
+    }
+
+    /// Returns the collections that are used in the transaction.
+    pub fn collections(&self) -> &TransactionCollections {
+        &self.collections
+    }
+
+    /// Returns the action of the transaction as stringified JavaScript code.
+    pub fn action(&self) -> &str {
+        &self.action
+    }
+
+    /// Sets the parameters that are passed to the action function.
+    pub fn set_params<T>(&mut self, params: T)
+        where T: Into<Option<P>>
+    {
+        self.params = params.into();
+    }
+
+    /// Returns the parameters that are passed to the action function.
+    pub fn params(&self) -> Option<&P> {
+        self.params.as_ref()
+    }
+
+    /// Sets whether the transaction shall write all data to disk before
+    /// returning.
+    pub fn set_wait_for_sync<W>(&mut self, wait_for_sync: W)
+        where W: Into<Option<bool>>
+    {
+        self.wait_for_sync = wait_for_sync.into();
+    }
+
+    /// Returns whether the transaction shall write all data to disk before
+    /// returning.
+    pub fn is_wait_for_sync(&self) -> Option<bool> {
+        self.wait_for_sync
+    }
+
+    /// Sets the timeout for waiting on collection locks.
+    ///
+    /// Setting the lock timeout to 0 will make ArangoDB not time out waiting
+    /// for a lock.
+    pub fn set_lock_timeout<T>(&mut self, lock_timeout: T)
+        where T: Into<Option<u32>>
+    {
+        self.lock_timeout = lock_timeout.into();
+    }
+
+    /// Returns the timeout for waiting on collection locks.
+    pub fn lock_timeout(&self) -> Option<u32> {
+        self.lock_timeout
+    }
+
+    /// Sets the transaction size limit in bytes.
+    pub fn set_max_transaction_size<S>(&mut self, max_transaction_size: S)
+        where S: Into<Option<u64>>
+    {
+        self.max_transaction_size = max_transaction_size.into();
+    }
+
+    /// Returns the transaction size limit in bytes.
+    pub fn max_transaction_size(&self) -> Option<u64> {
+        self.max_transaction_size
+    }
+
+    /// Sets the maximum total size of operations after which an intermediate
+    /// commit is performed automatically.
+    pub fn set_intermediate_commit_size<S>(&mut self, intermediate_commit_size: S)
+        where S: Into<Option<u64>>
+    {
+        self.intermediate_commit_size = intermediate_commit_size.into();
+    }
+
+    /// Returns the maximum total size of operations after which an
+    /// intermediate commit is performed automatically.
+    pub fn intermediate_commit_size(&self) -> Option<u64> {
+        self.intermediate_commit_size
+    }
+
+    /// Sets the maximum number of operations after which an intermediate
+    /// commit is performed automatically.
+    pub fn set_intermediate_commit_count<C>(&mut self, intermediate_commit_count: C)
+        where C: Into<Option<u64>>
+    {
+        self.intermediate_commit_count = intermediate_commit_count.into();
+    }
+
+    /// Returns the maximum number of operations after which an intermediate
+    /// commit is performed automatically.
+    pub fn intermediate_commit_count(&self) -> Option<u64> {
+        self.intermediate_commit_count
+    }
+}
@@ -0,0 +1,32 @@
+
+use serde_json;
+
+use rincon_core::api::types::Empty;
+use super::types::*;
+
+#[test]
+fn serialize_new_transaction_without_params() {
+    let collections = TransactionCollections::empty()
+        .with_read("customers")
+        .with_write("orders");
+    let transaction: NewTransaction<Empty> = NewTransaction::new(collections,
+        "function () { return 1; }");
+
+    let json_str = serde_json::to_string(&transaction).unwrap();
+
+    assert_eq!(r#"{"collections":{"read":["customers"],"write":["orders"]},"action":"function () { return 1; }"}"#, &json_str);
+}
+
+#[test]
+fn serialize_new_transaction_with_params_and_options() {
+    let collections = TransactionCollections::empty()
+        .with_exclusive("accounts");
+    let mut transaction = NewTransaction::with_params(collections,
+        "function (params) { return params.amount; }", json!({ "amount": 42 }));
+    transaction.set_wait_for_sync(true);
+    transaction.set_lock_timeout(5);
+
+    let json_str = serde_json::to_string(&transaction).unwrap();
+
+    assert_eq!(r#"{"collections":{"exclusive":["accounts"]},"action":"function (params) { return params.amount; }","params":{"amount":42},"waitForSync":true,"lockTimeout":5}"#, &json_str);
+}
@@ -8,6 +8,7 @@ extern crate rincon_test_helper;
 
 use rincon_core::api::connector::Execute;
 use rincon_client::admin::methods::*;
+use rincon_client::admin::types::{EngineType, ServerRole};
 
 use rincon_test_helper::*;
 
@@ -83,3 +84,35 @@ fn get_server_version_sub_part() {
     }, |_, _| {
     });
 }
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn get_engine() {
+    arango_system_db_test(|conn, ref mut core| {
+
+        let method = GetEngine::new();
+        let work = conn.execute(method);
+        let engine = core.run(work).unwrap();
+
+        assert_eq!(EngineType::MmFiles, engine.kind());
+        assert!(engine.supports().indexes().contains(&"primary".to_owned()));
+
+    }, |_, _| {
+    });
+}
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn get_server_role() {
+    arango_system_db_test(|conn, ref mut core| {
+
+        let method = GetServerRole::new();
+        let work = conn.execute(method);
+        let role = core.run(work).unwrap();
+
+        assert_eq!(ServerRole::Single, role);
+        assert!(!role.is_cluster());
+
+    }, |_, _| {
+    });
+}
@@ -23,6 +23,7 @@ use rincon_test_helper::*;
 
 
 #[test]
+#[cfg_attr(feature = "fake_server", ignore)]
 fn parse_valid_query() {
     arango_test_with_document_collection("aql_customers01", |conn, ref mut core| {
         let inserts = Query::new(
@@ -217,6 +218,7 @@ fn parse_valid_query() {
 }
 
 #[test]
+#[cfg_attr(feature = "fake_server", ignore)]
 fn parse_invalid_query() {
     arango_test_with_document_collection("aql_customers02", |conn, ref mut core| {
         let inserts = Query::new(
@@ -251,6 +253,7 @@ fn parse_invalid_query() {
 }
 
 #[test]
+#[cfg_attr(feature = "fake_server", ignore)]
 fn explain_valid_query() {
     arango_test_with_document_collection("aql_customers03", |conn, ref mut core| {
         let database = env::var(ENV_ARANGO_TEST_DATABASE).unwrap();
@@ -319,6 +322,7 @@ fn explain_valid_query() {
 }
 
 #[test]
+#[cfg_attr(feature = "fake_server", ignore)]
 fn explain_a_plan_with_some_optimizer_rules_applied() {
     arango_test_with_document_collection("aql_customers04", |conn, ref mut core| {
         let database = env::var(ENV_ARANGO_TEST_DATABASE).unwrap();
@@ -583,6 +587,7 @@ fn explain_a_plan_with_some_optimizer_rules_applied() {
 }
 
 #[test]
+#[cfg_attr(feature = "fake_server", ignore)]
 fn explain_2_plans_with_some_optimizer_rules_specified() {
     arango_test_with_document_collection("aql_customers05", |conn, ref mut core| {
         let database = env::var(ENV_ARANGO_TEST_DATABASE).unwrap();
@@ -827,6 +832,7 @@ fn explain_2_plans_with_some_optimizer_rules_specified() {
 }
 
 #[test]
+#[cfg_attr(feature = "fake_server", ignore)]
 fn explain_query_with_limit_and_offset() {
     arango_test_with_document_collection("aql_customers06", |conn, ref mut core| {
         let database = env::var(ENV_ARANGO_TEST_DATABASE).unwrap();
@@ -905,6 +911,7 @@ fn explain_query_with_limit_and_offset() {
 }
 
 #[test]
+#[cfg_attr(feature = "fake_server", ignore)]
 fn explain_query_with_basic_collect() {
     arango_test_with_document_collection("aql_customers07", |conn, ref mut core| {
         let database = env::var(ENV_ARANGO_TEST_DATABASE).unwrap();
@@ -1076,6 +1083,7 @@ fn explain_query_with_basic_collect() {
 }
 
 #[test]
+#[cfg_attr(feature = "fake_server", ignore)]
 fn explain_query_with_collect_into_group_variable() {
     arango_test_with_document_collection("aql_customers08", |conn, ref mut core| {
         let database = env::var(ENV_ARANGO_TEST_DATABASE).unwrap();
@@ -1267,6 +1275,7 @@ fn explain_query_with_collect_into_group_variable() {
 }
 
 #[test]
+#[cfg_attr(feature = "fake_server", ignore)]
 fn explain_query_with_collect_multiple_criteria() {
     arango_test_with_document_collection("aql_customers09", |conn, ref mut core| {
         let database = env::var(ENV_ARANGO_TEST_DATABASE).unwrap();
@@ -1519,6 +1528,7 @@ fn explain_query_with_collect_multiple_criteria() {
 }
 
 #[test]
+#[cfg_attr(feature = "fake_server", ignore)]
 fn explain_query_with_collect_count_aggregation() {
     arango_test_with_document_collection("aql_customers10", |conn, ref mut core| {
         let database = env::var(ENV_ARANGO_TEST_DATABASE).unwrap();
@@ -1713,6 +1723,7 @@ fn explain_query_with_collect_count_aggregation() {
 }
 
 #[test]
+#[cfg_attr(feature = "fake_server", ignore)]
 fn explain_query_with_collect_and_aggregation() {
     arango_test_with_document_collection("aql_customers11", |conn, ref mut core| {
         let database = env::var(ENV_ARANGO_TEST_DATABASE).unwrap();
@@ -1909,6 +1920,7 @@ fn explain_query_with_collect_and_aggregation() {
 }
 
 #[test]
+#[cfg_attr(feature = "fake_server", ignore)]
 fn explain_insert_into_collection() {
     arango_test_with_document_collection("aql_customers12", |conn, ref mut core| {
         let database = env::var(ENV_ARANGO_TEST_DATABASE).unwrap();
@@ -2060,6 +2072,7 @@ fn explain_insert_into_collection() {
 }
 
 #[test]
+#[cfg_attr(feature = "fake_server", ignore)]
 fn explain_remove_document_from_collection() {
     arango_test_with_document_collection("aql_customers13", |conn, ref mut core| {
         let database = env::var(ENV_ARANGO_TEST_DATABASE).unwrap();
@@ -2183,6 +2196,7 @@ fn explain_remove_document_from_collection() {
 }
 
 #[test]
+#[cfg_attr(feature = "fake_server", ignore)]
 fn explain_update_document() {
     arango_test_with_document_collection("aql_customers14", |conn, ref mut core| {
         let database = env::var(ENV_ARANGO_TEST_DATABASE).unwrap();
@@ -2378,6 +2392,7 @@ fn explain_update_document() {
 }
 
 #[test]
+#[cfg_attr(feature = "fake_server", ignore)]
 fn explain_replace_document() {
     arango_test_with_document_collection("aql_customers15", |conn, ref mut core| {
         let database = env::var(ENV_ARANGO_TEST_DATABASE).unwrap();
@@ -2616,6 +2631,7 @@ fn explain_replace_document() {
 }
 
 #[test]
+#[cfg_attr(feature = "fake_server", ignore)]
 fn explain_upsert_when_document_not_existing() {
     arango_test_with_document_collection("aql_customers16", |conn, ref mut core| {
         let database = env::var(ENV_ARANGO_TEST_DATABASE).unwrap();
@@ -2966,6 +2982,7 @@ fn explain_upsert_when_document_not_existing() {
 }
 
 #[test]
+#[cfg_attr(feature = "fake_server", ignore)]
 fn explain_upsert_when_document_is_existing() {
     arango_test_with_document_collection("aql_customers17", |conn, ref mut core| {
         let database = env::var(ENV_ARANGO_TEST_DATABASE).unwrap();
@@ -3325,6 +3342,7 @@ fn explain_upsert_when_document_is_existing() {
 }
 
 #[test]
+#[cfg_attr(feature = "fake_server", ignore)]
 fn explain_query_with_no_result() {
     arango_test_with_document_collection("aql_customers18", |conn, ref mut core| {
         let database = env::var(ENV_ARANGO_TEST_DATABASE).unwrap();
@@ -3406,6 +3424,7 @@ fn explain_query_with_no_result() {
 }
 
 #[test]
+#[cfg_attr(feature = "fake_server", ignore)]
 fn explain_query_with_simple_enumeration() {
     arango_test_with_document_collection("aql_customers19", |conn, ref mut core| {
 
@@ -3500,3 +3519,124 @@ fn explain_query_with_simple_enumeration() {
 
     });
 }
+
+#[test]
+fn create_list_and_delete_aql_functions() {
+    arango_test_with_user_db("test_aql_user10", "test_aql_db10", |conn, ref mut core| {
+
+        let bm25 = NewAqlFunction::new("scoring::bm25", "function (tf, idf) { return tf * idf; }")
+            .with_deterministic(true);
+        let created = core.run(conn.execute(CreateAqlFunction::new(bm25.clone()))).unwrap();
+        assert!(created);
+
+        let replaced = core.run(conn.execute(CreateAqlFunction::new(bm25))).unwrap();
+        assert!(!replaced);
+
+        let tfidf = NewAqlFunction::new("scoring::tfidf", "function (tf) { return tf; }");
+        core.run(conn.execute(CreateAqlFunction::new(tfidf))).unwrap();
+        let upper = NewAqlFunction::new("text::upper", "function (s) { return s.toUpperCase(); }");
+        core.run(conn.execute(CreateAqlFunction::new(upper))).unwrap();
+
+        let functions = core.run(conn.execute(ListAqlFunctions::with_namespace("scoring"))).unwrap();
+        assert_eq!(2, functions.len());
+        assert_eq!("scoring::bm25", functions[0].name());
+        assert_eq!("function (tf, idf) { return tf * idf; }", functions[0].code());
+        assert!(functions[0].is_deterministic());
+
+        let deleted = core.run(conn.execute(DeleteAqlFunction::group("scoring"))).unwrap();
+        assert_eq!(2, deleted);
+
+        let deleted = core.run(conn.execute(DeleteAqlFunction::new("text::upper"))).unwrap();
+        assert_eq!(1, deleted);
+
+        let functions = core.run(conn.execute(ListAqlFunctions::new())).unwrap();
+        assert!(functions.is_empty());
+    });
+}
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn create_aql_function_with_invalid_name() {
+    arango_test_with_user_db("test_aql_user11", "test_aql_db11", |conn, ref mut core| {
+
+        let function = NewAqlFunction::new("bm25", "function (tf, idf) { return tf * idf; }");
+        let result = core.run(conn.execute(CreateAqlFunction::new(function)));
+
+        match result {
+            Err(Error::Method(error)) => {
+                assert_eq!(400, error.status_code());
+                assert_eq!(ErrorCode::QueryFunctionInvalidName, error.error_code());
+            },
+            _ => panic!("Error::Method expected but got {:?}", result),
+        };
+    });
+}
+
+#[test]
+fn delete_not_existing_aql_function() {
+    arango_test_with_user_db("test_aql_user12", "test_aql_db12", |conn, ref mut core| {
+
+        let result = core.run(conn.execute(DeleteAqlFunction::new("scoring::bm25")));
+
+        match result {
+            Err(Error::Method(error)) => {
+                assert_eq!(404, error.status_code());
+                assert_eq!(ErrorCode::QueryFunctionNotFound, error.error_code());
+            },
+            _ => panic!("Error::Method expected but got {:?}", result),
+        };
+    });
+}
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn get_and_change_query_tracking_properties() {
+    arango_test_with_user_db("test_aql_user20", "test_aql_db20", |conn, ref mut core| {
+
+        let properties = core.run(conn.execute(GetQueryTrackingProperties::new())).unwrap();
+        assert!(properties.is_enabled());
+
+        let mut updates = QueryTrackingPropertiesUpdate::new();
+        updates.set_track_bind_vars(false);
+        updates.set_max_slow_queries(32);
+        updates.set_slow_query_threshold(2.5);
+        let changed = core.run(conn.execute(ChangeQueryTrackingProperties::new(updates))).unwrap();
+
+        assert!(!changed.is_track_bind_vars());
+        assert_eq!(32, changed.max_slow_queries());
+        assert_eq!(2.5, changed.slow_query_threshold());
+        assert_eq!(properties.max_query_string_length(), changed.max_query_string_length());
+    });
+}
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn get_current_and_slow_queries_and_clear_slow_queries() {
+    arango_test_with_user_db("test_aql_user21", "test_aql_db21", |conn, ref mut core| {
+
+        let current = core.run(conn.execute(GetCurrentQueries::new())).unwrap();
+        assert!(current.is_empty());
+
+        core.run(conn.execute(ClearSlowQueries::new())).unwrap();
+
+        let slow = core.run(conn.execute(GetSlowQueries::new())).unwrap();
+        assert!(slow.is_empty());
+    });
+}
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn kill_not_existing_query() {
+    arango_test_with_user_db("test_aql_user22", "test_aql_db22", |conn, ref mut core| {
+
+        let result = core.run(conn.execute(KillQuery::new("4711")));
+
+        match result {
+            Err(Error::Method(error)) => {
+                assert_eq!(404, error.status_code());
+                assert_eq!(ErrorCode::QueryNotFound, error.error_code());
+            },
+            _ => panic!("Error::Method expected but got {:?}", result),
+        };
+    });
+}
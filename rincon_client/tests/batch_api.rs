@@ -0,0 +1,150 @@
+#[macro_use] extern crate serde_json;
+extern crate tokio_core;
+
+extern crate rincon_core;
+extern crate rincon_connector;
+extern crate rincon_client;
+extern crate rincon_test_helper;
+
+use std::env;
+use std::fs;
+
+use serde_json::Value;
+use tokio_core::reactor::Core;
+
+use rincon_core::api::connector::{Connector, Error, Execute};
+use rincon_core::api::method::Operation;
+use rincon_core::api::ErrorCode;
+use rincon_connector::mock::{Expectation, MockConnector};
+use rincon_connector::recording::{RecordingConnector, ReplayConnector};
+use rincon_client::admin::methods::GetServerVersion;
+use rincon_client::batch::methods::*;
+use rincon_client::collection::methods::GetCollection;
+use rincon_client::document::methods::{GetDocument, InsertDocument};
+use rincon_client::document::types::{DocumentKey, NewDocument};
+
+use rincon_test_helper::*;
+
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn execute_batch_of_different_methods() {
+    arango_test_with_document_collection("batch_customers01", |conn, ref mut core| {
+
+        let batch = Batch::new()
+            .with_method(InsertDocument::new("batch_customers01",
+                NewDocument::from_content(json!({ "name": "Jane Doe" }))
+                    .with_key(DocumentKey::new("jane"))))
+            .with_method(GetServerVersion::new())
+            .with_method(GetCollection::with_name("batch_customers01"));
+        let response = core.run(conn.execute(batch)).unwrap();
+
+        assert_eq!(3, response.parts().len());
+        let (header, version, collection) = response.into_results();
+        assert_eq!("jane", header.unwrap().key().as_str());
+        assert_eq!("arango", version.unwrap().server());
+        assert_eq!("batch_customers01", collection.unwrap().name());
+
+        let document = core.run(conn.execute(GetDocument::<Value>::new("batch_customers01",
+            DocumentKey::new("jane")))).unwrap();
+        assert_eq!(&json!({ "name": "Jane Doe" }), document.content());
+    });
+}
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn execute_batch_with_failing_method() {
+    arango_test_with_document_collection("batch_customers02", |conn, ref mut core| {
+
+        let batch = Batch::new()
+            .with_method(GetCollection::with_name("batch_not_existing99"))
+            .with_method(GetCollection::with_name("batch_customers02"));
+        let (missing, collection) = core.run(conn.execute(batch)).unwrap().into_results();
+
+        match missing {
+            Err(Error::Method(error)) => {
+                assert_eq!(404, error.status_code());
+                assert_eq!(ErrorCode::ArangoCollectionNotFound, error.error_code());
+            },
+            _ => panic!("Error::ApiError expected but got {:?}", missing),
+        }
+        assert_eq!("batch_customers02", collection.unwrap().name());
+    });
+}
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn execute_batch_of_methods_of_same_type() {
+    arango_test_with_document_collection("batch_customers03", |conn, ref mut core| {
+
+        let inserts: Batch<Vec<_>> = ["anna", "bert"].iter()
+            .map(|key| InsertDocument::new("batch_customers03",
+                NewDocument::from_content(json!({ "name": key }))
+                    .with_key(DocumentKey::new(*key))))
+            .collect();
+        let number_of_inserts = inserts.len();
+        let headers = core.run(conn.execute(inserts)).unwrap().into_results(number_of_inserts);
+        assert_eq!(2, headers.len());
+        assert!(headers.iter().all(Result::is_ok));
+
+        let batch = Batch::from_methods(["anna", "carl", "bert"].iter()
+            .map(|key| GetDocument::<Value>::new("batch_customers03", DocumentKey::new(*key))));
+        assert_eq!(3, batch.len());
+        let documents = core.run(conn.execute(batch)).unwrap().into_results(3);
+
+        assert_eq!(3, documents.len());
+        assert_eq!(&json!({ "name": "anna" }), documents[0].as_ref().unwrap().content());
+        match documents[1] {
+            Err(Error::Method(ref error)) => {
+                assert_eq!(404, error.status_code());
+                assert_eq!(ErrorCode::ArangoDocumentNotFound, error.error_code());
+            },
+            ref result => panic!("Error::ApiError expected but got {:?}", result),
+        }
+        assert_eq!(&json!({ "name": "bert" }), documents[2].as_ref().unwrap().content());
+    });
+}
+
+fn mock_batch_server() -> MockConnector {
+    let connector = MockConnector::new();
+    connector.expect(Expectation::new(Operation::Create, "/_api/batch")
+        .in_database("shop")
+        .returns(json!("--XXXrinconBatchBoundaryXXX\r\n\
+            Content-Type: application/x-arango-batchpart\r\n\
+            Content-Id: 1\r\n\
+            \r\n\
+            HTTP/1.1 200 OK\r\n\
+            Content-Type: application/json; charset=utf-8\r\n\
+            \r\n\
+            {\"server\":\"arango\",\"version\":\"3.2.10\",\"license\":\"community\"}\r\n\
+            --XXXrinconBatchBoundaryXXX--\r\n")));
+    connector
+}
+
+#[test]
+fn execute_batch_on_mock_connector() {
+    let connector = mock_batch_server();
+
+    let mut core = Core::new().unwrap();
+    let batch = Batch::new().with_method(GetServerVersion::new());
+    let (version,) = core.run(connector.connection("shop").execute(batch)).unwrap().into_results();
+
+    assert_eq!("3.2.10", version.unwrap().version());
+    connector.verify();
+}
+
+#[test]
+fn replay_recorded_batch() {
+    let mut core = Core::new().unwrap();
+    let path = env::temp_dir().join("rincon_batch_recording.json");
+    let recorder = RecordingConnector::new(mock_batch_server(), path.clone());
+    let batch = Batch::new().with_method(GetServerVersion::new());
+    let _ = core.run(recorder.connection("shop").execute(batch.clone())).unwrap();
+    let replay = ReplayConnector::new(recorder.interactions());
+    recorder.save().unwrap();
+    fs::remove_file(&path).unwrap();
+
+    let (version,) = core.run(replay.connection("shop").execute(batch)).unwrap().into_results();
+
+    assert_eq!("3.2.10", version.unwrap().version());
+}
@@ -1,4 +1,5 @@
 
+#[macro_use] extern crate serde_json;
 extern crate tokio_core;
 
 extern crate rincon_core;
@@ -8,16 +9,29 @@ extern crate rincon_test_helper;
 
 use rincon_core::api::connector::{Error, Execute};
 use rincon_core::api::ErrorCode;
+use rincon_client::admin::methods::GetEngine;
+use rincon_client::admin::types::EngineType;
 use rincon_client::collection::methods::*;
 use rincon_client::collection::types::*;
+use rincon_client::document::methods::InsertDocuments;
+use rincon_client::document::types::NewDocument;
 
 use rincon_test_helper::*;
 
+/// Returns the journal size of a new collection, which is only reported by
+/// servers using the MMFiles storage engine.
+fn default_journal_size(engine: EngineType) -> Option<u64> {
+    match engine {
+        EngineType::MmFiles => Some(32 * 1024 * 1024),
+        EngineType::RocksDb => None,
+    }
+}
 
 #[test]
 fn create_collection_with_default_properties() {
     arango_test_with_user_db("test_coll_user1", "test_coll_db11", |conn, ref mut core| {
 
+        let engine = core.run(conn.execute(GetEngine::new())).unwrap().kind();
         let method = CreateCollection::with_name("test_collection1");
         let work = conn.execute(method);
         let collection = core.run(work).unwrap();
@@ -28,8 +42,10 @@ fn create_collection_with_default_properties() {
         assert!(!collection.is_system());
         assert!(!collection.is_wait_for_sync());
 
-        #[cfg(feature = "mmfiles")]
-        assert!(!collection.is_volatile());
+        match engine {
+            EngineType::MmFiles => assert_eq!(Some(false), collection.is_volatile()),
+            EngineType::RocksDb => assert_eq!(None, collection.is_volatile()),
+        }
     });
 }
 
@@ -37,6 +53,7 @@ fn create_collection_with_default_properties() {
 fn create_edge_collection_with_wait_for_sync() {
     arango_test_with_user_db("test_coll_user2", "test_coll_db21", |conn, ref mut core| {
 
+        let engine = core.run(conn.execute(GetEngine::new())).unwrap().kind();
         let mut new_collection = NewCollection::edges_with_name("test_collection1");
         new_collection.set_wait_for_sync(Some(true));
 
@@ -50,8 +67,10 @@ fn create_edge_collection_with_wait_for_sync() {
         assert!(!collection.is_system());
         assert!(collection.is_wait_for_sync());
 
-        #[cfg(feature = "mmfiles")]
-        assert!(!collection.is_volatile());
+        match engine {
+            EngineType::MmFiles => assert_eq!(Some(false), collection.is_volatile()),
+            EngineType::RocksDb => assert_eq!(None, collection.is_volatile()),
+        }
     });
 }
 
@@ -155,6 +174,7 @@ fn get_collection_should_return_an_error_if_collection_not_found() {
 fn get_collection_properties_should_return_collection_properties() {
     arango_test_with_user_db("test_coll_user8", "test_coll_db81", |conn, ref mut core| {
 
+        let engine = core.run(conn.execute(GetEngine::new())).unwrap().kind();
         let _ = core.run(conn.execute(CreateCollection::documents_with_name("test_collection1"))).unwrap();
         let _ = core.run(conn.execute(CreateCollection::edges_with_name("test_collection2"))).unwrap();
 
@@ -171,12 +191,18 @@ fn get_collection_properties_should_return_collection_properties() {
         assert_eq!(0, collection.key_options().last_value());
         assert!(!collection.is_wait_for_sync());
 
-        #[cfg(feature = "mmfiles")]
-        assert!(!collection.is_volatile());
-        #[cfg(feature = "mmfiles")]
-        assert!(collection.is_do_compact());
-        #[cfg(feature = "mmfiles")]
-        assert_eq!(8, collection.index_buckets());
+        match engine {
+            EngineType::MmFiles => {
+                assert_eq!(Some(false), collection.is_volatile());
+                assert_eq!(Some(true), collection.is_do_compact());
+                assert_eq!(Some(8), collection.index_buckets());
+            },
+            EngineType::RocksDb => {
+                assert_eq!(None, collection.is_volatile());
+                assert_eq!(None, collection.is_do_compact());
+                assert_eq!(None, collection.index_buckets());
+            },
+        }
     });
 }
 
@@ -206,13 +232,13 @@ fn get_collection_properties_should_return_an_error_if_collection_not_found() {
 fn change_collection_properties_wait_for_sync() {
     arango_test_with_user_db("test_coll_user10", "test_coll_db101", |conn, ref mut core| {
 
+        let engine = core.run(conn.execute(GetEngine::new())).unwrap().kind();
         let _ = core.run(conn.execute(CreateCollection::documents_with_name("test_collection1"))).unwrap();
         let original = core.run(conn.execute(GetCollectionProperties::with_name("test_collection1"))).unwrap();
 
         assert_eq!("test_collection1", original.name());
         assert!(!original.is_wait_for_sync());
-        #[cfg(feature = "mmfiles")]
-        assert_eq!(32 * 1024 * 1024, original.journal_size());
+        assert_eq!(default_journal_size(engine), original.journal_size());
 
         let mut updates = CollectionPropertiesUpdate::new();
         updates.set_wait_for_sync(Some(true));
@@ -222,22 +248,21 @@ fn change_collection_properties_wait_for_sync() {
 
         assert_eq!("test_collection1", updated.name());
         assert!(updated.is_wait_for_sync());
-        #[cfg(feature = "mmfiles")]
-        assert_eq!(32 * 1024 * 1024, updated.journal_size());
+        assert_eq!(default_journal_size(engine), updated.journal_size());
     });
 }
 
-#[cfg(feature = "mmfiles")]
 #[test]
 fn change_collection_properties_journal_size() {
     arango_test_with_user_db("test_coll_user11", "test_coll_db111", |conn, ref mut core| {
 
+        let engine = core.run(conn.execute(GetEngine::new())).unwrap().kind();
         let _ = core.run(conn.execute(CreateCollection::documents_with_name("test_collection1"))).unwrap();
         let original = core.run(conn.execute(GetCollectionProperties::with_name("test_collection1"))).unwrap();
 
         assert_eq!("test_collection1", original.name());
         assert!(!original.is_wait_for_sync());
-        assert_eq!(32 * 1024 * 1024, original.journal_size());
+        assert_eq!(default_journal_size(engine), original.journal_size());
 
         let mut updates = CollectionPropertiesUpdate::new();
         updates.set_journal_size(Some(128 * 1024 * 1024));
@@ -247,7 +272,10 @@ fn change_collection_properties_journal_size() {
 
         assert_eq!("test_collection1", updated.name());
         assert!(!updated.is_wait_for_sync());
-        assert_eq!(128 * 1024 * 1024, updated.journal_size());
+        match engine {
+            EngineType::MmFiles => assert_eq!(Some(128 * 1024 * 1024), updated.journal_size()),
+            EngineType::RocksDb => assert_eq!(None, updated.journal_size()),
+        }
     });
 }
 
@@ -291,3 +319,175 @@ fn rename_collection_to_empty_name() {
         }
     });
 }
+
+#[test]
+fn get_collection_count_of_collection_with_documents() {
+    arango_test_with_user_db("test_coll_user14", "test_coll_db141", |conn, ref mut core| {
+
+        let _ = core.run(conn.execute(CreateCollection::documents_with_name("test_collection1"))).unwrap();
+        let _ = core.run(conn.execute(InsertDocuments::new("test_collection1", vec![
+            NewDocument::from_content(json!({ "name": "Jane" })),
+            NewDocument::from_content(json!({ "name": "John" })),
+            NewDocument::from_content(json!({ "name": "Jack" })),
+        ]))).unwrap();
+
+        let method = GetCollectionCount::with_name("test_collection1");
+        let work = conn.execute(method);
+        let count = core.run(work).unwrap();
+
+        assert_eq!(3, count);
+    });
+}
+
+#[test]
+fn truncate_collection_should_remove_all_documents() {
+    arango_test_with_user_db("test_coll_user15", "test_coll_db151", |conn, ref mut core| {
+
+        let _ = core.run(conn.execute(CreateCollection::documents_with_name("test_collection1"))).unwrap();
+        let _ = core.run(conn.execute(InsertDocuments::new("test_collection1", vec![
+            NewDocument::from_content(json!({ "name": "Jane" })),
+            NewDocument::from_content(json!({ "name": "John" })),
+        ]))).unwrap();
+
+        let method = TruncateCollection::with_name("test_collection1");
+        let work = conn.execute(method);
+        let collection = core.run(work).unwrap();
+
+        assert_eq!("test_collection1", collection.name());
+        let count = core.run(conn.execute(GetCollectionCount::with_name("test_collection1"))).unwrap();
+        assert_eq!(0, count);
+    });
+}
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn get_collection_figures_of_collection_with_documents() {
+    arango_test_with_user_db("test_coll_user16", "test_coll_db161", |conn, ref mut core| {
+
+        let engine = core.run(conn.execute(GetEngine::new())).unwrap().kind();
+        let _ = core.run(conn.execute(CreateCollection::documents_with_name("test_collection1"))).unwrap();
+        let _ = core.run(conn.execute(InsertDocuments::new("test_collection1", vec![
+            NewDocument::from_content(json!({ "name": "Jane" })),
+            NewDocument::from_content(json!({ "name": "John" })),
+        ]))).unwrap();
+
+        let method = GetCollectionFigures::with_name("test_collection1");
+        let work = conn.execute(method);
+        let figures = core.run(work).unwrap();
+
+        assert_eq!("test_collection1", figures.name());
+        assert_eq!(2, figures.count());
+        assert_eq!(1, figures.figures().indexes().count());
+        assert_eq!(engine, figures.figures().engine_specific().engine());
+        match engine {
+            EngineType::MmFiles => assert_eq!(Some(2),
+                figures.figures().mmfiles().map(|mmfiles| mmfiles.alive().count())),
+            EngineType::RocksDb => assert!(figures.figures().rocksdb().is_some()),
+        }
+    });
+}
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn get_collection_checksum_with_revisions_and_data() {
+    arango_test_with_user_db("test_coll_user17", "test_coll_db171", |conn, ref mut core| {
+
+        let _ = core.run(conn.execute(CreateCollection::documents_with_name("test_collection1"))).unwrap();
+        let _ = core.run(conn.execute(InsertDocuments::new("test_collection1", vec![
+            NewDocument::from_content(json!({ "name": "Jane" })),
+        ]))).unwrap();
+
+        let keys_only = core.run(conn.execute(GetCollectionChecksum::with_name("test_collection1"))).unwrap();
+        let mut method = GetCollectionChecksum::with_name("test_collection1");
+        method.set_with_revisions(true);
+        method.set_with_data(true);
+        let work = conn.execute(method);
+        let checksum = core.run(work).unwrap();
+
+        assert_eq!("test_collection1", checksum.name());
+        assert_ne!(keys_only.checksum(), checksum.checksum());
+        assert_eq!(keys_only.revision(), checksum.revision());
+    });
+}
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn get_collection_revision_changes_on_insert() {
+    arango_test_with_user_db("test_coll_user18", "test_coll_db181", |conn, ref mut core| {
+
+        let _ = core.run(conn.execute(CreateCollection::documents_with_name("test_collection1"))).unwrap();
+        let before = core.run(conn.execute(GetCollectionRevision::with_name("test_collection1"))).unwrap();
+        let _ = core.run(conn.execute(InsertDocuments::new("test_collection1", vec![
+            NewDocument::from_content(json!({ "name": "Jane" })),
+        ]))).unwrap();
+
+        let method = GetCollectionRevision::with_name("test_collection1");
+        let work = conn.execute(method);
+        let after = core.run(work).unwrap();
+
+        assert_eq!("test_collection1", after.name());
+        assert_ne!(before.revision(), after.revision());
+    });
+}
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn unload_and_load_collection() {
+    arango_test_with_user_db("test_coll_user19", "test_coll_db191", |conn, ref mut core| {
+
+        let _ = core.run(conn.execute(CreateCollection::documents_with_name("test_collection1"))).unwrap();
+
+        let method = UnloadCollection::with_name("test_collection1");
+        let work = conn.execute(method);
+        let collection = core.run(work).unwrap();
+
+        assert_eq!("test_collection1", collection.name());
+        assert!(collection.status() == CollectionStatus::Unloaded
+            || collection.status() == CollectionStatus::BeingUnloaded);
+
+        let method = LoadCollection::with_name("test_collection1");
+        let work = conn.execute(method);
+        let collection = core.run(work).unwrap();
+
+        assert_eq!(CollectionStatus::Loaded, collection.status());
+    });
+}
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn load_collection_indexes_into_memory() {
+    arango_test_with_user_db("test_coll_user20", "test_coll_db201", |conn, ref mut core| {
+
+        let _ = core.run(conn.execute(CreateCollection::documents_with_name("test_collection1"))).unwrap();
+
+        let method = LoadCollectionIndexes::with_name("test_collection1");
+        let work = conn.execute(method);
+        let loaded = core.run(work).unwrap();
+
+        assert!(loaded);
+    });
+}
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn rotate_journal_of_collection_without_journal() {
+    arango_test_with_user_db("test_coll_user21", "test_coll_db211", |conn, ref mut core| {
+
+        let engine = core.run(conn.execute(GetEngine::new())).unwrap().kind();
+        let _ = core.run(conn.execute(CreateCollection::documents_with_name("test_collection1"))).unwrap();
+
+        let method = RotateCollectionJournal::with_name("test_collection1");
+        let work = conn.execute(method);
+        let result = core.run(work);
+
+        match (engine, result) {
+            (EngineType::MmFiles, Err(Error::Method(error))) => {
+                assert_eq!(400, error.status_code());
+                assert_eq!(ErrorCode::ArangoNoJournal, error.error_code());
+            },
+            //journals exist only with the MMFiles storage engine
+            (EngineType::RocksDb, Err(Error::Method(_))) => {},
+            (_, result) => panic!("Error::ApiError expected but got {:?}", result),
+        };
+    });
+}
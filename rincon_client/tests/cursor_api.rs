@@ -238,3 +238,91 @@ fn query_with_optimizer_rules() {
         assert_eq!(None, cursor.id());
     });
 }
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn export_reads_all_documents_in_batches_of_5_results() {
+    arango_test_with_document_collection("cursor_customers06", |conn, ref mut core| {
+
+        let inserts = Query::new(
+            "FOR i IN 1..12 \
+              INSERT { \
+                name: CONCAT('No.', i), \
+                age: i + 21 \
+              } IN cursor_customers06"
+        );
+        core.run(conn.execute(CreateCursor::<Empty>::from_query(inserts))).unwrap();
+
+        let mut new_export = NewExport::new();
+        new_export.set_batch_size(5);
+        new_export.set_count(true);
+        let method = CreateExportCursor::<JsonValue>::new("cursor_customers06".to_owned(), new_export);
+        let cursor = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(5, cursor.result().len());
+        assert!(cursor.has_more());
+        assert_eq!(Some(12), cursor.count());
+        let cursor_id = cursor.id().unwrap().to_owned();
+
+        let method = ReadNextBatchFromCursor::<JsonValue>::with_id_ref(&cursor_id);
+        let cursor = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(5, cursor.result().len());
+        assert!(cursor.has_more());
+
+        let method = ReadNextBatchFromCursor::<JsonValue>::with_id_ref(&cursor_id);
+        let cursor = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(2, cursor.result().len());
+        assert!(!cursor.has_more());
+        assert_eq!(None, cursor.id());
+    });
+}
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn export_documents_with_restricted_fields_and_limit() {
+    arango_test_with_document_collection("cursor_customers07", |conn, ref mut core| {
+
+        let inserts = Query::new(
+            "FOR i IN 1..10 \
+              INSERT { \
+                name: CONCAT('No.', i), \
+                age: i + 21 \
+              } IN cursor_customers07"
+        );
+        core.run(conn.execute(CreateCursor::<Empty>::from_query(inserts))).unwrap();
+
+        let mut new_export = NewExport::new();
+        new_export.set_limit(3);
+        new_export.set_restrict(ExportRestriction::include(vec!["name"]));
+        let method = CreateExportCursor::<JsonValue>::new("cursor_customers07".to_owned(), new_export);
+        let cursor = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(3, cursor.result().len());
+        assert!(!cursor.has_more());
+        for document in cursor.result() {
+            assert!(document["name"].as_str().unwrap().starts_with("No."));
+            assert!(document.get("age").is_none());
+            assert!(document.get("_key").is_none());
+        }
+    });
+}
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn export_of_not_existing_collection_fails() {
+    arango_test_with_document_collection("cursor_customers08", |conn, ref mut core| {
+
+        let method = CreateExportCursor::<JsonValue>::with_name("cursor_not_existing");
+        let result = core.run(conn.execute(method));
+
+        match result {
+            Err(Error::Method(error)) => {
+                assert_eq!(404, error.status_code());
+                assert_eq!(ErrorCode::ArangoCollectionNotFound, error.error_code());
+            },
+            _ => panic!("Error::ApiError expected but got {:?}", result),
+        }
+    });
+}
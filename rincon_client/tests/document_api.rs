@@ -5,6 +5,7 @@ extern crate tokio_core;
 extern crate rincon_core;
 extern crate rincon_connector;
 extern crate rincon_client;
+#[macro_use] extern crate rincon_derive;
 extern crate rincon_test_helper;
 
 use rincon_core::api::ErrorCode;
@@ -56,6 +57,19 @@ struct VipCustomer {
     status: String,
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ArangoDocument)]
+#[arango(collection = "customers84")]
+struct CustomerEntity {
+    #[arango(key)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    key: Option<DocumentKey>,
+    #[arango(rev)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    revision: Option<Revision>,
+    name: String,
+    age: u16,
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize)]
 struct CustomerUpdate {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -2394,3 +2408,43 @@ fn delete_multiple_documents_by_headers_return_old() {
         }
     });
 }
+
+#[test]
+fn insert_get_and_replace_entity() {
+    arango_test_with_document_collection("customers84", |conn, ref mut core| {
+
+        let customer = CustomerEntity {
+            key: None,
+            revision: None,
+            name: "Jane Doe".to_owned(),
+            age: 42,
+        };
+        let header = core.run(conn.execute(InsertDocument::with_entity(customer))).unwrap();
+
+        let document = core.run(conn.execute(
+            GetDocument::<Entity<CustomerEntity>>::with_entity_key(header.key().clone())
+        )).unwrap();
+        let mut customer = document.into_entity();
+
+        assert_eq!(Some(header.key()), customer.key.as_ref());
+        assert_eq!(Some(header.revision()), customer.revision.as_ref());
+        assert_eq!("Jane Doe", &customer.name);
+
+        customer.age = 43;
+        let updated = core.run(conn.execute(
+            ReplaceDocument::with_entity(customer.clone()).unwrap().with_return_new(true)
+        )).unwrap();
+
+        assert_eq!(header.key(), updated.key());
+        assert_eq!(header.revision(), updated.old_revision());
+        let new_customer = updated.new_content().unwrap().entity();
+        assert_eq!(43, new_customer.age);
+        assert_eq!(Some(updated.revision()), new_customer.revision.as_ref());
+
+        let outdated = core.run(conn.execute(ReplaceDocument::with_entity(customer).unwrap()));
+        match outdated {
+            Err(Error::Method(ref error)) => assert_eq!(ErrorCode::ArangoConflict, error.error_code()),
+            ref result => panic!("Expected a conflict, but got: {:?}", result),
+        }
+    });
+}
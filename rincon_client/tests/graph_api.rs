@@ -1,4 +1,5 @@
 
+#[macro_use] extern crate serde_json;
 extern crate tokio_core;
 
 extern crate rincon_core;
@@ -6,7 +7,11 @@ extern crate rincon_connector;
 extern crate rincon_client;
 extern crate rincon_test_helper;
 
+use serde_json::Value;
+
 use rincon_core::api::connector::Execute;
+use rincon_client::admin::methods::GetServerVersion;
+use rincon_client::document::types::NewDocument;
 use rincon_client::graph::methods::*;
 use rincon_client::graph::types::*;
 
@@ -24,10 +29,7 @@ fn create_graph() {
             ),
         ];
 
-        #[cfg(not(feature = "enterprise"))]
         let new_graph = NewGraph::new("test_graph1", edge_defs.clone());
-        #[cfg(feature = "enterprise")]
-        let new_graph = NewGraph::new("test_graph1", edge_defs.clone(), false);
 
         let method = CreateGraph::new(new_graph);
         let graph = core.run(conn.execute(method)).unwrap();
@@ -40,18 +42,21 @@ fn create_graph() {
         assert_eq!(edge_defs, graph.edge_definitions());
         assert_eq!(0, graph.orphan_collections().len());
 
-        #[cfg(feature = "enterprise")]
-        assert_eq!(false, graph.is_smart());
-        #[cfg(feature = "enterprise")]
-        assert_eq!("", graph.smart_graph_attribute());
+        assert_ne!(Some(true), graph.is_smart());
+        assert!(graph.smart_graph_attribute().unwrap_or_default().is_empty());
     });
 }
 
-#[cfg(feature = "enterprise")]
 #[test]
 fn create_smart_graph() {
     arango_test_with_user_db("test_graph_user11", "test_graph_db11", |conn, ref mut core| {
 
+        let version = core.run(conn.execute(GetServerVersion::new())).unwrap();
+        if !version.is_enterprise() {
+            //smart graphs require the enterprise edition of ArangoDB
+            return;
+        }
+
         let edge_defs = vec![
             EdgeDefinition::new("UsersInGroups",
                 vec!["Users".to_owned()],
@@ -59,7 +64,8 @@ fn create_smart_graph() {
             ),
         ];
 
-        let mut new_graph = NewGraph::new("test_graph1", edge_defs.clone(), true);
+        let mut new_graph = NewGraph::new("test_graph1", edge_defs.clone());
+        new_graph.set_smart(true);
         new_graph.options_mut().set_smart_graph_attribute("knows".to_owned());
         let new_graph = new_graph;
 
@@ -74,8 +80,8 @@ fn create_smart_graph() {
         assert_eq!(edge_defs, graph.edge_definitions());
         assert_eq!(0, graph.orphan_collections().len());
 
-        assert_eq!(true, graph.is_smart());
-        assert_eq!("knows", graph.smart_graph_attribute());
+        assert_eq!(Some(true), graph.is_smart());
+        assert_eq!(Some("knows"), graph.smart_graph_attribute());
     });
 }
 
@@ -94,10 +100,7 @@ fn create_graph_with_2_edge_definitions() {
             ),
         ];
 
-        #[cfg(not(feature = "enterprise"))]
         let new_graph = NewGraph::new("test_graph1", edge_defs.clone());
-        #[cfg(feature = "enterprise")]
-        let new_graph = NewGraph::new("test_graph1", edge_defs.clone(), false);
 
         let method = CreateGraph::new(new_graph);
         let graph = core.run(conn.execute(method)).unwrap();
@@ -110,10 +113,8 @@ fn create_graph_with_2_edge_definitions() {
         assert_eq!(edge_defs, graph.edge_definitions());
         assert_eq!(0, graph.orphan_collections().len());
 
-        #[cfg(feature = "enterprise")]
-            assert_eq!(false, graph.is_smart());
-        #[cfg(feature = "enterprise")]
-            assert_eq!("", graph.smart_graph_attribute());
+        assert_ne!(Some(true), graph.is_smart());
+        assert!(graph.smart_graph_attribute().unwrap_or_default().is_empty());
     });
 }
 
@@ -127,10 +128,7 @@ fn drop_graph() {
                 vec!["Groups".to_owned()]
             ),
         ];
-        #[cfg(not(feature = "enterprise"))]
         let new_graph = NewGraph::new("test_graph1", edge_defs);
-        #[cfg(feature = "enterprise")]
-        let new_graph = NewGraph::new("test_graph1", edge_defs, false);
         let graph = core.run(conn.execute(CreateGraph::new(new_graph))).unwrap();
         assert_eq!("_graphs/test_graph1".to_owned(), graph.id().to_string());
 
@@ -151,10 +149,7 @@ fn get_graph() {
                 vec!["Groups".to_owned()]
             ),
         ];
-        #[cfg(not(feature = "enterprise"))]
         let new_graph = NewGraph::new("test_graph1", edge_defs);
-        #[cfg(feature = "enterprise")]
-        let new_graph = NewGraph::new("test_graph1", edge_defs, false);
         let created = core.run(conn.execute(CreateGraph::new(new_graph))).unwrap();
         assert_eq!("_graphs/test_graph1".to_owned(), created.id().to_string());
 
@@ -175,10 +170,7 @@ fn list_graphs() {
                 vec!["Groups".to_owned()]
             ),
         ];
-        #[cfg(not(feature = "enterprise"))]
         let new_graph1 = NewGraph::new("test_graph1", edge_defs1);
-        #[cfg(feature = "enterprise")]
-        let new_graph1 = NewGraph::new("test_graph1", edge_defs1, false);
         let created1 = core.run(conn.execute(CreateGraph::new(new_graph1))).unwrap();
         assert_eq!("_graphs/test_graph1".to_owned(), created1.id().to_string());
 
@@ -188,10 +180,7 @@ fn list_graphs() {
                 vec!["Users".to_owned()]
             ),
         ];
-        #[cfg(not(feature = "enterprise"))]
         let new_graph2 = NewGraph::new("test_graph2", edge_defs2);
-        #[cfg(feature = "enterprise")]
-        let new_graph2 = NewGraph::new("test_graph2", edge_defs2, false);
         let created2 = core.run(conn.execute(CreateGraph::new(new_graph2))).unwrap();
         assert_eq!("_graphs/test_graph2".to_owned(), created2.id().to_string());
 
@@ -215,10 +204,7 @@ fn add_vertex_collection() {
                 vec!["Groups".to_owned()]
             ),
         ];
-        #[cfg(not(feature = "enterprise"))]
         let new_graph = NewGraph::new("test_graph1", edge_defs);
-        #[cfg(feature = "enterprise")]
-        let new_graph = NewGraph::new("test_graph1", edge_defs, false);
         let created = core.run(conn.execute(CreateGraph::new(new_graph))).unwrap();
         assert_eq!("_graphs/test_graph1".to_owned(), created.id().to_string());
         assert_eq!(0, created.orphan_collections().len());
@@ -244,18 +230,11 @@ fn remove_vertex_collection() {
                 vec!["Groups".to_owned()]
             ),
         ];
-        #[cfg(not(feature = "enterprise"))]
         let new_graph = NewGraph::new("test_graph1", edge_defs)
             .with_orphan_collections(vec![
                 "add_ons".to_owned(),
                 "spare".to_owned()
             ]);
-        #[cfg(feature = "enterprise")]
-        let new_graph = NewGraph::new("test_graph1", edge_defs, false)
-            .with_orphan_collections(vec![
-                "add_ons".to_owned(),
-                "spare".to_owned()
-            ]);
         let created = core.run(conn.execute(CreateGraph::new(new_graph))).unwrap();
         assert_eq!("_graphs/test_graph1".to_owned(), created.id().to_string());
         assert_eq!(2, created.orphan_collections().len());
@@ -280,18 +259,11 @@ fn list_vertex_collections() {
                 vec!["Groups".to_owned()]
             ),
         ];
-        #[cfg(not(feature = "enterprise"))]
         let new_graph = NewGraph::new("test_graph1", edge_defs)
             .with_orphan_collections(vec![
                 "add_ons".to_owned(),
                 "spare".to_owned()
             ]);
-        #[cfg(feature = "enterprise")]
-        let new_graph = NewGraph::new("test_graph1", edge_defs, false)
-            .with_orphan_collections(vec![
-                "add_ons".to_owned(),
-                "spare".to_owned()
-            ]);
         let created = core.run(conn.execute(CreateGraph::new(new_graph))).unwrap();
         assert_eq!("_graphs/test_graph1".to_owned(), created.id().to_string());
 
@@ -316,10 +288,7 @@ fn add_edge_definition() {
                 vec!["female".to_owned(), "male".to_owned()],
             ),
         ];
-        #[cfg(not(feature = "enterprise"))]
         let new_graph = NewGraph::new("social", edge_defs);
-        #[cfg(feature = "enterprise")]
-        let new_graph = NewGraph::new("social", edge_defs, false);
         let created = core.run(conn.execute(CreateGraph::new(new_graph))).unwrap();
         assert_eq!("_graphs/social".to_owned(), created.id().to_string());
 
@@ -362,10 +331,7 @@ fn remove_edge_definition() {
                 vec!["city".to_owned()],
             ),
         ];
-        #[cfg(not(feature = "enterprise"))]
         let new_graph = NewGraph::new("social", edge_defs);
-        #[cfg(feature = "enterprise")]
-        let new_graph = NewGraph::new("social", edge_defs, false);
         let created = core.run(conn.execute(CreateGraph::new(new_graph))).unwrap();
         assert_eq!("_graphs/social".to_owned(), created.id().to_string());
 
@@ -396,10 +362,7 @@ fn replace_edge_definition() {
                 vec!["female".to_owned(), "male".to_owned()],
             ),
         ];
-        #[cfg(not(feature = "enterprise"))]
         let new_graph = NewGraph::new("social", edge_defs);
-        #[cfg(feature = "enterprise")]
-        let new_graph = NewGraph::new("social", edge_defs, false);
         let created = core.run(conn.execute(CreateGraph::new(new_graph))).unwrap();
         assert_eq!("_graphs/social".to_owned(), created.id().to_string());
 
@@ -438,10 +401,7 @@ fn list_edge_collections() {
                 vec!["city".to_owned()],
             ),
         ];
-        #[cfg(not(feature = "enterprise"))]
         let new_graph = NewGraph::new("social", edge_defs);
-        #[cfg(feature = "enterprise")]
-        let new_graph = NewGraph::new("social", edge_defs, false);
         let created = core.run(conn.execute(CreateGraph::new(new_graph))).unwrap();
         assert_eq!("_graphs/social".to_owned(), created.id().to_string());
 
@@ -453,3 +413,157 @@ fn list_edge_collections() {
         assert_eq!(2, edges.len());
     });
 }
+
+#[test]
+fn insert_get_and_modify_vertex() {
+    arango_test_with_user_db("test_graph_user120", "test_graph_db120", |conn, ref mut core| {
+
+        let edge_defs = vec![
+            EdgeDefinition::new("knows",
+                vec!["persons".to_owned()],
+                vec!["persons".to_owned()],
+            ),
+        ];
+        let new_graph = NewGraph::new("social", edge_defs);
+        core.run(conn.execute(CreateGraph::new(new_graph))).unwrap();
+
+        let vertex = NewDocument::from_content(json!({ "name": "Alice", "age": 42 }));
+        let header = core.run(conn.execute(InsertVertex::new("social", "persons", vertex))).unwrap();
+        assert_eq!("persons", header.id().collection_name());
+
+        let method = ModifyVertex::new("social", header.id().clone(), json!({ "age": 43 }));
+        let updated = core.run(conn.execute(method)).unwrap();
+        assert_eq!(header.revision(), updated.old_revision());
+
+        let method = GetVertex::<Value>::with_id("social", header.id().clone());
+        let document = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(header.key(), document.key());
+        assert_eq!(updated.revision(), document.revision());
+        assert_eq!(&json!({ "name": "Alice", "age": 43 }), document.content());
+    });
+}
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn get_outbound_edges_of_vertex() {
+    arango_test_with_user_db("test_graph_user130", "test_graph_db130", |conn, ref mut core| {
+
+        let edge_defs = vec![
+            EdgeDefinition::new("knows",
+                vec!["persons".to_owned()],
+                vec!["persons".to_owned()],
+            ),
+        ];
+        let new_graph = NewGraph::new("social", edge_defs);
+        core.run(conn.execute(CreateGraph::new(new_graph))).unwrap();
+
+        let alice = core.run(conn.execute(InsertVertex::new("social", "persons",
+            NewDocument::from_content(json!({ "name": "Alice" }))))).unwrap();
+        let bob = core.run(conn.execute(InsertVertex::new("social", "persons",
+            NewDocument::from_content(json!({ "name": "Bob" }))))).unwrap();
+        let charlie = core.run(conn.execute(InsertVertex::new("social", "persons",
+            NewDocument::from_content(json!({ "name": "Charlie" }))))).unwrap();
+
+        core.run(conn.execute(InsertEdge::new("social", "knows",
+            NewEdge::new(alice.id().clone(), bob.id().clone(), json!({ "since": 2010 }))))).unwrap();
+        core.run(conn.execute(InsertEdge::new("social", "knows",
+            NewEdge::new(charlie.id().clone(), alice.id().clone(), json!({ "since": 2012 }))))).unwrap();
+
+        let method = GetEdges::<Value>::outbound("knows", alice.id().clone());
+        let edges = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(1, edges.len());
+        assert_eq!(alice.id(), edges[0].from());
+        assert_eq!(bob.id(), edges[0].to());
+        assert_eq!(&json!({ "since": 2010 }), edges[0].content());
+
+        let method = GetEdges::<Value>::inbound("knows", alice.id().clone());
+        let edges = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(1, edges.len());
+        assert_eq!(charlie.id(), edges[0].from());
+
+        let method = GetEdges::<Value>::any("knows", alice.id().clone());
+        let edges = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(2, edges.len());
+    });
+}
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn execute_traversal_of_graph() {
+    arango_test_with_user_db("test_graph_user140", "test_graph_db140", |conn, ref mut core| {
+
+        let edge_defs = vec![
+            EdgeDefinition::new("knows",
+                vec!["persons".to_owned()],
+                vec!["persons".to_owned()],
+            ),
+        ];
+        let new_graph = NewGraph::new("social", edge_defs);
+        core.run(conn.execute(CreateGraph::new(new_graph))).unwrap();
+
+        let alice = core.run(conn.execute(InsertVertex::new("social", "persons",
+            NewDocument::from_content(json!({ "name": "Alice" }))))).unwrap();
+        let bob = core.run(conn.execute(InsertVertex::new("social", "persons",
+            NewDocument::from_content(json!({ "name": "Bob" }))))).unwrap();
+        let charlie = core.run(conn.execute(InsertVertex::new("social", "persons",
+            NewDocument::from_content(json!({ "name": "Charlie" }))))).unwrap();
+
+        core.run(conn.execute(InsertEdge::new("social", "knows",
+            NewEdge::new(alice.id().clone(), bob.id().clone(), json!({ "since": 2010 }))))).unwrap();
+        core.run(conn.execute(InsertEdge::new("social", "knows",
+            NewEdge::new(bob.id().clone(), charlie.id().clone(), json!({ "since": 2012 }))))).unwrap();
+
+        let traversal = NewTraversal::with_graph(alice.id().clone(), "social")
+            .with_direction(Direction::Outbound)
+            .with_min_depth(1)
+            .with_uniqueness(TraversalUniqueness::new(Uniqueness::Global, None));
+        let method = ExecuteTraversal::<Value, Value>::new(traversal);
+        let result = core.run(conn.execute(method)).unwrap();
+
+        let names: Vec<&Value> = result.vertices().iter().map(|vertex| &vertex.content()["name"]).collect();
+        assert_eq!(vec![&json!("Bob"), &json!("Charlie")], names);
+
+        let paths = result.paths();
+        assert_eq!(2, paths.len());
+        assert_eq!(2, paths[1].len());
+        assert_eq!(3, paths[1].vertices().len());
+        assert_eq!(charlie.id(), paths[1].edges()[1].to());
+    });
+}
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn execute_traversal_with_custom_visitor() {
+    arango_test_with_user_db("test_graph_user141", "test_graph_db141", |conn, ref mut core| {
+
+        let edge_defs = vec![
+            EdgeDefinition::new("knows",
+                vec!["persons".to_owned()],
+                vec!["persons".to_owned()],
+            ),
+        ];
+        let new_graph = NewGraph::new("social", edge_defs);
+        core.run(conn.execute(CreateGraph::new(new_graph))).unwrap();
+
+        let alice = core.run(conn.execute(InsertVertex::new("social", "persons",
+            NewDocument::from_content(json!({ "name": "Alice" }))))).unwrap();
+        let bob = core.run(conn.execute(InsertVertex::new("social", "persons",
+            NewDocument::from_content(json!({ "name": "Bob" }))))).unwrap();
+
+        core.run(conn.execute(InsertEdge::new("social", "knows",
+            NewEdge::new(alice.id().clone(), bob.id().clone(), json!({ "since": 2010 }))))).unwrap();
+
+        let traversal = NewTraversal::with_graph(alice.id().clone(), "social")
+            .with_direction(Direction::Outbound)
+            .with_init("result.names = [];")
+            .with_visitor("result.names.push(vertex.name);");
+        let method = ExecuteCustomTraversal::<Value>::new(traversal);
+        let result = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(json!(["Alice", "Bob"]), result["names"]);
+    });
+}
@@ -0,0 +1,112 @@
+
+#[macro_use] extern crate serde_derive;
+extern crate tokio_core;
+
+extern crate rincon_core;
+extern crate rincon_connector;
+extern crate rincon_client;
+extern crate rincon_test_helper;
+
+use rincon_core::api::connector::Execute;
+use rincon_client::import::methods::*;
+use rincon_client::import::types::*;
+
+use rincon_test_helper::*;
+
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Customer {
+    #[serde(rename = "_key")]
+    key: String,
+    name: String,
+    age: u16,
+}
+
+fn customers() -> Vec<Customer> {
+    vec![
+        Customer { key: "1".to_owned(), name: "Jane Doe".to_owned(), age: 42 },
+        Customer { key: "2".to_owned(), name: "John Doe".to_owned(), age: 43 },
+        Customer { key: "3".to_owned(), name: "Erika Mustermann".to_owned(), age: 44 },
+    ]
+}
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn import_documents_as_list() {
+    arango_test_with_document_collection("import01", |conn, ref mut core| {
+
+        let method = ImportDocuments::new("import01", customers());
+        let result = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(3, result.created());
+        assert_eq!(0, result.errors());
+        assert_eq!(0, result.empty());
+        assert_eq!(0, result.updated());
+        assert_eq!(0, result.ignored());
+    });
+}
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn import_documents_line_by_line() {
+    arango_test_with_document_collection("import02", |conn, ref mut core| {
+
+        let method = ImportDocuments::new("import02", customers())
+            .with_import_type(ImportType::Documents);
+        let result = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(3, result.created());
+        assert_eq!(0, result.errors());
+    });
+}
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn import_documents_with_duplicates_and_details() {
+    arango_test_with_document_collection("import03", |conn, ref mut core| {
+
+        let _ = core.run(conn.execute(ImportDocuments::new("import03", customers()))).unwrap();
+
+        let method = ImportDocuments::new("import03", customers())
+            .with_details(true);
+        let result = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(0, result.created());
+        assert_eq!(3, result.errors());
+        assert_eq!(3, result.details().len());
+    });
+}
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn import_documents_and_update_duplicates() {
+    arango_test_with_document_collection("import04", |conn, ref mut core| {
+
+        let _ = core.run(conn.execute(ImportDocuments::new("import04", customers()))).unwrap();
+
+        let method = ImportDocuments::new("import04", customers())
+            .with_on_duplicate(OnDuplicate::Update);
+        let result = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(0, result.created());
+        assert_eq!(0, result.errors());
+        assert_eq!(3, result.updated());
+    });
+}
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn import_documents_and_ignore_duplicates() {
+    arango_test_with_document_collection("import05", |conn, ref mut core| {
+
+        let _ = core.run(conn.execute(ImportDocuments::new("import05", customers()))).unwrap();
+
+        let method = ImportDocuments::new("import05", customers())
+            .with_on_duplicate(OnDuplicate::Ignore);
+        let result = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(0, result.created());
+        assert_eq!(0, result.errors());
+        assert_eq!(3, result.ignored());
+    });
+}
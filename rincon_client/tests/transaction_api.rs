@@ -0,0 +1,88 @@
+
+extern crate tokio_core;
+
+extern crate rincon_core;
+extern crate rincon_connector;
+extern crate rincon_client;
+extern crate rincon_test_helper;
+
+use rincon_core::api::connector::{Error, Execute};
+use rincon_core::api::types::Empty;
+use rincon_client::transaction::methods::*;
+use rincon_client::transaction::types::*;
+
+use rincon_test_helper::*;
+
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn execute_transaction_that_inserts_a_document_and_returns_the_count() {
+    arango_test_with_document_collection("transactions01", |conn, ref mut core| {
+
+        let collections = TransactionCollections::empty()
+            .with_write("transactions01");
+        let transaction: NewTransaction<Empty> = NewTransaction::new(collections,
+            "function () { \
+                var db = require('@arangodb').db; \
+                db.transactions01.save({ name: 'Jane Doe' }); \
+                return db.transactions01.count(); \
+            }");
+
+        let method = ExecuteTransaction::new(transaction);
+        let count: u64 = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(1, count);
+    });
+}
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn execute_transaction_with_params() {
+    arango_test_with_document_collection("transactions02", |conn, ref mut core| {
+
+        let collections = TransactionCollections::empty()
+            .with_read("transactions02");
+        let transaction = NewTransaction::with_params(collections,
+            "function (params) { return params[0] + params[1]; }",
+            vec![40, 2]);
+
+        let method = ExecuteTransaction::new(transaction);
+        let sum: i32 = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(42, sum);
+    });
+}
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn execute_transaction_that_throws_an_exception_is_rolled_back() {
+    arango_test_with_document_collection("transactions03", |conn, ref mut core| {
+
+        let collections = TransactionCollections::empty()
+            .with_write("transactions03");
+        let transaction: NewTransaction<Empty> = NewTransaction::new(collections,
+            "function () { \
+                var db = require('@arangodb').db; \
+                db.transactions03.save({ name: 'Jane Doe' }); \
+                throw 'doh!'; \
+            }");
+
+        let method: ExecuteTransaction<Empty, Empty> = ExecuteTransaction::new(transaction);
+        let result = core.run(conn.execute(method));
+
+        match result {
+            Err(Error::Method(_)) => {},
+            _ => panic!("Error expected, but got: {:?}", &result),
+        }
+
+        let collections = TransactionCollections::empty()
+            .with_read("transactions03");
+        let transaction: NewTransaction<Empty> = NewTransaction::new(collections,
+            "function () { return require('@arangodb').db.transactions03.count(); }");
+
+        let method = ExecuteTransaction::new(transaction);
+        let count: u64 = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(0, count);
+    });
+}
@@ -55,7 +55,7 @@ fn create_user_with_name_should_return_newly_created_user_as_active() {
     let user_name = String::from("testuser1");
     arango_system_db_test(|conn, ref mut core| {
 
-        let new_user = NewUser::with_name(user_name.as_ref(), "testpass1");
+        let new_user = NewUser::with_name(user_name.as_str(), "testpass1");
         let method = CreateUser::new(new_user);
         let work = conn.execute(method);
         let user = core.run(work).unwrap();
@@ -65,7 +65,7 @@ fn create_user_with_name_should_return_newly_created_user_as_active() {
         assert_eq!(&EMPTY, user.extra());
 
     }, |conn, ref mut core| {
-        let method = RemoveUser::with_name(user_name.as_ref());
+        let method = RemoveUser::with_name(user_name.as_str());
         let work = conn.execute(method);
         core.run(work).unwrap();
     });
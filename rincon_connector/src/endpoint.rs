@@ -0,0 +1,360 @@
+
+use std::collections::HashMap;
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use rand::{self, Rng};
+
+use rincon_core::api::connector::Error;
+use rincon_core::api::datasource::{Endpoint, LoadBalancing};
+use rincon_core::api::method::{Method, Operation, Parameters, Prepare, RpcReturnType};
+use rincon_core::arango::protocol::{FIELD_ENDPOINTS, PATH_API_CLUSTER_ENDPOINTS};
+
+/// The time an endpoint that failed is skipped when selecting endpoints.
+const DEFAULT_UNAVAILABLE_COOLDOWN: Duration = Duration::from_secs(10);
+
+/// Selects the endpoints to which requests are sent.
+///
+/// For each request the selector determines the order in which the
+/// endpoints are tried, starting with the endpoint chosen by the configured
+/// `LoadBalancing` strategy followed by all other endpoints for failing
+/// over.
+///
+/// An endpoint that failed is moved behind all other endpoints for a
+/// cooldown period, so that it is only tried again if all other endpoints
+/// fail as well.
+#[derive(Debug)]
+pub(crate) struct EndpointSelector {
+    load_balancing: LoadBalancing,
+    cooldown: Duration,
+    state: RwLock<SelectorState>,
+    counter: AtomicUsize,
+}
+
+#[derive(Debug)]
+struct SelectorState {
+    endpoints: Vec<Endpoint>,
+    active: usize,
+    unavailable: HashMap<Endpoint, Instant>,
+}
+
+impl EndpointSelector {
+    pub(crate) fn new(endpoints: Vec<Endpoint>, load_balancing: LoadBalancing) -> Self {
+        EndpointSelector {
+            load_balancing,
+            cooldown: DEFAULT_UNAVAILABLE_COOLDOWN,
+            state: RwLock::new(SelectorState {
+                endpoints,
+                active: 0,
+                unavailable: HashMap::new(),
+            }),
+            counter: AtomicUsize::new(0),
+        }
+    }
+
+    /// Sets the time an endpoint that failed is skipped.
+    #[cfg(test)]
+    fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, SelectorState> {
+        self.state.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, SelectorState> {
+        self.state.write().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Returns the endpoint that is currently used when no load balancing
+    /// is applied.
+    pub(crate) fn active(&self) -> Endpoint {
+        let state = self.read();
+        state.endpoints[state.active].clone()
+    }
+
+    /// Returns all endpoints in the order in which they shall be tried for
+    /// the next request.
+    ///
+    /// Endpoints that failed within the cooldown period are placed behind
+    /// all other endpoints.
+    pub(crate) fn candidates(&self) -> Vec<Endpoint> {
+        let state = self.read();
+        let count = state.endpoints.len();
+        let start = match self.load_balancing {
+            LoadBalancing::None => state.active,
+            LoadBalancing::RoundRobin => self.counter.fetch_add(1, Ordering::Relaxed) % count,
+            LoadBalancing::Random => rand::thread_rng().gen_range(0, count),
+        };
+        let now = Instant::now();
+        let (available, unavailable): (Vec<_>, Vec<_>) = (0..count)
+            .map(|index| state.endpoints[(start + index) % count].clone())
+            .partition(|endpoint| match state.unavailable.get(endpoint) {
+                Some(failed) => now.duration_since(*failed) >= self.cooldown,
+                None => true,
+            });
+        available.into_iter().chain(unavailable).collect()
+    }
+
+    /// Marks the given endpoint as available after a request has been
+    /// served by it.
+    ///
+    /// When no load balancing is applied all subsequent requests are sent
+    /// to this endpoint.
+    pub(crate) fn mark_available(&self, endpoint: &Endpoint) {
+        if self.load_balancing != LoadBalancing::None {
+            if self.read().unavailable.contains_key(endpoint) {
+                self.write().unavailable.remove(endpoint);
+            }
+            return;
+        }
+        let mut state = self.write();
+        state.unavailable.remove(endpoint);
+        if state.endpoints[state.active] != *endpoint {
+            if let Some(index) = state.endpoints.iter().position(|known| known == endpoint) {
+                info!("Failing over to endpoint {}", endpoint);
+                state.active = index;
+            }
+        }
+    }
+
+    /// Marks the given endpoint as unavailable after a request to it failed,
+    /// so that it is skipped for the cooldown period.
+    pub(crate) fn mark_unavailable(&self, endpoint: &Endpoint) {
+        self.write().unavailable.insert(endpoint.clone(), Instant::now());
+    }
+
+    pub(crate) fn endpoints(&self) -> Vec<Endpoint> {
+        self.read().endpoints.clone()
+    }
+
+    /// Replaces the known endpoints by the given list of endpoints.
+    ///
+    /// The currently active endpoint stays active if it is contained in the
+    /// given list. An empty list is ignored.
+    pub(crate) fn set_endpoints(&self, endpoints: Vec<Endpoint>) {
+        if endpoints.is_empty() {
+            return;
+        }
+        let mut state = self.write();
+        let active = state.endpoints[state.active].clone();
+        state.active = endpoints.iter().position(|endpoint| *endpoint == active).unwrap_or(0);
+        state.unavailable.retain(|endpoint, _| endpoints.contains(endpoint));
+        state.endpoints = endpoints;
+    }
+}
+
+/// Returns whether the given error indicates that the request shall be
+/// sent to another endpoint.
+///
+/// A request that may not be repeated, e.g. a mutating operation, might
+/// already have been applied by the server if the connection fails while
+/// waiting for the response. Such a request is only sent to another
+/// endpoint if the connection has been refused or the server responded with
+/// status 503 (service unavailable).
+pub(crate) fn is_failover_error(error: &Error, refused: bool, repeatable: bool) -> bool {
+    match *error {
+        Error::Communication(_) | Error::Timeout(_) => repeatable || refused,
+        Error::Method(ref error) => error.status_code() == 503,
+        _ => false,
+    }
+}
+
+/// Fetches the endpoints of all coordinators in a cluster.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct GetClusterEndpoints {}
+
+impl GetClusterEndpoints {
+    pub(crate) fn new() -> Self {
+        GetClusterEndpoints {}
+    }
+}
+
+impl Method for GetClusterEndpoints {
+    type Result = Vec<ClusterEndpoint>;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: Some(FIELD_ENDPOINTS),
+        code_field: None,
+    };
+}
+
+impl Prepare for GetClusterEndpoints {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Read
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_CLUSTER_ENDPOINTS)
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub(crate) struct ClusterEndpoint {
+    endpoint: String,
+}
+
+impl ClusterEndpoint {
+    pub(crate) fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rincon_core::api;
+    use super::*;
+
+    fn endpoints() -> Vec<Endpoint> {
+        vec![
+            Endpoint::new("http", "db1", 8529),
+            Endpoint::new("http", "db2", 8529),
+            Endpoint::new("http", "db3", 8529),
+        ]
+    }
+
+    #[test]
+    fn without_load_balancing_the_first_endpoint_is_tried_first() {
+        let selector = EndpointSelector::new(endpoints(), LoadBalancing::None);
+
+        assert_eq!(endpoints(), selector.candidates());
+        assert_eq!(endpoints(), selector.candidates());
+    }
+
+    #[test]
+    fn without_load_balancing_the_endpoint_failed_over_to_stays_active() {
+        let selector = EndpointSelector::new(endpoints(), LoadBalancing::None);
+
+        selector.mark_available(&Endpoint::new("http", "db2", 8529));
+
+        assert_eq!(Endpoint::new("http", "db2", 8529), selector.active());
+        assert_eq!(vec![
+            Endpoint::new("http", "db2", 8529),
+            Endpoint::new("http", "db3", 8529),
+            Endpoint::new("http", "db1", 8529),
+        ], selector.candidates());
+    }
+
+    #[test]
+    fn round_robin_starts_with_the_next_endpoint_for_each_request() {
+        let selector = EndpointSelector::new(endpoints(), LoadBalancing::RoundRobin);
+
+        let first = selector.candidates();
+        let second = selector.candidates();
+        let third = selector.candidates();
+        let fourth = selector.candidates();
+
+        assert_eq!("db1", first[0].host());
+        assert_eq!("db2", second[0].host());
+        assert_eq!("db3", third[0].host());
+        assert_eq!("db1", fourth[0].host());
+        assert_eq!(vec!["db2", "db3", "db1"],
+            second.iter().map(|endpoint| endpoint.host()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn round_robin_skips_unavailable_endpoint_during_cooldown() {
+        let selector = EndpointSelector::new(endpoints(), LoadBalancing::RoundRobin);
+
+        selector.mark_unavailable(&Endpoint::new("http", "db2", 8529));
+
+        let hosts = |candidates: Vec<Endpoint>| candidates.iter()
+            .map(|endpoint| endpoint.host().to_owned()).collect::<Vec<_>>();
+        assert_eq!(vec!["db1", "db3", "db2"], hosts(selector.candidates()));
+        assert_eq!(vec!["db3", "db1", "db2"], hosts(selector.candidates()));
+        assert_eq!(vec!["db3", "db1", "db2"], hosts(selector.candidates()));
+    }
+
+    #[test]
+    fn unavailable_endpoint_is_tried_again_after_cooldown() {
+        let selector = EndpointSelector::new(endpoints(), LoadBalancing::None)
+            .with_cooldown(Duration::from_millis(0));
+
+        selector.mark_unavailable(&Endpoint::new("http", "db1", 8529));
+
+        assert_eq!(endpoints(), selector.candidates());
+    }
+
+    #[test]
+    fn available_endpoint_is_no_longer_skipped() {
+        let selector = EndpointSelector::new(endpoints(), LoadBalancing::RoundRobin);
+        selector.mark_unavailable(&Endpoint::new("http", "db1", 8529));
+
+        selector.mark_available(&Endpoint::new("http", "db1", 8529));
+
+        assert_eq!(endpoints(), selector.candidates());
+    }
+
+    #[test]
+    fn unavailable_endpoints_are_tried_if_all_endpoints_failed() {
+        let selector = EndpointSelector::new(endpoints(), LoadBalancing::None);
+
+        for endpoint in endpoints() {
+            selector.mark_unavailable(&endpoint);
+        }
+
+        assert_eq!(endpoints(), selector.candidates());
+    }
+
+    #[test]
+    fn random_selection_contains_all_endpoints() {
+        let selector = EndpointSelector::new(endpoints(), LoadBalancing::Random);
+
+        let mut candidates = selector.candidates();
+        candidates.sort_by(|a, b| a.host().cmp(b.host()));
+
+        assert_eq!(endpoints(), candidates);
+    }
+
+    #[test]
+    fn set_endpoints_keeps_the_active_endpoint() {
+        let selector = EndpointSelector::new(endpoints(), LoadBalancing::None);
+        selector.mark_available(&Endpoint::new("http", "db3", 8529));
+
+        selector.set_endpoints(vec![
+            Endpoint::new("http", "db3", 8529),
+            Endpoint::new("http", "db4", 8529),
+        ]);
+
+        assert_eq!(Endpoint::new("http", "db3", 8529), selector.active());
+        assert_eq!(2, selector.endpoints().len());
+    }
+
+    #[test]
+    fn service_unavailable_is_a_failover_error() {
+        let error = Error::Method(api::Error::new(503, api::ErrorCode::from_u16(503),
+            "service unavailable".to_owned()));
+
+        assert!(is_failover_error(&error, false, true));
+        assert!(is_failover_error(&error, false, false));
+        assert!(is_failover_error(&Error::Communication("connection reset".to_owned()), false, true));
+        assert!(!is_failover_error(&Error::Method(api::Error::new(404,
+            api::ErrorCode::from_u16(404), "not found".to_owned())), false, true));
+    }
+
+    #[test]
+    fn requests_that_may_not_be_repeated_only_fail_over_if_not_sent() {
+        let reset = Error::Communication("connection reset".to_owned());
+        let timeout = Error::Timeout("no response".to_owned());
+
+        assert!(!is_failover_error(&reset, false, false));
+        assert!(!is_failover_error(&timeout, false, false));
+        assert!(is_failover_error(&reset, true, false));
+    }
+}
@@ -1,36 +1,51 @@
 
+use std::io;
+use std::rc::Rc;
 use std::str::FromStr;
 use std::sync::Arc;
 
 use futures::{future, Future, Stream};
-use hyper::{self, Client, HttpVersion, Request, StatusCode, Uri};
+use futures::future::Loop;
+use hyper::{self, Chunk, Client, Headers, HttpVersion, Request, StatusCode, Uri};
 use hyper::client::HttpConnector;
 use hyper::header::{self, Authorization, Basic, Bearer, ContentLength, ContentType};
 use hyper_timeout::TimeoutConnector;
 use hyper_tls::HttpsConnector;
 use serde::ser::Serialize;
 use serde_json::{self, Value};
-use tokio_core::reactor;
-use url;
-use url::percent_encoding::DEFAULT_ENCODE_SET;
+use tokio_core::reactor::{self, Timeout};
 
 use rincon_core::api;
-use rincon_core::api::auth::{Authentication, Jwt};
+use rincon_core::api::auth::{Authentication, Credentials, Jwt};
 use rincon_core::api::connector::{Connector, Error, Execute, FutureResult};
-use rincon_core::api::datasource::DataSource;
-use rincon_core::api::method::{Method, Operation, Prepare, RpcReturnType};
+use rincon_core::api::datasource::{Codec, DataSource, Endpoint};
+use rincon_core::api::json_lines;
+use rincon_core::api::method::{request_path, ContentFormat, Method, Operation, Prepare,
+    RpcReturnType};
+use rincon_core::api::retry::RetryPolicy;
 use rincon_core::api::user_agent::UserAgent;
-use rincon_core::arango::protocol::{PATH_DB, SYSTEM_DATABASE};
+use rincon_core::arango::protocol::{BATCH_BOUNDARY, SYSTEM_DATABASE};
+use endpoint::{is_failover_error, EndpointSelector, GetClusterEndpoints};
+use pool::{HostLimitedConnector, PoolConfig, RequestLimiter};
+use recording::{Exchange, FutureExchange, Interaction, RecordedRequest, RecordedResponse};
+use token::{FutureToken, Login, TokenStore};
+use vpack::{self, CONTENT_TYPE_VPACK};
 
-type HttpClient = Client<TimeoutConnector<HttpsConnector<HttpConnector>>>;
+type HttpClient = Client<HostLimitedConnector<TimeoutConnector<HttpsConnector<HttpConnector>>>>;
+
+type FutureResponse = Box<Future<Item=Exchanged, Error=Error>>;
 
 //TODO find better name for BasicConnector
 #[derive(Debug)]
 pub struct BasicConnector {
     user_agent: &'static UserAgent,
     datasource: Arc<DataSource>,
-    token: Arc<Option<Jwt>>,
+    tokens: Rc<TokenStore>,
     client: Arc<HttpClient>,
+    limiter: Option<RequestLimiter>,
+    endpoints: Arc<EndpointSelector>,
+    retry_policy: Arc<RetryPolicy>,
+    reactor: reactor::Handle,
 }
 
 impl BasicConnector {
@@ -38,22 +53,86 @@ impl BasicConnector {
         user_agent: &'static UserAgent,
         datasource: DataSource,
         reactor: &reactor::Handle
+    ) -> Result<Self, Error> {
+        BasicConnector::with_pool_config(user_agent, datasource, PoolConfig::default(), reactor)
+    }
+
+    /// Creates a new `BasicConnector` that manages its connections and
+    /// queues its requests as specified by the given `PoolConfig`.
+    pub fn with_pool_config(
+        user_agent: &'static UserAgent,
+        datasource: DataSource,
+        pool_config: PoolConfig,
+        reactor: &reactor::Handle
     ) -> Result<Self, Error> {
         let https_connector = HttpsConnector::new(4, &reactor)
             .map_err(|cause| Error::Communication(cause.to_string()))?;
         let mut timeout_connector = TimeoutConnector::new(https_connector, &reactor);
         timeout_connector.set_connect_timeout(Some(*datasource.timeout()));
+        let limited_connector = HostLimitedConnector::new(timeout_connector,
+            pool_config.max_connections_per_host());
         let client = Client::configure()
-            .connector(timeout_connector)
+            .connector(limited_connector)
+            .keep_alive(pool_config.is_keep_alive())
+            .keep_alive_timeout(pool_config.idle_timeout().cloned())
             .build(reactor);
-        debug!("Creating new basic connector for {:?}", &datasource);
+        let limiter = pool_config.max_in_flight().map(RequestLimiter::new);
+        let endpoints = EndpointSelector::new(datasource.endpoints().to_vec(),
+            datasource.load_balancing());
+        debug!("Creating new basic connector for {:?} with {:?}", &datasource, &pool_config);
         Ok(BasicConnector {
             user_agent,
             datasource: Arc::new(datasource),
-            token: Arc::new(None),
+            tokens: Rc::new(TokenStore::new()),
             client: Arc::new(client),
+            limiter,
+            endpoints: Arc::new(endpoints),
+            retry_policy: Arc::new(RetryPolicy::default()),
+            reactor: reactor.clone(),
         })
     }
+
+    /// Sets the policy for retrying method calls that failed due to a
+    /// transient failure.
+    ///
+    /// The policy can be overridden for single method calls by wrapping the
+    /// method into `WithRetry`.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Arc::new(retry_policy);
+        self
+    }
+
+    /// Returns the policy for retrying method calls.
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// Returns the endpoints to which this connector currently sends
+    /// requests.
+    pub fn endpoints(&self) -> Vec<Endpoint> {
+        self.endpoints.endpoints()
+    }
+
+    /// Replaces the endpoints to which this connector sends requests by the
+    /// endpoints of all coordinators in the cluster.
+    ///
+    /// The endpoints are fetched from the server via
+    /// `GET /_api/cluster/endpoints`. On success the new list of endpoints
+    /// is returned.
+    pub fn refresh_endpoints(&self) -> Box<Future<Item=Vec<Endpoint>, Error=Error>> {
+        let endpoints = self.endpoints.clone();
+        Box::new(self.system_connection().execute(GetClusterEndpoints::new())
+            .and_then(move |cluster_endpoints| {
+                let refreshed = cluster_endpoints.iter()
+                    .map(|cluster_endpoint| Endpoint::from_url(cluster_endpoint.endpoint()))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|cause| Error::Deserialization(cause.to_string()))?;
+                debug!("Refreshed endpoints: {:?}", &refreshed);
+                endpoints.set_endpoints(refreshed);
+                Ok(endpoints.endpoints())
+            })
+        )
+    }
 }
 
 impl Connector for BasicConnector {
@@ -64,8 +143,12 @@ impl Connector for BasicConnector {
             user_agent: self.user_agent,
             datasource: self.datasource.clone(),
             database: Some(database_name.to_owned()),
-            token: self.token.clone(),
+            tokens: self.tokens.clone(),
             client: self.client.clone(),
+            limiter: self.limiter.clone(),
+            endpoints: self.endpoints.clone(),
+            retry_policy: self.retry_policy.clone(),
+            reactor: self.reactor.clone(),
         }
     }
 
@@ -74,11 +157,11 @@ impl Connector for BasicConnector {
     }
 
     fn accept_auth_token(&mut self, token: Jwt) {
-        self.token = Arc::new(Some(token));
+        self.tokens.accept(token);
     }
 
     fn invalidate_auth_token(&mut self) {
-        self.token = Arc::new(None)
+        self.tokens.clear();
     }
 }
 
@@ -88,8 +171,12 @@ pub struct BasicConnection {
     user_agent: &'static UserAgent,
     datasource: Arc<DataSource>,
     database: Option<String>,
-    token: Arc<Option<Jwt>>,
+    tokens: Rc<TokenStore>,
     client: Arc<HttpClient>,
+    limiter: Option<RequestLimiter>,
+    endpoints: Arc<EndpointSelector>,
+    retry_policy: Arc<RetryPolicy>,
+    reactor: reactor::Handle,
 }
 
 impl BasicConnection {
@@ -105,20 +192,66 @@ impl BasicConnection {
         self.database.as_ref().or_else(|| self.datasource.database_name())
     }
 
-    pub fn token(&self) -> Option<&Jwt> {
-        self.token.as_ref().as_ref()
+    /// Returns the token used to authenticate requests if there is a valid
+    /// one.
+    pub fn token(&self) -> Option<Jwt> {
+        self.tokens.current()
     }
 
     pub fn prepare_request<'p, P>(&self, prepare: &'p P) -> Result<Request, Error>
         where P: 'p + Prepare
+    {
+        let uri = build_request_uri(&self.endpoints.active(), self.database(), prepare);
+        let prepared = self.prepare(prepare)?;
+        match *self.datasource.authentication() {
+            Authentication::Jwt(_) => match self.tokens.current() {
+                Some(token) =>
+                    Ok(prepared.with_bearer(&token).to_request(uri)),
+                None =>
+                    Err(Error::NotAuthenticated(
+                        "the client must be authenticated first, \
+                         when using JWT authentication".into(),
+                    )),
+            },
+            _ => Ok(prepared.to_request(uri)),
+        }
+    }
+
+    fn targets<P>(&self, database_name: Option<&String>, prepare: &P) -> Vec<(Endpoint, Uri)>
+        where P: Prepare
+    {
+        self.endpoints.candidates().into_iter()
+            .map(|endpoint| {
+                let uri = build_request_uri(&endpoint, database_name, prepare);
+                (endpoint, uri)
+            }).collect()
+    }
+
+    fn sender(&self) -> RequestSender {
+        RequestSender {
+            client: self.client.clone(),
+            endpoints: self.endpoints.clone(),
+            limiter: self.limiter.clone(),
+        }
+    }
+
+    fn jwt_sender(&self, credentials: &Credentials) -> Result<JwtSender, Error> {
+        let login = Login::new(credentials);
+        Ok(JwtSender {
+            sender: self.sender(),
+            tokens: self.tokens.clone(),
+            login_prepared: self.prepare(&login)?,
+            login,
+        })
+    }
+
+    fn prepare<'p, P>(&self, prepare: &'p P) -> Result<PreparedRequest, Error>
+        where P: 'p + Prepare
     {
         let operation = prepare.operation();
         let http_method = http_method_for_operation(&operation);
-        let uri = build_request_uri(&self.datasource, self.database(), prepare);
-        let mut request = Request::new(http_method, uri);
-        request.set_version(HttpVersion::Http11);
+        let mut headers = Headers::new();
         {
-            let headers = request.headers_mut();
             headers.set(header_user_agent_for(self.user_agent));
             match *self.datasource.authentication() {
                 Authentication::Basic(ref credentials) => {
@@ -127,35 +260,91 @@ impl BasicConnection {
                         password: Some(credentials.password().to_owned()),
                     }))
                 },
-                Authentication::Jwt(_) => {
-                    match *self.token.as_ref() {
-                        Some(ref token) => {
-                            headers.set(Authorization(Bearer {
-                                token: token.to_owned(),
-                            }))
-                        },
-                        None => {
-                            return Err(Error::NotAuthenticated(
-                                "the client must be authenticated first, \
-                                 when using JWT authentication".into(),
-                            ));
-                        },
-                    }
-                },
-                Authentication::None => {},
+                Authentication::Jwt(_) | Authentication::None => {},
+            }
+            if self.datasource.codec() == Codec::VelocyPack {
+                headers.set_raw("Accept", CONTENT_TYPE_VPACK);
             }
             for &(ref name, ref value) in prepare.header().iter() {
                 headers.set_raw(name.to_string(), value.to_string());
             }
         }
-        if let Some(content) = prepare.content() {
-            let json = serialize_payload(content)?;
-            trace!("| request body: {:?}", String::from_utf8(json.clone()));
-            request.headers_mut().set(ContentType::json());
-            request.headers_mut().set(ContentLength(json.len() as u64));
-            request.set_body(json);
-        }
-        Ok(request)
+        let body = match prepare.content() {
+            Some(content) => {
+                let content_format = prepare.content_format();
+                let codec = codec_for_content(content_format, self.datasource.codec());
+                let payload = serialize_payload(content, content_format, codec)?;
+                trace!("| request body: {}", format_body(codec, &payload));
+                match (content_format, codec) {
+                    (ContentFormat::Multipart, _) => headers.set_raw("Content-Type",
+                        format!("multipart/form-data; boundary={}", BATCH_BOUNDARY)),
+                    (_, Codec::Json) => headers.set(ContentType::json()),
+                    (_, Codec::VelocyPack) => headers.set_raw("Content-Type", CONTENT_TYPE_VPACK),
+                }
+                headers.set(ContentLength(payload.len() as u64));
+                Some(payload)
+            },
+            None => None,
+        };
+        let retry_policy = prepare.retry_policy().unwrap_or(&self.retry_policy);
+        Ok(PreparedRequest {
+            http_method,
+            headers,
+            body,
+            repeatable: retry_policy.may_repeat(operation),
+        })
+    }
+}
+
+impl BasicConnection {
+    /// Sends the prepared request of the given method and retries it as
+    /// long as the retry policy allows it.
+    ///
+    /// The last response is returned even if it is an error response, so
+    /// that the caller can process it.
+    fn send_with_retry<P>(&self, prepare: &P, prepared: PreparedRequest) -> FutureResponse
+        where P: Prepare
+    {
+        let targets = self.targets(self.database(), prepare);
+        let send: Box<Fn() -> FutureResponse> = match *self.datasource.authentication() {
+            Authentication::Jwt(ref credentials) => {
+                let sender = match self.jwt_sender(credentials) {
+                    Ok(sender) => sender,
+                    Err(error) => return Box::new(future::err(error)),
+                };
+                Box::new(move || sender.send(prepared.clone(), targets.clone()))
+            },
+            _ => {
+                let sender = self.sender();
+                Box::new(move || sender.send(prepared.clone(), targets.clone()))
+            },
+        };
+        let operation = prepare.operation();
+        let retry_policy = Rc::new(prepare.retry_policy().cloned()
+            .unwrap_or_else(|| (*self.retry_policy).clone()));
+        let reactor = self.reactor.clone();
+        Box::new(future::loop_fn(1, move |attempt| {
+            let retry_policy = retry_policy.clone();
+            let reactor = reactor.clone();
+            send().then(move |result| {
+                let error = match result {
+                    Ok(ref response) if response.status_code.is_success() => None,
+                    Ok(ref response) =>
+                        Some(parse_error(response.status_code, response.codec(), &response.body)),
+                    Err(ref error) => Some(error.clone()),
+                };
+                match error {
+                    Some(ref error) if retry_policy.should_retry(operation, error, attempt) => {
+                        let delay = retry_policy.backoff(attempt + 1);
+                        warn!("Attempt {} failed: {}, retrying in {:?}", attempt, error, delay);
+                        future::Either::B(future::result(Timeout::new(delay, &reactor)).flatten()
+                            .map(move |_| Loop::Continue(attempt + 1))
+                            .map_err(|cause| Error::Communication(cause.to_string())))
+                    },
+                    _ => future::Either::A(future::result(result.map(Loop::Break))),
+                }
+            })
+        }))
     }
 }
 
@@ -163,79 +352,407 @@ impl Execute for BasicConnection {
     fn execute<M>(&self, method: M) -> FutureResult<M>
         where M: Method + Prepare + 'static
     {
-        match self.prepare_request(&method) {
-            Ok(request) => {
-                debug!("Sending {:?}", &request);
-                Box::new(self.client.request(request)
-                    .map_err(|cause| Error::Communication(cause.to_string()))
-                    .and_then(move |response| {
-                        let status_code = response.status();
-                        response.body().concat2()
-                            .map_err(|cause| Error::Communication(cause.to_string()))
-                            .and_then(move |buffer|
-                                parse_return_type::<M>(&method.return_type(), status_code, &buffer))
-                    })
-                )
-            },
-            Err(error) =>
-                Box::new(future::err(error)),
+        let prepared = match self.prepare(&method) {
+            Ok(prepared) => prepared,
+            Err(error) => return Box::new(future::err(error)),
+        };
+        let return_type = method.return_type();
+        let content_format = method.content_format();
+        Box::new(self.send_with_retry(&method, prepared).and_then(move |response|
+            parse_response::<M>(&return_type, content_format, response.status_code,
+                response.codec(), &response.body)
+        ))
+    }
+}
+
+impl Exchange for BasicConnection {
+    fn exchange<P>(&self, prepare: P) -> FutureExchange
+        where P: Prepare + 'static
+    {
+        let prepared = match self.prepare(&prepare) {
+            Ok(prepared) => prepared,
+            Err(error) => return Box::new(future::err(error)),
+        };
+        let http_method = prepared.http_method.clone();
+        let path = request_path(self.database().map(String::as_str), &prepare);
+        let body = prepared.body.clone();
+        Box::new(self.send_with_retry(&prepare, prepared).map(move |response| {
+            let request = RecordedRequest::from_http(&http_method, path,
+                &response.request_headers, body.as_ref().map(|body| &body[..]));
+            let response = RecordedResponse::from_http(response.status_code,
+                &response.headers, &response.body);
+            Interaction::new(request, response)
+        }))
+    }
+}
+
+/// Sends requests with a JWT, logging in first if there is no valid token.
+///
+/// If the server rejects the token, a new token is obtained and the request
+/// is sent once again.
+///
+/// The login request is sent to the endpoints already selected for the
+/// request, so that logging in does not advance the load balancing.
+#[derive(Clone)]
+struct JwtSender {
+    sender: RequestSender,
+    tokens: Rc<TokenStore>,
+    login: Login,
+    login_prepared: PreparedRequest,
+}
+
+impl JwtSender {
+    fn send(&self, prepared: PreparedRequest, targets: Vec<(Endpoint, Uri)>) -> FutureResponse {
+        let this = self.clone();
+        Box::new(TokenStore::token(&self.tokens, || self.login(&targets)).and_then(move |token| {
+            this.sender.send(prepared.with_bearer(&token), targets.clone())
+                .and_then(move |response| {
+                    if response.status_code != StatusCode::Unauthorized {
+                        return future::Either::A(future::ok(response));
+                    }
+                    debug!("Token has been rejected, retrying with a new token");
+                    this.tokens.invalidate(&token);
+                    future::Either::B(TokenStore::token(&this.tokens, || this.login(&targets))
+                        .and_then(move |token| this.sender.send(prepared.with_bearer(&token), targets)))
+                })
+        }))
+    }
+
+    fn login(&self, targets: &[(Endpoint, Uri)]) -> FutureToken {
+        debug!("Logging in to obtain a new token");
+        let login_targets = targets.iter()
+            .map(|(endpoint, _)| (endpoint.clone(), build_request_uri(endpoint, None, &self.login)))
+            .collect();
+        Box::new(self.sender.send(self.login_prepared.clone(), login_targets)
+            .and_then(|response| parse_return_type::<Login>(&Login::RETURN_TYPE,
+                response.status_code, response.codec(), &response.body))
+            .map(|response| response.jwt().to_owned())
+        )
+    }
+}
+
+/// Sends requests to the endpoints of a connector while respecting the
+/// limit of requests in flight.
+#[derive(Clone)]
+struct RequestSender {
+    client: Arc<HttpClient>,
+    endpoints: Arc<EndpointSelector>,
+    limiter: Option<RequestLimiter>,
+}
+
+impl RequestSender {
+    fn send(&self, prepared: PreparedRequest, targets: Vec<(Endpoint, Uri)>) -> FutureResponse {
+        let client = self.client.clone();
+        let endpoints = self.endpoints.clone();
+        match self.limiter {
+            Some(ref limiter) =>
+                Box::new(limiter.acquire().and_then(move |permit|
+                    send_with_failover(client, endpoints, prepared, targets)
+                        .then(move |result| {
+                            drop(permit);
+                            result
+                        })
+                )),
+            None =>
+                send_with_failover(client, endpoints, prepared, targets),
         }
     }
 }
 
-fn parse_return_type<M>(return_type: &RpcReturnType, status_code: StatusCode, payload: &[u8])
-    -> Result<<M as Method>::Result, Error>
+/// A request that can be sent to any endpoint.
+#[derive(Clone, Debug)]
+struct PreparedRequest {
+    http_method: hyper::Method,
+    headers: Headers,
+    body: Option<Vec<u8>>,
+    /// Whether the request may be sent to another endpoint after the
+    /// connection failed while waiting for the response.
+    repeatable: bool,
+}
+
+impl PreparedRequest {
+    fn with_bearer(&self, token: &str) -> Self {
+        let mut prepared = self.clone();
+        prepared.headers.set(Authorization(Bearer {
+            token: token.to_owned(),
+        }));
+        prepared
+    }
+
+    fn to_request(&self, uri: Uri) -> Request {
+        let mut request = Request::new(self.http_method.clone(), uri);
+        request.set_version(HttpVersion::Http11);
+        *request.headers_mut() = self.headers.clone();
+        if let Some(ref body) = self.body {
+            request.set_body(body.clone());
+        }
+        request
+    }
+}
+
+/// Sends the request to the first of the given targets. If the endpoint is
+/// not available or responds with status 503 the request is sent to the
+/// next target and the endpoint is skipped for subsequent requests until its
+/// cooldown period is over.
+///
+/// A request that may not be repeated is only sent to the next target if
+/// the connection has been refused, as otherwise it might already have been
+/// applied by the first endpoint.
+fn send_with_failover(
+    client: Arc<HttpClient>,
+    endpoints: Arc<EndpointSelector>,
+    prepared: PreparedRequest,
+    targets: Vec<(Endpoint, Uri)>,
+) -> FutureResponse {
+    Box::new(future::loop_fn((prepared, targets.into_iter()), move |(prepared, mut remaining)| {
+        let endpoints = endpoints.clone();
+        let (endpoint, uri) = match remaining.next() {
+            Some(target) => target,
+            None => return future::Either::A(future::err(
+                Error::Communication("no endpoint available".into()))),
+        };
+        future::Either::B(send_request(&client, prepared.to_request(uri))
+            .then(move |result| {
+                let failover = match result {
+                    Ok(ref response) => response.status_code == StatusCode::ServiceUnavailable,
+                    Err((ref error, ref refused)) =>
+                        is_failover_error(error, *refused, prepared.repeatable),
+                };
+                let result = result.map_err(|(error, _)| error);
+                if result.is_ok() && !failover {
+                    endpoints.mark_available(&endpoint);
+                } else {
+                    endpoints.mark_unavailable(&endpoint);
+                }
+                if failover && remaining.len() > 0 {
+                    warn!("Endpoint {} is not available, failing over", endpoint);
+                    Ok(Loop::Continue((prepared, remaining)))
+                } else {
+                    result.map(Loop::Break)
+                }
+            }))
+    }))
+}
+
+/// A response received from an endpoint together with the headers of the
+/// request as it has been sent.
+#[derive(Debug)]
+struct Exchanged {
+    request_headers: Headers,
+    status_code: StatusCode,
+    headers: Headers,
+    body: Chunk,
+}
+
+impl Exchanged {
+    fn codec(&self) -> Codec {
+        codec_of_response(&self.headers)
+    }
+}
+
+/// Sends the request to one endpoint.
+///
+/// On failure the error is returned together with a flag that tells
+/// whether the connection has been refused, so that the request has not
+/// reached the server.
+fn send_request(client: &HttpClient, request: Request)
+    -> Box<Future<Item=Exchanged, Error=(Error, bool)>>
+{
+    debug!("Sending {:?}", &request);
+    let request_headers = request.headers().clone();
+    Box::new(client.request(request)
+        .map_err(|cause| {
+            let refused = is_connection_refused(&cause);
+            (Error::Communication(cause.to_string()), refused)
+        })
+        .and_then(move |response| {
+            let status_code = response.status();
+            let headers = response.headers().clone();
+            response.body().concat2()
+                .map_err(|cause| (Error::Communication(cause.to_string()), false))
+                .map(move |body| Exchanged {
+                    request_headers,
+                    status_code,
+                    headers,
+                    body,
+                })
+        })
+    )
+}
+
+fn is_connection_refused(error: &hyper::Error) -> bool {
+    match *error {
+        hyper::Error::Io(ref cause) => cause.kind() == io::ErrorKind::ConnectionRefused,
+        _ => false,
+    }
+}
+
+pub(crate) fn parse_return_type<M>(
+    return_type: &RpcReturnType,
+    status_code: StatusCode,
+    codec: Codec,
+    payload: &[u8],
+) -> Result<<M as Method>::Result, Error>
     where M: Method
 {
     debug!("Received response with code {:?}", status_code);
     if status_code.is_success() {
-        let parse_result = match return_type.result_field {
-            Some(result_field) => match serde_json::from_slice(payload) {
-                Ok(Value::Object(ref mut obj)) => match obj.remove(result_field) {
-                    Some(result_value) =>
-                        serde_json::from_value(result_value),
-                    None =>
-                        serde_json::from_slice(payload),
-                },
-                _ =>
-                    serde_json::from_slice(payload),
-            },
-            None => serde_json::from_slice(payload),
+        let parse_result = match codec {
+            Codec::Json => parse_json_result::<M>(return_type, payload),
+            Codec::VelocyPack => parse_vpack_result::<M>(return_type, payload),
         };
         if parse_result.is_err() {
-            debug!("| response body: {}", String::from_utf8_lossy(payload));
+            debug!("| response body: {}", format_body(codec, payload));
         } else {
-            trace!("| response body: {}", String::from_utf8_lossy(payload));
+            trace!("| response body: {}", format_body(codec, payload));
         }
-        parse_result.map_err(|cause| Error::Deserialization(cause.to_string()))
+        parse_result.map_err(Error::Deserialization)
     } else {
-        debug!("| response body: {}", String::from_utf8_lossy(payload));
-        let api_error = serde_json::from_slice(payload).unwrap_or_else(|_| {
-            let error_code = api::ErrorCode::from_u16(status_code.as_u16());
-            let message = if payload.is_empty() {
-                error_code.description().to_owned()
-            } else {
-                String::from_utf8_lossy(payload).to_string()
-            };
-            api::Error::new(status_code.as_u16(), error_code, message)
-        });
-        Err(Error::Method(api_error))
+        debug!("| response body: {}", format_body(codec, payload));
+        Err(parse_error(status_code, codec, payload))
+    }
+}
+
+/// Converts the payload of an error response into an `Error::Method`.
+///
+/// If the payload does not contain an error the error is derived from the
+/// status code.
+pub(crate) fn parse_error(status_code: StatusCode, codec: Codec, payload: &[u8]) -> Error {
+    let parsed_error = match codec {
+        Codec::Json => serde_json::from_slice(payload).ok(),
+        Codec::VelocyPack => vpack::from_slice(payload).ok(),
+    };
+    let api_error = parsed_error.unwrap_or_else(|| {
+        let error_code = api::ErrorCode::from_u16(status_code.as_u16());
+        let message = if payload.is_empty() {
+            error_code.description().to_owned()
+        } else {
+            format_body(codec, payload)
+        };
+        api::Error::new(status_code.as_u16(), error_code, message)
+    });
+    Error::Method(api_error)
+}
+
+/// Parses the response of a method depending on the format of its content.
+///
+/// The body of a successful response to a method with multipart content is
+/// deserialized into the result of the method as one string.
+pub(crate) fn parse_response<M>(
+    return_type: &RpcReturnType,
+    content_format: ContentFormat,
+    status_code: StatusCode,
+    codec: Codec,
+    payload: &[u8],
+) -> Result<<M as Method>::Result, Error>
+    where M: Method
+{
+    match content_format {
+        ContentFormat::Multipart if status_code.is_success() => {
+            debug!("Received response with code {:?}", status_code);
+            trace!("| response body: {}", String::from_utf8_lossy(payload));
+            let body = String::from_utf8(payload.to_vec())
+                .map_err(|cause| Error::Deserialization(cause.to_string()))?;
+            serde_json::from_value(Value::String(body))
+                .map_err(|cause| Error::Deserialization(cause.to_string()))
+        },
+        _ => parse_return_type::<M>(return_type, status_code, codec, payload),
     }
 }
 
-fn serialize_payload<T>(content: &T) -> Result<Vec<u8>, Error>
+fn parse_json_result<M>(return_type: &RpcReturnType, payload: &[u8])
+    -> Result<<M as Method>::Result, String>
+    where M: Method
+{
+    match return_type.result_field {
+        Some(result_field) => match serde_json::from_slice(payload) {
+            Ok(Value::Object(ref mut obj)) => match obj.remove(result_field) {
+                Some(result_value) =>
+                    serde_json::from_value(result_value),
+                None =>
+                    serde_json::from_slice(payload),
+            },
+            _ =>
+                serde_json::from_slice(payload),
+        },
+        None => serde_json::from_slice(payload),
+    }.map_err(|cause| cause.to_string())
+}
+
+fn parse_vpack_result<M>(return_type: &RpcReturnType, payload: &[u8])
+    -> Result<<M as Method>::Result, String>
+    where M: Method
+{
+    match return_type.result_field {
+        Some(result_field) => vpack::from_slice_field(payload, result_field)
+            .unwrap_or_else(|| vpack::from_slice(payload)),
+        None => vpack::from_slice(payload),
+    }.map_err(|cause| cause.to_string())
+}
+
+/// Returns a printable representation of a request or response body for
+/// logging.
+fn format_body(codec: Codec, payload: &[u8]) -> String {
+    match codec {
+        Codec::Json => String::from_utf8_lossy(payload).to_string(),
+        Codec::VelocyPack => match vpack::from_slice::<Value>(payload) {
+            Ok(value) => value.to_string(),
+            Err(_) => format!("<{} bytes of VelocyPack>", payload.len()),
+        },
+    }
+}
+
+/// Returns the codec used to encode the content of a request. Content that
+/// is sent as JSON lines or as multipart body is always encoded as JSON.
+pub(crate) fn codec_for_content(format: ContentFormat, codec: Codec) -> Codec {
+    match format {
+        ContentFormat::Json => codec,
+        ContentFormat::JsonLines | ContentFormat::Multipart => Codec::Json,
+    }
+}
+
+/// Returns the codec of a response as specified by its content type.
+fn codec_of_response(headers: &Headers) -> Codec {
+    match headers.get_raw("Content-Type").and_then(|raw| raw.one()) {
+        Some(content_type) if content_type.starts_with(CONTENT_TYPE_VPACK.as_bytes()) =>
+            Codec::VelocyPack,
+        _ =>
+            Codec::Json,
+    }
+}
+
+pub(crate) fn serialize_payload<T>(content: &T, format: ContentFormat, codec: Codec)
+    -> Result<Vec<u8>, Error>
     where T: Serialize
 {
-    serde_json::to_vec(content).map_err(|cause| Error::Serialization(cause.to_string()))
+    match format {
+        ContentFormat::Json => match codec {
+            Codec::Json =>
+                serde_json::to_vec(content)
+                    .map_err(|cause| Error::Serialization(cause.to_string())),
+            Codec::VelocyPack =>
+                vpack::to_vec(content)
+                    .map_err(|cause| Error::Serialization(cause.to_string())),
+        },
+        ContentFormat::JsonLines =>
+            json_lines::to_vec(content)
+                .map_err(|cause| Error::Serialization(cause.to_string())),
+        ContentFormat::Multipart => match serde_json::to_value(content) {
+            Ok(Value::String(body)) => Ok(body.into_bytes()),
+            Ok(_) => Err(Error::Serialization(
+                "content must be a string to be sent as multipart body".into())),
+            Err(cause) => Err(Error::Serialization(cause.to_string())),
+        },
+    }
 }
 
-fn header_user_agent_for(agent: &UserAgent) -> header::UserAgent {
+pub(crate) fn header_user_agent_for(agent: &UserAgent) -> header::UserAgent {
     let agent_string = format!("Mozilla/5.0 (compatible; {}/{}.{}; +{})",
         agent.name(), agent.version().major(), agent.version().minor(), agent.homepage());
     header::UserAgent::new(agent_string)
 }
 
-fn http_method_for_operation(operation: &Operation) -> hyper::Method {
+pub(crate) fn http_method_for_operation(operation: &Operation) -> hyper::Method {
     use self::hyper::Method;
     match *operation {
         Operation::Create => Method::Post,
@@ -248,40 +765,22 @@ fn http_method_for_operation(operation: &Operation) -> hyper::Method {
 }
 
 fn build_request_uri<P>(
-    datasource: &DataSource,
+    endpoint: &Endpoint,
     database_name: Option<&String>,
     prepare: &P
 ) -> Uri
     where P: Prepare
 {
     let mut request_uri = String::new();
-    request_uri.push_str(datasource.protocol());
+    request_uri.push_str(endpoint.protocol());
     request_uri.push_str("://");
-    request_uri.push_str(datasource.host());
+    request_uri.push_str(endpoint.host());
     request_uri.push(':');
-    request_uri.push_str(&datasource.port().to_string());
-    if let Some(database_name) = database_name {
-        request_uri.push_str(PATH_DB);
-        request_uri.push_str(&percent_encode(database_name));
-    }
-    request_uri.push_str(&percent_encode(&prepare.path()));
-    if !prepare.parameters().is_empty() {
-        request_uri.push('?');
-        for &(ref key, ref value) in prepare.parameters().iter() {
-            request_uri.push_str(&percent_encode(key));
-            request_uri.push('=');
-            request_uri.push_str(&percent_encode(&value.to_string()));
-            request_uri.push('&');
-        }
-        request_uri.pop();
-    }
+    request_uri.push_str(&endpoint.port().to_string());
+    request_uri.push_str(&request_path(database_name.map(String::as_str), prepare));
     Uri::from_str(&request_uri).unwrap()
 }
 
-fn percent_encode(value: &str) -> String {
-    url::percent_encoding::percent_encode(value.as_bytes(), DEFAULT_ENCODE_SET).to_string()
-}
-
 #[cfg(test)]
 mod tests {
     use std::iter::FromIterator;
@@ -289,8 +788,19 @@ mod tests {
     use rincon_core::api::auth::{Authentication, Credentials};
     use rincon_core::api::method::{Parameters, Prepare};
     use rincon_core::api::user_agent::Version;
+    use rincon_core::arango::protocol::{FIELD_CODE, FIELD_RESULT};
     use super::*;
 
+    struct GetResult;
+
+    impl Method for GetResult {
+        type Result = Vec<String>;
+        const RETURN_TYPE: RpcReturnType = RpcReturnType {
+            result_field: Some(FIELD_RESULT),
+            code_field: Some(FIELD_CODE),
+        };
+    }
+
     struct Prepared<'a> {
         operation: Operation,
         path: &'a str,
@@ -322,6 +832,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn http_method_for_operation_matches_name_of_operation() {
+        for operation in &[Operation::Create, Operation::Read, Operation::Modify,
+            Operation::Replace, Operation::Delete, Operation::ReadHeader]
+        {
+            assert_eq!(operation.http_method(), http_method_for_operation(operation).as_ref());
+        }
+    }
+
     #[test]
     fn build_request_uri_for_http() {
         let datasource = DataSource::from_url("http://localhost:8529").unwrap();
@@ -332,7 +851,7 @@ mod tests {
             content: None,
         };
 
-        let uri = build_request_uri(&datasource, None, &prepared);
+        let uri = build_request_uri(datasource.endpoint(), None, &prepared);
 
         assert_eq!("http://localhost:8529/_api/user", uri.to_string());
     }
@@ -349,7 +868,7 @@ mod tests {
             content: None,
         };
 
-        let uri = build_request_uri(&datasource, None, &prepared);
+        let uri = build_request_uri(datasource.endpoint(), None, &prepared);
 
         assert_eq!("https://localhost:8529/_api/user", uri.to_string());
     }
@@ -365,7 +884,7 @@ mod tests {
             content: None,
         };
 
-        let uri = build_request_uri(&datasource, Some(&"given_db_name".to_owned()), &prepared);
+        let uri = build_request_uri(datasource.endpoint(), Some(&"given_db_name".to_owned()), &prepared);
 
         assert_eq!("https://localhost:8529/_db/given_db_name/_api/collection", uri.to_string());
     }
@@ -381,7 +900,7 @@ mod tests {
             content: None,
         };
 
-        let uri = build_request_uri(&datasource, datasource.database_name(), &prepared);
+        let uri = build_request_uri(datasource.endpoint(), datasource.database_name(), &prepared);
 
         assert_eq!("https://localhost:8529/_db/url_test/_api/collection", uri.to_string());
     }
@@ -397,7 +916,7 @@ mod tests {
             content: None,
         };
 
-        let uri = build_request_uri(&datasource, datasource.database_name(), &prepared);
+        let uri = build_request_uri(datasource.endpoint(), datasource.database_name(), &prepared);
 
         assert_eq!("https://localhost:8529/_db/the%20big%20data/_api/document\
                 ?id=25", uri.to_string());
@@ -414,7 +933,7 @@ mod tests {
             content: None,
         };
 
-        let uri = build_request_uri(&datasource, datasource.database_name(), &prepared);
+        let uri = build_request_uri(datasource.endpoint(), datasource.database_name(), &prepared);
 
         assert_eq!("https://localhost:8529/_db/the%20b%C3%BCg%20data/_api/document\
                 ?id=25&name=JuneReport", uri.to_string());
@@ -431,7 +950,7 @@ mod tests {
             content: None,
         };
 
-        let uri = build_request_uri(&datasource, Some(&"the big data".to_owned()), &prepared);
+        let uri = build_request_uri(datasource.endpoint(), Some(&"the big data".to_owned()), &prepared);
 
         assert_eq!("https://localhost:8529/_db/the%20big%20data/_api/document\
                 ?id=25&name=JuneReport&max=42", uri.to_string());
@@ -484,4 +1003,108 @@ mod tests {
         );
     }
 
+    #[test]
+    fn serialize_payload_as_json() {
+        let content = json!([{ "name": "Jane" }, { "name": "John" }]);
+
+        let payload = serialize_payload(&content, ContentFormat::Json, Codec::Json).unwrap();
+
+        assert_eq!(r#"[{"name":"Jane"},{"name":"John"}]"#, String::from_utf8(payload).unwrap());
+    }
+
+    #[test]
+    fn serialize_payload_as_json_lines() {
+        let content = json!([{ "name": "Jane" }, { "name": "John" }]);
+
+        let payload = serialize_payload(&content, ContentFormat::JsonLines, Codec::Json).unwrap();
+
+        assert_eq!("{\"name\":\"Jane\"}\n{\"name\":\"John\"}\n", String::from_utf8(payload).unwrap());
+    }
+
+    #[test]
+    fn serialize_payload_as_json_lines_requires_an_array() {
+        let content = json!({ "name": "Jane" });
+
+        let result = serialize_payload(&content, ContentFormat::JsonLines, Codec::Json);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn serialize_payload_as_multipart() {
+        let content = json!("--boundary\r\n\r\nGET /_api/version HTTP/1.1\r\n\r\n--boundary--");
+
+        let payload = serialize_payload(&content, ContentFormat::Multipart, Codec::Json).unwrap();
+
+        assert_eq!("--boundary\r\n\r\nGET /_api/version HTTP/1.1\r\n\r\n--boundary--",
+            String::from_utf8(payload).unwrap());
+    }
+
+    #[test]
+    fn serialize_payload_as_multipart_requires_a_string() {
+        let content = json!({ "name": "Jane" });
+
+        let result = serialize_payload(&content, ContentFormat::Multipart, Codec::Json);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn serialize_payload_as_velocypack() {
+        let content = json!({ "name": "Jane" });
+
+        let payload = serialize_payload(&content, ContentFormat::Json, Codec::VelocyPack).unwrap();
+
+        assert_eq!(vpack::to_vec(&content).unwrap(), payload);
+    }
+
+    #[test]
+    fn content_sent_as_json_lines_is_always_encoded_as_json() {
+        assert_eq!(Codec::Json, codec_for_content(ContentFormat::JsonLines, Codec::VelocyPack));
+        assert_eq!(Codec::Json, codec_for_content(ContentFormat::Multipart, Codec::VelocyPack));
+        assert_eq!(Codec::VelocyPack, codec_for_content(ContentFormat::Json, Codec::VelocyPack));
+    }
+
+    #[test]
+    fn codec_of_response_is_taken_from_content_type() {
+        let mut headers = Headers::new();
+        assert_eq!(Codec::Json, codec_of_response(&headers));
+
+        headers.set(ContentType::json());
+        assert_eq!(Codec::Json, codec_of_response(&headers));
+
+        headers.set_raw("Content-Type", CONTENT_TYPE_VPACK);
+        assert_eq!(Codec::VelocyPack, codec_of_response(&headers));
+    }
+
+    #[test]
+    fn parse_result_field_from_velocypack() {
+        let payload = vpack::to_vec(&json!({
+            "error": false,
+            "code": 200,
+            "result": [ "customers", "orders" ],
+        })).unwrap();
+
+        let result = parse_return_type::<GetResult>(&GetResult::RETURN_TYPE,
+            StatusCode::Ok, Codec::VelocyPack, &payload);
+
+        assert_eq!(Ok(vec!["customers".to_owned(), "orders".to_owned()]), result);
+    }
+
+    #[test]
+    fn parse_error_from_velocypack() {
+        let payload = vpack::to_vec(&json!({
+            "error": true,
+            "code": 404,
+            "errorNum": 1203,
+            "errorMessage": "collection or view not found",
+        })).unwrap();
+
+        let result = parse_return_type::<GetResult>(&GetResult::RETURN_TYPE,
+            StatusCode::NotFound, Codec::VelocyPack, &payload);
+
+        assert_eq!(Err(Error::Method(api::Error::new(404,
+            api::ErrorCode::ArangoCollectionNotFound, "collection or view not found"))), result);
+    }
+
 }
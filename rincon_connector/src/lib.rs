@@ -13,6 +13,7 @@
     unused_qualifications,
 )]
 
+extern crate base64;
 extern crate failure;
 extern crate futures;
 extern crate hyper;
@@ -20,11 +21,21 @@ extern crate hyper_timeout;
 extern crate hyper_tls;
 #[macro_use] extern crate log;
 extern crate native_tls;
-extern crate serde;
-extern crate serde_json;
+extern crate rand;
+#[macro_use] extern crate serde;
+#[macro_use] extern crate serde_derive;
+#[macro_use] extern crate serde_json;
 extern crate tokio_core;
-extern crate url;
+extern crate tokio_io;
+extern crate tokio_tls;
 
 extern crate rincon_core;
 
+mod endpoint;
 pub mod http;
+pub mod mock;
+pub mod pool;
+pub mod recording;
+mod token;
+pub mod vpack;
+pub mod vst;
@@ -0,0 +1,608 @@
+//! A connector that answers requests from a script of expectations instead
+//! of sending them to a server.
+//!
+//! The `MockConnector` makes it possible to unit test code that uses the
+//! driver without a running ArangoDB server. Each expected request is
+//! described by an `Expectation` which defines how to match the request and
+//! which response to return. At the end of a test `verify()` checks that all
+//! expected requests have been received and no unexpected one.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! let connector = MockConnector::new();
+//! connector.expect(Expectation::new(Operation::Create, "/_api/database")
+//!     .in_database("_system")
+//!     .with_body(json!({ "name": "shop", "users": [] }))
+//!     .returns(json!({ "error": false, "code": 201, "result": true })));
+//!
+//! let session = ArangoSession::new(connector.clone(), Core::new().unwrap()).unwrap();
+//! session.create_database::<Empty>(NewDatabase::new("shop", vec![])).unwrap();
+//!
+//! connector.verify();
+//! ```
+
+use std::cell::RefCell;
+use std::fmt::{self, Display};
+use std::rc::Rc;
+
+use futures::future;
+use hyper::{Headers, StatusCode};
+use hyper::header::ContentType;
+use serde_json::{self, Value as JsonValue};
+
+use rincon_core::api::auth::Jwt;
+use rincon_core::api::connector::{Connector, Error, Execute, FutureResult};
+use rincon_core::api::datasource::Codec;
+use rincon_core::api::method::{ContentFormat, Method, Operation, Parameters, Prepare};
+use rincon_core::api::types::Value;
+use rincon_core::arango::protocol::{BATCH_BOUNDARY, SYSTEM_DATABASE};
+use http::parse_response;
+use recording::{Exchange, FutureExchange, Interaction, RecordedRequest, RecordedResponse};
+
+/// A request as received by a `MockConnection`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MockRequest {
+    database: String,
+    operation: Operation,
+    path: String,
+    parameters: Parameters,
+    body: Option<JsonValue>,
+}
+
+impl MockRequest {
+    fn from_prepare<P>(database: &str, prepare: &P) -> Result<Self, Error>
+        where P: Prepare
+    {
+        let body = match prepare.content() {
+            Some(content) => Some(serde_json::to_value(content)
+                .map_err(|cause| Error::Serialization(cause.to_string()))?),
+            None => None,
+        };
+        Ok(MockRequest {
+            database: database.to_owned(),
+            operation: prepare.operation(),
+            path: prepare.path(),
+            parameters: prepare.parameters(),
+            body,
+        })
+    }
+
+    pub fn database(&self) -> &str {
+        &self.database
+    }
+
+    pub fn operation(&self) -> Operation {
+        self.operation
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn parameters(&self) -> &Parameters {
+        &self.parameters
+    }
+
+    pub fn body(&self) -> Option<&JsonValue> {
+        self.body.as_ref()
+    }
+}
+
+impl Display for MockRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} /_db/{}{} {}", self.operation, self.database, self.path, self.parameters)?;
+        if let Some(ref body) = self.body {
+            write!(f, " {}", body)?;
+        }
+        Ok(())
+    }
+}
+
+/// The scripted response to an expected request.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MockResponse {
+    /// The given JSON payload is returned with the given HTTP status code.
+    ///
+    /// The payload is processed in the same way as a payload received from
+    /// the server, e.g. an error payload with a status code of 4xx or 5xx
+    /// results in an `Error::Method`.
+    ///
+    /// The multipart body of a response to a method with multipart content,
+    /// like a batch, is given as JSON string.
+    Payload(u16, JsonValue),
+    /// The given error is returned.
+    Error(Error),
+}
+
+/// Describes an expected request and the response to return for it.
+///
+/// A request matches the expectation if it has the given operation and
+/// path and all optionally defined criteria, i.e. database, parameters and
+/// body.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Expectation {
+    operation: Operation,
+    path: String,
+    database: Option<String>,
+    parameters: Vec<(String, Value)>,
+    body: Option<JsonValue>,
+    response: MockResponse,
+    times: usize,
+    calls: usize,
+}
+
+impl Expectation {
+    /// Constructs a new `Expectation` for one request with the given
+    /// operation and path that returns an empty JSON object.
+    pub fn new<P>(operation: Operation, path: P) -> Self
+        where P: Into<String>
+    {
+        Expectation {
+            operation,
+            path: path.into(),
+            database: None,
+            parameters: Vec::new(),
+            body: None,
+            response: MockResponse::Payload(200, JsonValue::Object(Default::default())),
+            times: 1,
+            calls: 0,
+        }
+    }
+
+    /// Only matches requests to the given database.
+    pub fn in_database<D>(mut self, database: D) -> Self
+        where D: Into<String>
+    {
+        self.database = Some(database.into());
+        self
+    }
+
+    /// Only matches requests that have a parameter with the given name and
+    /// value.
+    pub fn with_parameter<N, V>(mut self, name: N, value: V) -> Self
+        where N: Into<String>, V: Into<Value>
+    {
+        self.parameters.push((name.into(), value.into()));
+        self
+    }
+
+    /// Only matches requests whose body is equal to the given JSON value.
+    pub fn with_body(mut self, body: JsonValue) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    /// Returns the given JSON payload with HTTP status 200.
+    pub fn returns(self, payload: JsonValue) -> Self {
+        self.returns_with_status(200, payload)
+    }
+
+    /// Returns the given JSON payload with the given HTTP status code.
+    pub fn returns_with_status(mut self, status_code: u16, payload: JsonValue) -> Self {
+        self.response = MockResponse::Payload(status_code, payload);
+        self
+    }
+
+    /// Returns the given error.
+    pub fn fails_with(mut self, error: Error) -> Self {
+        self.response = MockResponse::Error(error);
+        self
+    }
+
+    /// Sets how often the request is expected. The default is once.
+    pub fn times(mut self, times: usize) -> Self {
+        self.times = times;
+        self
+    }
+
+    pub fn operation(&self) -> Operation {
+        self.operation
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn response(&self) -> &MockResponse {
+        &self.response
+    }
+
+    /// Returns how often a matching request has been received.
+    pub fn calls(&self) -> usize {
+        self.calls
+    }
+
+    fn is_satisfied(&self) -> bool {
+        self.calls >= self.times
+    }
+
+    fn matches(&self, request: &MockRequest) -> bool {
+        self.operation == request.operation
+            && self.path == request.path
+            && self.database.as_ref().is_none_or(|database| *database == request.database)
+            && self.parameters.iter().all(|parameter|
+                request.parameters.iter().any(|received| received == parameter))
+            && self.body.as_ref().is_none_or(|body| Some(body) == request.body.as_ref())
+    }
+}
+
+impl Display for Expectation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} ", self.operation)?;
+        if let Some(ref database) = self.database {
+            write!(f, "/_db/{}", database)?;
+        }
+        f.write_str(&self.path)?;
+        for (name, value) in &self.parameters {
+            write!(f, " {}={}", name, value)?;
+        }
+        if let Some(ref body) = self.body {
+            write!(f, " {}", body)?;
+        }
+        write!(f, " (expected {} times, received {} times)", self.times, self.calls)
+    }
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    expectations: Vec<Expectation>,
+    requests: Vec<MockRequest>,
+    unexpected: Vec<MockRequest>,
+    token: Option<Jwt>,
+}
+
+impl MockState {
+    fn respond(&mut self, request: MockRequest) -> Result<MockResponse, Error> {
+        self.requests.push(request.clone());
+        let expectation = self.expectations.iter_mut()
+            .find(|expectation| !expectation.is_satisfied() && expectation.matches(&request));
+        match expectation {
+            Some(expectation) => {
+                expectation.calls += 1;
+                Ok(expectation.response.clone())
+            },
+            None => {
+                let error = Error::Communication(format!("unexpected request: {}", request));
+                self.unexpected.push(request);
+                Err(error)
+            },
+        }
+    }
+}
+
+/// A connector that answers requests from a script of expectations.
+///
+/// Clones of a `MockConnector` share the same expectations, so one clone
+/// can be passed to the code under test and another one kept to verify the
+/// expectations.
+#[derive(Clone, Debug, Default)]
+pub struct MockConnector {
+    state: Rc<RefCell<MockState>>,
+}
+
+impl MockConnector {
+    /// Constructs a new `MockConnector` without any expectations.
+    pub fn new() -> Self {
+        MockConnector::default()
+    }
+
+    /// Adds an expected request.
+    ///
+    /// Requests are matched against the expectations in the order they have
+    /// been added. An expectation that has received the expected number of
+    /// requests does not match any further request.
+    pub fn expect(&self, expectation: Expectation) -> &Self {
+        self.state.borrow_mut().expectations.push(expectation);
+        self
+    }
+
+    /// Returns all requests received so far.
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.state.borrow().requests.clone()
+    }
+
+    /// Returns the token accepted by this connector if there is one.
+    pub fn auth_token(&self) -> Option<Jwt> {
+        self.state.borrow().token.clone()
+    }
+
+    /// Checks that all expected requests have been received and no
+    /// unexpected one.
+    ///
+    /// Returns a description of all violations if the check fails.
+    pub fn check(&self) -> Result<(), String> {
+        let state = self.state.borrow();
+        let mut violations = Vec::new();
+        for expectation in state.expectations.iter().filter(|expectation| !expectation.is_satisfied()) {
+            violations.push(format!("missing request: {}", expectation));
+        }
+        for request in &state.unexpected {
+            violations.push(format!("unexpected request: {}", request));
+        }
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations.join("\n"))
+        }
+    }
+
+    /// Verifies that all expected requests have been received and no
+    /// unexpected one.
+    ///
+    /// # Panics
+    ///
+    /// Panics with a description of all violations if the verification
+    /// fails.
+    pub fn verify(&self) {
+        if let Err(violations) = self.check() {
+            panic!("expectations of mock connector not met:\n{}", violations);
+        }
+    }
+}
+
+impl Connector for MockConnector {
+    type Connection = MockConnection;
+
+    fn connection(&self, database_name: &str) -> MockConnection {
+        MockConnection {
+            database: database_name.to_owned(),
+            state: self.state.clone(),
+        }
+    }
+
+    fn system_connection(&self) -> MockConnection {
+        self.connection(SYSTEM_DATABASE)
+    }
+
+    fn accept_auth_token(&mut self, token: Jwt) {
+        self.state.borrow_mut().token = Some(token);
+    }
+
+    fn invalidate_auth_token(&mut self) {
+        self.state.borrow_mut().token = None;
+    }
+}
+
+/// A connection of a `MockConnector` to one database.
+#[derive(Debug)]
+pub struct MockConnection {
+    database: String,
+    state: Rc<RefCell<MockState>>,
+}
+
+impl MockConnection {
+    pub fn database(&self) -> &str {
+        &self.database
+    }
+}
+
+impl Execute for MockConnection {
+    fn execute<M>(&self, method: M) -> FutureResult<M>
+        where M: Method + Prepare + 'static
+    {
+        let content_format = method.content_format();
+        let result = MockRequest::from_prepare(&self.database, &method)
+            .and_then(|request| self.state.borrow_mut().respond(request))
+            .and_then(|response| match response {
+                MockResponse::Payload(status_code, payload) => {
+                    let status_code = StatusCode::try_from(status_code)
+                        .map_err(|_| Error::Communication(format!("invalid status code {}", status_code)))?;
+                    let payload = payload_bytes(content_format, &payload)?;
+                    parse_response::<M>(&method.return_type(), content_format, status_code,
+                        Codec::Json, &payload)
+                },
+                MockResponse::Error(error) => Err(error),
+            });
+        Box::new(future::result(result))
+    }
+}
+
+/// Returns the body of a response with the given payload.
+fn payload_bytes(content_format: ContentFormat, payload: &JsonValue) -> Result<Vec<u8>, Error> {
+    match (content_format, payload) {
+        (ContentFormat::Multipart, JsonValue::String(body)) => Ok(body.as_bytes().to_vec()),
+        _ => serde_json::to_vec(payload).map_err(|cause| Error::Serialization(cause.to_string())),
+    }
+}
+
+impl Exchange for MockConnection {
+    fn exchange<P>(&self, prepare: P) -> FutureExchange
+        where P: Prepare + 'static
+    {
+        let result = RecordedRequest::from_prepare(&self.database, &prepare)
+            .and_then(|request| {
+                let response = MockRequest::from_prepare(&self.database, &prepare)
+                    .and_then(|mock_request| self.state.borrow_mut().respond(mock_request))?;
+                match response {
+                    MockResponse::Payload(status_code, payload) => {
+                        let status_code = StatusCode::try_from(status_code).map_err(|_|
+                            Error::Communication(format!("invalid status code {}", status_code)))?;
+                        let payload = payload_bytes(prepare.content_format(), &payload)?;
+                        let mut headers = Headers::new();
+                        match prepare.content_format() {
+                            ContentFormat::Multipart => headers.set_raw("Content-Type",
+                                format!("multipart/form-data; boundary={}", BATCH_BOUNDARY)),
+                            ContentFormat::Json | ContentFormat::JsonLines =>
+                                headers.set(ContentType::json()),
+                        }
+                        Ok(Interaction::new(request,
+                            RecordedResponse::from_http(status_code, &headers, &payload)))
+                    },
+                    MockResponse::Error(error) => Err(error),
+                }
+            });
+        Box::new(future::result(result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::Future;
+
+    use rincon_core::api::method::RpcReturnType;
+    use rincon_core::arango::protocol::{FIELD_CODE, FIELD_RESULT};
+    use rincon_core::arango::ErrorCode;
+    use rincon_core::api;
+    use super::*;
+
+    struct ListNames {
+        path: &'static str,
+        filter: Option<&'static str>,
+        body: Option<JsonValue>,
+    }
+
+    impl ListNames {
+        fn new(path: &'static str) -> Self {
+            ListNames {
+                path,
+                filter: None,
+                body: None,
+            }
+        }
+    }
+
+    impl Method for ListNames {
+        type Result = Vec<String>;
+        const RETURN_TYPE: RpcReturnType = RpcReturnType {
+            result_field: Some(FIELD_RESULT),
+            code_field: Some(FIELD_CODE),
+        };
+    }
+
+    impl Prepare for ListNames {
+        type Content = JsonValue;
+
+        fn operation(&self) -> Operation {
+            if self.body.is_some() { Operation::Create } else { Operation::Read }
+        }
+
+        fn path(&self) -> String {
+            String::from(self.path)
+        }
+
+        fn parameters(&self) -> Parameters {
+            let mut parameters = Parameters::new();
+            if let Some(filter) = self.filter {
+                parameters.insert("filter", filter);
+            }
+            parameters
+        }
+
+        fn header(&self) -> Parameters {
+            Parameters::empty()
+        }
+
+        fn content(&self) -> Option<&Self::Content> {
+            self.body.as_ref()
+        }
+    }
+
+    #[test]
+    fn return_scripted_result_for_matching_request() {
+        let connector = MockConnector::new();
+        connector.expect(Expectation::new(Operation::Read, "/_api/collection")
+            .in_database("shop")
+            .returns(json!({ "error": false, "code": 200, "result": ["customers", "orders"] })));
+
+        let result = connector.connection("shop")
+            .execute(ListNames::new("/_api/collection")).wait();
+
+        assert_eq!(Ok(vec!["customers".to_owned(), "orders".to_owned()]), result);
+        connector.verify();
+    }
+
+    #[test]
+    fn match_requests_on_parameters_and_body() {
+        let connector = MockConnector::new();
+        connector
+            .expect(Expectation::new(Operation::Create, "/_api/names")
+                .with_body(json!({ "prefix": "a" }))
+                .returns(json!({ "result": ["anna"] })))
+            .expect(Expectation::new(Operation::Read, "/_api/names")
+                .with_parameter("filter", "b")
+                .returns(json!({ "result": ["bert"] })));
+        let connection = connector.system_connection();
+
+        let filtered = connection.execute(ListNames {
+            filter: Some("b"),
+            ..ListNames::new("/_api/names")
+        }).wait();
+        let created = connection.execute(ListNames {
+            body: Some(json!({ "prefix": "a" })),
+            ..ListNames::new("/_api/names")
+        }).wait();
+
+        assert_eq!(Ok(vec!["bert".to_owned()]), filtered);
+        assert_eq!(Ok(vec!["anna".to_owned()]), created);
+        assert_eq!(2, connector.requests().len());
+        connector.verify();
+    }
+
+    #[test]
+    fn return_error_payload_as_method_error() {
+        let connector = MockConnector::new();
+        connector.expect(Expectation::new(Operation::Read, "/_api/collection/orders")
+            .returns_with_status(404, json!({
+                "error": true,
+                "code": 404,
+                "errorNum": 1203,
+                "errorMessage": "collection or view not found",
+            })));
+
+        let result = connector.system_connection()
+            .execute(ListNames::new("/_api/collection/orders")).wait();
+
+        assert_eq!(Err(Error::Method(api::Error::new(404, ErrorCode::ArangoCollectionNotFound,
+            "collection or view not found"))), result);
+    }
+
+    #[test]
+    fn return_scripted_error() {
+        let connector = MockConnector::new();
+        connector.expect(Expectation::new(Operation::Read, "/_api/version")
+            .fails_with(Error::Timeout("no response".into())));
+
+        let result = connector.system_connection()
+            .execute(ListNames::new("/_api/version")).wait();
+
+        assert_eq!(Err(Error::Timeout("no response".into())), result);
+    }
+
+    #[test]
+    fn expectation_is_used_the_given_number_of_times() {
+        let connector = MockConnector::new();
+        connector.expect(Expectation::new(Operation::Read, "/_api/version")
+            .returns(json!({ "result": [] }))
+            .times(2));
+        let connection = connector.system_connection();
+
+        assert!(connection.execute(ListNames::new("/_api/version")).wait().is_ok());
+        assert!(connection.execute(ListNames::new("/_api/version")).wait().is_ok());
+        assert!(connection.execute(ListNames::new("/_api/version")).wait().is_err());
+    }
+
+    #[test]
+    fn check_reports_missing_and_unexpected_requests() {
+        let connector = MockConnector::new();
+        connector.expect(Expectation::new(Operation::Read, "/_api/collection")
+            .in_database("shop"));
+
+        let result = connector.connection("other")
+            .execute(ListNames::new("/_api/collection")).wait();
+
+        assert!(result.is_err());
+        let violations = connector.check().unwrap_err();
+        assert!(violations.contains("missing request: Read /_db/shop/_api/collection"));
+        assert!(violations.contains("unexpected request: Read /_db/other/_api/collection"));
+    }
+
+    #[test]
+    #[should_panic(expected = "expectations of mock connector not met")]
+    fn verify_panics_if_expected_request_is_missing() {
+        let connector = MockConnector::new();
+        connector.expect(Expectation::new(Operation::Read, "/_api/version"));
+
+        connector.verify();
+    }
+}
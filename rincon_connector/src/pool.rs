@@ -0,0 +1,478 @@
+
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{self, Debug};
+use std::io::{self, Read, Write};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use futures::{Async, Future, Poll};
+use futures::task::{self, Task};
+use hyper::Uri;
+use hyper::client::Service;
+use tokio_io::{AsyncRead, AsyncWrite};
+
+use rincon_core::api::connector::Error;
+
+/// The default time after which an idle connection is closed.
+pub const DEFAULT_IDLE_TIMEOUT: u64 = 90;
+
+/// Configuration of the connection pool and the request queue of a
+/// connector.
+///
+/// By default connections are kept alive and reused for subsequent requests,
+/// and neither the number of concurrent requests nor the number of
+/// connections per host is limited.
+#[allow(missing_copy_implementations)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolConfig {
+    keep_alive: bool,
+    idle_timeout: Option<Duration>,
+    max_in_flight: Option<usize>,
+    max_connections_per_host: Option<usize>,
+}
+
+impl PoolConfig {
+    /// Constructs a new `PoolConfig` with default settings.
+    pub fn new() -> Self {
+        PoolConfig {
+            keep_alive: true,
+            idle_timeout: Some(Duration::from_secs(DEFAULT_IDLE_TIMEOUT)),
+            max_in_flight: None,
+            max_connections_per_host: None,
+        }
+    }
+
+    /// Sets whether connections shall be kept alive and reused for
+    /// subsequent requests.
+    ///
+    /// If set to `false` a new connection is opened for every request.
+    pub fn with_keep_alive(mut self, keep_alive: bool) -> Self {
+        self.keep_alive = keep_alive;
+        self
+    }
+
+    /// Sets the duration after which an idle connection is closed.
+    ///
+    /// If set to `None` idle connections are kept open until the server
+    /// closes them.
+    pub fn with_idle_timeout<D>(mut self, idle_timeout: D) -> Self
+        where D: Into<Option<Duration>>
+    {
+        self.idle_timeout = idle_timeout.into();
+        self
+    }
+
+    /// Sets the maximum number of requests that are sent concurrently.
+    ///
+    /// The limit applies to all requests of the connector together,
+    /// regardless of the endpoint they are sent to. Further requests are
+    /// queued and sent in the order they have been issued as soon as a
+    /// running request is completed. If set to `None` the number of
+    /// concurrent requests is not limited.
+    pub fn with_max_in_flight<M>(mut self, max_in_flight: M) -> Self
+        where M: Into<Option<usize>>
+    {
+        self.max_in_flight = max_in_flight.into();
+        self
+    }
+
+    /// Sets the maximum number of connections that are open to each host.
+    ///
+    /// The limit applies to idle connections kept in the pool as well as to
+    /// connections serving a request. If all connections to a host are in
+    /// use, a request waits until one of them becomes idle or is closed. If
+    /// set to `None` the number of connections per host is not limited.
+    pub fn with_max_connections_per_host<M>(mut self, max_connections_per_host: M) -> Self
+        where M: Into<Option<usize>>
+    {
+        self.max_connections_per_host = max_connections_per_host.into();
+        self
+    }
+
+    /// Returns whether connections are kept alive.
+    pub fn is_keep_alive(&self) -> bool {
+        self.keep_alive
+    }
+
+    /// Returns the duration after which an idle connection is closed.
+    pub fn idle_timeout(&self) -> Option<&Duration> {
+        self.idle_timeout.as_ref()
+    }
+
+    /// Returns the maximum number of requests that are sent concurrently.
+    pub fn max_in_flight(&self) -> Option<usize> {
+        self.max_in_flight
+    }
+
+    /// Returns the maximum number of connections that are open to each host.
+    pub fn max_connections_per_host(&self) -> Option<usize> {
+        self.max_connections_per_host
+    }
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        PoolConfig::new()
+    }
+}
+
+/// Limits the number of requests in flight and queues further requests.
+#[derive(Clone)]
+pub(crate) struct RequestLimiter {
+    state: Arc<Mutex<LimiterState>>,
+}
+
+struct LimiterState {
+    max_in_flight: usize,
+    in_flight: usize,
+    next_id: u64,
+    waiting: VecDeque<(u64, Task)>,
+}
+
+impl LimiterState {
+    fn has_capacity(&self) -> bool {
+        self.in_flight < self.max_in_flight
+    }
+
+    fn notify_next(&self) {
+        if self.has_capacity() {
+            if let Some((_, task)) = self.waiting.front() {
+                task.notify();
+            }
+        }
+    }
+}
+
+impl RequestLimiter {
+    pub(crate) fn new(max_in_flight: usize) -> Self {
+        RequestLimiter {
+            state: Arc::new(Mutex::new(LimiterState {
+                max_in_flight,
+                in_flight: 0,
+                next_id: 0,
+                waiting: VecDeque::new(),
+            })),
+        }
+    }
+
+    /// Returns a future that resolves to a `Permit` as soon as a request
+    /// may be sent.
+    pub(crate) fn acquire(&self) -> Acquire {
+        Acquire {
+            state: self.state.clone(),
+            queued: None,
+        }
+    }
+
+    fn lock(state: &Mutex<LimiterState>) -> MutexGuard<'_, LimiterState> {
+        state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Debug for RequestLimiter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let state = RequestLimiter::lock(&self.state);
+        f.debug_struct("RequestLimiter")
+            .field("max_in_flight", &state.max_in_flight)
+            .field("in_flight", &state.in_flight)
+            .field("waiting", &state.waiting.len())
+            .finish()
+    }
+}
+
+/// A future that waits until a request may be sent.
+pub(crate) struct Acquire {
+    state: Arc<Mutex<LimiterState>>,
+    queued: Option<u64>,
+}
+
+impl Future for Acquire {
+    type Item = Permit;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let mut state = RequestLimiter::lock(&self.state);
+        match self.queued {
+            None => if state.has_capacity() && state.waiting.is_empty() {
+                state.in_flight += 1;
+            } else {
+                let id = state.next_id;
+                state.next_id = state.next_id.wrapping_add(1);
+                state.waiting.push_back((id, task::current()));
+                self.queued = Some(id);
+                return Ok(Async::NotReady);
+            },
+            Some(id) => {
+                let is_next = state.waiting.front().is_some_and(|&(front, _)| front == id);
+                if is_next && state.has_capacity() {
+                    state.waiting.pop_front();
+                    state.in_flight += 1;
+                    self.queued = None;
+                    state.notify_next();
+                } else {
+                    if let Some(entry) = state.waiting.iter_mut().find(|entry| entry.0 == id) {
+                        entry.1 = task::current();
+                    }
+                    return Ok(Async::NotReady);
+                }
+            },
+        }
+        Ok(Async::Ready(Permit { state: self.state.clone() }))
+    }
+}
+
+impl Drop for Acquire {
+    fn drop(&mut self) {
+        if let Some(id) = self.queued {
+            let mut state = RequestLimiter::lock(&self.state);
+            state.waiting.retain(|entry| entry.0 != id);
+            state.notify_next();
+        }
+    }
+}
+
+/// Permission to send a request. The permit is released when dropped.
+pub(crate) struct Permit {
+    state: Arc<Mutex<LimiterState>>,
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        let mut state = RequestLimiter::lock(&self.state);
+        state.in_flight -= 1;
+        state.notify_next();
+    }
+}
+
+/// A connector that limits the number of connections open to each host.
+///
+/// A connection counts against the limit of its host until the stream
+/// returned by the wrapped connector is dropped. Connection attempts beyond
+/// the limit wait until a connection to the same host is closed.
+#[derive(Debug)]
+pub(crate) struct HostLimitedConnector<C> {
+    inner: Rc<C>,
+    max_per_host: Option<usize>,
+    limiters: Rc<RefCell<HashMap<String, RequestLimiter>>>,
+}
+
+impl<C> HostLimitedConnector<C> {
+    pub(crate) fn new(inner: C, max_per_host: Option<usize>) -> Self {
+        HostLimitedConnector {
+            inner: Rc::new(inner),
+            max_per_host,
+            limiters: Rc::new(RefCell::new(HashMap::new())),
+        }
+    }
+
+    fn limiter(&self, uri: &Uri) -> Option<RequestLimiter> {
+        self.max_per_host.map(|max_per_host| {
+            let host = format!("{}://{}", uri.scheme().unwrap_or("http"),
+                uri.authority().unwrap_or_default());
+            self.limiters.borrow_mut().entry(host)
+                .or_insert_with(|| RequestLimiter::new(max_per_host))
+                .clone()
+        })
+    }
+}
+
+impl<C> Service for HostLimitedConnector<C>
+    where C: Service<Request=Uri, Error=io::Error> + 'static
+{
+    type Request = Uri;
+    type Response = LimitedStream<C::Response>;
+    type Error = io::Error;
+    type Future = Box<dyn Future<Item=Self::Response, Error=Self::Error>>;
+
+    fn call(&self, uri: Uri) -> Self::Future {
+        let inner = self.inner.clone();
+        match self.limiter(&uri) {
+            Some(limiter) => Box::new(limiter.acquire()
+                .map_err(|error| io::Error::other(error.to_string()))
+                .and_then(move |permit| inner.call(uri)
+                    .map(|stream| LimitedStream { stream, _permit: Some(permit) }))
+            ),
+            None => Box::new(inner.call(uri)
+                .map(|stream| LimitedStream { stream, _permit: None })
+            ),
+        }
+    }
+}
+
+/// A connection that releases its slot of the per host limit when dropped.
+pub(crate) struct LimitedStream<S> {
+    stream: S,
+    _permit: Option<Permit>,
+}
+
+impl<S> Read for LimitedStream<S>
+    where S: Read
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.stream.read(buf)
+    }
+}
+
+impl<S> Write for LimitedStream<S>
+    where S: Write
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.stream.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.stream.flush()
+    }
+}
+
+impl<S> AsyncRead for LimitedStream<S>
+    where S: AsyncRead
+{}
+
+impl<S> AsyncWrite for LimitedStream<S>
+    where S: AsyncWrite
+{
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        self.stream.shutdown()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use std::io::Cursor;
+
+    use futures::future::{self, FutureResult};
+    use futures::executor::{self, Notify, Spawn};
+    use super::*;
+
+    struct NoopNotify;
+
+    impl Notify for NoopNotify {
+        fn notify(&self, _id: usize) {}
+    }
+
+    fn poll<F>(future: &mut Spawn<F>) -> Option<F::Item>
+        where F: Future, F::Error: Debug
+    {
+        match future.poll_future_notify(&Arc::new(NoopNotify), 0) {
+            Ok(Async::Ready(item)) => Some(item),
+            Ok(Async::NotReady) => None,
+            Err(error) => panic!("unexpected error: {:?}", error),
+        }
+    }
+
+    struct InMemoryConnector;
+
+    impl Service for InMemoryConnector {
+        type Request = Uri;
+        type Response = Cursor<Vec<u8>>;
+        type Error = io::Error;
+        type Future = FutureResult<Self::Response, Self::Error>;
+
+        fn call(&self, _uri: Uri) -> Self::Future {
+            future::ok(Cursor::new(Vec::new()))
+        }
+    }
+
+    fn uri(text: &str) -> Uri {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn pool_config_defaults_to_keep_alive_without_limit() {
+        let config = PoolConfig::default();
+
+        assert!(config.is_keep_alive());
+        assert_eq!(Some(&Duration::from_secs(DEFAULT_IDLE_TIMEOUT)), config.idle_timeout());
+        assert_eq!(None, config.max_in_flight());
+        assert_eq!(None, config.max_connections_per_host());
+    }
+
+    #[test]
+    fn limiter_grants_permits_up_to_max_in_flight() {
+        let limiter = RequestLimiter::new(2);
+        let mut first = executor::spawn(limiter.acquire());
+        let mut second = executor::spawn(limiter.acquire());
+        let mut third = executor::spawn(limiter.acquire());
+
+        let permit1 = poll(&mut first);
+        let permit2 = poll(&mut second);
+        let permit3 = poll(&mut third);
+
+        assert!(permit1.is_some());
+        assert!(permit2.is_some());
+        assert!(permit3.is_none());
+    }
+
+    #[test]
+    fn limiter_grants_queued_requests_in_order_when_permits_are_released() {
+        let limiter = RequestLimiter::new(1);
+        let mut first = executor::spawn(limiter.acquire());
+        let mut second = executor::spawn(limiter.acquire());
+        let mut third = executor::spawn(limiter.acquire());
+
+        let permit1 = poll(&mut first);
+        assert!(permit1.is_some());
+        assert!(poll(&mut second).is_none());
+        assert!(poll(&mut third).is_none());
+
+        drop(permit1);
+
+        assert!(poll(&mut third).is_none());
+        let permit2 = poll(&mut second);
+        assert!(permit2.is_some());
+        assert!(poll(&mut third).is_none());
+
+        drop(permit2);
+
+        assert!(poll(&mut third).is_some());
+    }
+
+    #[test]
+    fn limiter_skips_dropped_requests_in_queue() {
+        let limiter = RequestLimiter::new(1);
+        let mut first = executor::spawn(limiter.acquire());
+        let mut second = executor::spawn(limiter.acquire());
+        let mut third = executor::spawn(limiter.acquire());
+
+        let permit1 = poll(&mut first);
+        assert!(poll(&mut second).is_none());
+        assert!(poll(&mut third).is_none());
+
+        drop(second);
+        drop(permit1);
+
+        assert!(poll(&mut third).is_some());
+    }
+
+    #[test]
+    fn connector_limits_connections_per_host() {
+        let connector = HostLimitedConnector::new(InMemoryConnector, Some(1));
+        let mut first = executor::spawn(connector.call(uri("http://db1:8529/_api/version")));
+        let mut second = executor::spawn(connector.call(uri("http://db1:8529/_api/version")));
+        let mut other = executor::spawn(connector.call(uri("http://db2:8529/_api/version")));
+
+        let stream1 = poll(&mut first);
+        assert!(stream1.is_some());
+        assert!(poll(&mut second).is_none());
+        assert!(poll(&mut other).is_some());
+
+        drop(stream1);
+
+        assert!(poll(&mut second).is_some());
+    }
+
+    #[test]
+    fn connector_without_limit_opens_any_number_of_connections() {
+        let connector = HostLimitedConnector::new(InMemoryConnector, None);
+        let mut first = executor::spawn(connector.call(uri("http://db1:8529/_api/version")));
+        let mut second = executor::spawn(connector.call(uri("http://db1:8529/_api/version")));
+
+        assert!(poll(&mut first).is_some());
+        assert!(poll(&mut second).is_some());
+    }
+}
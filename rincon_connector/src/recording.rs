@@ -0,0 +1,830 @@
+//! Connectors that record the requests sent to a server and replay the
+//! recorded responses later on.
+//!
+//! The `RecordingConnector` wraps another connector whose connections
+//! implement the `Exchange` trait, like the `BasicConnector`, the
+//! `VstConnector` and the `MockConnector`. It passes all requests to the wrapped connector and
+//! captures each request as it has been sent together with the response as
+//! it has been received in a fixture file. The `ReplayConnector` serves the
+//! responses from such a fixture file without connecting to a server. It
+//! fails on any request that has not been recorded.
+//!
+//! This way integration tests can be recorded once against a real server
+//! and then run deterministically on machines without an ArangoDB server.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! // record the interactions with the server
+//! let connector = BasicConnector::new(&MyUserAgent, datasource, &core.handle()).unwrap();
+//! let connector = RecordingConnector::new(connector, "tests/fixtures/version.json");
+//! let version = core.run(connector.system_connection().execute(GetServerVersion::new())).unwrap();
+//! connector.save().unwrap();
+//!
+//! // replay the recorded interactions
+//! let connector = ReplayConnector::from_file("tests/fixtures/version.json").unwrap();
+//! let replayed = core.run(connector.system_connection().execute(GetServerVersion::new())).unwrap();
+//! assert_eq!(version, replayed);
+//! ```
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use futures::{future, Future};
+use hyper::{self, Headers, StatusCode};
+use serde_json::{self, Value as JsonValue};
+
+use rincon_core::api::auth::Jwt;
+use rincon_core::api::connector::{Connector, Error, Execute, FutureResult};
+use rincon_core::api::datasource::Codec;
+use rincon_core::api::method::{request_path, ContentFormat, Method, Prepare, RpcReturnType};
+use rincon_core::arango::protocol::SYSTEM_DATABASE;
+use http::{http_method_for_operation, parse_response, serialize_payload};
+use vpack::{self, CONTENT_TYPE_VPACK};
+
+/// The name of the header that holds the content type of a body.
+const HEADER_CONTENT_TYPE: &str = "Content-Type";
+
+/// The name of the header that holds the credentials of a request.
+const HEADER_AUTHORIZATION: &str = "Authorization";
+
+/// The value that replaces credentials in recorded headers.
+const REDACTED: &str = "<redacted>";
+
+/// The future type for the interactions returned by the `Exchange` trait.
+pub type FutureExchange = Box<dyn Future<Item=Interaction, Error=Error>>;
+
+/// Sends the request of a method and returns the request as it has been
+/// sent and the response as it has been received without processing it.
+///
+/// Connections implementing this trait can be recorded by the
+/// `RecordingConnector`.
+pub trait Exchange {
+    /// Sends the request of the given method.
+    ///
+    /// A response with an error status is returned as interaction. An
+    /// error is only returned if no response has been received.
+    fn exchange<P>(&self, prepare: P) -> FutureExchange
+        where P: Prepare + 'static;
+}
+
+/// A request as recorded in a fixture file.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    method: String,
+    uri: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    headers: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<JsonValue>,
+}
+
+impl RecordedRequest {
+    /// Constructs the request of the given method as it would be sent with
+    /// a JSON body without any headers other than those of the method.
+    pub(crate) fn from_prepare<P>(database: &str, prepare: &P) -> Result<Self, Error>
+        where P: Prepare
+    {
+        let body = match prepare.content() {
+            Some(content) =>
+                Some(serialize_payload(content, prepare.content_format(), Codec::Json)?),
+            None => None,
+        };
+        let mut headers = BTreeMap::new();
+        for (name, value) in prepare.header().iter() {
+            headers.insert(name.to_owned(), value.to_string());
+        }
+        if body.is_some() {
+            headers.insert(HEADER_CONTENT_TYPE.to_owned(), "application/json".to_owned());
+        }
+        Ok(RecordedRequest {
+            method: http_method_for_operation(&prepare.operation()).to_string(),
+            uri: request_path(Some(database), prepare),
+            body: body.map(|body| decode_body(&headers, &body)),
+            headers,
+        })
+    }
+
+    /// Constructs the request as it has been sent over HTTP.
+    ///
+    /// The values of authorization headers are redacted, so that no
+    /// credentials are written to fixture files.
+    pub(crate) fn from_http(method: &hyper::Method, uri: String, headers: &Headers,
+        body: Option<&[u8]>) -> Self
+    {
+        let headers = recorded_headers(headers);
+        RecordedRequest {
+            method: method.to_string(),
+            uri,
+            body: body.map(|body| decode_body(&headers, body)),
+            headers,
+        }
+    }
+
+    /// Constructs the request as it has been sent over VelocyStream.
+    ///
+    /// The meta data of the request is recorded as its headers.
+    pub(crate) fn from_vst(method: &hyper::Method, uri: String, meta: &BTreeMap<String, String>,
+        body: Option<&[u8]>) -> Self
+    {
+        let headers = meta.clone();
+        RecordedRequest {
+            method: method.to_string(),
+            uri,
+            body: body.map(|body| decode_body(&headers, body)),
+            headers,
+        }
+    }
+
+    /// Returns whether this request has the same method, URI and body as
+    /// the given one.
+    ///
+    /// Headers are not compared as they contain values that differ between
+    /// the recording and the replay, like the authorization or the user
+    /// agent.
+    pub fn matches(&self, other: &RecordedRequest) -> bool {
+        self.method == other.method && self.uri == other.uri && self.body == other.body
+    }
+
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    pub fn headers(&self) -> &BTreeMap<String, String> {
+        &self.headers
+    }
+
+    pub fn body(&self) -> Option<&JsonValue> {
+        self.body.as_ref()
+    }
+}
+
+impl Display for RecordedRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.method, self.uri)?;
+        if let Some(ref body) = self.body {
+            write!(f, " {}", body)?;
+        }
+        Ok(())
+    }
+}
+
+/// The kinds of errors that are recorded as failures.
+///
+/// Errors returned by the server are recorded as payload with the status
+/// code of the response.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FailureKind {
+    Communication,
+    Deserialization,
+    NotAuthenticated,
+    Serialization,
+    Timeout,
+}
+
+/// A response as recorded in a fixture file.
+///
+/// The payload of a response is recorded as JSON if the body is JSON or
+/// VelocyPack, otherwise as string.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RecordedResponse {
+    /// A response with the given status code, headers and payload.
+    Payload {
+        status: u16,
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        headers: BTreeMap<String, String>,
+        payload: JsonValue,
+    },
+    /// A request that failed without a response from the server.
+    Failure {
+        kind: FailureKind,
+        message: String,
+    },
+}
+
+impl RecordedResponse {
+    /// Constructs the response as it has been received over HTTP.
+    pub(crate) fn from_http(status_code: StatusCode, headers: &Headers, body: &[u8]) -> Self {
+        let headers = recorded_headers(headers);
+        RecordedResponse::Payload {
+            status: status_code.as_u16(),
+            payload: decode_body(&headers, body),
+            headers,
+        }
+    }
+
+    /// Constructs the response as it has been received over VelocyStream.
+    ///
+    /// The meta data of the response is recorded as its headers. The body
+    /// is decoded with the given codec.
+    pub(crate) fn from_vst(status_code: u16, meta: &BTreeMap<String, String>, codec: Codec,
+        body: &[u8]) -> Self
+    {
+        let payload = match codec {
+            Codec::Json => serde_json::from_slice(body).ok(),
+            Codec::VelocyPack => vpack::from_slice(body).ok(),
+        };
+        RecordedResponse::Payload {
+            status: status_code,
+            headers: meta.clone(),
+            payload: payload.unwrap_or_else(||
+                JsonValue::String(String::from_utf8_lossy(body).into_owned())),
+        }
+    }
+
+    /// Constructs the response of a request for which no response has been
+    /// received.
+    fn from_error(error: Error) -> Self {
+        match error {
+            Error::Method(error) => RecordedResponse::Payload {
+                status: error.status_code(),
+                headers: BTreeMap::new(),
+                payload: json!({
+                    "error": true,
+                    "code": error.status_code(),
+                    "errorNum": error.error_code().as_u16(),
+                    "errorMessage": error.message(),
+                }),
+            },
+            Error::Communication(message) =>
+                RecordedResponse::Failure { kind: FailureKind::Communication, message },
+            Error::Deserialization(message) =>
+                RecordedResponse::Failure { kind: FailureKind::Deserialization, message },
+            Error::NotAuthenticated(message) =>
+                RecordedResponse::Failure { kind: FailureKind::NotAuthenticated, message },
+            Error::Serialization(message) =>
+                RecordedResponse::Failure { kind: FailureKind::Serialization, message },
+            Error::Timeout(message) =>
+                RecordedResponse::Failure { kind: FailureKind::Timeout, message },
+        }
+    }
+
+    /// Converts this response into the result of the given method in the
+    /// same way as a response received from the server.
+    fn to_result<M>(&self, return_type: &RpcReturnType, content_format: ContentFormat)
+        -> Result<<M as Method>::Result, Error>
+        where M: Method
+    {
+        match *self {
+            RecordedResponse::Payload { status, ref headers, ref payload } => {
+                let status_code = StatusCode::try_from(status)
+                    .map_err(|_| Error::Deserialization(format!("invalid status code {}", status)))?;
+                let payload = match *payload {
+                    JsonValue::String(ref text) if !is_structured(headers) =>
+                        text.as_bytes().to_vec(),
+                    _ => serde_json::to_vec(payload)
+                        .map_err(|cause| Error::Serialization(cause.to_string()))?,
+                };
+                parse_response::<M>(return_type, content_format, status_code, Codec::Json,
+                    &payload)
+            },
+            RecordedResponse::Failure { kind, ref message } => {
+                let message = message.to_owned();
+                Err(match kind {
+                    FailureKind::Communication => Error::Communication(message),
+                    FailureKind::Deserialization => Error::Deserialization(message),
+                    FailureKind::NotAuthenticated => Error::NotAuthenticated(message),
+                    FailureKind::Serialization => Error::Serialization(message),
+                    FailureKind::Timeout => Error::Timeout(message),
+                })
+            },
+        }
+    }
+}
+
+/// A request and its response.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    request: RecordedRequest,
+    response: RecordedResponse,
+}
+
+impl Interaction {
+    pub fn new(request: RecordedRequest, response: RecordedResponse) -> Self {
+        Interaction {
+            request,
+            response,
+        }
+    }
+
+    pub fn request(&self) -> &RecordedRequest {
+        &self.request
+    }
+
+    pub fn response(&self) -> &RecordedResponse {
+        &self.response
+    }
+}
+
+/// Reads the interactions from the given fixture file.
+pub fn read_fixture<P>(path: P) -> io::Result<Vec<Interaction>>
+    where P: AsRef<Path>
+{
+    let file = File::open(path)?;
+    serde_json::from_reader(BufReader::new(file))
+        .map_err(|cause| io::Error::new(io::ErrorKind::InvalidData, cause))
+}
+
+/// Writes the given interactions to the given fixture file.
+pub fn write_fixture<P>(path: P, interactions: &[Interaction]) -> io::Result<()>
+    where P: AsRef<Path>
+{
+    let file = File::create(path)?;
+    serde_json::to_writer_pretty(BufWriter::new(file), interactions)
+        .map_err(io::Error::other)
+}
+
+/// Converts the given HTTP headers into recorded headers.
+fn recorded_headers(headers: &Headers) -> BTreeMap<String, String> {
+    headers.iter()
+        .map(|header| {
+            let value = if header.name().eq_ignore_ascii_case(HEADER_AUTHORIZATION) {
+                REDACTED.to_owned()
+            } else {
+                header.value_string()
+            };
+            (header.name().to_owned(), value)
+        })
+        .collect()
+}
+
+/// Returns whether the content type in the given headers is JSON or
+/// VelocyPack. A missing content type is considered to be JSON.
+fn is_structured(headers: &BTreeMap<String, String>) -> bool {
+    headers.iter()
+        .find(|&(name, _)| name.eq_ignore_ascii_case(HEADER_CONTENT_TYPE))
+        .is_none_or(|(_, value)| value.contains("json") || value.starts_with(CONTENT_TYPE_VPACK))
+}
+
+/// Decodes the given body as JSON value.
+///
+/// A VelocyPack body is converted into JSON. A body that is neither JSON
+/// nor VelocyPack is recorded as string.
+fn decode_body(headers: &BTreeMap<String, String>, body: &[u8]) -> JsonValue {
+    let is_vpack = headers.iter()
+        .any(|(name, value)| name.eq_ignore_ascii_case(HEADER_CONTENT_TYPE)
+            && value.starts_with(CONTENT_TYPE_VPACK));
+    let decoded = if is_vpack {
+        vpack::from_slice(body).ok()
+    } else if is_structured(headers) {
+        serde_json::from_slice(body).ok()
+    } else {
+        None
+    };
+    decoded.unwrap_or_else(|| JsonValue::String(String::from_utf8_lossy(body).into_owned()))
+}
+
+#[derive(Debug)]
+struct Recording {
+    path: PathBuf,
+    interactions: Vec<Interaction>,
+    saved: bool,
+}
+
+impl Recording {
+    fn save(&mut self) -> io::Result<()> {
+        write_fixture(&self.path, &self.interactions)?;
+        self.saved = true;
+        Ok(())
+    }
+}
+
+impl Drop for Recording {
+    fn drop(&mut self) {
+        if !self.saved {
+            if let Err(error) = self.save() {
+                error!("Could not write fixture file {:?}: {}", self.path, error);
+            }
+        }
+    }
+}
+
+/// A connector that records all requests and responses of the connector it
+/// wraps in a fixture file.
+///
+/// The fixture file is written when `save()` is called or when the last
+/// clone of this connector and all its connections are dropped.
+#[derive(Clone, Debug)]
+pub struct RecordingConnector<C> {
+    inner: C,
+    recording: Rc<RefCell<Recording>>,
+}
+
+impl<C> RecordingConnector<C>
+    where C: Connector
+{
+    /// Constructs a new `RecordingConnector` that wraps the given connector
+    /// and writes the recorded interactions to the given fixture file.
+    pub fn new<P>(inner: C, path: P) -> Self
+        where P: Into<PathBuf>
+    {
+        RecordingConnector {
+            inner,
+            recording: Rc::new(RefCell::new(Recording {
+                path: path.into(),
+                interactions: Vec::new(),
+                saved: false,
+            })),
+        }
+    }
+
+    /// Returns the interactions recorded so far.
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.recording.borrow().interactions.clone()
+    }
+
+    /// Writes the interactions recorded so far to the fixture file.
+    pub fn save(&self) -> io::Result<()> {
+        self.recording.borrow_mut().save()
+    }
+}
+
+impl<C> Connector for RecordingConnector<C>
+    where C: Connector, <C as Connector>::Connection: Exchange
+{
+    type Connection = RecordingConnection<<C as Connector>::Connection>;
+
+    fn connection(&self, database_name: &str) -> Self::Connection {
+        RecordingConnection {
+            database: database_name.to_owned(),
+            inner: self.inner.connection(database_name),
+            recording: self.recording.clone(),
+        }
+    }
+
+    fn system_connection(&self) -> Self::Connection {
+        self.connection(SYSTEM_DATABASE)
+    }
+
+    fn accept_auth_token(&mut self, token: Jwt) {
+        self.inner.accept_auth_token(token);
+    }
+
+    fn invalidate_auth_token(&mut self) {
+        self.inner.invalidate_auth_token();
+    }
+}
+
+/// A connection of a `RecordingConnector` to one database.
+#[derive(Debug)]
+pub struct RecordingConnection<T> {
+    database: String,
+    inner: T,
+    recording: Rc<RefCell<Recording>>,
+}
+
+impl<T> Execute for RecordingConnection<T>
+    where T: Exchange
+{
+    fn execute<M>(&self, method: M) -> FutureResult<M>
+        where M: Method + Prepare + 'static
+    {
+        let request = match RecordedRequest::from_prepare(&self.database, &method) {
+            Ok(request) => request,
+            Err(error) => return Box::new(future::err(error)),
+        };
+        let return_type = method.return_type();
+        let content_format = method.content_format();
+        let recording = self.recording.clone();
+        Box::new(self.inner.exchange(method).then(move |result| {
+            let interaction = result.unwrap_or_else(|error|
+                Interaction::new(request, RecordedResponse::from_error(error)));
+            let result = interaction.response.to_result::<M>(&return_type, content_format);
+            let mut recording = recording.borrow_mut();
+            recording.interactions.push(interaction);
+            recording.saved = false;
+            result
+        }))
+    }
+}
+
+#[derive(Debug)]
+struct Replay {
+    interactions: Vec<Interaction>,
+    replayed: Vec<bool>,
+}
+
+impl Replay {
+    fn respond(&mut self, request: &RecordedRequest) -> Option<RecordedResponse> {
+        let replayed = &mut self.replayed;
+        self.interactions.iter().enumerate()
+            .find(|&(index, interaction)| !replayed[index] && interaction.request.matches(request))
+            .map(|(index, interaction)| {
+                replayed[index] = true;
+                interaction.response.clone()
+            })
+    }
+}
+
+/// A connector that serves recorded responses without connecting to a
+/// server.
+///
+/// A request is answered with the response of the first recorded
+/// interaction with an equal request that has not been replayed yet. A
+/// request that has not been recorded fails with an
+/// `Error::Communication`.
+#[derive(Clone, Debug)]
+pub struct ReplayConnector {
+    replay: Rc<RefCell<Replay>>,
+}
+
+impl ReplayConnector {
+    /// Constructs a new `ReplayConnector` that serves the given
+    /// interactions.
+    pub fn new(interactions: Vec<Interaction>) -> Self {
+        let replayed = vec![false; interactions.len()];
+        ReplayConnector {
+            replay: Rc::new(RefCell::new(Replay {
+                interactions,
+                replayed,
+            })),
+        }
+    }
+
+    /// Constructs a new `ReplayConnector` that serves the interactions of
+    /// the given fixture file.
+    pub fn from_file<P>(path: P) -> io::Result<Self>
+        where P: AsRef<Path>
+    {
+        read_fixture(path).map(ReplayConnector::new)
+    }
+
+    /// Returns the recorded requests that have not been replayed yet.
+    pub fn pending_requests(&self) -> Vec<RecordedRequest> {
+        let replay = self.replay.borrow();
+        replay.interactions.iter().zip(replay.replayed.iter())
+            .filter(|&(_, replayed)| !replayed)
+            .map(|(interaction, _)| interaction.request.clone())
+            .collect()
+    }
+}
+
+impl Connector for ReplayConnector {
+    type Connection = ReplayConnection;
+
+    fn connection(&self, database_name: &str) -> ReplayConnection {
+        ReplayConnection {
+            database: database_name.to_owned(),
+            replay: self.replay.clone(),
+        }
+    }
+
+    fn system_connection(&self) -> ReplayConnection {
+        self.connection(SYSTEM_DATABASE)
+    }
+
+    fn accept_auth_token(&mut self, _token: Jwt) {}
+
+    fn invalidate_auth_token(&mut self) {}
+}
+
+/// A connection of a `ReplayConnector` to one database.
+#[derive(Debug)]
+pub struct ReplayConnection {
+    database: String,
+    replay: Rc<RefCell<Replay>>,
+}
+
+impl Execute for ReplayConnection {
+    fn execute<M>(&self, method: M) -> FutureResult<M>
+        where M: Method + Prepare + 'static
+    {
+        let result = RecordedRequest::from_prepare(&self.database, &method)
+            .and_then(|request| match self.replay.borrow_mut().respond(&request) {
+                Some(response) =>
+                    response.to_result::<M>(&method.return_type(), method.content_format()),
+                None => Err(Error::Communication(format!("unrecorded request: {}", request))),
+            });
+        Box::new(future::result(result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use rincon_core::api;
+    use rincon_core::api::method::{Operation, Parameters};
+    use rincon_core::arango::protocol::{FIELD_CODE, FIELD_RESULT};
+    use rincon_core::arango::ErrorCode;
+    use mock::{Expectation, MockConnector};
+    use super::*;
+
+    struct GetNames {
+        collection: &'static str,
+        filter: Option<&'static str>,
+    }
+
+    impl GetNames {
+        fn new(collection: &'static str) -> Self {
+            GetNames {
+                collection,
+                filter: None,
+            }
+        }
+    }
+
+    impl Method for GetNames {
+        type Result = Vec<String>;
+        const RETURN_TYPE: RpcReturnType = RpcReturnType {
+            result_field: Some(FIELD_RESULT),
+            code_field: Some(FIELD_CODE),
+        };
+    }
+
+    impl Prepare for GetNames {
+        type Content = ();
+
+        fn operation(&self) -> Operation {
+            Operation::Read
+        }
+
+        fn path(&self) -> String {
+            format!("/_api/names/{}", self.collection)
+        }
+
+        fn parameters(&self) -> Parameters {
+            let mut parameters = Parameters::new();
+            if let Some(filter) = self.filter {
+                parameters.insert("filter", filter);
+            }
+            parameters
+        }
+
+        fn header(&self) -> Parameters {
+            Parameters::empty()
+        }
+
+        fn content(&self) -> Option<&Self::Content> {
+            None
+        }
+    }
+
+    fn fixture_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("rincon_recording_{}.json", name))
+    }
+
+    fn mock_server() -> MockConnector {
+        let connector = MockConnector::new();
+        connector
+            .expect(Expectation::new(Operation::Read, "/_api/names/customers")
+                .in_database("shop")
+                .returns(json!({ "error": false, "code": 200, "result": ["anna", "bert"] })))
+            .expect(Expectation::new(Operation::Read, "/_api/names/customers")
+                .in_database("shop")
+                .with_parameter("filter", "b")
+                .returns(json!({ "error": false, "code": 200, "result": ["bert"] })))
+            .expect(Expectation::new(Operation::Read, "/_api/names/orders")
+                .in_database("shop")
+                .returns_with_status(404, json!({
+                    "error": true,
+                    "code": 404,
+                    "errorNum": 1203,
+                    "errorMessage": "collection or view not found",
+                })));
+        connector
+    }
+
+    fn run_requests<C>(connector: &C) -> Vec<Result<Vec<String>, Error>>
+        where C: Connector
+    {
+        let connection = connector.connection("shop");
+        vec![
+            connection.execute(GetNames::new("customers")).wait(),
+            connection.execute(GetNames { filter: Some("b"), ..GetNames::new("customers") }).wait(),
+            connection.execute(GetNames::new("orders")).wait(),
+        ]
+    }
+
+    #[test]
+    fn record_requests_and_responses() {
+        let path = fixture_path("record");
+        let connector = RecordingConnector::new(mock_server(), path.clone());
+
+        let results = run_requests(&connector);
+
+        assert_eq!(Ok(vec!["anna".to_owned(), "bert".to_owned()]), results[0]);
+        assert_eq!(Ok(vec!["bert".to_owned()]), results[1]);
+        assert_eq!(Err(Error::Method(api::Error::new(404, ErrorCode::ArangoCollectionNotFound,
+            "collection or view not found"))), results[2]);
+        let interactions = connector.interactions();
+        assert_eq!(3, interactions.len());
+        assert_eq!("GET", interactions[1].request().method());
+        assert_eq!("/_db/shop/_api/names/customers?filter=b", interactions[1].request().uri());
+        let mut headers = BTreeMap::new();
+        headers.insert("Content-Type".to_owned(), "application/json".to_owned());
+        assert_eq!(&RecordedResponse::Payload {
+            status: 200,
+            headers,
+            payload: json!({ "error": false, "code": 200, "result": ["bert"] }),
+        }, interactions[1].response());
+        drop(connector);
+        assert_eq!(interactions, read_fixture(&path).unwrap());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn replay_recorded_fixture_file() {
+        let path = fixture_path("replay");
+        let recorder = RecordingConnector::new(mock_server(), path.clone());
+        let recorded = run_requests(&recorder);
+        recorder.save().unwrap();
+
+        let connector = ReplayConnector::from_file(&path).unwrap();
+        let replayed = run_requests(&connector);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(recorded, replayed);
+        assert!(connector.pending_requests().is_empty());
+    }
+
+    #[test]
+    fn replay_fails_on_unrecorded_request() {
+        let connector = ReplayConnector::new(vec![
+            Interaction::new(
+                RecordedRequest {
+                    method: "GET".into(),
+                    uri: "/_db/shop/_api/names/customers".into(),
+                    headers: BTreeMap::new(),
+                    body: None,
+                },
+                RecordedResponse::Payload {
+                    status: 200,
+                    headers: BTreeMap::new(),
+                    payload: json!({ "result": [] }),
+                },
+            ),
+        ]);
+        let connection = connector.connection("shop");
+
+        let unrecorded = connection.execute(GetNames::new("orders")).wait();
+        let recorded = connection.execute(GetNames::new("customers")).wait();
+        let repeated = connection.execute(GetNames::new("customers")).wait();
+
+        assert_eq!(Err(Error::Communication(
+            "unrecorded request: GET /_db/shop/_api/names/orders".into())), unrecorded);
+        assert_eq!(Ok(vec![]), recorded);
+        assert!(repeated.is_err());
+    }
+
+    #[test]
+    fn record_and_replay_failures() {
+        let server = MockConnector::new();
+        server.expect(Expectation::new(Operation::Read, "/_api/names/customers")
+            .fails_with(Error::Timeout("no response within 30s".into())));
+        let recorder = RecordingConnector::new(server, fixture_path("failure"));
+        let recorded = recorder.system_connection().execute(GetNames::new("customers")).wait();
+        let path = fixture_path("failure");
+        recorder.save().unwrap();
+
+        let connector = ReplayConnector::from_file(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        let replayed = connector.system_connection().execute(GetNames::new("customers")).wait();
+
+        assert_eq!(Err(Error::Timeout("no response within 30s".into())), recorded);
+        assert_eq!(recorded, replayed);
+    }
+
+    #[test]
+    fn record_status_code_of_response() {
+        let server = MockConnector::new();
+        server.expect(Expectation::new(Operation::Read, "/_api/names/customers")
+            .returns_with_status(203, json!({ "result": ["anna"] })));
+        let recorder = RecordingConnector::new(server, fixture_path("status"));
+
+        let result = recorder.system_connection().execute(GetNames::new("customers")).wait();
+        let interactions = recorder.interactions();
+        recorder.save().unwrap();
+        fs::remove_file(fixture_path("status")).unwrap();
+
+        assert_eq!(Ok(vec!["anna".to_owned()]), result);
+        match *interactions[0].response() {
+            RecordedResponse::Payload { status, .. } => assert_eq!(203, status),
+            ref response => panic!("RecordedResponse::Payload expected but got {:?}", response),
+        }
+    }
+
+    #[test]
+    fn authorization_header_is_redacted() {
+        let mut headers = Headers::new();
+        headers.set_raw("Authorization", "Basic cm9vdDpzM2NyM3Q=");
+        headers.set_raw("User-Agent", "rincon");
+
+        let request = RecordedRequest::from_http(&hyper::Method::Get,
+            "/_api/version".to_owned(), &headers, None);
+
+        assert_eq!(Some(&"<redacted>".to_owned()), request.headers().get("Authorization"));
+        assert_eq!(Some(&"rincon".to_owned()), request.headers().get("User-Agent"));
+    }
+}
@@ -0,0 +1,324 @@
+
+use std::cell::RefCell;
+use std::fmt::{self, Debug};
+use std::rc::{Rc, Weak};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use base64;
+use futures::{future, Future};
+use futures::future::Shared;
+use serde_json;
+
+use rincon_core::api::auth::{Credentials, Jwt};
+use rincon_core::api::connector::Error;
+use rincon_core::api::method::{Method, Operation, Parameters, Prepare, RpcReturnType};
+use rincon_core::arango::protocol::{FIELD_CODE, PATH_OPEN_AUTH};
+
+/// The time before the expiration of a token when the token is refreshed.
+pub const TOKEN_REFRESH_MARGIN: u64 = 60;
+
+pub(crate) type FutureToken = Box<dyn Future<Item=Jwt, Error=Error>>;
+
+/// A JSON Web Token (JWT) together with the time it expires.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct AuthToken {
+    jwt: Jwt,
+    expires_at: Option<SystemTime>,
+}
+
+impl AuthToken {
+    pub(crate) fn new(jwt: Jwt) -> Self {
+        let expires_at = decode_expiration(&jwt);
+        AuthToken {
+            jwt,
+            expires_at,
+        }
+    }
+
+    pub(crate) fn jwt(&self) -> &Jwt {
+        &self.jwt
+    }
+
+    pub(crate) fn expires_at(&self) -> Option<&SystemTime> {
+        self.expires_at.as_ref()
+    }
+
+    /// Returns whether the token is still valid at the given time, taking
+    /// the refresh margin into account.
+    fn is_valid_at(&self, time: SystemTime) -> bool {
+        self.expires_at.is_none_or(|expires_at|
+            time + Duration::from_secs(TOKEN_REFRESH_MARGIN) < expires_at)
+    }
+}
+
+/// Decodes the `exp` claim of the given JWT.
+///
+/// Returns `None` if the token does not contain an `exp` claim or can not
+/// be decoded.
+fn decode_expiration(jwt: &str) -> Option<SystemTime> {
+    let payload = jwt.split('.').nth(1)?;
+    let decoded = base64::decode_config(payload, base64::URL_SAFE_NO_PAD).ok()?;
+    let claims: serde_json::Value = serde_json::from_slice(&decoded).ok()?;
+    let exp = claims.get("exp")?.as_u64()?;
+    Some(UNIX_EPOCH + Duration::from_secs(exp))
+}
+
+fn new_token(jwt: Jwt) -> AuthToken {
+    let token = AuthToken::new(jwt);
+    debug!("Accepted token that expires at {:?}", token.expires_at());
+    token
+}
+
+/// Holds the token used to authenticate requests and shares a pending login
+/// between all requests that are waiting for a token.
+#[derive(Debug, Default)]
+pub(crate) struct TokenStore {
+    state: RefCell<TokenState>,
+}
+
+#[derive(Default)]
+struct TokenState {
+    token: Option<AuthToken>,
+    pending: Option<Shared<FutureToken>>,
+}
+
+impl Debug for TokenState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TokenState")
+            .field("token", &self.token)
+            .field("pending", &self.pending.is_some())
+            .finish()
+    }
+}
+
+impl TokenStore {
+    pub(crate) fn new() -> Self {
+        TokenStore::default()
+    }
+
+    /// Returns the current token if it is not about to expire.
+    pub(crate) fn current(&self) -> Option<Jwt> {
+        self.state.borrow().token.as_ref()
+            .filter(|token| token.is_valid_at(SystemTime::now()))
+            .map(|token| token.jwt().to_owned())
+    }
+
+    pub(crate) fn accept(&self, jwt: Jwt) {
+        self.state.borrow_mut().token = Some(new_token(jwt));
+    }
+
+    pub(crate) fn clear(&self) {
+        self.state.borrow_mut().token = None;
+    }
+
+    /// Discards the current token if it is the given one.
+    ///
+    /// A token that has been refreshed in the meantime is kept.
+    pub(crate) fn invalidate(&self, jwt: &str) {
+        let mut state = self.state.borrow_mut();
+        if state.token.as_ref().is_some_and(|token| token.jwt() == jwt) {
+            state.token = None;
+        }
+    }
+
+    /// Returns a valid token.
+    ///
+    /// If there is no valid token the given `login` function is called to
+    /// obtain a new one. While the login is pending all further requests for
+    /// a token wait for the same login.
+    pub(crate) fn token<F>(store: &Rc<TokenStore>, login: F) -> FutureToken
+        where F: FnOnce() -> FutureToken
+    {
+        if let Some(jwt) = store.current() {
+            return Box::new(future::ok(jwt));
+        }
+        let pending = store.state.borrow().pending.clone();
+        let pending = match pending {
+            Some(pending) => pending,
+            None => {
+                let weak_store: Weak<TokenStore> = Rc::downgrade(store);
+                let login: FutureToken = Box::new(login().then(move |result| {
+                    if let Some(store) = weak_store.upgrade() {
+                        let mut state = store.state.borrow_mut();
+                        state.pending = None;
+                        if let Ok(ref jwt) = result {
+                            state.token = Some(new_token(jwt.to_owned()));
+                        }
+                    }
+                    result
+                }));
+                let pending = login.shared();
+                store.state.borrow_mut().pending = Some(pending.clone());
+                pending
+            },
+        };
+        Box::new(pending
+            .map(|jwt| (*jwt).clone())
+            .map_err(|error| (*error).clone()))
+    }
+}
+
+/// Logs in to the server to obtain a JWT.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Login {
+    request: LoginRequest,
+}
+
+impl Login {
+    pub(crate) fn new(credentials: &Credentials) -> Self {
+        Login {
+            request: LoginRequest {
+                username: credentials.username().to_owned(),
+                password: credentials.password().to_owned(),
+            },
+        }
+    }
+}
+
+impl Method for Login {
+    type Result = LoginResponse;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for Login {
+    type Content = LoginRequest;
+
+    fn operation(&self) -> Operation {
+        Operation::Create
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_OPEN_AUTH)
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        Some(&self.request)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub(crate) struct LoginRequest {
+    username: String,
+    password: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub(crate) struct LoginResponse {
+    jwt: Jwt,
+}
+
+impl LoginResponse {
+    pub(crate) fn jwt(&self) -> &Jwt {
+        &self.jwt
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::sync::Arc;
+
+    use futures::{Async, Future};
+    use futures::executor::{self, Notify};
+    use futures::sync::oneshot;
+    use super::*;
+
+    struct NoopNotify;
+
+    impl Notify for NoopNotify {
+        fn notify(&self, _id: usize) {}
+    }
+
+    fn jwt_with_claims(claims: &str) -> Jwt {
+        format!("{}.{}.signature",
+            base64::encode_config(r#"{"alg":"HS256","typ":"JWT"}"#, base64::URL_SAFE_NO_PAD),
+            base64::encode_config(claims, base64::URL_SAFE_NO_PAD))
+    }
+
+    fn seconds_from_now(seconds: u64) -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() + seconds
+    }
+
+    #[test]
+    fn auth_token_decodes_expiration() {
+        let token = AuthToken::new(jwt_with_claims(r#"{"exp":1520000000,"iss":"arangodb"}"#));
+
+        assert_eq!(Some(&(UNIX_EPOCH + Duration::from_secs(1520000000))), token.expires_at());
+    }
+
+    #[test]
+    fn auth_token_without_expiration_is_always_valid() {
+        let token = AuthToken::new(jwt_with_claims(r#"{"iss":"arangodb"}"#));
+
+        assert_eq!(None, token.expires_at());
+        assert!(token.is_valid_at(SystemTime::now()));
+    }
+
+    #[test]
+    fn auth_token_that_can_not_be_decoded_is_always_valid() {
+        let token = AuthToken::new("not a jwt".to_owned());
+
+        assert_eq!(None, token.expires_at());
+    }
+
+    #[test]
+    fn auth_token_is_invalid_shortly_before_expiration() {
+        let expires_soon = AuthToken::new(jwt_with_claims(
+            &format!(r#"{{"exp":{}}}"#, seconds_from_now(TOKEN_REFRESH_MARGIN / 2))));
+        let expires_later = AuthToken::new(jwt_with_claims(
+            &format!(r#"{{"exp":{}}}"#, seconds_from_now(3600))));
+
+        assert!(!expires_soon.is_valid_at(SystemTime::now()));
+        assert!(expires_later.is_valid_at(SystemTime::now()));
+    }
+
+    #[test]
+    fn token_store_invalidates_only_the_given_token() {
+        let store = TokenStore::new();
+        store.accept("token1".to_owned());
+
+        store.invalidate("token0");
+        assert_eq!(Some("token1".to_owned()), store.current());
+
+        store.invalidate("token1");
+        assert_eq!(None, store.current());
+    }
+
+    #[test]
+    fn token_store_shares_a_pending_login() {
+        let store = Rc::new(TokenStore::new());
+        let logins = Rc::new(Cell::new(0));
+        let (sender, receiver) = oneshot::channel::<Jwt>();
+        let receiver = Rc::new(RefCell::new(Some(receiver)));
+
+        let login = || -> FutureToken {
+            logins.set(logins.get() + 1);
+            let receiver = receiver.borrow_mut().take().unwrap();
+            Box::new(receiver.map_err(|_| Error::Communication("canceled".into())))
+        };
+        let mut first = executor::spawn(TokenStore::token(&store, login));
+        let mut second = executor::spawn(TokenStore::token(&store, login));
+        let notify = Arc::new(NoopNotify);
+
+        assert_eq!(Ok(Async::NotReady), first.poll_future_notify(&notify, 0));
+        assert_eq!(Ok(Async::NotReady), second.poll_future_notify(&notify, 0));
+
+        sender.send("token1".to_owned()).unwrap();
+
+        assert_eq!(Ok(Async::Ready("token1".to_owned())), first.poll_future_notify(&notify, 0));
+        assert_eq!(Ok(Async::Ready("token1".to_owned())), second.poll_future_notify(&notify, 0));
+        assert_eq!(1, logins.get());
+        assert_eq!(Some("token1".to_owned()), store.current());
+    }
+}
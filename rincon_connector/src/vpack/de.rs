@@ -0,0 +1,411 @@
+
+use std::str;
+
+use serde::de::{self, Deserialize, DeserializeSeed, IntoDeserializer, Visitor};
+
+use super::{Error, Result};
+
+/// Deserializes a value of type `T` from the given VelocyPack bytes.
+pub fn from_slice<'de, T>(data: &'de [u8]) -> Result<T>
+    where T: Deserialize<'de>
+{
+    T::deserialize(Deserializer::from_slice(data)?)
+}
+
+/// Deserializes the attribute with the given name of the VelocyPack object
+/// in the given bytes.
+///
+/// Returns `None` if the bytes do not contain an object or the object does
+/// not have an attribute with the given name.
+pub fn from_slice_field<'de, T>(data: &'de [u8], field: &str) -> Option<Result<T>>
+    where T: Deserialize<'de>
+{
+    let deserializer = match Deserializer::from_slice(data) {
+        Ok(deserializer) => deserializer,
+        Err(error) => return Some(Err(error)),
+    };
+    match deserializer.field(field) {
+        Ok(Some(value)) => Some(T::deserialize(value)),
+        Ok(None) => None,
+        Err(error) => Some(Err(error)),
+    }
+}
+
+/// A deserializer that reads one VelocyPack value.
+#[derive(Clone, Copy, Debug)]
+pub struct Deserializer<'de> {
+    data: &'de [u8],
+}
+
+impl<'de> Deserializer<'de> {
+    /// Constructs a new `Deserializer` for the first value in the given
+    /// bytes.
+    pub fn from_slice(data: &'de [u8]) -> Result<Self> {
+        let length = value_len(data)?;
+        Ok(Deserializer {
+            data: &data[..length],
+        })
+    }
+
+    fn head(&self) -> u8 {
+        self.data[0]
+    }
+
+    /// Returns the value of the attribute with the given name if this value
+    /// is an object.
+    fn field(&self, name: &str) -> Result<Option<Deserializer<'de>>> {
+        if !is_object(self.head()) {
+            return Ok(None);
+        }
+        let mut items = Items::new(self.data)?;
+        while let Some(key) = items.next_value()? {
+            let value = items.next_value()?
+                .ok_or_else(|| Error::new("missing value in object"))?;
+            if key.key()? == name {
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+
+    fn key(&self) -> Result<&'de str> {
+        match self.head() {
+            0x31 => Ok("_key"),
+            0x32 => Ok("_rev"),
+            0x33 => Ok("_id"),
+            0x34 => Ok("_from"),
+            0x35 => Ok("_to"),
+            0x40..=0xbf => self.str(),
+            head => Err(Error::new(format!("invalid object key type 0x{:02x}", head))),
+        }
+    }
+
+    fn str(&self) -> Result<&'de str> {
+        let bytes = match self.head() {
+            0xbf => &self.data[9..],
+            _ => &self.data[1..],
+        };
+        str::from_utf8(bytes).map_err(|error| Error::new(error.to_string()))
+    }
+
+    fn bytes(&self) -> &'de [u8] {
+        let size = (self.head() - 0xbf) as usize;
+        &self.data[1 + size..]
+    }
+}
+
+fn is_object(head: u8) -> bool {
+    matches!(head, 0x0a..=0x12 | 0x14)
+}
+
+fn read_uint(data: &[u8], size: usize) -> Result<u64> {
+    if data.len() < size {
+        return Err(Error::new("unexpected end of data"));
+    }
+    Ok(data[..size].iter().rev().fold(0, |value, byte| (value << 8) | u64::from(*byte)))
+}
+
+fn read_varint(data: &[u8]) -> Result<(u64, usize)> {
+    let mut value = 0;
+    for (index, byte) in data.iter().enumerate().take(10) {
+        value |= u64::from(byte & 0x7f) << (index * 7);
+        if byte & 0x80 == 0 {
+            return Ok((value, index + 1));
+        }
+    }
+    Err(Error::new("invalid variable length integer"))
+}
+
+fn read_reversed_varint(data: &[u8]) -> Result<u64> {
+    let mut value = 0;
+    for (index, byte) in data.iter().rev().enumerate().take(10) {
+        value |= u64::from(byte & 0x7f) << (index * 7);
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(Error::new("invalid variable length integer"))
+}
+
+/// Returns the byte size of the width fields of an array or object with
+/// the given head.
+fn width(head: u8, first: u8) -> usize {
+    1 << ((head - first) % 4)
+}
+
+/// Returns the number of bytes of the first VelocyPack value in the given
+/// bytes.
+pub fn value_len(data: &[u8]) -> Result<usize> {
+    let head = *data.first().ok_or_else(|| Error::new("unexpected end of data"))?;
+    let length = match head {
+        0x01 | 0x0a | 0x18..=0x1a | 0x1e | 0x1f | 0x30..=0x3f => 1,
+        0x02..=0x09 => checked_len(0, read_uint(&data[1..], width(head, 0x02))?)?,
+        0x0b..=0x12 => checked_len(0, read_uint(&data[1..], width(head, 0x0b))?)?,
+        0x13 | 0x14 => checked_len(0, read_varint(&data[1..])?.0)?,
+        0x1b | 0x1c => 9,
+        0x20..=0x27 => 1 + (head - 0x1f) as usize,
+        0x28..=0x2f => 1 + (head - 0x27) as usize,
+        0x40..=0xbe => 1 + (head - 0x40) as usize,
+        0xbf => checked_len(9, read_uint(&data[1..], 8)?)?,
+        0xc0..=0xc7 => {
+            let size = (head - 0xbf) as usize;
+            checked_len(1 + size, read_uint(&data[1..], size)?)?
+        },
+        _ => return Err(Error::new(format!("unsupported VelocyPack type 0x{:02x}", head))),
+    };
+    if length == 0 || length > data.len() {
+        return Err(Error::new("unexpected end of data"));
+    }
+    Ok(length)
+}
+
+/// Adds a length read from the data to the given header size without
+/// overflowing.
+fn checked_len(header: usize, length: u64) -> Result<usize> {
+    if length > usize::MAX as u64 {
+        return Err(Error::new("length out of range"));
+    }
+    (length as usize).checked_add(header)
+        .ok_or_else(|| Error::new("length out of range"))
+}
+
+/// Iterates over the values of an array or the keys and values of an
+/// object in the order they are stored.
+#[derive(Debug)]
+struct Items<'de> {
+    data: &'de [u8],
+    position: usize,
+    end: usize,
+    remaining: Option<usize>,
+}
+
+impl<'de> Items<'de> {
+    fn new(data: &'de [u8]) -> Result<Self> {
+        let head = data[0];
+        let length = data.len();
+        let (start, end, count) = match head {
+            0x01 | 0x0a => (1, 1, Some(0)),
+            0x02..=0x05 => {
+                let start = 1 + width(head, 0x02);
+                if length < start {
+                    return Err(Error::new("unexpected end of data"));
+                }
+                (start, length, None)
+            },
+            0x06..=0x09 | 0x0b..=0x12 => {
+                let width = width(head, if head <= 0x09 { 0x06 } else { 0x0b });
+                //the byte length and the number of items are stored with the same width
+                if length < 1 + 2 * width {
+                    return Err(Error::new("unexpected end of data"));
+                }
+                let (start, count) = if width == 8 {
+                    (1 + width, read_uint(&data[length - 8..], 8)? as usize)
+                } else {
+                    (1 + 2 * width, read_uint(&data[1 + width..], width)? as usize)
+                };
+                (start, length, Some(count))
+            },
+            0x13 | 0x14 => {
+                let (_, size) = read_varint(&data[1..])?;
+                if length < 1 + size {
+                    return Err(Error::new("unexpected end of data"));
+                }
+                let count = read_reversed_varint(data)? as usize;
+                (1 + size, length, Some(count))
+            },
+            _ => return Err(Error::new("expected an array or an object")),
+        };
+        let mut position = start;
+        while position < end && data[position] == 0 {
+            position += 1;
+        }
+        let remaining = match count {
+            Some(count) if is_object(head) => Some(count.checked_mul(2)
+                .ok_or_else(|| Error::new("number of items out of range"))?),
+            count => count,
+        };
+        Ok(Items {
+            data,
+            position,
+            end,
+            remaining,
+        })
+    }
+
+    fn len(&self) -> Option<usize> {
+        self.remaining
+    }
+
+    fn next_value(&mut self) -> Result<Option<Deserializer<'de>>> {
+        match self.remaining {
+            Some(0) => return Ok(None),
+            None if self.position >= self.end => return Ok(None),
+            _ => {},
+        }
+        let value = Deserializer::from_slice(&self.data[self.position..self.end])?;
+        self.position += value.data.len();
+        self.remaining = self.remaining.map(|remaining| remaining - 1);
+        Ok(Some(value))
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+        where V: Visitor<'de>
+    {
+        let head = self.head();
+        match head {
+            0x01..=0x09 | 0x13 => visitor.visit_seq(Items::new(self.data)?),
+            0x0a..=0x12 | 0x14 => visitor.visit_map(Items::new(self.data)?),
+            0x18 => visitor.visit_unit(),
+            0x19 => visitor.visit_bool(false),
+            0x1a => visitor.visit_bool(true),
+            0x1b => visitor.visit_f64(f64::from_bits(read_uint(&self.data[1..], 8)?)),
+            0x1c => visitor.visit_i64(read_uint(&self.data[1..], 8)? as i64),
+            0x20..=0x27 => {
+                let size = (head - 0x1f) as usize;
+                let shift = 64 - size * 8;
+                let value = (read_uint(&self.data[1..], size)? << shift) as i64 >> shift;
+                visitor.visit_i64(value)
+            },
+            0x28..=0x2f => visitor.visit_u64(read_uint(&self.data[1..], (head - 0x27) as usize)?),
+            0x30..=0x39 => visitor.visit_u64(u64::from(head - 0x30)),
+            0x3a..=0x3f => visitor.visit_i64(i64::from(head) - 0x40),
+            0x40..=0xbf => visitor.visit_borrowed_str(self.str()?),
+            0xc0..=0xc7 => visitor.visit_borrowed_bytes(self.bytes()),
+            _ => Err(Error::new(format!("unsupported VelocyPack type 0x{:02x}", head))),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+        where V: Visitor<'de>
+    {
+        match self.head() {
+            0x18 => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+        where V: Visitor<'de>
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+        where V: Visitor<'de>
+    {
+        match self.head() {
+            0x40..=0xbf => visitor.visit_enum(self.str()?.into_deserializer()),
+            head if is_object(head) => {
+                let mut items = Items::new(self.data)?;
+                if items.len() != Some(2) {
+                    return Err(Error::new("expected an object with a single attribute"));
+                }
+                let variant = items.next_value()?.ok_or_else(|| Error::new("missing variant"))?;
+                let value = items.next_value()?.ok_or_else(|| Error::new("missing value"))?;
+                visitor.visit_enum(Enum { variant, value })
+            },
+            _ => Err(Error::new("expected a string or an object")),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for Items<'de> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+        where T: DeserializeSeed<'de>
+    {
+        match self.next_value()? {
+            Some(value) => seed.deserialize(value).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.remaining
+    }
+}
+
+impl<'de> de::MapAccess<'de> for Items<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+        where K: DeserializeSeed<'de>
+    {
+        match self.next_value()? {
+            Some(key) => seed.deserialize(key.key()?.into_deserializer()).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+        where V: DeserializeSeed<'de>
+    {
+        match self.next_value()? {
+            Some(value) => seed.deserialize(value),
+            None => Err(Error::new("missing value in object")),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.remaining.map(|remaining| remaining / 2)
+    }
+}
+
+struct Enum<'de> {
+    variant: Deserializer<'de>,
+    value: Deserializer<'de>,
+}
+
+impl<'de> de::EnumAccess<'de> for Enum<'de> {
+    type Error = Error;
+    type Variant = Deserializer<'de>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant)>
+        where V: DeserializeSeed<'de>
+    {
+        let variant = seed.deserialize(self.variant.key()?.into_deserializer())?;
+        Ok((variant, self.value))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for Deserializer<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        Deserialize::deserialize(self)
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value>
+        where T: DeserializeSeed<'de>
+    {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+        where V: Visitor<'de>
+    {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value>
+        where V: Visitor<'de>
+    {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
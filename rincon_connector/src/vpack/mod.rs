@@ -0,0 +1,71 @@
+//! Serialization and deserialization of VelocyPack (VPack), the binary
+//! format natively used by ArangoDB.
+//!
+//! The serializer writes arrays and objects in the compact format. The
+//! deserializer reads all array and object formats, integers, doubles,
+//! UTC dates (as milliseconds since the epoch), strings and binary blobs.
+//! BCD numbers, tagged values and custom types are not supported.
+
+mod de;
+mod ser;
+#[cfg(test)] mod tests;
+
+pub use self::de::{from_slice, from_slice_field, value_len, Deserializer};
+pub use self::ser::{to_vec, Serializer};
+
+use std::error;
+use std::fmt::{self, Display};
+
+use serde::{de as serde_de, ser as serde_ser};
+
+/// The MIME type of VelocyPack content.
+pub const CONTENT_TYPE_VPACK: &str = "application/x-velocypack";
+
+/// The result of a VelocyPack serialization or deserialization.
+pub type Result<T> = ::std::result::Result<T, Error>;
+
+/// An error that occurred during VelocyPack serialization or
+/// deserialization.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error {
+    message: String,
+}
+
+impl Error {
+    pub(crate) fn new<M>(message: M) -> Self
+        where M: Into<String>
+    {
+        Error {
+            message: message.into(),
+        }
+    }
+
+    /// Returns the message of this error.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl error::Error for Error {
+    fn description(&self) -> &str {
+        &self.message
+    }
+}
+
+impl serde_ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::new(msg.to_string())
+    }
+}
+
+impl serde_de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::new(msg.to_string())
+    }
+}
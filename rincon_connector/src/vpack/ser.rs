@@ -0,0 +1,642 @@
+
+use serde::ser::{self, Serialize};
+
+use super::{Error, Result};
+
+/// Serializes the given value into a VelocyPack byte vector.
+pub fn to_vec<T>(value: &T) -> Result<Vec<u8>>
+    where T: ?Sized + Serialize
+{
+    let mut serializer = Serializer::new();
+    value.serialize(&mut serializer)?;
+    Ok(serializer.into_inner())
+}
+
+/// A serializer that writes VelocyPack.
+#[derive(Debug, Default)]
+pub struct Serializer {
+    output: Vec<u8>,
+}
+
+impl Serializer {
+    /// Constructs a new `Serializer` with an empty output buffer.
+    pub fn new() -> Self {
+        Serializer {
+            output: Vec::new(),
+        }
+    }
+
+    /// Returns the serialized VelocyPack bytes.
+    pub fn into_inner(self) -> Vec<u8> {
+        self.output
+    }
+
+    fn write_null(&mut self) {
+        self.output.push(0x18);
+    }
+
+    fn write_bool(&mut self, value: bool) {
+        self.output.push(if value { 0x1a } else { 0x19 });
+    }
+
+    fn write_i64(&mut self, value: i64) {
+        if (0..=9).contains(&value) {
+            self.output.push(0x30 + value as u8);
+        } else if (-6..0).contains(&value) {
+            self.output.push((0x40 + value) as u8);
+        } else {
+            let mut size = 8;
+            while size > 1 {
+                let bits = (size - 1) * 8 - 1;
+                if (-(1 << bits)..(1 << bits)).contains(&value) {
+                    size -= 1;
+                } else {
+                    break;
+                }
+            }
+            self.output.push(0x1f + size as u8);
+            write_uint(&mut self.output, value as u64, size);
+        }
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        if value <= 9 {
+            self.output.push(0x30 + value as u8);
+        } else {
+            let size = uint_size(value);
+            self.output.push(0x27 + size as u8);
+            write_uint(&mut self.output, value, size);
+        }
+    }
+
+    fn write_f64(&mut self, value: f64) {
+        self.output.push(0x1b);
+        write_uint(&mut self.output, value.to_bits(), 8);
+    }
+
+    fn write_str(&mut self, value: &str) {
+        let length = value.len();
+        if length <= 126 {
+            self.output.push(0x40 + length as u8);
+        } else {
+            self.output.push(0xbf);
+            write_uint(&mut self.output, length as u64, 8);
+        }
+        self.output.extend_from_slice(value.as_bytes());
+    }
+
+    fn write_bytes(&mut self, value: &[u8]) {
+        let size = uint_size(value.len() as u64);
+        self.output.push(0xbf + size as u8);
+        write_uint(&mut self.output, value.len() as u64, size);
+        self.output.extend_from_slice(value);
+    }
+
+    /// Writes a compact array or object with the given content.
+    fn write_compact(&mut self, is_object: bool, content: &[u8], count: usize) {
+        if count == 0 {
+            self.output.push(if is_object { 0x0a } else { 0x01 });
+            return;
+        }
+        let count_size = varint_size(count as u64);
+        let mut length_size = 1;
+        let mut byte_length = 1 + length_size + content.len() + count_size;
+        while varint_size(byte_length as u64) != length_size {
+            length_size = varint_size(byte_length as u64);
+            byte_length = 1 + length_size + content.len() + count_size;
+        }
+        self.output.push(if is_object { 0x14 } else { 0x13 });
+        write_varint(&mut self.output, byte_length as u64);
+        self.output.extend_from_slice(content);
+        write_reversed_varint(&mut self.output, count as u64);
+    }
+
+    fn compound(&mut self, is_object: bool, variant: Option<&'static str>) -> Compound<'_> {
+        Compound {
+            parent: self,
+            content: Serializer::new(),
+            count: 0,
+            is_object,
+            variant,
+        }
+    }
+}
+
+fn uint_size(value: u64) -> usize {
+    let mut size = 1;
+    while size < 8 && value >> (size * 8) != 0 {
+        size += 1;
+    }
+    size
+}
+
+fn write_uint(output: &mut Vec<u8>, value: u64, size: usize) {
+    for index in 0..size {
+        output.push((value >> (index * 8)) as u8);
+    }
+}
+
+fn varint_size(mut value: u64) -> usize {
+    let mut size = 1;
+    while value >= 0x80 {
+        value >>= 7;
+        size += 1;
+    }
+    size
+}
+
+fn write_varint(output: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        output.push((value & 0x7f) as u8 | 0x80);
+        value >>= 7;
+    }
+    output.push(value as u8);
+}
+
+fn write_reversed_varint(output: &mut Vec<u8>, value: u64) {
+    let start = output.len();
+    write_varint(output, value);
+    output[start..].reverse();
+}
+
+/// Serializes the elements of an array or the entries of an object.
+#[derive(Debug)]
+pub struct Compound<'a> {
+    parent: &'a mut Serializer,
+    content: Serializer,
+    count: usize,
+    is_object: bool,
+    variant: Option<&'static str>,
+}
+
+impl<'a> Compound<'a> {
+    fn element<T>(&mut self, value: &T) -> Result<()>
+        where T: ?Sized + Serialize
+    {
+        self.count += 1;
+        value.serialize(&mut self.content)
+    }
+
+    fn entry<T>(&mut self, key: &str, value: &T) -> Result<()>
+        where T: ?Sized + Serialize
+    {
+        self.count += 1;
+        self.content.write_str(key);
+        value.serialize(&mut self.content)
+    }
+
+    fn finish(self) -> Result<()> {
+        match self.variant {
+            Some(variant) => {
+                let mut payload = Serializer::new();
+                payload.write_compact(self.is_object, &self.content.output, self.count);
+                let mut wrapped = Serializer::new();
+                wrapped.write_str(variant);
+                wrapped.output.extend_from_slice(&payload.output);
+                self.parent.write_compact(true, &wrapped.output, 1);
+            },
+            None =>
+                self.parent.write_compact(self.is_object, &self.content.output, self.count),
+        }
+        Ok(())
+    }
+}
+
+impl<'a> ser::Serializer for &'a mut Serializer {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    fn serialize_bool(self, value: bool) -> Result<()> {
+        self.write_bool(value);
+        Ok(())
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<()> {
+        self.serialize_i64(i64::from(value))
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<()> {
+        self.serialize_i64(i64::from(value))
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<()> {
+        self.serialize_i64(i64::from(value))
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<()> {
+        self.write_i64(value);
+        Ok(())
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<()> {
+        self.serialize_u64(u64::from(value))
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<()> {
+        self.serialize_u64(u64::from(value))
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<()> {
+        self.serialize_u64(u64::from(value))
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<()> {
+        self.write_u64(value);
+        Ok(())
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<()> {
+        self.serialize_f64(f64::from(value))
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<()> {
+        self.write_f64(value);
+        Ok(())
+    }
+
+    fn serialize_char(self, value: char) -> Result<()> {
+        let mut buffer = [0; 4];
+        self.write_str(value.encode_utf8(&mut buffer));
+        Ok(())
+    }
+
+    fn serialize_str(self, value: &str) -> Result<()> {
+        self.write_str(value);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<()> {
+        self.write_bytes(value);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        self.write_null();
+        Ok(())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<()>
+        where T: ?Sized + Serialize
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        self.write_null();
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _index: u32, variant: &'static str)
+        -> Result<()>
+    {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<()>
+        where T: ?Sized + Serialize
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<()>
+        where T: ?Sized + Serialize
+    {
+        let mut compound = self.compound(true, None);
+        compound.entry(variant, value)?;
+        compound.finish()
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Ok(self.compound(false, None))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
+        Ok(self.compound(false, None))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize)
+        -> Result<Self::SerializeTupleStruct>
+    {
+        Ok(self.compound(false, None))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Ok(self.compound(false, Some(variant)))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        Ok(self.compound(true, None))
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        Ok(self.compound(true, None))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Ok(self.compound(true, Some(variant)))
+    }
+}
+
+impl<'a> ser::SerializeSeq for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+        where T: ?Sized + Serialize
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeTuple for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+        where T: ?Sized + Serialize
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeTupleStruct for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+        where T: ?Sized + Serialize
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeTupleVariant for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+        where T: ?Sized + Serialize
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeMap for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<()>
+        where T: ?Sized + Serialize
+    {
+        self.count += 1;
+        key.serialize(MapKeySerializer { output: &mut self.content })
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<()>
+        where T: ?Sized + Serialize
+    {
+        value.serialize(&mut self.content)
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeStruct for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+        where T: ?Sized + Serialize
+    {
+        self.entry(key, value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeStructVariant for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+        where T: ?Sized + Serialize
+    {
+        self.entry(key, value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish()
+    }
+}
+
+/// Serializes the keys of a map, which must be strings or numbers.
+struct MapKeySerializer<'a> {
+    output: &'a mut Serializer,
+}
+
+fn key_must_be_a_string() -> Error {
+    Error::new("key must be a string")
+}
+
+impl<'a> ser::Serializer for MapKeySerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = ser::Impossible<(), Error>;
+    type SerializeTuple = ser::Impossible<(), Error>;
+    type SerializeTupleStruct = ser::Impossible<(), Error>;
+    type SerializeTupleVariant = ser::Impossible<(), Error>;
+    type SerializeMap = ser::Impossible<(), Error>;
+    type SerializeStruct = ser::Impossible<(), Error>;
+    type SerializeStructVariant = ser::Impossible<(), Error>;
+
+    fn serialize_bool(self, _value: bool) -> Result<()> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<()> {
+        self.serialize_str(&value.to_string())
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<()> {
+        self.serialize_str(&value.to_string())
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<()> {
+        self.serialize_str(&value.to_string())
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<()> {
+        self.serialize_str(&value.to_string())
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<()> {
+        self.serialize_str(&value.to_string())
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<()> {
+        self.serialize_str(&value.to_string())
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<()> {
+        self.serialize_str(&value.to_string())
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<()> {
+        self.serialize_str(&value.to_string())
+    }
+
+    fn serialize_f32(self, _value: f32) -> Result<()> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_f64(self, _value: f64) -> Result<()> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_char(self, value: char) -> Result<()> {
+        let mut buffer = [0; 4];
+        self.serialize_str(value.encode_utf8(&mut buffer))
+    }
+
+    fn serialize_str(self, value: &str) -> Result<()> {
+        self.output.write_str(value);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, _value: &[u8]) -> Result<()> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_some<T>(self, _value: &T) -> Result<()>
+        where T: ?Sized + Serialize
+    {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _index: u32, variant: &'static str)
+        -> Result<()>
+    {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<()>
+        where T: ?Sized + Serialize
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<()>
+        where T: ?Sized + Serialize
+    {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize)
+        -> Result<Self::SerializeTupleStruct>
+    {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Err(key_must_be_a_string())
+    }
+}
@@ -0,0 +1,213 @@
+
+use std::collections::BTreeMap;
+
+use serde_json::{self, Value};
+
+use super::*;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Customer {
+    #[serde(rename = "_key")]
+    key: String,
+    name: String,
+    age: u16,
+    balance: f64,
+    active: bool,
+    nickname: Option<String>,
+    tags: Vec<String>,
+    kind: Kind,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Kind {
+    Private,
+    Business { vat_id: String },
+    Partner(u32),
+}
+
+#[test]
+fn serialize_small_values() {
+    assert_eq!(vec![0x18], to_vec(&()).unwrap());
+    assert_eq!(vec![0x1a], to_vec(&true).unwrap());
+    assert_eq!(vec![0x19], to_vec(&false).unwrap());
+    assert_eq!(vec![0x35], to_vec(&5u8).unwrap());
+    assert_eq!(vec![0x3f], to_vec(&-1i32).unwrap());
+    assert_eq!(vec![0x3a], to_vec(&-6i32).unwrap());
+    assert_eq!(vec![0x28, 0x0a], to_vec(&10u32).unwrap());
+    assert_eq!(vec![0x20, 0xf9], to_vec(&-7i64).unwrap());
+    assert_eq!(vec![0x21, 0x00, 0x01], to_vec(&256i64).unwrap());
+    assert_eq!(vec![0x43, b'a', b'b', b'c'], to_vec("abc").unwrap());
+    assert_eq!(vec![0x01], to_vec(&Vec::<u8>::new()).unwrap());
+    assert_eq!(vec![0x0a], to_vec(&BTreeMap::<String, u8>::new()).unwrap());
+}
+
+#[test]
+fn serialize_compact_array() {
+    let bytes = to_vec(&vec![1, 2, 3]).unwrap();
+
+    assert_eq!(vec![0x13, 0x06, 0x31, 0x32, 0x33, 0x03], bytes);
+}
+
+#[test]
+fn deserialize_integers_of_all_sizes() {
+    assert_eq!(-7i64, from_slice::<i64>(&[0x20, 0xf9]).unwrap());
+    assert_eq!(-32768i64, from_slice::<i64>(&[0x21, 0x00, 0x80]).unwrap());
+    assert_eq!(65535u64, from_slice::<u64>(&[0x29, 0xff, 0xff]).unwrap());
+    assert_eq!(i64::MIN, from_slice::<i64>(&to_vec(&i64::MIN).unwrap()).unwrap());
+    assert_eq!(u64::MAX, from_slice::<u64>(&to_vec(&u64::MAX).unwrap()).unwrap());
+}
+
+#[test]
+fn deserialize_array_with_index_table() {
+    // [1, "a", [2]] with a 1 byte index table
+    let bytes = [0x06, 0x0c, 0x03, 0x31, 0x41, b'a', 0x02, 0x03, 0x32, 0x03, 0x04, 0x06];
+
+    let value: Value = from_slice(&bytes).unwrap();
+
+    assert_eq!(json!([1, "a", [2]]), value);
+}
+
+#[test]
+fn deserialize_array_without_index_table() {
+    // [1, 2, 3] with equally sized members
+    let bytes = [0x02, 0x05, 0x31, 0x32, 0x33];
+
+    let value: Vec<u8> = from_slice(&bytes).unwrap();
+
+    assert_eq!(vec![1, 2, 3], value);
+}
+
+#[test]
+fn deserialize_object_with_sorted_index_table() {
+    // {"a": 1, "b": 2} with a 1 byte index table
+    let bytes = [0x0b, 0x0b, 0x02, 0x41, b'a', 0x31, 0x41, b'b', 0x32, 0x03, 0x06];
+
+    let value: Value = from_slice(&bytes).unwrap();
+
+    assert_eq!(json!({ "a": 1, "b": 2 }), value);
+}
+
+#[test]
+fn deserialize_object_with_translated_attribute_names() {
+    // {"_key": "k1", "_id": "c/k1"} with the attribute names stored as integers
+    let bytes = [0x14, 0x0d, 0x31, 0x42, b'k', b'1', 0x33, 0x44, b'c', b'/', b'k', b'1', 0x02];
+
+    let value: Value = from_slice(&bytes).unwrap();
+
+    assert_eq!(json!({ "_key": "k1", "_id": "c/k1" }), value);
+}
+
+#[test]
+fn round_trip_struct_with_nested_values() {
+    let customers = vec![
+        Customer {
+            key: "1".to_owned(),
+            name: "Jane Doe".to_owned(),
+            age: 42,
+            balance: -1234.5,
+            active: true,
+            nickname: None,
+            tags: vec!["gold".to_owned(), "newsletter".to_owned()],
+            kind: Kind::Private,
+        },
+        Customer {
+            key: "2".to_owned(),
+            name: "x".repeat(200),
+            age: 7,
+            balance: 0.25,
+            active: false,
+            nickname: Some("Joe".to_owned()),
+            tags: vec![],
+            kind: Kind::Business { vat_id: "DE123".to_owned() },
+        },
+        Customer {
+            key: "3".to_owned(),
+            name: "Acme".to_owned(),
+            age: 300,
+            balance: 1e10,
+            active: true,
+            nickname: None,
+            tags: vec!["partner".to_owned()],
+            kind: Kind::Partner(70_000),
+        },
+    ];
+
+    let bytes = to_vec(&customers).unwrap();
+    let deserialized: Vec<Customer> = from_slice(&bytes).unwrap();
+
+    assert_eq!(customers, deserialized);
+}
+
+#[test]
+fn round_trip_json_value() {
+    let value = json!({
+        "result": [ { "_key": "1", "values": [1, -2, 3.5, null, true] } ],
+        "hasMore": false,
+        "count": 1,
+        "extra": { "stats": { "writesExecuted": 0, "executionTime": 0.0012 } },
+    });
+
+    let bytes = to_vec(&value).unwrap();
+    let deserialized: Value = from_slice(&bytes).unwrap();
+
+    assert_eq!(value, deserialized);
+    assert_eq!(serde_json::to_string(&value).unwrap(),
+        serde_json::to_string(&deserialized).unwrap());
+}
+
+#[test]
+fn deserialize_field_of_object() {
+    let bytes = to_vec(&json!({ "error": false, "result": { "name": "customers" } })).unwrap();
+
+    let result: Option<Result<BTreeMap<String, String>>> = from_slice_field(&bytes, "result");
+    let missing: Option<Result<Value>> = from_slice_field(&bytes, "missing");
+
+    let mut expected = BTreeMap::new();
+    expected.insert("name".to_owned(), "customers".to_owned());
+    assert_eq!(Some(Ok(expected)), result);
+    assert_eq!(None, missing);
+}
+
+#[test]
+fn deserialize_truncated_data_fails() {
+    let bytes = to_vec(&vec!["a", "b", "c"]).unwrap();
+
+    assert!(from_slice::<Vec<String>>(&bytes[..bytes.len() - 2]).is_err());
+}
+
+#[test]
+fn deserialize_array_shorter_than_its_header_fails() {
+    let bytes = [0x09, 0x03, 0, 0, 0, 0, 0, 0, 0];
+
+    assert_eq!(Err(Error::new("unexpected end of data")), from_slice::<Vec<u64>>(&bytes));
+}
+
+#[test]
+fn deserialize_array_with_length_shorter_than_its_header_fails() {
+    let bytes = [0x08, 0x02, 0, 0, 0];
+
+    assert_eq!(Err(Error::new("unexpected end of data")), from_slice::<Vec<u64>>(&bytes));
+}
+
+#[test]
+fn deserialize_long_string_with_overflowing_length_fails() {
+    let bytes = [0xbf, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0];
+
+    assert_eq!(Err(Error::new("length out of range")), from_slice::<String>(&bytes));
+}
+
+#[test]
+fn deserialize_binary_with_overflowing_length_fails() {
+    let bytes = [0xc7, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0];
+
+    assert_eq!(Err(Error::new("length out of range")), from_slice::<Vec<u8>>(&bytes));
+}
+
+#[test]
+fn deserialize_compact_object_with_overflowing_count_fails() {
+    let bytes = [0x14, 0x0c, 0x01, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff];
+
+    assert_eq!(Err(Error::new("number of items out of range")),
+        from_slice::<BTreeMap<String, u64>>(&bytes));
+}
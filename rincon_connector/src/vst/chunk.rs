@@ -0,0 +1,269 @@
+
+use std::collections::HashMap;
+
+use rincon_core::api::connector::Error;
+
+/// The preamble a client sends to the server after connecting to select
+/// VelocyStream in version 1.1.
+pub const VST_1_1: &[u8] = b"VST/1.1\r\n\r\n";
+
+/// The size of the header of a chunk in VelocyStream 1.1.
+pub const CHUNK_HEADER_SIZE: usize = 24;
+
+/// The maximum size of a chunk including its header that is sent by
+/// default.
+pub const DEFAULT_MAX_CHUNK_SIZE: usize = 30 * 1024;
+
+/// The maximum length of a message that is accepted from the server by
+/// default.
+pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 128 * 1024 * 1024;
+
+/// The header of a chunk.
+///
+/// In VelocyStream 1.1 every chunk carries the total length of the message
+/// it belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct ChunkHeader {
+    length: u32,
+    chunk_x: u32,
+    message_id: u64,
+    message_length: u64,
+}
+
+impl ChunkHeader {
+    pub(crate) fn decode(bytes: &[u8; CHUNK_HEADER_SIZE]) -> Result<Self, Error> {
+        let header = ChunkHeader {
+            length: read_u32(&bytes[0..4]),
+            chunk_x: read_u32(&bytes[4..8]),
+            message_id: read_u64(&bytes[8..16]),
+            message_length: read_u64(&bytes[16..24]),
+        };
+        if (header.length as usize) < CHUNK_HEADER_SIZE {
+            return Err(Error::Communication(
+                format!("invalid VelocyStream chunk length {}", header.length)));
+        }
+        Ok(header)
+    }
+
+    /// Returns the number of bytes of the chunk data following the header.
+    pub(crate) fn data_length(&self) -> usize {
+        self.length as usize - CHUNK_HEADER_SIZE
+    }
+
+    pub(crate) fn message_id(&self) -> u64 {
+        self.message_id
+    }
+
+    pub(crate) fn is_first(&self) -> bool {
+        self.chunk_x & 1 == 1
+    }
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    bytes.iter().rev().fold(0, |value, byte| (value << 8) | u32::from(*byte))
+}
+
+fn read_u64(bytes: &[u8]) -> u64 {
+    bytes.iter().rev().fold(0, |value, byte| (value << 8) | u64::from(*byte))
+}
+
+fn write_u32(output: &mut Vec<u8>, value: u32) {
+    for index in 0..4 {
+        output.push((value >> (index * 8)) as u8);
+    }
+}
+
+fn write_u64(output: &mut Vec<u8>, value: u64) {
+    for index in 0..8 {
+        output.push((value >> (index * 8)) as u8);
+    }
+}
+
+/// Splits the given message into chunks of at most the given size and
+/// returns the encoded chunks one after the other.
+pub(crate) fn encode_message(message_id: u64, message: &[u8], max_chunk_size: usize) -> Vec<u8> {
+    let max_data_length = max_chunk_size.max(CHUNK_HEADER_SIZE + 1) - CHUNK_HEADER_SIZE;
+    let number_of_chunks = message.len().div_ceil(max_data_length).max(1);
+    let mut output = Vec::with_capacity(message.len() + number_of_chunks * CHUNK_HEADER_SIZE);
+    let mut data = message.chunks(max_data_length);
+    for index in 0..number_of_chunks {
+        let chunk = data.next().unwrap_or(&[]);
+        let chunk_x = if index == 0 {
+            ((number_of_chunks as u32) << 1) | 1
+        } else {
+            (index as u32) << 1
+        };
+        write_u32(&mut output, (CHUNK_HEADER_SIZE + chunk.len()) as u32);
+        write_u32(&mut output, chunk_x);
+        write_u64(&mut output, message_id);
+        write_u64(&mut output, message.len() as u64);
+        output.extend_from_slice(chunk);
+    }
+    output
+}
+
+/// Collects the chunks of the messages received over one connection.
+///
+/// Chunks of different messages may be interleaved, but the chunks of one
+/// message are expected in order.
+#[derive(Debug)]
+pub(crate) struct MessageAssembler {
+    max_message_size: usize,
+    partial: HashMap<u64, Vec<u8>>,
+}
+
+impl MessageAssembler {
+    /// Constructs an assembler that rejects messages longer than the given
+    /// maximum size.
+    pub(crate) fn new(max_message_size: usize) -> Self {
+        MessageAssembler {
+            max_message_size,
+            partial: HashMap::new(),
+        }
+    }
+
+    /// Checks the lengths announced by the given chunk header against the
+    /// maximum message size before any memory is allocated for the chunk.
+    pub(crate) fn check(&self, header: &ChunkHeader) -> Result<(), Error> {
+        if header.message_length > self.max_message_size as u64 {
+            return Err(Error::Communication(format!(
+                "VelocyStream message {} of {} bytes exceeds the maximum message size of {} bytes",
+                header.message_id, header.message_length, self.max_message_size)));
+        }
+        if header.data_length() > self.max_message_size {
+            return Err(Error::Communication(format!(
+                "VelocyStream chunk of {} bytes exceeds the maximum message size of {} bytes",
+                header.data_length(), self.max_message_size)));
+        }
+        Ok(())
+    }
+
+    /// Adds a received chunk and returns the message id and the content of
+    /// the message if the chunk completes a message.
+    pub(crate) fn add(&mut self, header: &ChunkHeader, data: Vec<u8>)
+        -> Result<Option<(u64, Vec<u8>)>, Error>
+    {
+        self.check(header)?;
+        let message_id = header.message_id();
+        let message_length = header.message_length as usize;
+        let mut message = if header.is_first() {
+            if self.partial.contains_key(&message_id) {
+                return Err(Error::Communication(
+                    format!("duplicate first chunk for message {}", message_id)));
+            }
+            if data.len() == message_length {
+                return Ok(Some((message_id, data)));
+            }
+            let mut message = Vec::with_capacity(message_length);
+            message.extend_from_slice(&data);
+            message
+        } else {
+            let mut message = self.partial.remove(&message_id).ok_or_else(||
+                Error::Communication(format!("unexpected chunk for message {}", message_id)))?;
+            message.extend_from_slice(&data);
+            message
+        };
+        if message.len() >= message_length {
+            message.truncate(message_length);
+            Ok(Some((message_id, message)))
+        } else {
+            self.partial.insert(message_id, message);
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_chunks(mut bytes: &[u8]) -> Vec<(ChunkHeader, Vec<u8>)> {
+        let mut chunks = Vec::new();
+        while !bytes.is_empty() {
+            let mut header_bytes = [0; CHUNK_HEADER_SIZE];
+            header_bytes.copy_from_slice(&bytes[..CHUNK_HEADER_SIZE]);
+            let header = ChunkHeader::decode(&header_bytes).unwrap();
+            let end = CHUNK_HEADER_SIZE + header.data_length();
+            chunks.push((header, bytes[CHUNK_HEADER_SIZE..end].to_vec()));
+            bytes = &bytes[end..];
+        }
+        chunks
+    }
+
+    #[test]
+    fn encode_small_message_as_single_chunk() {
+        let bytes = encode_message(7, &[0x18], DEFAULT_MAX_CHUNK_SIZE);
+
+        assert_eq!(vec![
+            25, 0, 0, 0,
+            3, 0, 0, 0,
+            7, 0, 0, 0, 0, 0, 0, 0,
+            1, 0, 0, 0, 0, 0, 0, 0,
+            0x18,
+        ], bytes);
+    }
+
+    #[test]
+    fn encode_large_message_in_several_chunks() {
+        let message = (0..100u8).collect::<Vec<_>>();
+
+        let chunks = decode_chunks(&encode_message(42, &message, CHUNK_HEADER_SIZE + 40));
+
+        assert_eq!(3, chunks.len());
+        assert_eq!(vec![true, false, false],
+            chunks.iter().map(|(header, _)| header.is_first()).collect::<Vec<_>>());
+        assert_eq!(vec![40, 40, 20],
+            chunks.iter().map(|(_, data)| data.len()).collect::<Vec<_>>());
+        assert_eq!(7, chunks[0].0.chunk_x);
+        assert_eq!(4, chunks[2].0.chunk_x);
+    }
+
+    #[test]
+    fn assemble_interleaved_messages() {
+        let first = (0..50u8).collect::<Vec<_>>();
+        let second = (100..180u8).collect::<Vec<_>>();
+        let mut first_chunks = decode_chunks(&encode_message(1, &first, 54)).into_iter();
+        let mut second_chunks = decode_chunks(&encode_message(2, &second, 54)).into_iter();
+        let mut assembler = MessageAssembler::new(DEFAULT_MAX_MESSAGE_SIZE);
+
+        let (header, data) = first_chunks.next().unwrap();
+        assert_eq!(None, assembler.add(&header, data).unwrap());
+        let (header, data) = second_chunks.next().unwrap();
+        assert_eq!(None, assembler.add(&header, data).unwrap());
+        let (header, data) = first_chunks.next().unwrap();
+        assert_eq!(Some((1, first)), assembler.add(&header, data).unwrap());
+        let (header, data) = second_chunks.next().unwrap();
+        assert_eq!(None, assembler.add(&header, data).unwrap());
+        let (header, data) = second_chunks.next().unwrap();
+        assert_eq!(Some((2, second)), assembler.add(&header, data).unwrap());
+    }
+
+    #[test]
+    fn chunk_of_unknown_message_is_an_error() {
+        let mut chunks = decode_chunks(&encode_message(9, &[0; 60], 54)).into_iter();
+        chunks.next();
+        let (header, data) = chunks.next().unwrap();
+
+        assert!(MessageAssembler::new(DEFAULT_MAX_MESSAGE_SIZE).add(&header, data).is_err());
+    }
+
+    #[test]
+    fn message_exceeding_max_message_size_is_rejected() {
+        let mut header_bytes = [0; CHUNK_HEADER_SIZE];
+        header_bytes.copy_from_slice(&encode_message(3, &[0; 10], 54)[..CHUNK_HEADER_SIZE]);
+        header_bytes[16..24].copy_from_slice(&[0xff; 8]);
+        let header = ChunkHeader::decode(&header_bytes).unwrap();
+
+        assert!(MessageAssembler::new(DEFAULT_MAX_MESSAGE_SIZE).check(&header).is_err());
+        assert!(MessageAssembler::new(DEFAULT_MAX_MESSAGE_SIZE).add(&header, vec![0; 10]).is_err());
+    }
+
+    #[test]
+    fn chunk_exceeding_max_message_size_is_rejected() {
+        let chunks = decode_chunks(&encode_message(4, &[0; 30], 54));
+        let (ref header, _) = chunks[0];
+
+        assert!(MessageAssembler::new(20).check(header).is_err());
+        assert!(MessageAssembler::new(30).check(header).is_ok());
+    }
+}
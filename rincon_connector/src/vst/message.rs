@@ -0,0 +1,220 @@
+
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use rincon_core::api::connector::Error;
+use rincon_core::api::datasource::Codec;
+use rincon_core::api::method::{Operation, Parameters};
+use vpack::{self, CONTENT_TYPE_VPACK};
+
+const VERSION: u8 = 1;
+const TYPE_REQUEST: u8 = 1;
+const TYPE_RESPONSE: u8 = 2;
+const TYPE_AUTHENTICATION: u16 = 1000;
+
+const META_CONTENT_TYPE: &str = "content-type";
+
+/// The credentials sent with the authentication message.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum AuthMessage<'a> {
+    Plain {
+        username: &'a str,
+        password: &'a str,
+    },
+    Jwt(&'a str),
+}
+
+/// Encodes an authentication message.
+pub(crate) fn encode_authentication(auth: &AuthMessage) -> Result<Vec<u8>, Error> {
+    match *auth {
+        AuthMessage::Plain { username, password } =>
+            vpack::to_vec(&(VERSION, TYPE_AUTHENTICATION, "plain", username, password)),
+        AuthMessage::Jwt(token) =>
+            vpack::to_vec(&(VERSION, TYPE_AUTHENTICATION, "jwt", token)),
+    }.map_err(|cause| Error::Serialization(cause.to_string()))
+}
+
+/// A request message consisting of a header and an optional body.
+#[derive(Clone, Debug)]
+pub(crate) struct RequestMessage<'a> {
+    pub(crate) database: &'a str,
+    pub(crate) operation: Operation,
+    pub(crate) path: &'a str,
+    pub(crate) parameters: Parameters,
+    pub(crate) meta: BTreeMap<String, String>,
+    pub(crate) body: Option<Vec<u8>>,
+}
+
+impl<'a> RequestMessage<'a> {
+    pub(crate) fn encode(&self) -> Result<Vec<u8>, Error> {
+        let parameters = self.parameters.iter()
+            .map(|(name, value)| (name.to_owned(), value.to_string()))
+            .collect::<BTreeMap<_, _>>();
+        let mut message = vpack::to_vec(&(
+            VERSION,
+            TYPE_REQUEST,
+            self.database,
+            request_type_for_operation(&self.operation),
+            self.path,
+            parameters,
+            &self.meta,
+        )).map_err(|cause| Error::Serialization(cause.to_string()))?;
+        if let Some(ref body) = self.body {
+            message.extend_from_slice(body);
+        }
+        Ok(message)
+    }
+}
+
+fn meta_value(value: &Value) -> String {
+    match *value {
+        Value::String(ref value) => value.to_owned(),
+        ref value => value.to_string(),
+    }
+}
+
+fn request_type_for_operation(operation: &Operation) -> u8 {
+    match *operation {
+        Operation::Delete => 0,
+        Operation::Read => 1,
+        Operation::Create => 2,
+        Operation::Replace => 3,
+        Operation::ReadHeader => 4,
+        Operation::Modify => 5,
+    }
+}
+
+/// A response message as received from the server.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ResponseMessage {
+    status_code: u16,
+    meta: BTreeMap<String, String>,
+    message: Vec<u8>,
+    body_start: usize,
+}
+
+impl ResponseMessage {
+    pub(crate) fn decode(message: Vec<u8>) -> Result<Self, Error> {
+        let header_length = vpack::value_len(&message)
+            .map_err(|cause| Error::Deserialization(cause.to_string()))?;
+        let header: Vec<Value> = vpack::from_slice(&message)
+            .map_err(|cause| Error::Deserialization(cause.to_string()))?;
+        let message_type = header.get(1).and_then(Value::as_u64);
+        if message_type != Some(u64::from(TYPE_RESPONSE)) {
+            return Err(Error::Deserialization(
+                format!("expected a response message, but got message type {:?}", message_type)));
+        }
+        let status_code = header.get(2).and_then(Value::as_u64)
+            .ok_or_else(|| Error::Deserialization("response message without status code".into()))?;
+        let meta = match header.get(3) {
+            Some(Value::Object(meta)) => meta.iter()
+                .map(|(name, value)| (name.to_lowercase(), meta_value(value)))
+                .collect(),
+            _ => BTreeMap::new(),
+        };
+        Ok(ResponseMessage {
+            status_code: status_code as u16,
+            meta,
+            message,
+            body_start: header_length,
+        })
+    }
+
+    pub(crate) fn status_code(&self) -> u16 {
+        self.status_code
+    }
+
+    pub(crate) fn meta(&self) -> &BTreeMap<String, String> {
+        &self.meta
+    }
+
+    pub(crate) fn body(&self) -> &[u8] {
+        &self.message[self.body_start..]
+    }
+
+    /// Returns the codec of the body as specified by the content type.
+    ///
+    /// The body of a response without a content type is VelocyPack.
+    pub(crate) fn codec(&self) -> Codec {
+        match self.meta.get(META_CONTENT_TYPE) {
+            Some(content_type) if !content_type.starts_with(CONTENT_TYPE_VPACK) =>
+                Codec::Json,
+            _ =>
+                Codec::VelocyPack,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_plain_authentication() {
+        let message = encode_authentication(&AuthMessage::Plain {
+            username: "root",
+            password: "s3cr3t",
+        }).unwrap();
+
+        let decoded: Value = vpack::from_slice(&message).unwrap();
+        assert_eq!(json!([1, 1000, "plain", "root", "s3cr3t"]), decoded);
+    }
+
+    #[test]
+    fn encode_request_with_body() {
+        let mut meta = BTreeMap::new();
+        meta.insert(META_CONTENT_TYPE.to_owned(), CONTENT_TYPE_VPACK.to_owned());
+        let body = vpack::to_vec(&json!({ "name": "customers" })).unwrap();
+        let request = RequestMessage {
+            database: "shop",
+            operation: Operation::Create,
+            path: "/_api/collection",
+            parameters: Parameters::from(vec![("waitForSync", true)]),
+            meta,
+            body: Some(body.clone()),
+        };
+
+        let message = request.encode().unwrap();
+
+        let header_length = vpack::value_len(&message).unwrap();
+        let header: Value = vpack::from_slice(&message).unwrap();
+        assert_eq!(json!([1, 1, "shop", 2, "/_api/collection",
+            { "waitForSync": "true" },
+            { "content-type": "application/x-velocypack" },
+        ]), header);
+        assert_eq!(&body[..], &message[header_length..]);
+    }
+
+    #[test]
+    fn decode_response_with_body() {
+        let mut message = vpack::to_vec(&json!([1, 2, 201,
+            { "Content-Type": "application/x-velocypack" }])).unwrap();
+        message.extend_from_slice(&vpack::to_vec(&json!({ "error": false })).unwrap());
+
+        let response = ResponseMessage::decode(message).unwrap();
+
+        assert_eq!(201, response.status_code());
+        assert_eq!(Codec::VelocyPack, response.codec());
+        assert_eq!(json!({ "error": false }), vpack::from_slice::<Value>(response.body()).unwrap());
+    }
+
+    #[test]
+    fn decode_response_with_json_body() {
+        let mut message = vpack::to_vec(&json!([1, 2, 200,
+            { "content-type": "application/json" }])).unwrap();
+        message.extend_from_slice(br#"{"error":false}"#);
+
+        let response = ResponseMessage::decode(message).unwrap();
+
+        assert_eq!(Codec::Json, response.codec());
+        assert_eq!(br#"{"error":false}"#, response.body());
+    }
+
+    #[test]
+    fn decode_request_as_response_is_an_error() {
+        let message = vpack::to_vec(&json!([1, 1, "_system", 1, "/_api/version", {}, {}])).unwrap();
+
+        assert!(ResponseMessage::decode(message).is_err());
+    }
+}
@@ -1,22 +1,31 @@
 
 extern crate dotenv;
 extern crate hyper;
+#[macro_use] extern crate serde_json;
 extern crate tokio_core;
 
 extern crate rincon_core;
 extern crate rincon_connector;
 extern crate rincon_test_helper;
 
+use std::env;
+use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
 use std::time::Duration;
 
 use tokio_core::reactor::Core;
 
+use rincon_core::api::auth::{Authentication, Credentials};
 use rincon_core::api::connector::{Connector, Error, Execute};
 use rincon_core::api::method::{Method, Operation, Parameters, Prepare, RpcReturnType};
 use rincon_core::api::types::JsonValue;
-use rincon_core::api::datasource::DataSource;
-use rincon_core::arango::protocol::{PARAM_DETAILS, PATH_API_VERSION};
+use rincon_core::api::datasource::{DataSource, Endpoint, LoadBalancing};
+use rincon_core::arango::protocol::{PARAM_DETAILS, PATH_API_CURSOR, PATH_API_VERSION};
 use rincon_connector::http::BasicConnector;
+use rincon_connector::recording::{RecordedResponse, RecordingConnector};
 
 use rincon_test_helper::*;
 
@@ -62,6 +71,90 @@ impl Prepare for GetServerVersion {
     }
 }
 
+#[derive(Clone, Debug, PartialEq)]
+struct CreateCursor {
+    query: JsonValue,
+}
+
+impl Method for CreateCursor {
+    type Result = JsonValue;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: None,
+    };
+}
+
+impl Prepare for CreateCursor {
+    type Content = JsonValue;
+
+    fn operation(&self) -> Operation {
+        Operation::Create
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_CURSOR)
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        Some(&self.query)
+    }
+}
+
+/// Returns an endpoint on which no server is listening.
+fn refusing_endpoint() -> Endpoint {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    drop(listener);
+    Endpoint::new("http", "127.0.0.1", port)
+}
+
+/// Returns an endpoint that accepts connections and reads the request but
+/// closes the connection without sending a response.
+fn dropping_endpoint() -> Endpoint {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut buffer = [0; 4096];
+            let _ = stream.read(&mut buffer);
+        }
+    });
+    Endpoint::new("http", "127.0.0.1", port)
+}
+
+/// Returns an endpoint that issues a JWT on login and answers any other
+/// request with a server version. The port of the endpoint is recorded in
+/// the given log for each request other than a login.
+fn recording_endpoint(log: Arc<Mutex<Vec<u16>>>) -> Endpoint {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut buffer = [0; 4096];
+            let length = stream.read(&mut buffer).unwrap_or_default();
+            let request = String::from_utf8_lossy(&buffer[..length]).into_owned();
+            let body = if request.contains("/_open/auth") {
+                r#"{"jwt":"token"}"#
+            } else {
+                log.lock().unwrap().push(port);
+                r#"{"server":"arango","version":"3.2.10","license":"community"}"#
+            };
+            let _ = write!(stream, "HTTP/1.1 200 OK\r\nConnection: close\r\n\
+                Content-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(), body);
+        }
+    });
+    Endpoint::new("http", "127.0.0.1", port)
+}
+
 #[ignore]
 #[test]
 fn establish_connection_timeout() {
@@ -83,3 +176,134 @@ fn establish_connection_timeout() {
         e => panic!("Expected timeout error, got {:?}", e),
     }
 }
+
+#[test]
+fn execute_with_jwt_authentication_logs_in_automatically() {
+    let mut core = Core::new().unwrap();
+    let (username, password) = root_user();
+    let datasource = system_datasource()
+        .with_authentication(Authentication::Jwt(Credentials::new(username, password)));
+    let connector = BasicConnector::new(&MyUserAgent, datasource, &core.handle()).unwrap();
+    let conn = connector.system_connection();
+    assert!(conn.token().is_none());
+
+    let version = core.run(conn.execute(GetServerVersion { details: false })).unwrap();
+
+    assert!(version.is_object());
+    assert!(conn.token().is_some());
+}
+
+#[test]
+fn execute_with_jwt_authentication_retries_with_new_token_when_rejected() {
+    let mut core = Core::new().unwrap();
+    let (username, password) = root_user();
+    let datasource = system_datasource()
+        .with_authentication(Authentication::Jwt(Credentials::new(username, password)));
+    let mut connector = BasicConnector::new(&MyUserAgent, datasource, &core.handle()).unwrap();
+    connector.accept_auth_token("invalid.token.signature".to_owned());
+    let conn = connector.system_connection();
+
+    let version = core.run(conn.execute(GetServerVersion { details: false })).unwrap();
+
+    assert!(version.is_object());
+    assert_ne!(Some("invalid.token.signature".to_owned()), conn.token());
+}
+
+#[test]
+fn read_fails_over_to_next_endpoint_when_connection_is_dropped() {
+    let mut core = Core::new().unwrap();
+    let datasource = system_datasource();
+    let endpoints = vec![dropping_endpoint(), datasource.endpoint().clone()];
+    let connector = BasicConnector::new(&MyUserAgent, datasource.with_endpoints(endpoints),
+        &core.handle()).unwrap();
+    let conn = connector.system_connection();
+
+    let version = core.run(conn.execute(GetServerVersion { details: false })).unwrap();
+
+    assert!(version.is_object());
+}
+
+#[test]
+fn mutation_is_not_sent_to_next_endpoint_when_connection_is_dropped() {
+    let mut core = Core::new().unwrap();
+    let datasource = system_datasource();
+    let endpoints = vec![dropping_endpoint(), datasource.endpoint().clone()];
+    let connector = BasicConnector::new(&MyUserAgent, datasource.with_endpoints(endpoints),
+        &core.handle()).unwrap();
+    let conn = connector.system_connection();
+
+    let result = core.run(conn.execute(CreateCursor { query: json!({ "query": "RETURN 1" }) }));
+
+    match result {
+        Err(Error::Communication(_)) => {},
+        _ => panic!("Error::Communication expected but got {:?}", result),
+    }
+}
+
+#[test]
+fn mutation_fails_over_to_next_endpoint_when_connection_is_refused() {
+    let mut core = Core::new().unwrap();
+    let datasource = system_datasource();
+    let endpoints = vec![refusing_endpoint(), datasource.endpoint().clone()];
+    let connector = BasicConnector::new(&MyUserAgent, datasource.with_endpoints(endpoints),
+        &core.handle()).unwrap();
+    let conn = connector.system_connection();
+
+    let cursor = core.run(conn.execute(CreateCursor { query: json!({ "query": "RETURN 1" }) }))
+        .unwrap();
+
+    assert_eq!(json!([1]), cursor["result"]);
+}
+
+#[test]
+fn requests_with_jwt_authentication_alternate_between_endpoints() {
+    let mut core = Core::new().unwrap();
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let endpoints = vec![recording_endpoint(log.clone()), recording_endpoint(log.clone())];
+    let ports: Vec<u16> = endpoints.iter().map(Endpoint::port).collect();
+    let datasource = DataSource::from_url("http://127.0.0.1:8529").unwrap()
+        .with_endpoints(endpoints)
+        .with_load_balancing(LoadBalancing::RoundRobin)
+        .with_authentication(Authentication::Jwt(
+            Credentials::new("root".to_owned(), "s3cr3t".to_owned())));
+    let connector = BasicConnector::new(&MyUserAgent, datasource, &core.handle()).unwrap();
+    let conn = connector.system_connection();
+
+    for _ in 0..4 {
+        core.run(conn.execute(GetServerVersion { details: false })).unwrap();
+    }
+
+    assert_eq!(vec![ports[0], ports[1], ports[0], ports[1]], *log.lock().unwrap());
+}
+
+#[test]
+fn record_request_and_response_as_exchanged_over_http() {
+    let mut core = Core::new().unwrap();
+    let datasource = system_datasource();
+    let connector = BasicConnector::new(&MyUserAgent, datasource, &core.handle()).unwrap();
+    let path = env::temp_dir().join("rincon_connection_recording.json");
+    let recorder = RecordingConnector::new(connector, path.clone());
+
+    let version = core.run(recorder.system_connection()
+        .execute(GetServerVersion { details: false })).unwrap();
+    let interactions = recorder.interactions();
+    recorder.save().unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert!(version.is_object());
+    let request = interactions[0].request();
+    assert_eq!("GET", request.method());
+    assert_eq!("/_db/_system/_api/version", request.uri());
+    assert!(request.headers().contains_key("User-Agent"));
+    if let Some(authorization) = request.headers().get("Authorization") {
+        assert_eq!("<redacted>", authorization);
+    }
+    match *interactions[0].response() {
+        RecordedResponse::Payload { status, ref headers, ref payload } => {
+            assert_eq!(200, status);
+            assert!(headers.contains_key("Content-Type"));
+            assert_eq!(&version, payload);
+        },
+        ref response => panic!("RecordedResponse::Payload expected but got {:?}", response),
+    }
+}
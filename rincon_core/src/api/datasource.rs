@@ -1,5 +1,6 @@
 
 use std::env;
+use std::fmt::{self, Display};
 use std::time::Duration;
 
 use url::Url;
@@ -14,19 +15,125 @@ pub const DEFAULT_PASSWORD: &str = "";
 pub const DEFAULT_DATABASE_NAME: &str = "_system";
 pub const DEFAULT_TIMEOUT: u64 = 30;
 
+const PROTOCOL_HTTP: &str = "http";
+const PROTOCOL_HTTPS: &str = "https";
+const PROTOCOL_SSL: &str = "ssl";
+const PROTOCOL_TCP: &str = "tcp";
+
 pub const ENV_ROOT_PASSWORD: &str = "ARANGO_ROOT_PASSWORD";
 
 #[derive(Clone, PartialEq, Eq, Debug, Fail)]
 pub enum Error {
     #[fail(display = "Invalid URL: {}", _0)]
     InvalidUrl(String),
+    #[fail(display = "No endpoint specified")]
+    NoEndpoint,
 }
 
-#[derive(Clone, Debug)]
-pub struct DataSource {
+/// An endpoint under which an ArangoDB server is reachable.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Endpoint {
     protocol: String,
     host: String,
     port: u16,
+}
+
+impl Endpoint {
+    /// Constructs a new `Endpoint` with the given protocol, host and port.
+    pub fn new<P, H>(protocol: P, host: H, port: u16) -> Self
+        where P: Into<String>, H: Into<String>
+    {
+        Endpoint {
+            protocol: protocol.into(),
+            host: host.into(),
+            port,
+        }
+    }
+
+    /// Parses an `Endpoint` from the given URL.
+    ///
+    /// Besides `http` and `https` URLs this function also accepts the `tcp`
+    /// and `ssl` URLs as they are reported by an ArangoDB cluster.
+    pub fn from_url(url: &str) -> Result<Self, Error> {
+        let url = Url::parse(url).map_err(|cause| Error::InvalidUrl(cause.to_string()))?;
+        Ok(Endpoint::from(&url))
+    }
+
+    pub fn protocol(&self) -> &str {
+        &self.protocol
+    }
+
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+}
+
+impl<'a> From<&'a Url> for Endpoint {
+    fn from(url: &'a Url) -> Self {
+        let protocol = match url.scheme() {
+            PROTOCOL_TCP => PROTOCOL_HTTP,
+            PROTOCOL_SSL => PROTOCOL_HTTPS,
+            scheme => scheme,
+        };
+        Endpoint {
+            protocol: protocol.to_owned(),
+            host: url.host_str().unwrap_or(DEFAULT_HOST).to_owned(),
+            port: url.port().unwrap_or(DEFAULT_PORT),
+        }
+    }
+}
+
+impl Display for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}://{}:{}", self.protocol, self.host, self.port)
+    }
+}
+
+impl Default for Endpoint {
+    fn default() -> Self {
+        Endpoint {
+            protocol: DEFAULT_PROTOCOL.to_owned(),
+            host: DEFAULT_HOST.to_owned(),
+            port: DEFAULT_PORT,
+        }
+    }
+}
+
+/// This enum defines the strategies for distributing requests over the
+/// endpoints of a `DataSource`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum LoadBalancing {
+    /// All requests are sent to the same endpoint. Another endpoint is only
+    /// used when failing over, e.g. in an active failover setup.
+    #[default]
+    None,
+    /// The requests are sent to the endpoints one after the other.
+    RoundRobin,
+    /// Each request is sent to a randomly chosen endpoint.
+    Random,
+}
+
+/// This enum defines the formats in which the content of requests and
+/// responses is encoded.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Codec {
+    /// The content is encoded as JSON (`application/json`).
+    #[default]
+    Json,
+    /// The content is encoded as VelocyPack (`application/x-velocypack`),
+    /// the binary format used by ArangoDB internally.
+    VelocyPack,
+}
+
+#[derive(Clone, Debug)]
+pub struct DataSource {
+    endpoints: Vec<Endpoint>,
+    load_balancing: LoadBalancing,
+    codec: Codec,
     database_name: Option<String>,
     authentication: Authentication,
     timeout: Duration,
@@ -35,9 +142,6 @@ pub struct DataSource {
 impl DataSource {
     pub fn from_url(url: &str) -> Result<Self, Error> {
         let url = Url::parse(url).map_err(|cause| Error::InvalidUrl(cause.to_string()))?;
-        let protocol = url.scheme();
-        let host = url.host_str().unwrap_or(DEFAULT_HOST);
-        let port = url.port().unwrap_or(DEFAULT_PORT);
         let username = if url.username().is_empty() {
             DEFAULT_USERNAME
         } else {
@@ -53,9 +157,9 @@ impl DataSource {
         //TODO parse database name from url path segments
         let database_name = None;
         Ok(DataSource {
-            protocol: protocol.to_owned(),
-            host: host.to_owned(),
-            port,
+            endpoints: vec![Endpoint::from(&url)],
+            load_balancing: LoadBalancing::default(),
+            codec: Codec::default(),
             database_name,
             authentication: Authentication::Basic(Credentials::new(
                 username.to_owned(),
@@ -64,6 +168,20 @@ impl DataSource {
         })
     }
 
+    /// Creates a `DataSource` with several endpoints from the given URLs.
+    ///
+    /// The credentials are taken from the first URL. Requests are sent to
+    /// the first endpoint and only fail over to the other endpoints unless
+    /// another `LoadBalancing` strategy is set.
+    pub fn from_urls(urls: &[&str]) -> Result<Self, Error> {
+        let (first, others) = urls.split_first().ok_or(Error::NoEndpoint)?;
+        let mut datasource = DataSource::from_url(first)?;
+        for url in others {
+            datasource.endpoints.push(Endpoint::from_url(url)?);
+        }
+        Ok(datasource)
+    }
+
     pub fn use_database<DbName>(&self, database_name: DbName) -> Self
         where DbName: Into<String>
     {
@@ -74,9 +192,9 @@ impl DataSource {
             Some(database_name.to_owned())
         };
         DataSource {
-            protocol: self.protocol.clone(),
-            host: self.host.clone(),
-            port: self.port.clone(),
+            endpoints: self.endpoints.clone(),
+            load_balancing: self.load_balancing,
+            codec: self.codec,
             database_name,
             authentication: self.authentication.clone(),
             timeout: self.timeout.clone(),
@@ -85,9 +203,9 @@ impl DataSource {
 
     pub fn use_default_database(&self) -> Self {
         DataSource {
-            protocol: self.protocol.clone(),
-            host: self.host.clone(),
-            port: self.port.clone(),
+            endpoints: self.endpoints.clone(),
+            load_balancing: self.load_balancing,
+            codec: self.codec,
             database_name: None,
             authentication: self.authentication.clone(),
             timeout: self.timeout.clone(),
@@ -99,9 +217,9 @@ impl DataSource {
             Credentials::new(username.to_owned(), password.to_owned())
         );
         DataSource {
-            protocol: self.protocol.clone(),
-            host: self.host.clone(),
-            port: self.port.clone(),
+            endpoints: self.endpoints.clone(),
+            load_balancing: self.load_balancing,
+            codec: self.codec,
             database_name: self.database_name.clone(),
             authentication,
             timeout: self.timeout.clone(),
@@ -110,9 +228,9 @@ impl DataSource {
 
     pub fn with_authentication(&self, authentication: Authentication) -> Self {
         DataSource {
-            protocol: self.protocol.clone(),
-            host: self.host.clone(),
-            port: self.port.clone(),
+            endpoints: self.endpoints.clone(),
+            load_balancing: self.load_balancing,
+            codec: self.codec,
             database_name: self.database_name.clone(),
             authentication,
             timeout: self.timeout.clone(),
@@ -121,9 +239,9 @@ impl DataSource {
 
     pub fn without_authentication(&self) -> Self {
         DataSource {
-            protocol: self.protocol.clone(),
-            host: self.host.clone(),
-            port: self.port.clone(),
+            endpoints: self.endpoints.clone(),
+            load_balancing: self.load_balancing,
+            codec: self.codec,
             database_name: self.database_name.clone(),
             authentication: Authentication::None,
             timeout: self.timeout.clone(),
@@ -134,25 +252,89 @@ impl DataSource {
         where D: Into<Duration>
     {
         DataSource {
-            protocol: self.protocol.clone(),
-            host: self.host.clone(),
-            port: self.port.clone(),
+            endpoints: self.endpoints.clone(),
+            load_balancing: self.load_balancing,
+            codec: self.codec,
             authentication: self.authentication.clone(),
             database_name: self.database_name.clone(),
             timeout: timeout.into(),
         }
     }
 
+    /// Returns a copy of this `DataSource` that uses the given endpoints.
+    ///
+    /// If the given list is empty the endpoints of this `DataSource` are
+    /// kept.
+    pub fn with_endpoints(&self, endpoints: Vec<Endpoint>) -> Self {
+        let endpoints = if endpoints.is_empty() {
+            self.endpoints.clone()
+        } else {
+            endpoints
+        };
+        DataSource {
+            endpoints,
+            load_balancing: self.load_balancing,
+            codec: self.codec,
+            database_name: self.database_name.clone(),
+            authentication: self.authentication.clone(),
+            timeout: self.timeout,
+        }
+    }
+
+    /// Returns a copy of this `DataSource` that distributes requests over
+    /// its endpoints according to the given strategy.
+    pub fn with_load_balancing(&self, load_balancing: LoadBalancing) -> Self {
+        DataSource {
+            endpoints: self.endpoints.clone(),
+            load_balancing,
+            codec: self.codec,
+            database_name: self.database_name.clone(),
+            authentication: self.authentication.clone(),
+            timeout: self.timeout,
+        }
+    }
+
+    /// Returns a copy of this `DataSource` that encodes the content of
+    /// requests and responses with the given codec.
+    pub fn with_codec(&self, codec: Codec) -> Self {
+        DataSource {
+            endpoints: self.endpoints.clone(),
+            load_balancing: self.load_balancing,
+            codec,
+            database_name: self.database_name.clone(),
+            authentication: self.authentication.clone(),
+            timeout: self.timeout,
+        }
+    }
+
+    /// Returns the primary endpoint of this `DataSource`.
+    pub fn endpoint(&self) -> &Endpoint {
+        &self.endpoints[0]
+    }
+
+    /// Returns all endpoints of this `DataSource`.
+    pub fn endpoints(&self) -> &[Endpoint] {
+        &self.endpoints
+    }
+
+    pub fn load_balancing(&self) -> LoadBalancing {
+        self.load_balancing
+    }
+
+    pub fn codec(&self) -> Codec {
+        self.codec
+    }
+
     pub fn protocol(&self) -> &str {
-        &self.protocol
+        self.endpoint().protocol()
     }
 
     pub fn host(&self) -> &str {
-        &self.host
+        self.endpoint().host()
     }
 
     pub fn port(&self) -> u16 {
-        self.port
+        self.endpoint().port()
     }
 
     pub fn database_name(&self) -> Option<&String> {
@@ -171,9 +353,9 @@ impl DataSource {
 impl Default for DataSource {
     fn default() -> Self {
         DataSource {
-            protocol: DEFAULT_PROTOCOL.to_owned(),
-            host: DEFAULT_HOST.to_owned(),
-            port: DEFAULT_PORT,
+            endpoints: vec![Endpoint::default()],
+            load_balancing: LoadBalancing::default(),
+            codec: Codec::default(),
             authentication: Authentication::Basic(Credentials::new(
                 DEFAULT_USERNAME.to_owned(),
                 DEFAULT_PASSWORD.to_owned())),
@@ -0,0 +1,226 @@
+//! Serialization of content into JSON lines.
+//!
+//! The content must serialize into a sequence. Each element of the sequence
+//! is written as a separate JSON document followed by a newline. The
+//! elements are serialized one after the other directly into the writer,
+//! so that large sequences are not held in memory as intermediate JSON
+//! values.
+
+use std::io::Write;
+
+use serde::ser::{self, Error as SerError, Impossible, Serialize, Serializer};
+use serde_json::{self, Error};
+
+const NOT_A_SEQUENCE: &str = "content must be an array to be sent as JSON lines";
+
+/// Serializes the given value as JSON lines into the given writer.
+pub fn to_writer<W, T>(writer: W, value: &T) -> Result<(), Error>
+    where W: Write, T: ?Sized + Serialize
+{
+    value.serialize(&mut JsonLinesSerializer { writer })
+}
+
+/// Serializes the given value as JSON lines into a byte vector.
+pub fn to_vec<T>(value: &T) -> Result<Vec<u8>, Error>
+    where T: ?Sized + Serialize
+{
+    let mut payload = Vec::new();
+    to_writer(&mut payload, value)?;
+    Ok(payload)
+}
+
+struct JsonLinesSerializer<W> {
+    writer: W,
+}
+
+fn not_a_sequence<T>() -> Result<T, Error> {
+    Err(Error::custom(NOT_A_SEQUENCE))
+}
+
+impl<W> Serializer for &mut JsonLinesSerializer<W>
+    where W: Write
+{
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = Impossible<(), Error>;
+    type SerializeStruct = Impossible<(), Error>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> {
+        Ok(self)
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<(), Error>
+        where T: ?Sized + Serialize
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<(), Error>
+        where T: ?Sized + Serialize
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_bool(self, _value: bool) -> Result<(), Error> {
+        not_a_sequence()
+    }
+
+    fn serialize_i8(self, _value: i8) -> Result<(), Error> {
+        not_a_sequence()
+    }
+
+    fn serialize_i16(self, _value: i16) -> Result<(), Error> {
+        not_a_sequence()
+    }
+
+    fn serialize_i32(self, _value: i32) -> Result<(), Error> {
+        not_a_sequence()
+    }
+
+    fn serialize_i64(self, _value: i64) -> Result<(), Error> {
+        not_a_sequence()
+    }
+
+    fn serialize_u8(self, _value: u8) -> Result<(), Error> {
+        not_a_sequence()
+    }
+
+    fn serialize_u16(self, _value: u16) -> Result<(), Error> {
+        not_a_sequence()
+    }
+
+    fn serialize_u32(self, _value: u32) -> Result<(), Error> {
+        not_a_sequence()
+    }
+
+    fn serialize_u64(self, _value: u64) -> Result<(), Error> {
+        not_a_sequence()
+    }
+
+    fn serialize_f32(self, _value: f32) -> Result<(), Error> {
+        not_a_sequence()
+    }
+
+    fn serialize_f64(self, _value: f64) -> Result<(), Error> {
+        not_a_sequence()
+    }
+
+    fn serialize_char(self, _value: char) -> Result<(), Error> {
+        not_a_sequence()
+    }
+
+    fn serialize_str(self, _value: &str) -> Result<(), Error> {
+        not_a_sequence()
+    }
+
+    fn serialize_bytes(self, _value: &[u8]) -> Result<(), Error> {
+        not_a_sequence()
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        not_a_sequence()
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        not_a_sequence()
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        not_a_sequence()
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _variant_index: u32,
+        _variant: &'static str) -> Result<(), Error>
+    {
+        not_a_sequence()
+    }
+
+    fn serialize_newtype_variant<T>(self, _name: &'static str, _variant_index: u32,
+        _variant: &'static str, _value: &T) -> Result<(), Error>
+        where T: ?Sized + Serialize
+    {
+        not_a_sequence()
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize)
+        -> Result<Self::SerializeTupleStruct, Error>
+    {
+        not_a_sequence()
+    }
+
+    fn serialize_tuple_variant(self, _name: &'static str, _variant_index: u32,
+        _variant: &'static str, _len: usize) -> Result<Self::SerializeTupleVariant, Error>
+    {
+        not_a_sequence()
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        not_a_sequence()
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize)
+        -> Result<Self::SerializeStruct, Error>
+    {
+        not_a_sequence()
+    }
+
+    fn serialize_struct_variant(self, _name: &'static str, _variant_index: u32,
+        _variant: &'static str, _len: usize) -> Result<Self::SerializeStructVariant, Error>
+    {
+        not_a_sequence()
+    }
+}
+
+impl<W> JsonLinesSerializer<W>
+    where W: Write
+{
+    fn write_line<T>(&mut self, value: &T) -> Result<(), Error>
+        where T: ?Sized + Serialize
+    {
+        serde_json::to_writer(&mut self.writer, value)?;
+        self.writer.write_all(b"\n").map_err(Error::io)
+    }
+}
+
+impl<W> ser::SerializeSeq for &mut JsonLinesSerializer<W>
+    where W: Write
+{
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+        where T: ?Sized + Serialize
+    {
+        self.write_line(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.writer.flush().map_err(Error::io)
+    }
+}
+
+impl<W> ser::SerializeTuple for &mut JsonLinesSerializer<W>
+    where W: Write
+{
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+        where T: ?Sized + Serialize
+    {
+        self.write_line(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.writer.flush().map_err(Error::io)
+    }
+}
@@ -0,0 +1,58 @@
+
+use std::collections::HashMap;
+
+use super::json_lines::*;
+
+#[derive(Serialize)]
+struct Customer {
+    name: &'static str,
+    age: u16,
+}
+
+#[derive(Serialize)]
+struct Customers(Vec<Customer>);
+
+#[test]
+fn serialize_vec_of_structs_as_json_lines() {
+    let customers = vec![
+        Customer { name: "Jane", age: 42 },
+        Customer { name: "John", age: 17 },
+    ];
+
+    let payload = to_vec(&customers).unwrap();
+
+    assert_eq!("{\"name\":\"Jane\",\"age\":42}\n{\"name\":\"John\",\"age\":17}\n",
+        String::from_utf8(payload).unwrap());
+}
+
+#[test]
+fn serialize_newtype_of_vec_as_json_lines() {
+    let customers = Customers(vec![Customer { name: "Jane", age: 42 }]);
+
+    let payload = to_vec(&customers).unwrap();
+
+    assert_eq!("{\"name\":\"Jane\",\"age\":42}\n", String::from_utf8(payload).unwrap());
+}
+
+#[test]
+fn serialize_empty_vec_as_json_lines() {
+    let payload = to_vec(&Vec::<Customer>::new()).unwrap();
+
+    assert!(payload.is_empty());
+}
+
+#[test]
+fn serialize_struct_as_json_lines_fails() {
+    let result = to_vec(&Customer { name: "Jane", age: 42 });
+
+    assert_eq!("content must be an array to be sent as JSON lines",
+        result.unwrap_err().to_string());
+}
+
+#[test]
+fn serialize_map_as_json_lines_fails() {
+    let mut map = HashMap::new();
+    map.insert("name", "Jane");
+
+    assert!(to_vec(&map).is_err());
+}
@@ -7,9 +7,12 @@ use std::vec::IntoIter;
 
 use serde::de::DeserializeOwned;
 use serde::ser::Serialize;
+use url::percent_encoding::{percent_encode, DEFAULT_ENCODE_SET};
 
+use api::retry::RetryPolicy;
 use api::types::Value;
 use arango::ErrorCode;
+use arango::protocol::PATH_DB;
 
 pub trait Method {
     type Result: DeserializeOwned;
@@ -32,9 +35,40 @@ pub trait Prepare {
     fn header(&self) -> Parameters;
 
     fn content(&self) -> Option<&Self::Content>;
+
+    /// Returns the format in which the content is sent to the server.
+    ///
+    /// Most methods send their content as one JSON value, which is the
+    /// default.
+    fn content_format(&self) -> ContentFormat {
+        ContentFormat::Json
+    }
+
+    /// Returns the retry policy for this method if it shall be executed with
+    /// another policy than the one of the connector.
+    fn retry_policy(&self) -> Option<&RetryPolicy> {
+        None
+    }
 }
 
-#[derive(Clone, Copy, Debug)]
+/// Defines the formats in which the content of a method call can be sent to
+/// the server.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContentFormat {
+    /// The content is sent as one JSON value.
+    Json,
+    /// The content must serialize to a JSON array. Each element of the array
+    /// is sent as a separate JSON document on its own line.
+    JsonLines,
+    /// The content must serialize to a string, which is sent as is as body
+    /// of type `multipart/form-data` with the parts separated by the
+    /// `BATCH_BOUNDARY`. The body of a successful response is not parsed by
+    /// the connector but deserialized into the result of the method as one
+    /// string.
+    Multipart,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Operation {
     Create,
     Read,
@@ -44,6 +78,51 @@ pub enum Operation {
     ReadHeader,
 }
 
+impl Operation {
+    /// Returns the name of the HTTP method that performs this operation.
+    pub fn http_method(&self) -> &'static str {
+        match *self {
+            Operation::Create => "POST",
+            Operation::Read => "GET",
+            Operation::Modify => "PATCH",
+            Operation::Replace => "PUT",
+            Operation::Delete => "DELETE",
+            Operation::ReadHeader => "HEAD",
+        }
+    }
+}
+
+/// Builds the path and the query of the HTTP request for the given method.
+///
+/// If a database name is given the path addresses the method in that
+/// database. The database name, the path and the parameters are percent
+/// encoded.
+pub fn request_path<P>(database_name: Option<&str>, prepare: &P) -> String
+    where P: Prepare + ?Sized
+{
+    let mut request_path = String::new();
+    if let Some(database_name) = database_name {
+        request_path.push_str(PATH_DB);
+        request_path.push_str(&encode(database_name));
+    }
+    request_path.push_str(&encode(&prepare.path()));
+    if !prepare.parameters().is_empty() {
+        request_path.push('?');
+        for (key, value) in prepare.parameters().iter() {
+            request_path.push_str(&encode(key));
+            request_path.push('=');
+            request_path.push_str(&encode(&value.to_string()));
+            request_path.push('&');
+        }
+        request_path.pop();
+    }
+    request_path
+}
+
+fn encode(value: &str) -> String {
+    percent_encode(value.as_bytes(), DEFAULT_ENCODE_SET).to_string()
+}
+
 #[derive(Clone, PartialEq)]
 pub struct Parameters {
     list: Vec<(String, Value)>,
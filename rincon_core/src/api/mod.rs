@@ -2,9 +2,14 @@
 pub mod auth;
 pub mod connector;
 pub mod datasource;
+#[cfg(test)] mod datasource_tests;
+pub mod json_lines;
+#[cfg(test)] mod json_lines_tests;
 pub mod method;
 pub mod query;
 #[cfg(test)] mod query_tests;
+pub mod retry;
+#[cfg(test)] mod retry_tests;
 //pub mod statement;
 pub mod types;
 #[cfg(test)] mod types_tests;
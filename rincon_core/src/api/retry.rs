@@ -0,0 +1,264 @@
+
+use std::time::Duration;
+
+use rand::{self, Rng};
+
+use api::connector::Error;
+use api::method::{ContentFormat, Method, Operation, Parameters, Prepare, RpcReturnType};
+use arango::ErrorCode;
+
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+pub const DEFAULT_INITIAL_BACKOFF_MILLIS: u64 = 100;
+pub const DEFAULT_MAX_BACKOFF_MILLIS: u64 = 10_000;
+
+/// Defines whether and how often a method call is retried if it fails due
+/// to a transient failure.
+///
+/// The following failures are considered transient:
+///
+/// * communication errors, e.g. dropped connections
+/// * timeouts
+/// * responses with HTTP status 503 (service unavailable)
+/// * the ArangoDB error codes listed by `retryable_error_codes()`, which are
+///   1200 (write-write conflict) and 1457 (cluster timeout) by default
+///
+/// Reading operations are retried by default. Mutating operations are only
+/// retried if explicitly allowed by `with_retry_mutations(true)`.
+///
+/// The delay between two attempts grows exponentially starting with the
+/// initial backoff up to the max backoff. If jitter is enabled a random
+/// delay between half and the full backoff is chosen.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    retry_mutations: bool,
+    retryable_error_codes: Vec<ErrorCode>,
+}
+
+impl RetryPolicy {
+    /// Constructs a new `RetryPolicy` with default settings.
+    pub fn new() -> Self {
+        RetryPolicy {
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            initial_backoff: Duration::from_millis(DEFAULT_INITIAL_BACKOFF_MILLIS),
+            max_backoff: Duration::from_millis(DEFAULT_MAX_BACKOFF_MILLIS),
+            jitter: true,
+            retry_mutations: false,
+            retryable_error_codes: vec![ErrorCode::ArangoConflict, ErrorCode::ClusterTimeout],
+        }
+    }
+
+    /// Constructs a `RetryPolicy` that never retries a failed method call.
+    pub fn never() -> Self {
+        RetryPolicy::new().with_max_attempts(1)
+    }
+
+    /// Sets the maximum number of attempts including the first one.
+    ///
+    /// A value of 0 is treated as 1.
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets the delay before the first retry and the maximum delay between
+    /// two attempts.
+    pub fn with_backoff(mut self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff.max(initial_backoff);
+        self
+    }
+
+    /// Sets whether the delay between two attempts shall be randomized.
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets whether mutating operations shall be retried as well.
+    ///
+    /// Only allow this for method calls that can safely be executed more
+    /// than once, as a failed call might have been executed by the server
+    /// anyway.
+    pub fn with_retry_mutations(mut self, retry_mutations: bool) -> Self {
+        self.retry_mutations = retry_mutations;
+        self
+    }
+
+    /// Sets the ArangoDB error codes which are considered transient.
+    pub fn with_retryable_error_codes(mut self, error_codes: Vec<ErrorCode>) -> Self {
+        self.retryable_error_codes = error_codes;
+        self
+    }
+
+    /// Returns the maximum number of attempts including the first one.
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Returns the delay before the first retry.
+    pub fn initial_backoff(&self) -> &Duration {
+        &self.initial_backoff
+    }
+
+    /// Returns the maximum delay between two attempts.
+    pub fn max_backoff(&self) -> &Duration {
+        &self.max_backoff
+    }
+
+    /// Returns whether the delay between two attempts is randomized.
+    pub fn is_jitter(&self) -> bool {
+        self.jitter
+    }
+
+    /// Returns whether mutating operations are retried as well.
+    pub fn is_retry_mutations(&self) -> bool {
+        self.retry_mutations
+    }
+
+    /// Returns the ArangoDB error codes which are considered transient.
+    pub fn retryable_error_codes(&self) -> &[ErrorCode] {
+        &self.retryable_error_codes
+    }
+
+    /// Returns whether a method call of the given operation that failed
+    /// with the given error in the given attempt shall be retried.
+    ///
+    /// Attempts are counted starting with 1.
+    pub fn should_retry(&self, operation: Operation, error: &Error, attempt: u32) -> bool {
+        attempt < self.max_attempts
+            && self.may_repeat(operation)
+            && self.is_retryable(error)
+    }
+
+    /// Returns whether a method call of the given operation may be sent to
+    /// the server more than once.
+    ///
+    /// This is the case for reading operations and, if allowed by
+    /// `with_retry_mutations(true)`, for mutating operations.
+    pub fn may_repeat(&self, operation: Operation) -> bool {
+        self.retry_mutations || is_idempotent(operation)
+    }
+
+    /// Returns whether the given error is considered a transient failure.
+    pub fn is_retryable(&self, error: &Error) -> bool {
+        match *error {
+            Error::Communication(_) | Error::Timeout(_) => true,
+            Error::Method(ref error) =>
+                error.status_code() == 503
+                    || self.retryable_error_codes.contains(&error.error_code()),
+            _ => false,
+        }
+    }
+
+    /// Returns the delay before the given attempt.
+    ///
+    /// Attempts are counted starting with 1, so the delay before the second
+    /// attempt is the initial backoff.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(2).min(31);
+        let backoff = self.initial_backoff.checked_mul(1 << exponent)
+            .map_or(self.max_backoff, |backoff| backoff.min(self.max_backoff));
+        if self.jitter {
+            let millis = duration_millis(&backoff);
+            let half = millis / 2;
+            Duration::from_millis(half + rand::thread_rng().gen_range(0, millis - half + 1))
+        } else {
+            backoff
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::new()
+    }
+}
+
+fn is_idempotent(operation: Operation) -> bool {
+    match operation {
+        Operation::Read | Operation::ReadHeader => true,
+        Operation::Create | Operation::Modify | Operation::Replace | Operation::Delete => false,
+    }
+}
+
+fn duration_millis(duration: &Duration) -> u64 {
+    duration.as_secs() * 1000 + u64::from(duration.subsec_millis())
+}
+
+/// Executes a method with a specific `RetryPolicy` instead of the retry
+/// policy of the connector.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WithRetry<M> {
+    method: M,
+    retry_policy: RetryPolicy,
+}
+
+impl<M> WithRetry<M> {
+    /// Constructs a new `WithRetry` that executes the given method with the
+    /// given retry policy.
+    pub fn new(method: M, retry_policy: RetryPolicy) -> Self {
+        WithRetry {
+            method,
+            retry_policy,
+        }
+    }
+
+    /// Returns the method to be executed.
+    pub fn method(&self) -> &M {
+        &self.method
+    }
+
+    /// Returns the retry policy to be applied.
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+}
+
+impl<M> Method for WithRetry<M>
+    where M: Method
+{
+    type Result = M::Result;
+    const RETURN_TYPE: RpcReturnType = M::RETURN_TYPE;
+
+    fn return_type(&self) -> RpcReturnType {
+        self.method.return_type()
+    }
+}
+
+impl<M> Prepare for WithRetry<M>
+    where M: Prepare
+{
+    type Content = M::Content;
+
+    fn operation(&self) -> Operation {
+        self.method.operation()
+    }
+
+    fn path(&self) -> String {
+        self.method.path()
+    }
+
+    fn parameters(&self) -> Parameters {
+        self.method.parameters()
+    }
+
+    fn header(&self) -> Parameters {
+        self.method.header()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        self.method.content()
+    }
+
+    fn content_format(&self) -> ContentFormat {
+        self.method.content_format()
+    }
+
+    fn retry_policy(&self) -> Option<&RetryPolicy> {
+        Some(&self.retry_policy)
+    }
+}
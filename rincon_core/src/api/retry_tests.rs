@@ -0,0 +1,100 @@
+
+use std::time::Duration;
+
+use api;
+use api::connector::Error;
+use api::method::Operation;
+use arango::ErrorCode;
+use super::retry::*;
+
+fn method_error(status_code: u16, error_code: ErrorCode) -> Error {
+    Error::Method(api::Error::new(status_code, error_code, "failed".to_owned()))
+}
+
+#[test]
+fn default_retry_policy_retries_reads_on_communication_error() {
+    let policy = RetryPolicy::default();
+    let error = Error::Communication("connection reset".to_owned());
+
+    assert!(policy.should_retry(Operation::Read, &error, 1));
+    assert!(policy.should_retry(Operation::ReadHeader, &error, 2));
+    assert!(!policy.should_retry(Operation::Read, &error, 3));
+}
+
+#[test]
+fn default_retry_policy_does_not_retry_mutations() {
+    let policy = RetryPolicy::default();
+    let error = method_error(503, ErrorCode::HttpServiceUnavailable);
+
+    assert!(!policy.should_retry(Operation::Create, &error, 1));
+    assert!(!policy.should_retry(Operation::Modify, &error, 1));
+    assert!(!policy.should_retry(Operation::Replace, &error, 1));
+    assert!(!policy.should_retry(Operation::Delete, &error, 1));
+}
+
+#[test]
+fn retry_policy_retries_mutations_if_allowed() {
+    let policy = RetryPolicy::default().with_retry_mutations(true);
+    let error = method_error(409, ErrorCode::ArangoConflict);
+
+    assert!(policy.should_retry(Operation::Modify, &error, 1));
+}
+
+#[test]
+fn only_reads_may_be_repeated_unless_mutations_are_allowed() {
+    let policy = RetryPolicy::new();
+    assert!(policy.may_repeat(Operation::Read));
+    assert!(policy.may_repeat(Operation::ReadHeader));
+    assert!(!policy.may_repeat(Operation::Create));
+    assert!(!policy.may_repeat(Operation::Delete));
+
+    let policy = RetryPolicy::new().with_retry_mutations(true);
+    assert!(policy.may_repeat(Operation::Create));
+    assert!(policy.may_repeat(Operation::Delete));
+}
+
+#[test]
+fn retry_policy_considers_service_unavailable_and_cluster_timeout_as_retryable() {
+    let policy = RetryPolicy::default();
+
+    assert!(policy.is_retryable(&method_error(503, ErrorCode::HttpServiceUnavailable)));
+    assert!(policy.is_retryable(&method_error(500, ErrorCode::ClusterTimeout)));
+    assert!(policy.is_retryable(&method_error(409, ErrorCode::ArangoConflict)));
+    assert!(policy.is_retryable(&Error::Timeout("timed out".to_owned())));
+    assert!(!policy.is_retryable(&method_error(404, ErrorCode::ArangoDocumentNotFound)));
+    assert!(!policy.is_retryable(&Error::Deserialization("invalid".to_owned())));
+}
+
+#[test]
+fn retry_policy_never_does_not_retry() {
+    let policy = RetryPolicy::never();
+    let error = Error::Communication("connection reset".to_owned());
+
+    assert!(!policy.should_retry(Operation::Read, &error, 1));
+}
+
+#[test]
+fn backoff_without_jitter_grows_exponentially_up_to_max() {
+    let policy = RetryPolicy::default()
+        .with_backoff(Duration::from_millis(100), Duration::from_millis(500))
+        .with_jitter(false);
+
+    assert_eq!(Duration::from_millis(100), policy.backoff(2));
+    assert_eq!(Duration::from_millis(200), policy.backoff(3));
+    assert_eq!(Duration::from_millis(400), policy.backoff(4));
+    assert_eq!(Duration::from_millis(500), policy.backoff(5));
+    assert_eq!(Duration::from_millis(500), policy.backoff(40));
+}
+
+#[test]
+fn backoff_with_jitter_is_between_half_and_full_backoff() {
+    let policy = RetryPolicy::default()
+        .with_backoff(Duration::from_millis(100), Duration::from_millis(500))
+        .with_jitter(true);
+
+    for _ in 0..100 {
+        let backoff = policy.backoff(3);
+        assert!(backoff >= Duration::from_millis(100), "{:?}", backoff);
+        assert!(backoff <= Duration::from_millis(200), "{:?}", backoff);
+    }
+}
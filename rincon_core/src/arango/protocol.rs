@@ -8,10 +8,18 @@ use serde::ser::{Serialize, Serializer};
 
 use api::{self, method};
 
+pub const BATCH_BOUNDARY: &str = "XXXrinconBatchBoundaryXXX";
+
+pub const CONTENT_TYPE_BATCH_PART: &str = "application/x-arango-batchpart";
+
 pub const FIELD_CODE: &str = "code";
 pub const FIELD_COLLECTIONS: &str = "collections";
+pub const FIELD_COUNT: &str = "count";
+pub const FIELD_DELETED_COUNT: &str = "deletedCount";
 pub const FIELD_EDGE: &str = "edge";
+pub const FIELD_EDGES: &str = "edges";
 pub const FIELD_EDGE_DEFINITIONS: &str = "edgeDefinitions";
+pub const FIELD_ENDPOINTS: &str = "endpoints";
 pub const FIELD_ENTITY_FROM: &str = "_from";
 pub const FIELD_ENTITY_ID: &str = "_id";
 pub const FIELD_ENTITY_KEY: &str = "_key";
@@ -26,6 +34,7 @@ pub const FIELD_ERROR_NUMBER: &str = "errorNum";
 pub const FIELD_GRAPH: &str = "graph";
 pub const FIELD_GRAPHS: &str = "graphs";
 pub const FIELD_ID: &str = "id";
+pub const FIELD_IS_NEWLY_CREATED: &str = "isNewlyCreated";
 pub const FIELD_IS_SMART: &str = "isSmart";
 pub const FIELD_NAME: &str = "name";
 pub const FIELD_NUMBER_OF_SHARDS: &str = "numberOfShards";
@@ -33,43 +42,80 @@ pub const FIELD_ORPHAN_COLLECTIONS: &str = "orphanCollections";
 pub const FIELD_REMOVED: &str = "removed";
 pub const FIELD_REPLICATION_FACTOR: &str = "replicationFactor";
 pub const FIELD_RESULT: &str = "result";
+pub const FIELD_ROLE: &str = "role";
 pub const FIELD_SMART_GRAPH_ATTRIBUTE: &str = "smartGraphAttribute";
 pub const FIELD_VERTEX: &str = "vertex";
 
+pub const HEADER_CONTENT_ID: &str = "Content-Id";
+pub const HEADER_CONTENT_TYPE: &str = "Content-Type";
 pub const HEADER_IF_MATCH: &str = "If-Match";
 pub const HEADER_IF_NON_MATCH: &str = "If-None-Match";
 
 pub const PARAM_COLLECTION: &str = "collection";
+pub const PARAM_COMPLETE: &str = "complete";
 pub const PARAM_DETAILS: &str = "details";
+pub const PARAM_DIRECTION: &str = "direction";
 pub const PARAM_EXCLUDE_SYSTEM: &str = "excludeSystem";
+pub const PARAM_FROM_PREFIX: &str = "fromPrefix";
+pub const PARAM_GROUP: &str = "group";
 pub const PARAM_IGNORE_REVISIONS: &str = "ignoreRevs";
 pub const PARAM_KEEP_NULL: &str = "keepNull";
 pub const PARAM_MERGE_OBJECTS: &str = "mergeObjects";
+pub const PARAM_NAMESPACE: &str = "namespace";
+pub const PARAM_ON_DUPLICATE: &str = "onDuplicate";
+pub const PARAM_OVERWRITE: &str = "overwrite";
 pub const PARAM_RETURN_NEW: &str = "returnNew";
 pub const PARAM_RETURN_OLD: &str = "returnOld";
+pub const PARAM_TO_PREFIX: &str = "toPrefix";
+pub const PARAM_TYPE: &str = "type";
+pub const PARAM_VERTEX: &str = "vertex";
 pub const PARAM_WAIT_FOR_SYNC: &str = "waitForSync";
 pub const PARAM_WAIT_FOR_SYNC_REPLICATION: &str = "waitForSyncReplication";
+pub const PARAM_WITH_DATA: &str = "withData";
+pub const PARAM_WITH_REVISIONS: &str = "withRevisions";
 
 pub const PATH_ADMIN: &str = "/_admin";
+pub const PATH_API_AQLFUNCTION: &str = "/_api/aqlfunction";
+pub const PATH_API_BATCH: &str = "/_api/batch";
+pub const PATH_API_CLUSTER_ENDPOINTS: &str = "/_api/cluster/endpoints";
 pub const PATH_API_COLLECTION: &str = "/_api/collection";
 pub const PATH_API_CURSOR: &str = "/_api/cursor";
 pub const PATH_API_DATABASE: &str = "/_api/database";
 pub const PATH_API_DOCUMENT: &str = "/_api/document";
+pub const PATH_API_EDGES: &str = "/_api/edges";
+pub const PATH_API_ENGINE: &str = "/_api/engine";
 pub const PATH_API_EXPLAIN: &str = "/_api/explain";
+pub const PATH_API_EXPORT: &str = "/_api/export";
 pub const PATH_API_GHARIAL: &str = "/_api/gharial";
+pub const PATH_API_IMPORT: &str = "/_api/import";
 pub const PATH_API_INDEX: &str = "/_api/index";
 pub const PATH_API_QUERY: &str = "/_api/query";
+pub const PATH_API_TRANSACTION: &str = "/_api/transaction";
+pub const PATH_API_TRAVERSAL: &str = "/_api/traversal";
 pub const PATH_API_USER: &str = "/_api/user";
 pub const PATH_API_VERSION: &str = "/_api/version";
 pub const PATH_OPEN_AUTH: &str = "/_open/auth";
 
+pub const PATH_CHECKSUM: &str = "/checksum";
+pub const PATH_COUNT: &str = "/count";
 pub const PATH_CURRENT: &str = "/current";
 pub const PATH_DATABASE: &str = "/database";
 pub const PATH_DB: &str = "/_db/";
 pub const PATH_EDGE: &str = "/edge";
+pub const PATH_FIGURES: &str = "/figures";
+pub const PATH_ID: &str = "/id";
+pub const PATH_LOAD: &str = "/load";
+pub const PATH_LOAD_INDEXES_INTO_MEMORY: &str = "/loadIndexesIntoMemory";
 pub const PATH_PROPERTIES: &str = "/properties";
 pub const PATH_RENAME: &str = "/rename";
+pub const PATH_REVISION: &str = "/revision";
+pub const PATH_ROLE: &str = "/role";
+pub const PATH_ROTATE: &str = "/rotate";
+pub const PATH_SERVER: &str = "/server";
+pub const PATH_SLOW: &str = "/slow";
 pub const PATH_TARGET_VERSION: &str = "/target-version";
+pub const PATH_TRUNCATE: &str = "/truncate";
+pub const PATH_UNLOAD: &str = "/unload";
 pub const PATH_USER: &str = "/user";
 pub const PATH_VERTEX: &str = "/vertex";
 
@@ -15,6 +15,7 @@
 
 #[macro_use] extern crate failure;
 extern crate futures;
+extern crate rand;
 extern crate regex;
 extern crate serde;
 #[macro_use] extern crate serde_derive;
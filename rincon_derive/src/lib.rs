@@ -0,0 +1,400 @@
+//! Derive macros for the [Rincon ArangoDB Rust driver][Rincon project].
+//!
+//! The `ArangoDocument` derive implements the
+//! `rincon_client::document::types::ArangoDocument` trait for a struct with
+//! named fields. The struct is bound to a collection with the
+//! `#[arango(collection = "name")]` attribute and the fields holding system
+//! attributes are marked with one of
+//!
+//! * `#[arango(key)]` for the `_key` attribute
+//! * `#[arango(id)]` for the `_id` attribute
+//! * `#[arango(rev)]` for the `_rev` attribute
+//! * `#[arango(from)]` for the `_from` attribute of an edge
+//! * `#[arango(to)]` for the `_to` attribute of an edge
+//!
+//! The fields for `_key`, `_id` and `_rev` may be of the type `DocumentKey`,
+//! `DocumentId` and `Revision` respectively, or of type `String`. As these
+//! attributes are assigned by the server they are usually wrapped in an
+//! `Option`. Fields are mapped by their serialized name, which honours
+//! `#[serde(rename = "...")]` on the field and `#[serde(rename_all = "...")]`
+//! on the struct.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! #[macro_use] extern crate rincon_derive;
+//! #[macro_use] extern crate serde_derive;
+//!
+//! #[derive(Debug, Serialize, Deserialize, ArangoDocument)]
+//! #[arango(collection = "customers")]
+//! struct Customer {
+//!     #[arango(key)]
+//!     key: Option<DocumentKey>,
+//!     #[arango(rev)]
+//!     revision: Option<Revision>,
+//!     name: String,
+//! }
+//! ```
+//!
+//! [Rincon project]: https://github.com/innoave/rincon
+
+#![doc(html_root_url = "https://docs.rs/rincon_derive/0.1.0")]
+
+#![warn(
+    missing_copy_implementations,
+    missing_debug_implementations,
+//    missing_docs,
+    trivial_casts,
+    trivial_numeric_casts,
+    unsafe_code,
+    unstable_features,
+    unused_import_braces,
+    unused_qualifications,
+)]
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use] extern crate quote;
+extern crate syn;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use syn::{Data, DeriveInput, Fields, Ident, Lit, Meta, NestedMeta, Type};
+
+const ATTRIBUTE_ARANGO: &str = "arango";
+const ATTRIBUTE_SERDE: &str = "serde";
+
+#[proc_macro_derive(ArangoDocument, attributes(arango))]
+pub fn derive_arango_document(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = match syn::parse(input) {
+        Ok(input) => input,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    match expand_arango_document(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SystemAttribute {
+    Key,
+    Id,
+    Revision,
+    From,
+    To,
+}
+
+impl SystemAttribute {
+    fn from_marker(marker: &str) -> Option<Self> {
+        use self::SystemAttribute::*;
+        match marker {
+            "key" => Some(Key),
+            "id" => Some(Id),
+            "rev" => Some(Revision),
+            "from" => Some(From),
+            "to" => Some(To),
+            _ => None,
+        }
+    }
+}
+
+/// A field that holds a system attribute.
+struct SystemField<'a> {
+    ident: &'a Ident,
+    ty: &'a Type,
+    serialized_name: String,
+}
+
+#[derive(Default)]
+struct SystemFields<'a> {
+    key: Option<SystemField<'a>>,
+    id: Option<SystemField<'a>>,
+    revision: Option<SystemField<'a>>,
+    from: Option<SystemField<'a>>,
+    to: Option<SystemField<'a>>,
+}
+
+impl<'a> SystemFields<'a> {
+    fn slot(&mut self, attribute: SystemAttribute) -> &mut Option<SystemField<'a>> {
+        use self::SystemAttribute::*;
+        match attribute {
+            Key => &mut self.key,
+            Id => &mut self.id,
+            Revision => &mut self.revision,
+            From => &mut self.from,
+            To => &mut self.to,
+        }
+    }
+}
+
+fn expand_arango_document(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let named_fields = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => &fields.named,
+            _ => return Err(syn::Error::new_spanned(input,
+                "ArangoDocument can only be derived for structs with named fields")),
+        },
+        _ => return Err(syn::Error::new_spanned(input,
+            "ArangoDocument can only be derived for structs")),
+    };
+
+    let collection_name = collection_name(input)?;
+    let rename_rule = rename_rule(input)?;
+
+    let mut system_fields = SystemFields::default();
+    for field in named_fields {
+        let ident = field.ident.as_ref().expect("named field");
+        for attribute in arango_markers(&field.attrs)? {
+            let slot = system_fields.slot(attribute);
+            if slot.is_some() {
+                return Err(syn::Error::new_spanned(field,
+                    "the same system attribute is mapped to more than one field"));
+            }
+            *slot = Some(SystemField {
+                ident,
+                ty: &field.ty,
+                serialized_name: serialized_name(field, rename_rule)?,
+            });
+        }
+    }
+
+    let field_name = |field: &Option<SystemField>| match *field {
+        Some(ref field) => {
+            let name = &field.serialized_name;
+            quote!(::std::option::Option::Some(#name))
+        },
+        None => quote!(::std::option::Option::None),
+    };
+    let key_field = field_name(&system_fields.key);
+    let id_field = field_name(&system_fields.id);
+    let revision_field = field_name(&system_fields.revision);
+    let from_field = field_name(&system_fields.from);
+    let to_field = field_name(&system_fields.to);
+
+    let types = quote!(::rincon_client::document::types);
+
+    let getter = |field: &Option<SystemField>, attribute: TokenStream2| match *field {
+        Some(SystemField { ident, ty, .. }) => quote! {
+            <#ty as #types::SystemAttribute<#attribute>>::to_attribute(&self.#ident)
+        },
+        None => quote!(::std::option::Option::None),
+    };
+    let document_key = getter(&system_fields.key, quote!(#types::DocumentKey));
+    let document_revision = getter(&system_fields.revision, quote!(#types::Revision));
+
+    let setter = |field: &Option<SystemField>, attribute: TokenStream2, value: &str| match *field {
+        Some(SystemField { ident, ty, .. }) => {
+            let value = Ident::new(value, Span::call_site());
+            quote! {
+                self.#ident = <#ty as #types::SystemAttribute<#attribute>>::from_attribute(#value);
+            }
+        },
+        None => quote!(),
+    };
+    let set_id = setter(&system_fields.id, quote!(#types::DocumentId), "id");
+    let set_key = setter(&system_fields.key, quote!(#types::DocumentKey), "key");
+    let set_revision = setter(&system_fields.revision, quote!(#types::Revision), "revision");
+
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #types::ArangoDocument for #name #type_generics #where_clause {
+            const COLLECTION_NAME: &'static str = #collection_name;
+            const KEY_FIELD: ::std::option::Option<&'static str> = #key_field;
+            const ID_FIELD: ::std::option::Option<&'static str> = #id_field;
+            const REVISION_FIELD: ::std::option::Option<&'static str> = #revision_field;
+            const FROM_FIELD: ::std::option::Option<&'static str> = #from_field;
+            const TO_FIELD: ::std::option::Option<&'static str> = #to_field;
+
+            fn document_key(&self) -> ::std::option::Option<#types::DocumentKey> {
+                #document_key
+            }
+
+            fn document_revision(&self) -> ::std::option::Option<#types::Revision> {
+                #document_revision
+            }
+
+            #[allow(unused_variables)]
+            fn set_document_header(
+                &mut self,
+                id: &#types::DocumentId,
+                key: &#types::DocumentKey,
+                revision: &#types::Revision,
+            ) {
+                #set_id
+                #set_key
+                #set_revision
+            }
+        }
+    })
+}
+
+/// Returns the nested items of all attributes with the given name.
+fn nested_items(attrs: &[syn::Attribute], name: &str) -> syn::Result<Vec<NestedMeta>> {
+    let mut items = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident(name)) {
+        match attr.parse_meta()? {
+            Meta::List(list) => items.extend(list.nested),
+            meta => return Err(syn::Error::new_spanned(meta,
+                format!("expected an attribute of the form #[{}(...)]", name))),
+        }
+    }
+    Ok(items)
+}
+
+fn collection_name(input: &DeriveInput) -> syn::Result<String> {
+    let mut collection_name = None;
+    for item in nested_items(&input.attrs, ATTRIBUTE_ARANGO)? {
+        match item {
+            NestedMeta::Meta(Meta::NameValue(ref name_value)) if name_value.path.is_ident("collection") => {
+                match name_value.lit {
+                    Lit::Str(ref value) => collection_name = Some(value.value()),
+                    ref lit => return Err(syn::Error::new_spanned(lit,
+                        "the collection name must be a string literal")),
+                }
+            },
+            item => return Err(syn::Error::new_spanned(item,
+                "unknown arango attribute, expected `collection = \"...\"`")),
+        }
+    }
+    collection_name.ok_or_else(|| syn::Error::new_spanned(&input.ident,
+        "missing attribute #[arango(collection = \"...\")]"))
+}
+
+fn arango_markers(attrs: &[syn::Attribute]) -> syn::Result<Vec<SystemAttribute>> {
+    let mut markers = Vec::new();
+    for item in nested_items(attrs, ATTRIBUTE_ARANGO)? {
+        let marker = match item {
+            NestedMeta::Meta(Meta::Path(ref path)) => path.get_ident()
+                .and_then(|ident| SystemAttribute::from_marker(&ident.to_string())),
+            _ => None,
+        };
+        match marker {
+            Some(marker) => markers.push(marker),
+            None => return Err(syn::Error::new_spanned(item,
+                "unknown arango attribute, expected one of `key`, `id`, `rev`, `from` or `to`")),
+        }
+    }
+    Ok(markers)
+}
+
+/// The casings of serde's `rename_all` attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    fn from_str(rule: &str) -> Option<Self> {
+        use self::RenameRule::*;
+        match rule {
+            "lowercase" => Some(Lower),
+            "UPPERCASE" => Some(Upper),
+            "PascalCase" => Some(Pascal),
+            "camelCase" => Some(Camel),
+            "snake_case" => Some(Snake),
+            "SCREAMING_SNAKE_CASE" => Some(ScreamingSnake),
+            "kebab-case" => Some(Kebab),
+            "SCREAMING-KEBAB-CASE" => Some(ScreamingKebab),
+            _ => None,
+        }
+    }
+
+    /// Applies this rule to the name of a field the same way serde does.
+    fn apply_to_field(self, field: &str) -> String {
+        use self::RenameRule::*;
+        match self {
+            Lower | Snake => field.to_owned(),
+            Upper | ScreamingSnake => field.to_ascii_uppercase(),
+            Pascal => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(ch);
+                    }
+                }
+                pascal
+            },
+            Camel => {
+                let pascal = Pascal.apply_to_field(field);
+                let mut chars = pascal.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                    None => pascal,
+                }
+            },
+            Kebab => field.replace('_', "-"),
+            ScreamingKebab => ScreamingSnake.apply_to_field(field).replace('_', "-"),
+        }
+    }
+}
+
+/// Returns the name given for serialization by a serde attribute of the
+/// form `name = "..."` or `name(serialize = "...")`.
+fn serialize_value(item: &NestedMeta, name: &str) -> syn::Result<Option<syn::LitStr>> {
+    match *item {
+        NestedMeta::Meta(Meta::NameValue(ref name_value)) if name_value.path.is_ident(name) =>
+            match name_value.lit {
+                Lit::Str(ref value) => Ok(Some(value.clone())),
+                ref lit => Err(syn::Error::new_spanned(lit,
+                    format!("expected serde {} attribute to be a string", name))),
+            },
+        NestedMeta::Meta(Meta::List(ref list)) if list.path.is_ident(name) => {
+            for nested in &list.nested {
+                if let NestedMeta::Meta(Meta::NameValue(ref name_value)) = *nested {
+                    if name_value.path.is_ident("serialize") {
+                        return match name_value.lit {
+                            Lit::Str(ref value) => Ok(Some(value.clone())),
+                            ref lit => Err(syn::Error::new_spanned(lit,
+                                format!("expected serde {} attribute to be a string", name))),
+                        };
+                    }
+                }
+            }
+            Ok(None)
+        },
+        _ => Ok(None),
+    }
+}
+
+fn rename_rule(input: &DeriveInput) -> syn::Result<Option<RenameRule>> {
+    let mut rename_rule = None;
+    for item in nested_items(&input.attrs, ATTRIBUTE_SERDE)? {
+        if let Some(value) = serialize_value(&item, "rename_all")? {
+            rename_rule = Some(RenameRule::from_str(&value.value()).ok_or_else(||
+                syn::Error::new_spanned(&value, format!("unknown rename rule for serde \
+                    rename_all attribute: {}", value.value())))?);
+        }
+    }
+    Ok(rename_rule)
+}
+
+/// Returns the name of the field as serialized by serde.
+fn serialized_name(field: &syn::Field, rename_rule: Option<RenameRule>) -> syn::Result<String> {
+    for item in nested_items(&field.attrs, ATTRIBUTE_SERDE)? {
+        if let Some(value) = serialize_value(&item, "rename")? {
+            return Ok(value.value());
+        }
+    }
+    let name = field.ident.as_ref().expect("named field").to_string();
+    let name = name.trim_start_matches("r#");
+    Ok(match rename_rule {
+        Some(rule) => rule.apply_to_field(name),
+        None => name.to_owned(),
+    })
+}
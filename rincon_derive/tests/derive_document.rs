@@ -0,0 +1,222 @@
+
+#[macro_use] extern crate rincon_derive;
+#[macro_use] extern crate serde_derive;
+#[macro_use] extern crate serde_json;
+
+extern crate rincon_client;
+extern crate rincon_core;
+
+use rincon_core::api::method::Prepare;
+use rincon_client::document::methods::*;
+use rincon_client::document::types::*;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ArangoDocument)]
+#[arango(collection = "customers")]
+struct Customer {
+    #[arango(key)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    key: Option<DocumentKey>,
+    #[arango(id)]
+    #[serde(default)]
+    id: Option<String>,
+    #[arango(rev)]
+    #[serde(default, rename = "version")]
+    revision: Option<Revision>,
+    name: String,
+    age: u16,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ArangoDocument)]
+#[arango(collection = "knows")]
+struct Knows {
+    #[arango(key)]
+    key: Option<String>,
+    #[arango(from)]
+    from: DocumentId,
+    #[arango(to)]
+    to: DocumentId,
+    since: u16,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ArangoDocument)]
+#[arango(collection = "orders")]
+#[serde(rename_all = "camelCase")]
+struct Order {
+    #[arango(key)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    order_key: Option<DocumentKey>,
+    #[arango(rev)]
+    #[serde(default, rename(serialize = "orderVersion", deserialize = "orderVersion"))]
+    order_revision: Option<Revision>,
+    total_amount: u32,
+}
+
+#[test]
+fn derive_collection_name_and_system_fields() {
+    assert_eq!("customers", Customer::COLLECTION_NAME);
+    assert_eq!(Some("key"), Customer::KEY_FIELD);
+    assert_eq!(Some("id"), Customer::ID_FIELD);
+    assert_eq!(Some("version"), Customer::REVISION_FIELD);
+    assert_eq!(None, Customer::FROM_FIELD);
+    assert_eq!(None, Customer::TO_FIELD);
+
+    assert_eq!("knows", Knows::COLLECTION_NAME);
+    assert_eq!(Some("from"), Knows::FROM_FIELD);
+    assert_eq!(Some("to"), Knows::TO_FIELD);
+    assert_eq!(None, Knows::REVISION_FIELD);
+}
+
+#[test]
+fn derive_system_fields_of_renamed_struct() {
+    assert_eq!(Some("orderKey"), Order::KEY_FIELD);
+    assert_eq!(Some("orderVersion"), Order::REVISION_FIELD);
+}
+
+#[test]
+fn serialize_entity_of_renamed_struct() {
+    let order = Order {
+        order_key: Some(DocumentKey::new("o1")),
+        order_revision: Some(Revision::new("_Wf2")),
+        total_amount: 250,
+    };
+
+    let json = serde_json::to_value(Entity::new(order)).unwrap();
+
+    assert_eq!(json!({ "_key": "o1", "_rev": "_Wf2", "totalAmount": 250 }), json);
+}
+
+#[test]
+fn serialize_entity_with_system_attributes() {
+    let customer = Customer {
+        key: Some(DocumentKey::new("jane")),
+        id: Some("customers/jane".to_owned()),
+        revision: Some(Revision::new("_Wf1")),
+        name: "Jane Doe".to_owned(),
+        age: 42,
+    };
+
+    let json = serde_json::to_value(Entity::new(customer)).unwrap();
+
+    assert_eq!(json!({ "_key": "jane", "_rev": "_Wf1", "name": "Jane Doe", "age": 42 }), json);
+}
+
+#[test]
+fn serialize_new_entity_without_system_attributes() {
+    let customer = Customer {
+        key: None,
+        id: None,
+        revision: None,
+        name: "Jane Doe".to_owned(),
+        age: 42,
+    };
+
+    let json = serde_json::to_value(Entity::new(customer)).unwrap();
+
+    assert_eq!(json!({ "name": "Jane Doe", "age": 42 }), json);
+}
+
+#[test]
+fn serialize_edge_entity() {
+    let knows = Knows {
+        key: None,
+        from: DocumentId::new("persons", "alice"),
+        to: DocumentId::new("persons", "bob"),
+        since: 2010,
+    };
+
+    let json = serde_json::to_value(Entity::new(knows)).unwrap();
+
+    assert_eq!(json!({ "_from": "persons/alice", "_to": "persons/bob", "since": 2010 }), json);
+}
+
+#[test]
+fn deserialize_edge_entity() {
+    let json = json!({ "_key": "k1", "_from": "persons/alice", "_to": "persons/bob", "since": 2010 });
+
+    let knows: Entity<Knows> = serde_json::from_value(json).unwrap();
+
+    assert_eq!(&Knows {
+        key: Some("k1".to_owned()),
+        from: DocumentId::new("persons", "alice"),
+        to: DocumentId::new("persons", "bob"),
+        since: 2010,
+    }, knows.entity());
+}
+
+#[test]
+fn deserialize_document_into_entity() {
+    let json = json!({
+        "_id": "customers/jane",
+        "_key": "jane",
+        "_rev": "_Wf1",
+        "name": "Jane Doe",
+        "age": 42,
+    });
+
+    let document: Document<Entity<Customer>> = serde_json::from_value(json).unwrap();
+
+    assert_eq!(Customer {
+        key: Some(DocumentKey::new("jane")),
+        id: Some("customers/jane".to_owned()),
+        revision: Some(Revision::new("_Wf1")),
+        name: "Jane Doe".to_owned(),
+        age: 42,
+    }, document.into_entity());
+}
+
+#[test]
+fn insert_document_with_entity() {
+    let customer = Customer {
+        key: None,
+        id: None,
+        revision: None,
+        name: "Jane Doe".to_owned(),
+        age: 42,
+    };
+
+    let method = InsertDocument::with_entity(customer);
+
+    assert_eq!("/_api/document/customers", method.path());
+    let content = serde_json::to_value(method.content().unwrap()).unwrap();
+    assert_eq!(json!({ "name": "Jane Doe", "age": 42 }), content);
+}
+
+#[test]
+fn get_document_with_entity_key() {
+    let method = GetDocument::<Entity<Customer>>::with_entity_key(DocumentKey::new("jane"));
+
+    assert_eq!("/_api/document/customers/jane", method.path());
+}
+
+#[test]
+fn replace_document_with_entity_checks_revision() {
+    let customer = Customer {
+        key: Some(DocumentKey::new("jane")),
+        id: None,
+        revision: Some(Revision::new("_Wf1")),
+        name: "Jane Doe".to_owned(),
+        age: 43,
+    };
+
+    let method = ReplaceDocument::with_entity(customer).unwrap();
+
+    assert_eq!("/_api/document/customers/jane", method.path());
+    assert_eq!(Some(false), method.ignore_revisions());
+    let content = serde_json::to_value(method.content().unwrap()).unwrap();
+    assert_eq!(json!({ "_key": "jane", "_rev": "_Wf1", "name": "Jane Doe", "age": 43 }), content);
+}
+
+#[test]
+fn replace_document_with_entity_without_key_fails() {
+    let knows = Knows {
+        key: None,
+        from: DocumentId::new("persons", "alice"),
+        to: DocumentId::new("persons", "bob"),
+        since: 2010,
+    };
+
+    let result = ReplaceDocument::with_entity(knows);
+
+    assert_eq!(Err("An entity must hold a document key to be replaced in collection 'knows'"
+        .to_owned()), result);
+}
@@ -17,6 +17,7 @@
 
 extern crate futures;
 extern crate serde;
+extern crate serde_json;
 extern crate tokio_core;
 
 extern crate rincon_core;
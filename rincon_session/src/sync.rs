@@ -1,30 +1,54 @@
 
 use std::cell::RefCell;
+use std::fmt::Debug;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
 use std::rc::Rc;
+use std::vec;
 
 use futures::Future;
 use serde::de::DeserializeOwned;
 use serde::ser::Serialize;
+use serde_json;
 use tokio_core::reactor::Core;
 
 pub use rincon_core::api::connector::{Connector, Error};
+pub use rincon_core::api::method::ResultList;
 pub use rincon_core::api::query::Query;
 pub use rincon_core::api::types::Empty;
+pub use rincon_client::admin::types::{ServerInfo, ServerRole, ServerVersion};
+pub use rincon_client::aql::types::{AqlFunction, NewAqlFunction};
+pub use rincon_client::collection::types::Collection;
 pub use rincon_client::cursor::types::{Cursor, NewCursor};
 pub use rincon_client::database::types::{Database, NewDatabase};
-pub use rincon_client::graph::types::{EdgeDefinition, Graph, NewGraph};
+pub use rincon_client::document::types::{Document, DocumentHeader, DocumentId,
+    DocumentKey, DocumentUpdate, NewDocument, Revision, UpdatedDocument,
+    UpdatedDocumentHeader};
+pub use rincon_client::graph::types::{Edge, EdgeDefinition, Graph, NewEdge, NewGraph,
+    VertexCollection};
+pub use rincon_client::transaction::types::{NewTransaction, TransactionCollections};
 pub use rincon_client::user::types::{NewUser, UserExtra};
 
 use rincon_core::api::connector::Execute;
 use rincon_core::arango::protocol::SYSTEM_DATABASE;
-use rincon_client::cursor::methods::CreateCursor;
+use rincon_client::admin::methods::{GetServerId, GetServerRole, GetServerVersion};
+use rincon_client::aql::methods::{CreateAqlFunction, ListAqlFunctions};
+use rincon_client::collection::methods::{GetCollectionCount, TruncateCollection};
+use rincon_client::cursor::methods::{CreateCursor, DeleteCursor, ReadNextBatchFromCursor};
 use rincon_client::database::methods::{CreateDatabase, DropDatabase};
-use rincon_client::graph::methods::CreateGraph;
+use rincon_client::document::methods::{DeleteDocument, GetDocument, InsertDocument,
+    InsertDocuments, ModifyDocument, ReplaceDocument};
+use rincon_client::graph::methods::{AddEdgeDefinition, AddVertexCollection, CreateGraph,
+    DropGraph, GetEdge, GetGraph, GetVertex, InsertEdge, InsertVertex, ModifyEdge,
+    ModifyVertex, RemoveEdge, RemoveEdgeDefinition, RemoveVertex, RemoveVertexCollection,
+    ReplaceEdge, ReplaceVertex};
+use rincon_client::transaction::methods::ExecuteTransaction;
 
 #[derive(Debug)]
 pub struct ArangoSession<C> {
     connector: Rc<C>,
     core: Rc<RefCell<Core>>,
+    server: Option<ServerInfo>,
 }
 
 impl<C> ArangoSession<C>
@@ -34,9 +58,42 @@ impl<C> ArangoSession<C>
         Ok(ArangoSession {
             connector: Rc::new(connector),
             core: Rc::new(RefCell::new(core)),
+            server: None,
         })
     }
 
+    /// Connects this session to the server.
+    ///
+    /// When connecting, the role, the id and the license of the server are
+    /// detected. The returned session provides them through the `server()`
+    /// function.
+    pub fn connect(self) -> Result<Self, Error> {
+        let server = {
+            let connection = self.connector.system_connection();
+            let mut core = self.core.borrow_mut();
+            let role = core.run(connection.execute(GetServerRole::new()))?;
+            let id = if role.is_cluster() {
+                Some(core.run(connection.execute(GetServerId::new()))?)
+            } else {
+                None
+            };
+            let version = core.run(connection.execute(GetServerVersion::new()))?;
+            ServerInfo::new(role, id, version)
+        };
+        Ok(ArangoSession {
+            server: Some(server),
+            ..self
+        })
+    }
+
+    /// Returns the role, the id and the license of the server.
+    ///
+    /// Returns `None` if this session has not been connected to the server
+    /// by calling the `connect()` function.
+    pub fn server(&self) -> Option<&ServerInfo> {
+        self.server.as_ref()
+    }
+
     pub fn close(self) {
         //TODO see if a close() method has any purpose
     }
@@ -127,6 +184,71 @@ impl<C> DatabaseSession<C>
         )
     }
 
+    /// Executes a query and returns an iterator over all results.
+    ///
+    /// The iterator fetches further batches of results from the server on
+    /// demand. If the iterator is dropped before all results are fetched
+    /// the cursor on the server is deleted.
+    ///
+    /// All cursor options and query execution options are left to their
+    /// default settings.
+    ///
+    /// To specify cursor options and/or query execution options use the
+    /// `query_opt_iter(&self, NewCursor)` function.
+    pub fn query_iter<T>(&self, query: Query) -> Result<CursorIter<T, C>, Error>
+        where T: 'static + DeserializeOwned
+    {
+        self.query(query).map(|cursor| self.cursor_iter(cursor))
+    }
+
+    /// Executes a query and returns an iterator over all results.
+    ///
+    /// The iterator fetches further batches of results from the server on
+    /// demand. If the iterator is dropped before all results are fetched
+    /// the cursor on the server is deleted.
+    ///
+    /// It requires a `NewCursor` struct as a parameter which allows full
+    /// control over all supported cursor options and query execution options.
+    pub fn query_opt_iter<T>(&self, new_cursor: NewCursor) -> Result<CursorIter<T, C>, Error>
+        where T: 'static + DeserializeOwned
+    {
+        self.query_opt(new_cursor).map(|cursor| self.cursor_iter(cursor))
+    }
+
+    /// Returns an iterator over all results of the given cursor.
+    ///
+    /// The iterator starts with the results contained in the given cursor
+    /// and fetches further batches of results from the server on demand.
+    pub fn cursor_iter<T>(&self, cursor: Cursor<T>) -> CursorIter<T, C>
+        where T: 'static + DeserializeOwned
+    {
+        CursorIter::new(cursor, self.database_name.clone(), self.connector.clone(), self.core.clone())
+    }
+
+    /// Executes a transaction in the database represented by this
+    /// `DatabaseSession` and returns the result of its action.
+    ///
+    /// The transaction is committed if the action returns normally and it
+    /// is rolled back if the action throws an exception.
+    pub fn transaction<P, R>(&self, transaction: NewTransaction<P>) -> Result<R, Error>
+        where P: 'static + Serialize, R: 'static + DeserializeOwned
+    {
+        self.core.borrow_mut().run(self.connector.connection(&self.database_name)
+            .execute(ExecuteTransaction::new(transaction))
+        )
+    }
+
+    /// Returns a session for the collection with the given name that stores
+    /// documents with content of type `T`.
+    ///
+    /// The collection is not checked for existence.
+    pub fn use_collection<T>(&self, collection_name: &str) -> CollectionSession<T, C>
+        where T: 'static + Serialize + DeserializeOwned + Debug
+    {
+        CollectionSession::new(collection_name.to_owned(), self.database_name.clone(),
+            self.connector.clone(), self.core.clone())
+    }
+
     /// Creates a new graph in the database represented by this
     /// `DatabaseSession`.
     pub fn create_graph(&self, new_graph: NewGraph) -> Result<GraphSession<C>, Error> {
@@ -138,6 +260,284 @@ impl<C> DatabaseSession<C>
                 .and_then(|graph| GraphSession::new(graph, database_name, connector, core))
         )
     }
+
+    /// Returns a session for the existing graph with the given name.
+    pub fn use_graph(&self, graph_name: &str) -> Result<GraphSession<C>, Error> {
+        let core = self.core.clone();
+        let connector = self.connector.clone();
+        let database_name = self.database_name.clone();
+        self.core.borrow_mut().run(self.connector.connection(&self.database_name)
+            .execute(GetGraph::with_name(graph_name))
+                .and_then(|graph| GraphSession::new(graph, database_name, connector, core))
+        )
+    }
+
+    /// Returns the AQL user functions registered in the database represented
+    /// by this `DatabaseSession`.
+    pub fn aql_functions(&self) -> Result<Vec<AqlFunction>, Error> {
+        self.core.borrow_mut().run(self.connector.connection(&self.database_name)
+            .execute(ListAqlFunctions::new())
+        )
+    }
+
+    /// Registers the given AQL user functions in the database represented by
+    /// this `DatabaseSession` and returns the number of functions that have
+    /// been created or replaced.
+    ///
+    /// Functions that are already registered with the same code are left
+    /// untouched. Thus this function can be called on every start of an
+    /// application.
+    pub fn register_aql_functions<F>(&self, functions: F) -> Result<usize, Error>
+        where F: IntoIterator<Item=NewAqlFunction>
+    {
+        let registered = self.aql_functions()?;
+        let mut count = 0;
+        for function in functions {
+            if !registered.iter().any(|existing| function.is_registered_as(existing)) {
+                self.core.borrow_mut().run(self.connector.connection(&self.database_name)
+                    .execute(CreateAqlFunction::new(function))
+                )?;
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+}
+
+/// A session for the documents of one collection.
+///
+/// The content of the documents is of type `T`.
+#[derive(Debug)]
+pub struct CollectionSession<T, C> {
+    collection_name: String,
+    database_name: String,
+    connector: Rc<C>,
+    core: Rc<RefCell<Core>>,
+    content: PhantomData<T>,
+}
+
+impl<T, C> CollectionSession<T, C>
+    where T: 'static + Serialize + DeserializeOwned + Debug, C: 'static + Connector
+{
+    fn new(collection_name: String, database_name: String, connector: Rc<C>, core: Rc<RefCell<Core>>) -> Self {
+        CollectionSession {
+            collection_name,
+            database_name,
+            connector,
+            core,
+            content: PhantomData,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.collection_name
+    }
+
+    /// Fetches the document with the given key.
+    pub fn get(&self, key: DocumentKey) -> Result<Document<T>, Error> {
+        self.core.borrow_mut().run(self.connector.connection(&self.database_name)
+            .execute(GetDocument::with_key(self.collection_name.clone(), key))
+        )
+    }
+
+    /// Inserts a new document and returns its header.
+    pub fn insert<D>(&self, document: D) -> Result<DocumentHeader, Error>
+        where D: Into<NewDocument<T>>
+    {
+        self.core.borrow_mut().run(self.connector.connection(&self.database_name)
+            .execute(InsertDocument::new(self.collection_name.clone(), document.into()))
+        )
+    }
+
+    /// Inserts multiple documents in one request and returns a header or
+    /// an error for each of them.
+    pub fn insert_many<Docs, D>(&self, documents: Docs) -> Result<ResultList<DocumentHeader>, Error>
+        where Docs: IntoIterator<Item=D>, D: Into<NewDocument<T>>
+    {
+        let documents = Vec::from_iter(documents.into_iter().map(Into::into));
+        self.core.borrow_mut().run(self.connector.connection(&self.database_name)
+            .execute(InsertDocuments::new(self.collection_name.clone(), documents))
+        )
+    }
+
+    /// Replaces the document identified by the key of the given
+    /// `DocumentUpdate`.
+    ///
+    /// If the update holds a revision the document is only replaced if the
+    /// revision matches the current revision of the stored document.
+    pub fn replace(&self, document: DocumentUpdate<T>) -> Result<UpdatedDocument<T, T>, Error> {
+        let document_id = DocumentId::new(self.collection_name.clone(), document.key().as_str());
+        let ignore_revisions = document.revision().map(|_| false);
+        self.core.borrow_mut().run(self.connector.connection(&self.database_name)
+            .execute(ReplaceDocument::new(document_id, document)
+                .with_ignore_revisions(ignore_revisions))
+        )
+    }
+
+    /// Partially updates the document identified by the key of the given
+    /// `DocumentUpdate`.
+    ///
+    /// If the update holds a revision the document is only updated if the
+    /// revision matches the current revision of the stored document.
+    pub fn update<Upd>(&self, update: DocumentUpdate<Upd>) -> Result<UpdatedDocument<T, T>, Error>
+        where Upd: 'static + Serialize + Debug
+    {
+        let document_id = DocumentId::new(self.collection_name.clone(), update.key().as_str());
+        let ignore_revisions = update.revision().map(|_| false);
+        self.core.borrow_mut().run(self.connector.connection(&self.database_name)
+            .execute(ModifyDocument::new(document_id, update)
+                .with_ignore_revisions(ignore_revisions))
+        )
+    }
+
+    /// Removes the document with the given key and returns its header.
+    pub fn remove(&self, key: DocumentKey) -> Result<DocumentHeader, Error> {
+        self.core.borrow_mut().run(self.connector.connection(&self.database_name)
+            .execute(DeleteDocument::with_key(self.collection_name.clone(), key))
+        )
+    }
+
+    /// Returns the number of documents in the collection.
+    pub fn count(&self) -> Result<u64, Error> {
+        self.core.borrow_mut().run(self.connector.connection(&self.database_name)
+            .execute(GetCollectionCount::with_name(self.collection_name.clone()))
+        )
+    }
+
+    /// Removes all documents from the collection.
+    pub fn truncate(&self) -> Result<Collection, Error> {
+        self.core.borrow_mut().run(self.connector.connection(&self.database_name)
+            .execute(TruncateCollection::with_name(self.collection_name.clone()))
+        )
+    }
+
+    /// Returns an iterator over all documents that match the given example.
+    ///
+    /// A document matches if each attribute of the example is equal to the
+    /// attribute of the same name in the document. The example must
+    /// serialize into a Json object.
+    pub fn find_by_example<E>(&self, example: &E) -> Result<CursorIter<Document<T>, C>, Error>
+        where E: Serialize
+    {
+        let example = serde_json::to_value(example)
+            .map_err(|error| Error::Serialization(error.to_string()))?;
+        if !example.is_object() {
+            return Err(Error::Serialization(format!(
+                "An example must serialize into a Json object, but got: {}", example)));
+        }
+        //bind parameters can not hold objects, so the example is passed as Json string
+        let mut query = Query::new(
+            "FOR doc IN @@collection FILTER MATCHES(doc, JSON_PARSE(@example)) RETURN doc");
+        query.set_parameter("@collection", self.collection_name.clone());
+        query.set_parameter("example", example.to_string());
+        let cursor = self.core.borrow_mut().run(self.connector.connection(&self.database_name)
+            .execute(CreateCursor::from_query(query))
+        )?;
+        Ok(CursorIter::new(cursor, self.database_name.clone(), self.connector.clone(), self.core.clone()))
+    }
+}
+
+/// An iterator over all results of a cursor.
+///
+/// The iterator fetches the next batch of results from the server whenever
+/// the results of the current batch are consumed. If the iterator is
+/// dropped before all results are fetched the cursor is deleted on the
+/// server.
+#[derive(Debug)]
+pub struct CursorIter<T, C>
+    where C: 'static + Connector
+{
+    batch: vec::IntoIter<T>,
+    cursor_id: Option<String>,
+    has_more: bool,
+    failed: bool,
+    database_name: String,
+    connector: Rc<C>,
+    core: Rc<RefCell<Core>>,
+}
+
+impl<T, C> CursorIter<T, C>
+    where T: 'static + DeserializeOwned, C: 'static + Connector
+{
+    fn new(cursor: Cursor<T>, database_name: String, connector: Rc<C>, core: Rc<RefCell<Core>>) -> Self {
+        let cursor_id = cursor.id().cloned();
+        let has_more = cursor.has_more();
+        CursorIter {
+            batch: cursor.into_result().into_iter(),
+            cursor_id,
+            has_more,
+            failed: false,
+            database_name,
+            connector,
+            core,
+        }
+    }
+
+    /// Returns the id of the cursor on the server if there are more results
+    /// to be fetched.
+    pub fn cursor_id(&self) -> Option<&String> {
+        self.cursor_id.as_ref()
+    }
+
+    /// Returns whether there are more results to be fetched from the server.
+    pub fn has_more(&self) -> bool {
+        self.has_more
+    }
+
+    /// Fetches the next batch of results.
+    ///
+    /// If fetching fails the cursor is still regarded as having more
+    /// results, so that it is deleted when this iterator is dropped. The
+    /// iterator does not fetch again but returns `None` after the error.
+    fn fetch_next_batch(&mut self, cursor_id: String) -> Result<(), Error> {
+        let cursor = self.core.borrow_mut().run(self.connector.connection(&self.database_name)
+            .execute(ReadNextBatchFromCursor::new(cursor_id))
+        )?;
+        self.cursor_id = cursor.id().cloned();
+        self.has_more = cursor.has_more();
+        self.batch = cursor.into_result().into_iter();
+        Ok(())
+    }
+}
+
+impl<T, C> Iterator for CursorIter<T, C>
+    where T: 'static + DeserializeOwned, C: 'static + Connector
+{
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(result) = self.batch.next() {
+                return Some(Ok(result));
+            }
+            if !self.has_more || self.failed {
+                return None;
+            }
+            match self.cursor_id.clone() {
+                Some(cursor_id) => if let Err(error) = self.fetch_next_batch(cursor_id) {
+                    self.failed = true;
+                    return Some(Err(error));
+                },
+                None => return None,
+            }
+        }
+    }
+}
+
+impl<T, C> Drop for CursorIter<T, C>
+    where C: 'static + Connector
+{
+    fn drop(&mut self) {
+        if !self.has_more {
+            return;
+        }
+        if let Some(cursor_id) = self.cursor_id.take() {
+            if let Ok(mut core) = self.core.try_borrow_mut() {
+                let _ = core.run(self.connector.connection(&self.database_name)
+                    .execute(DeleteCursor::new(cursor_id)));
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -160,7 +560,250 @@ impl<C> GraphSession<C>
         })
     }
 
+    pub fn name(&self) -> &str {
+        self.graph.name()
+    }
+
     pub fn graph(&self) -> &Graph {
         &self.graph
     }
+
+    /// Returns a session for the vertex collection with the given name that
+    /// stores vertices with content of type `T`.
+    ///
+    /// The collection is not checked for being part of the graph.
+    pub fn vertex_collection<T>(&self, collection_name: &str) -> Result<VertexCollectionSession<T, C>, Error>
+        where T: 'static + Serialize + DeserializeOwned + Debug
+    {
+        VertexCollectionSession::new(self.graph.name().to_owned(), collection_name.to_owned(),
+            self.database_name.clone(), self.connector.clone(), self.core.clone())
+    }
+
+    /// Returns a session for the edge collection with the given name that
+    /// stores edges with content of type `T`.
+    ///
+    /// The collection is not checked for being part of the graph.
+    pub fn edge_collection<T>(&self, collection_name: &str) -> Result<EdgeCollectionSession<T, C>, Error>
+        where T: 'static + Serialize + DeserializeOwned + Debug
+    {
+        EdgeCollectionSession::new(self.graph.name().to_owned(), collection_name.to_owned(),
+            self.database_name.clone(), self.connector.clone(), self.core.clone())
+    }
+
+    /// Adds a vertex collection to the graph and returns the updated graph.
+    ///
+    /// The collection is created if it does not exist.
+    pub fn add_vertex_collection(&mut self, collection_name: &str) -> Result<&Graph, Error> {
+        let graph = self.core.borrow_mut().run(self.connector.connection(&self.database_name)
+            .execute(AddVertexCollection::new(self.graph.name(), VertexCollection::new(collection_name)))
+        )?;
+        self.graph = graph;
+        Ok(&self.graph)
+    }
+
+    /// Removes a vertex collection from the graph and returns the updated
+    /// graph.
+    ///
+    /// Only vertex collections that are not used in an edge definition can
+    /// be removed. The collection itself is not dropped.
+    pub fn remove_vertex_collection(&mut self, collection_name: &str) -> Result<&Graph, Error> {
+        let graph = self.core.borrow_mut().run(self.connector.connection(&self.database_name)
+            .execute(RemoveVertexCollection::new(self.graph.name(), collection_name))
+        )?;
+        self.graph = graph;
+        Ok(&self.graph)
+    }
+
+    /// Adds an edge definition to the graph and returns the updated graph.
+    ///
+    /// Collections of the edge definition that do not exist are created.
+    pub fn add_edge_definition(&mut self, edge_definition: EdgeDefinition) -> Result<&Graph, Error> {
+        let graph = self.core.borrow_mut().run(self.connector.connection(&self.database_name)
+            .execute(AddEdgeDefinition::new(self.graph.name(), edge_definition))
+        )?;
+        self.graph = graph;
+        Ok(&self.graph)
+    }
+
+    /// Removes the edge definition for the given edge collection from the
+    /// graph and returns the updated graph.
+    ///
+    /// The collections of the edge definition are not dropped.
+    pub fn remove_edge_definition(&mut self, edge_definition_name: &str) -> Result<&Graph, Error> {
+        let graph = self.core.borrow_mut().run(self.connector.connection(&self.database_name)
+            .execute(RemoveEdgeDefinition::new(self.graph.name(), edge_definition_name))
+        )?;
+        self.graph = graph;
+        Ok(&self.graph)
+    }
+
+    /// Drops the graph that is used in this session.
+    ///
+    /// The collections of the graph are not dropped. After calling this
+    /// function the associated `GraphSession` is no longer valid.
+    pub fn drop(self) -> Result<bool, Error> {
+        self.core.borrow_mut().run(self.connector.connection(&self.database_name)
+            .execute(DropGraph::with_name(self.graph.name()))
+        )
+    }
+}
+
+/// A session for the vertices of one vertex collection of a graph.
+///
+/// The content of the vertices is of type `T`.
+#[derive(Debug)]
+pub struct VertexCollectionSession<T, C> {
+    graph_name: String,
+    collection_name: String,
+    database_name: String,
+    connector: Rc<C>,
+    core: Rc<RefCell<Core>>,
+    content: PhantomData<T>,
+}
+
+impl<T, C> VertexCollectionSession<T, C>
+    where T: 'static + Serialize + DeserializeOwned + Debug, C: 'static + Connector
+{
+    fn new(graph_name: String, collection_name: String, database_name: String, connector: Rc<C>, core: Rc<RefCell<Core>>) -> Result<Self, Error> {
+        Ok(VertexCollectionSession {
+            graph_name,
+            collection_name,
+            database_name,
+            connector,
+            core,
+            content: PhantomData,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.collection_name
+    }
+
+    pub fn graph_name(&self) -> &str {
+        &self.graph_name
+    }
+
+    /// Fetches the vertex with the given key.
+    pub fn get(&self, key: DocumentKey) -> Result<Document<T>, Error> {
+        self.core.borrow_mut().run(self.connector.connection(&self.database_name)
+            .execute(GetVertex::with_key(self.graph_name.clone(), self.collection_name.clone(), key))
+        )
+    }
+
+    /// Inserts a new vertex and returns its header.
+    pub fn insert<D>(&self, vertex: D) -> Result<DocumentHeader, Error>
+        where D: Into<NewDocument<T>>
+    {
+        self.core.borrow_mut().run(self.connector.connection(&self.database_name)
+            .execute(InsertVertex::new(self.graph_name.clone(), self.collection_name.clone(), vertex.into()))
+        )
+    }
+
+    /// Replaces the vertex with the given key.
+    pub fn replace<D>(&self, key: DocumentKey, vertex: D) -> Result<UpdatedDocumentHeader, Error>
+        where D: Into<NewDocument<T>>
+    {
+        let vertex_id = DocumentId::new(self.collection_name.clone(), key.as_str());
+        self.core.borrow_mut().run(self.connector.connection(&self.database_name)
+            .execute(ReplaceVertex::new(self.graph_name.clone(), vertex_id, vertex.into()))
+        )
+    }
+
+    /// Partially updates the vertex with the given key.
+    pub fn update<Upd>(&self, key: DocumentKey, update: Upd) -> Result<UpdatedDocumentHeader, Error>
+        where Upd: 'static + Serialize + Debug
+    {
+        let vertex_id = DocumentId::new(self.collection_name.clone(), key.as_str());
+        self.core.borrow_mut().run(self.connector.connection(&self.database_name)
+            .execute(ModifyVertex::new(self.graph_name.clone(), vertex_id, update))
+        )
+    }
+
+    /// Removes the vertex with the given key.
+    ///
+    /// All edges of the graph that are connected to the vertex are removed
+    /// as well.
+    pub fn remove(&self, key: DocumentKey) -> Result<bool, Error> {
+        self.core.borrow_mut().run(self.connector.connection(&self.database_name)
+            .execute(RemoveVertex::with_key(self.graph_name.clone(), self.collection_name.clone(), key))
+        )
+    }
+}
+
+/// A session for the edges of one edge collection of a graph.
+///
+/// The content of the edges is of type `T`.
+#[derive(Debug)]
+pub struct EdgeCollectionSession<T, C> {
+    graph_name: String,
+    collection_name: String,
+    database_name: String,
+    connector: Rc<C>,
+    core: Rc<RefCell<Core>>,
+    content: PhantomData<T>,
+}
+
+impl<T, C> EdgeCollectionSession<T, C>
+    where T: 'static + Serialize + DeserializeOwned + Debug, C: 'static + Connector
+{
+    fn new(graph_name: String, collection_name: String, database_name: String, connector: Rc<C>, core: Rc<RefCell<Core>>) -> Result<Self, Error> {
+        Ok(EdgeCollectionSession {
+            graph_name,
+            collection_name,
+            database_name,
+            connector,
+            core,
+            content: PhantomData,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.collection_name
+    }
+
+    pub fn graph_name(&self) -> &str {
+        &self.graph_name
+    }
+
+    /// Fetches the edge with the given key.
+    pub fn get(&self, key: DocumentKey) -> Result<Edge<T>, Error> {
+        self.core.borrow_mut().run(self.connector.connection(&self.database_name)
+            .execute(GetEdge::with_key(self.graph_name.clone(), self.collection_name.clone(), key))
+        )
+    }
+
+    /// Inserts a new edge and returns its header.
+    ///
+    /// The vertices connected by the edge must conform to the edge
+    /// definition of this collection.
+    pub fn insert(&self, edge: NewEdge<T>) -> Result<DocumentHeader, Error> {
+        self.core.borrow_mut().run(self.connector.connection(&self.database_name)
+            .execute(InsertEdge::new(self.graph_name.clone(), self.collection_name.clone(), edge))
+        )
+    }
+
+    /// Replaces the edge with the given key.
+    pub fn replace(&self, key: DocumentKey, edge: NewEdge<T>) -> Result<UpdatedDocumentHeader, Error> {
+        let edge_id = DocumentId::new(self.collection_name.clone(), key.as_str());
+        self.core.borrow_mut().run(self.connector.connection(&self.database_name)
+            .execute(ReplaceEdge::new(self.graph_name.clone(), edge_id, edge))
+        )
+    }
+
+    /// Partially updates the edge with the given key.
+    pub fn update<Upd>(&self, key: DocumentKey, update: Upd) -> Result<UpdatedDocumentHeader, Error>
+        where Upd: 'static + Serialize + Debug
+    {
+        let edge_id = DocumentId::new(self.collection_name.clone(), key.as_str());
+        self.core.borrow_mut().run(self.connector.connection(&self.database_name)
+            .execute(ModifyEdge::new(self.graph_name.clone(), edge_id, update))
+        )
+    }
+
+    /// Removes the edge with the given key.
+    pub fn remove(&self, key: DocumentKey) -> Result<bool, Error> {
+        self.core.borrow_mut().run(self.connector.connection(&self.database_name)
+            .execute(RemoveEdge::with_key(self.graph_name.clone(), self.collection_name.clone(), key))
+        )
+    }
 }
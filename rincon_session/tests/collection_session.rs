@@ -0,0 +1,163 @@
+
+#[macro_use] extern crate hamcrest;
+#[macro_use] extern crate serde_derive;
+
+extern crate tokio_core;
+
+extern crate rincon_core;
+extern crate rincon_client;
+extern crate rincon_connector;
+extern crate rincon_session;
+extern crate rincon_test_helper;
+
+use hamcrest::prelude::*;
+
+use rincon_core::api::connector::Execute;
+use rincon_core::api::ErrorCode;
+use rincon_client::collection::methods::CreateCollection;
+use rincon_session::*;
+
+use rincon_test_helper::*;
+
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Customer {
+    name: String,
+    city: String,
+    age: u16,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+struct CustomerAge {
+    age: u16,
+}
+
+fn customer(name: &str, city: &str, age: u16) -> Customer {
+    Customer {
+        name: name.to_owned(),
+        city: city.to_owned(),
+        age,
+    }
+}
+
+#[test]
+fn insert_get_and_remove_document() {
+    arango_session_test_with_user_db("socius20", "the_social_network20", |connector, mut core| {
+        core.run(connector.connection("the_social_network20")
+            .execute(CreateCollection::documents_with_name("customers"))).unwrap();
+
+        let arango = ArangoSession::new(connector, core).unwrap();
+        let database = arango.use_database("the_social_network20").unwrap();
+        let customers = database.use_collection::<Customer>("customers");
+
+        let header = customers.insert(customer("Jane Doe", "Vienna", 42)).unwrap();
+        let document = customers.get(header.key().clone()).unwrap();
+
+        let jane = customer("Jane Doe", "Vienna", 42);
+        assert_that!(document.key(), is(equal_to(header.key())));
+        assert_that!(document.content(), is(equal_to(&jane)));
+
+        let removed = customers.remove(header.key().clone()).unwrap();
+
+        assert_that!(&removed, is(equal_to(&header)));
+        match customers.get(header.key().clone()) {
+            Err(Error::Method(ref error)) =>
+                assert_that!(error.error_code(), is(equal_to(ErrorCode::ArangoDocumentNotFound))),
+            ref result => panic!("Expected document not found error, but got: {:?}", result),
+        }
+    });
+}
+
+#[test]
+fn replace_and_update_document() {
+    arango_session_test_with_user_db("socius21", "the_social_network21", |connector, mut core| {
+        core.run(connector.connection("the_social_network21")
+            .execute(CreateCollection::documents_with_name("customers"))).unwrap();
+
+        let arango = ArangoSession::new(connector, core).unwrap();
+        let database = arango.use_database("the_social_network21").unwrap();
+        let customers = database.use_collection::<Customer>("customers");
+
+        let header = customers.insert(customer("Jane Doe", "Vienna", 42)).unwrap();
+
+        let replaced = customers.replace(DocumentUpdate::new(header.key().clone(),
+            customer("Jane Doe", "Graz", 42))).unwrap();
+
+        assert_that!(replaced.old_revision(), is(equal_to(header.revision())));
+
+        let updated = customers.update(DocumentUpdate::new(header.key().clone(),
+            CustomerAge { age: 43 })).unwrap();
+        let document = customers.get(header.key().clone()).unwrap();
+
+        let jane = customer("Jane Doe", "Graz", 43);
+        assert_that!(document.revision(), is(equal_to(updated.revision())));
+        assert_that!(document.content(), is(equal_to(&jane)));
+
+        let outdated = customers.replace(DocumentUpdate::new(header.key().clone(),
+            customer("Jane Doe", "Linz", 43)).with_revision(header.revision().clone()));
+
+        match outdated {
+            Err(Error::Method(ref error)) =>
+                assert_that!(error.error_code(), is(equal_to(ErrorCode::ArangoConflict))),
+            ref result => panic!("Expected conflict error, but got: {:?}", result),
+        }
+    });
+}
+
+#[test]
+fn insert_many_count_and_truncate() {
+    arango_session_test_with_user_db("socius22", "the_social_network22", |connector, mut core| {
+        core.run(connector.connection("the_social_network22")
+            .execute(CreateCollection::documents_with_name("customers"))).unwrap();
+
+        let arango = ArangoSession::new(connector, core).unwrap();
+        let database = arango.use_database("the_social_network22").unwrap();
+        let customers = database.use_collection::<Customer>("customers");
+
+        let headers = customers.insert_many(vec![
+            customer("Jane Doe", "Vienna", 42),
+            customer("John Doe", "Graz", 41),
+            customer("Jack Doe", "Vienna", 17),
+        ]).unwrap();
+
+        assert_that!(headers.iter().filter(|header| header.is_ok()).count(), is(equal_to(3)));
+        assert_that!(customers.count().unwrap(), is(equal_to(3)));
+
+        customers.truncate().unwrap();
+
+        assert_that!(customers.count().unwrap(), is(equal_to(0)));
+    });
+}
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn find_documents_by_example() {
+    arango_session_test_with_user_db("socius23", "the_social_network23", |connector, mut core| {
+        core.run(connector.connection("the_social_network23")
+            .execute(CreateCollection::documents_with_name("customers"))).unwrap();
+
+        let arango = ArangoSession::new(connector, core).unwrap();
+        let database = arango.use_database("the_social_network23").unwrap();
+        let customers = database.use_collection::<Customer>("customers");
+
+        customers.insert_many(vec![
+            customer("Jane Doe", "Vienna", 42),
+            customer("John Doe", "Graz", 42),
+            customer("Jack Doe", "Vienna", 17),
+        ]).unwrap();
+
+        #[derive(Serialize)]
+        struct Example<'a> {
+            city: &'a str,
+            age: u16,
+        }
+
+        let found: Result<Vec<_>, Error> = customers.find_by_example(&Example { city: "Vienna", age: 42 })
+            .unwrap().collect();
+        let found = found.unwrap();
+
+        let jane = customer("Jane Doe", "Vienna", 42);
+        assert_that!(found.len(), is(equal_to(1)));
+        assert_that!(found[0].content(), is(equal_to(&jane)));
+    });
+}
@@ -1,15 +1,19 @@
 
 #[macro_use] extern crate hamcrest;
-
+#[macro_use] extern crate serde_json;
 extern crate tokio_core;
 
 extern crate rincon_client;
 extern crate rincon_connector;
+extern crate rincon_core;
 extern crate rincon_session;
 extern crate rincon_test_helper;
 
 use hamcrest::prelude::*;
+use tokio_core::reactor::Core;
 
+use rincon_core::api::method::Operation;
+use rincon_connector::mock::{Expectation, MockConnector};
 use rincon_session::*;
 
 use rincon_test_helper::*;
@@ -31,3 +35,195 @@ fn create_graph() {
         assert_that!(graph_session.graph().name(), is(equal_to("social")));
     });
 }
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn execute_transaction() {
+    arango_session_test_with_user_db("socius11", "the_social_network11", |connector, core| {
+
+        let arango = ArangoSession::new(connector, core).unwrap();
+        let database = arango.use_database("the_social_network11").unwrap();
+
+        let transaction = NewTransaction::with_params(TransactionCollections::empty(),
+            "function (params) { return params[0] * params[1]; }",
+            vec![6, 7]);
+
+        let product: i32 = database.transaction(transaction).unwrap();
+
+        assert_that!(product, is(equal_to(42)));
+    });
+}
+
+#[test]
+fn iterate_over_query_results_in_batches() {
+    arango_session_test_with_user_db("socius12", "the_social_network12", |connector, core| {
+
+        let arango = ArangoSession::new(connector, core).unwrap();
+        let database = arango.use_database("the_social_network12").unwrap();
+
+        let mut new_cursor = NewCursor::from(Query::new("FOR i IN 1..10 RETURN i"));
+        new_cursor.set_batch_size(3);
+
+        let results: Result<Vec<i32>, Error> = database.query_opt_iter(new_cursor).unwrap().collect();
+
+        assert_that!(results.unwrap(), is(equal_to(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10])));
+    });
+}
+
+#[test]
+fn drop_query_iterator_before_all_results_are_fetched() {
+    arango_session_test_with_user_db("socius13", "the_social_network13", |connector, core| {
+
+        let arango = ArangoSession::new(connector, core).unwrap();
+        let database = arango.use_database("the_social_network13").unwrap();
+
+        let mut new_cursor = NewCursor::from(Query::new("FOR i IN 1..10 RETURN i"));
+        new_cursor.set_batch_size(3);
+
+        let results: Vec<i32> = {
+            let iter = database.query_opt_iter(new_cursor).unwrap();
+            assert_that!(iter.has_more(), is(equal_to(true)));
+            iter.take(4).map(|result| result.unwrap()).collect()
+        };
+
+        assert_that!(results, is(equal_to(vec![1, 2, 3, 4])));
+    });
+}
+
+#[test]
+fn register_aql_functions_only_once() {
+    arango_session_test_with_user_db("socius14", "the_social_network14", |connector, core| {
+
+        let arango = ArangoSession::new(connector, core).unwrap();
+        let database = arango.use_database("the_social_network14").unwrap();
+        let functions = vec![
+            NewAqlFunction::new("scoring::bm25", "function (tf, idf) { return tf * idf; }")
+                .with_deterministic(true),
+            NewAqlFunction::new("scoring::tfidf", "function (tf) { return tf; }"),
+        ];
+
+        let registered = database.register_aql_functions(functions.clone()).unwrap();
+
+        assert_that!(registered, is(equal_to(2)));
+
+        let registered = database.register_aql_functions(functions).unwrap();
+
+        assert_that!(registered, is(equal_to(0)));
+
+        let registered = database.register_aql_functions(vec![
+            NewAqlFunction::new("scoring::tfidf", "function (tf) { return 2 * tf; }"),
+        ]).unwrap();
+
+        assert_that!(registered, is(equal_to(1)));
+        let functions = database.aql_functions().unwrap();
+        assert_that!(functions.len(), is(equal_to(2)));
+    });
+}
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn register_aql_functions_twice_on_live_server() {
+    arango_session_test_with_user_db("socius15", "the_social_network15", |connector, core| {
+
+        let arango = ArangoSession::new(connector, core).unwrap();
+        let database = arango.use_database("the_social_network15").unwrap();
+        let functions = vec![
+            NewAqlFunction::new("scoring::bm25", "function (tf, idf) {\n  return tf * idf;\n}\n")
+                .with_deterministic(true),
+            NewAqlFunction::new("scoring::tfidf", "  function (tf) { return tf; }"),
+        ];
+
+        let registered = database.register_aql_functions(functions.clone()).unwrap();
+
+        assert_that!(registered, is(equal_to(2)));
+
+        let registered = database.register_aql_functions(functions).unwrap();
+
+        assert_that!(registered, is(equal_to(0)));
+    });
+}
+
+fn mock_cursor_server() -> MockConnector {
+    let connector = MockConnector::new();
+    connector.expect(Expectation::new(Operation::Create, "/_api/cursor")
+        .in_database("shop")
+        .returns_with_status(201, json!({
+            "id": "4711",
+            "result": [1, 2, 3],
+            "hasMore": true,
+            "error": false,
+            "code": 201,
+        })));
+    connector
+}
+
+#[test]
+fn dropping_query_iterator_early_deletes_the_cursor() {
+    let connector = mock_cursor_server();
+    connector.expect(Expectation::new(Operation::Delete, "/_api/cursor/4711")
+        .in_database("shop")
+        .returns_with_status(202, json!({ "id": "4711", "error": false, "code": 202 })));
+
+    let arango = ArangoSession::new(connector.clone(), Core::new().unwrap()).unwrap();
+    let database = arango.use_database("shop").unwrap();
+    let results: Vec<i32> = database.query_iter(Query::new("FOR i IN 1..10 RETURN i")).unwrap()
+        .take(2).map(|result| result.unwrap()).collect();
+
+    assert_that!(results, is(equal_to(vec![1, 2])));
+    connector.verify();
+}
+
+#[test]
+fn dropping_query_iterator_after_failed_fetch_deletes_the_cursor() {
+    let connector = mock_cursor_server();
+    connector.expect(Expectation::new(Operation::Replace, "/_api/cursor/4711")
+        .in_database("shop")
+        .fails_with(Error::Timeout("no response within 30s".into())));
+    connector.expect(Expectation::new(Operation::Delete, "/_api/cursor/4711")
+        .in_database("shop")
+        .returns_with_status(202, json!({ "id": "4711", "error": false, "code": 202 })));
+
+    let arango = ArangoSession::new(connector.clone(), Core::new().unwrap()).unwrap();
+    let database = arango.use_database("shop").unwrap();
+    let results: Result<Vec<i32>, Error> =
+        database.query_iter(Query::new("FOR i IN 1..10 RETURN i")).unwrap().collect();
+
+    assert_that!(results, is(equal_to(Err(Error::Timeout("no response within 30s".into())))));
+    connector.verify();
+}
+
+#[test]
+fn query_iterator_ends_after_failed_fetch() {
+    let connector = mock_cursor_server();
+    connector.expect(Expectation::new(Operation::Replace, "/_api/cursor/4711")
+        .in_database("shop")
+        .returns_with_status(404, json!({
+            "error": true,
+            "code": 404,
+            "errorNum": 1600,
+            "errorMessage": "cursor not found",
+        })));
+    connector.expect(Expectation::new(Operation::Delete, "/_api/cursor/4711")
+        .in_database("shop")
+        .returns_with_status(404, json!({
+            "error": true,
+            "code": 404,
+            "errorNum": 1600,
+            "errorMessage": "cursor not found",
+        })));
+
+    let arango = ArangoSession::new(connector.clone(), Core::new().unwrap()).unwrap();
+    let database = arango.use_database("shop").unwrap();
+    let mut results = Vec::new();
+    let mut errors = 0;
+    for result in database.query_iter(Query::new("FOR i IN 1..10 RETURN i")).unwrap().take(10) {
+        match result {
+            Ok(value) => results.push(value),
+            Err(_) => errors += 1,
+        }
+    }
+
+    assert_that!(results, is(equal_to(vec![1, 2, 3])));
+    assert_that!(errors, is(equal_to(1)));
+    connector.verify();
+}
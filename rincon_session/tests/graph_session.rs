@@ -0,0 +1,175 @@
+#[macro_use] extern crate hamcrest;
+#[macro_use] extern crate serde_derive;
+
+extern crate tokio_core;
+
+extern crate rincon_core;
+extern crate rincon_client;
+extern crate rincon_connector;
+extern crate rincon_session;
+extern crate rincon_test_helper;
+
+use hamcrest::prelude::*;
+
+use rincon_core::api::ErrorCode;
+use rincon_session::*;
+
+use rincon_test_helper::*;
+
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Person {
+    name: String,
+    age: u16,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+struct PersonAge {
+    age: u16,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Knows {
+    since: u16,
+}
+
+fn person(name: &str, age: u16) -> Person {
+    Person {
+        name: name.to_owned(),
+        age,
+    }
+}
+
+fn knows_graph() -> NewGraph {
+    NewGraph::with_name("social")
+        .with_edge_definitions(vec![
+            EdgeDefinition::new("knows", vec!["persons".to_owned()], vec!["persons".to_owned()]),
+        ])
+}
+
+#[test]
+fn insert_get_update_and_remove_vertex() {
+    arango_session_test_with_user_db("socius30", "the_social_network30", |connector, core| {
+
+        let arango = ArangoSession::new(connector, core).unwrap();
+        let database = arango.use_database("the_social_network30").unwrap();
+        let graph = database.create_graph(knows_graph()).unwrap();
+        let persons = graph.vertex_collection::<Person>("persons").unwrap();
+
+        let header = persons.insert(person("Alice", 42)).unwrap();
+        let vertex = persons.get(header.key().clone()).unwrap();
+
+        let alice = person("Alice", 42);
+        assert_that!(vertex.id(), is(equal_to(header.id())));
+        assert_that!(vertex.content(), is(equal_to(&alice)));
+
+        let updated = persons.update(header.key().clone(), PersonAge { age: 43 }).unwrap();
+
+        let vertex = persons.get(header.key().clone()).unwrap();
+        let alice = person("Alice", 43);
+        assert_that!(updated.old_revision(), is(equal_to(header.revision())));
+        assert_that!(vertex.content(), is(equal_to(&alice)));
+
+        persons.replace(header.key().clone(), person("Alicia", 44)).unwrap();
+
+        let vertex = persons.get(header.key().clone()).unwrap();
+        let alicia = person("Alicia", 44);
+        assert_that!(vertex.content(), is(equal_to(&alicia)));
+
+        let removed = persons.remove(header.key().clone()).unwrap();
+
+        assert_that!(removed, is(equal_to(true)));
+        match persons.get(header.key().clone()) {
+            Err(Error::Method(ref error)) =>
+                assert_that!(error.error_code(), is(equal_to(ErrorCode::ArangoDocumentNotFound))),
+            ref result => panic!("Expected document not found error, but got: {:?}", result),
+        }
+    });
+}
+
+#[test]
+fn insert_get_update_and_remove_edge() {
+    arango_session_test_with_user_db("socius31", "the_social_network31", |connector, core| {
+
+        let arango = ArangoSession::new(connector, core).unwrap();
+        let database = arango.use_database("the_social_network31").unwrap();
+        let graph = database.create_graph(knows_graph()).unwrap();
+        let persons = graph.vertex_collection::<Person>("persons").unwrap();
+        let knows = graph.edge_collection::<Knows>("knows").unwrap();
+
+        let alice = persons.insert(person("Alice", 42)).unwrap();
+        let bob = persons.insert(person("Bob", 39)).unwrap();
+
+        let header = knows.insert(NewEdge::new(alice.id().clone(), bob.id().clone(),
+            Knows { since: 2010 })).unwrap();
+        let edge = knows.get(header.key().clone()).unwrap();
+
+        assert_that!(edge.from(), is(equal_to(alice.id())));
+        assert_that!(edge.to(), is(equal_to(bob.id())));
+        assert_that!(edge.content(), is(equal_to(&Knows { since: 2010 })));
+
+        knows.update(header.key().clone(), Knows { since: 2012 }).unwrap();
+
+        assert_that!(knows.get(header.key().clone()).unwrap().content(),
+            is(equal_to(&Knows { since: 2012 })));
+
+        let removed = persons.remove(bob.key().clone()).unwrap();
+
+        assert_that!(removed, is(equal_to(true)));
+        match knows.get(header.key().clone()) {
+            Err(Error::Method(ref error)) =>
+                assert_that!(error.error_code(), is(equal_to(ErrorCode::ArangoDocumentNotFound))),
+            ref result => panic!("Expected document not found error, but got: {:?}", result),
+        }
+    });
+}
+
+#[test]
+fn add_and_remove_edge_definition_and_vertex_collection() {
+    arango_session_test_with_user_db("socius32", "the_social_network32", |connector, core| {
+
+        let arango = ArangoSession::new(connector, core).unwrap();
+        let database = arango.use_database("the_social_network32").unwrap();
+        let mut graph = database.create_graph(knows_graph()).unwrap();
+
+        graph.add_edge_definition(EdgeDefinition::new("lives_in",
+            vec!["persons".to_owned()], vec!["cities".to_owned()])).unwrap();
+
+        assert_that!(graph.graph().edge_definitions().len(), is(equal_to(2)));
+
+        graph.add_vertex_collection("companies").unwrap();
+
+        let orphans = ["companies".to_owned()];
+        assert_that!(graph.graph().orphan_collections(), is(equal_to(&orphans[..])));
+
+        graph.remove_vertex_collection("companies").unwrap();
+        graph.remove_edge_definition("lives_in").unwrap();
+
+        let orphans = ["cities".to_owned()];
+        assert_that!(graph.graph().edge_definitions().len(), is(equal_to(1)));
+        assert_that!(graph.graph().orphan_collections(), is(equal_to(&orphans[..])));
+    });
+}
+
+#[test]
+fn use_and_drop_graph() {
+    arango_session_test_with_user_db("socius33", "the_social_network33", |connector, core| {
+
+        let arango = ArangoSession::new(connector, core).unwrap();
+        let database = arango.use_database("the_social_network33").unwrap();
+        database.create_graph(knows_graph()).unwrap();
+
+        let graph = database.use_graph("social").unwrap();
+
+        assert_that!(graph.name(), is(equal_to("social")));
+
+        let dropped = graph.drop().unwrap();
+
+        assert_that!(dropped, is(equal_to(true)));
+        match database.use_graph("social") {
+            Err(Error::Method(ref error)) =>
+                assert_that!(error.error_code(), is(equal_to(ErrorCode::GraphNotFound))),
+            ref result => panic!("Expected graph not found error, but got: {:?}", result),
+        }
+    });
+}
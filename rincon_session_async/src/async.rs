@@ -1,29 +1,68 @@
 
+use std::fmt::{self, Debug};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
 use std::rc::Rc;
+use std::vec;
 
-use futures::Future;
+use futures::{future, Async, Future, Poll, Stream};
+use futures::future::Executor;
 use serde::de::DeserializeOwned;
 use serde::ser::Serialize;
 
 pub use rincon_core::api::connector::Error;
 pub use rincon_core::api::query::Query;
 pub use rincon_core::api::types::Empty;
+pub use rincon_client::admin::types::{ServerInfo, ServerRole, ServerVersion};
+pub use rincon_client::aql::types::{AqlFunction, NewAqlFunction};
 pub use rincon_client::cursor::types::{Cursor, NewCursor};
 pub use rincon_client::database::types::{Database, NewDatabase};
-pub use rincon_client::graph::types::{EdgeDefinition, Graph, NewGraph};
+pub use rincon_client::document::types::{Document, DocumentHeader, DocumentId,
+    DocumentKey, NewDocument, UpdatedDocumentHeader};
+pub use rincon_client::graph::types::{Edge, EdgeDefinition, Graph, NewEdge, NewGraph,
+    VertexCollection};
+pub use rincon_client::transaction::types::{NewTransaction, TransactionCollections};
 pub use rincon_client::user::types::{NewUser, UserExtra};
 
 use rincon_core::api::connector::{Connector, Execute};
+use rincon_core::api::method::{Method, Prepare};
 use rincon_core::arango::protocol::SYSTEM_DATABASE;
-use rincon_client::cursor::methods::CreateCursor;
+use rincon_client::admin::methods::{GetServerId, GetServerRole, GetServerVersion};
+use rincon_client::aql::methods::{CreateAqlFunction, ListAqlFunctions};
+use rincon_client::cursor::methods::{CreateCursor, DeleteCursor, ReadNextBatchFromCursor};
 use rincon_client::database::methods::{CreateDatabase, DropDatabase};
-use rincon_client::graph::methods::CreateGraph;
+use rincon_client::graph::methods::{AddEdgeDefinition, AddVertexCollection, CreateGraph,
+    DropGraph, GetEdge, GetGraph, GetVertex, InsertEdge, InsertVertex, ModifyEdge,
+    ModifyVertex, RemoveEdge, RemoveEdgeDefinition, RemoveVertex, RemoveVertexCollection,
+    ReplaceEdge, ReplaceVertex};
+use rincon_client::transaction::methods::ExecuteTransaction;
 
 pub type FutureResult<T> = Box<Future<Item=T, Error=Error>>;
 
+/// A future that is spawned by a session to do some work in the background,
+/// like deleting a cursor that is no longer needed.
+pub type BackgroundTask = Box<dyn Future<Item=(), Error=()>>;
+
+#[derive(Clone)]
+struct TaskExecutor(Rc<dyn Executor<BackgroundTask>>);
+
+impl TaskExecutor {
+    fn spawn(&self, task: BackgroundTask) {
+        let _ = self.0.execute(task);
+    }
+}
+
+impl Debug for TaskExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("TaskExecutor")
+    }
+}
+
 #[derive(Debug)]
 pub struct ArangoSession<C> {
     connector: Rc<C>,
+    executor: Option<TaskExecutor>,
+    server: Option<ServerInfo>,
 }
 
 impl<C> ArangoSession<C>
@@ -32,31 +71,82 @@ impl<C> ArangoSession<C>
     pub fn new(connector: C) -> Self {
         ArangoSession {
             connector: Rc::new(connector),
+            executor: None,
+            server: None,
+        }
+    }
+
+    /// Creates a new `ArangoSession` that uses the given executor to spawn
+    /// background tasks.
+    ///
+    /// Background tasks are used to clean up resources on the server, like
+    /// deleting cursors of streams that are dropped before all results have
+    /// been fetched. A `tokio_core::reactor::Handle` can be used as executor.
+    pub fn with_executor<E>(connector: C, executor: E) -> Self
+        where E: 'static + Executor<BackgroundTask>
+    {
+        ArangoSession {
+            connector: Rc::new(connector),
+            executor: Some(TaskExecutor(Rc::new(executor))),
+            server: None,
         }
     }
 
+    /// Connects this session to the server.
+    ///
+    /// When connecting, the role, the id and the license of the server are
+    /// detected. The returned session provides them through the `server()`
+    /// function.
+    pub fn connect(self) -> FutureResult<Self> {
+        let connection = self.connector.system_connection();
+        let version = connection.execute(GetServerVersion::new());
+        Box::new(connection.execute(GetServerRole::new())
+            .and_then(move |role| {
+                let id: FutureResult<Option<String>> = if role.is_cluster() {
+                    Box::new(connection.execute(GetServerId::new()).map(Some))
+                } else {
+                    Box::new(future::ok(None))
+                };
+                id.join(version).map(move |(id, version)| ServerInfo::new(role, id, version))
+            })
+            .map(move |server| ArangoSession {
+                server: Some(server),
+                ..self
+            })
+        )
+    }
+
+    /// Returns the role, the id and the license of the server.
+    ///
+    /// Returns `None` if this session has not been connected to the server
+    /// by calling the `connect()` function.
+    pub fn server(&self) -> Option<&ServerInfo> {
+        self.server.as_ref()
+    }
+
     pub fn close(self) {
         //TODO see if a close() method has any purpose
     }
 
     pub fn use_system_database(&self) -> DatabaseSession<C> {
-        DatabaseSession::new(SYSTEM_DATABASE.to_owned(), self.connector.clone())
+        DatabaseSession::new(SYSTEM_DATABASE.to_owned(), self.connector.clone(), self.executor.clone())
     }
 
     pub fn use_database<DbName>(&self, database_name: DbName) -> DatabaseSession<C>
         where DbName: Into<String>
     {
-        DatabaseSession::new(database_name.into(), self.connector.clone())
+        DatabaseSession::new(database_name.into(), self.connector.clone(), self.executor.clone())
     }
 
     pub fn create_database<UserInfo>(&self, new_database: NewDatabase<UserInfo>) -> FutureResult<DatabaseSession<C>>
         where UserInfo: UserExtra + Serialize + 'static
     {
         let connector = self.connector.clone();
+        let executor = self.executor.clone();
         let database_name = new_database.name().to_owned();
         Box::new(self.connector.system_connection()
             .execute(CreateDatabase::new(new_database))
-                .map(move |_| DatabaseSession::new(database_name, connector))
+                .map(move |_| DatabaseSession::new(database_name, connector, executor))
         )
     }
 }
@@ -65,15 +155,17 @@ impl<C> ArangoSession<C>
 pub struct DatabaseSession<C> {
     database_name: String,
     connector: Rc<C>,
+    executor: Option<TaskExecutor>,
 }
 
 impl<C> DatabaseSession<C>
     where C: 'static + Connector
 {
-    fn new(database_name: String, connector: Rc<C>) -> Self {
+    fn new(database_name: String, connector: Rc<C>, executor: Option<TaskExecutor>) -> Self {
         DatabaseSession {
             database_name,
             connector,
+            executor,
         }
     }
 
@@ -122,16 +214,255 @@ impl<C> DatabaseSession<C>
         )
     }
 
+    /// Executes a query and returns a stream of all results.
+    ///
+    /// The stream fetches further batches of results from the server on
+    /// demand. If the stream is dropped before all results are fetched the
+    /// cursor on the server is deleted, provided the session has been
+    /// created with an executor. Otherwise the cursor is left to expire on
+    /// the server.
+    ///
+    /// All cursor options and query execution options are left to their
+    /// default settings.
+    ///
+    /// To specify cursor options and/or query execution options use the
+    /// `query_opt_stream(&self, NewCursor)` function.
+    pub fn query_stream<T>(&self, query: Query) -> FutureResult<CursorStream<T, C>>
+        where T: 'static + DeserializeOwned
+    {
+        let database_name = self.database_name.clone();
+        let connector = self.connector.clone();
+        let executor = self.executor.clone();
+        Box::new(self.query(query)
+            .map(move |cursor| CursorStream::new(cursor, database_name, connector, executor))
+        )
+    }
+
+    /// Executes a query and returns a stream of all results.
+    ///
+    /// The stream fetches further batches of results from the server on
+    /// demand. If the stream is dropped before all results are fetched the
+    /// cursor on the server is deleted, provided the session has been
+    /// created with an executor.
+    ///
+    /// It requires a `NewCursor` struct as a parameter which allows full
+    /// control over all supported cursor options and query execution options.
+    pub fn query_opt_stream<T>(&self, new_cursor: NewCursor) -> FutureResult<CursorStream<T, C>>
+        where T: 'static + DeserializeOwned
+    {
+        let database_name = self.database_name.clone();
+        let connector = self.connector.clone();
+        let executor = self.executor.clone();
+        Box::new(self.query_opt(new_cursor)
+            .map(move |cursor| CursorStream::new(cursor, database_name, connector, executor))
+        )
+    }
+
+    /// Returns a stream of all results of the given cursor.
+    ///
+    /// The stream starts with the results contained in the given cursor
+    /// and fetches further batches of results from the server on demand.
+    pub fn cursor_stream<T>(&self, cursor: Cursor<T>) -> CursorStream<T, C>
+        where T: 'static + DeserializeOwned
+    {
+        CursorStream::new(cursor, self.database_name.clone(), self.connector.clone(), self.executor.clone())
+    }
+
+    /// Executes a transaction in the database represented by this
+    /// `DatabaseSession` and returns the result of its action.
+    ///
+    /// The transaction is committed if the action returns normally and it
+    /// is rolled back if the action throws an exception.
+    pub fn transaction<P, R>(&self, transaction: NewTransaction<P>) -> FutureResult<R>
+        where P: 'static + Serialize, R: 'static + DeserializeOwned
+    {
+        Box::new(self.connector.connection(&self.database_name)
+            .execute(ExecuteTransaction::new(transaction))
+        )
+    }
+
     /// Creates a new graph in the database represented by this
     /// `DatabaseSession`.
     pub fn create_graph(&self, new_graph: NewGraph) -> FutureResult<GraphSession<C>> {
         let connector = self.connector.clone();
+        let executor = self.executor.clone();
         let database_name = self.database_name.clone();
         Box::new(self.connector.connection(&self.database_name)
             .execute(CreateGraph::new(new_graph))
-                .map(|graph| GraphSession::new(graph, database_name, connector))
+                .map(|graph| GraphSession::new(graph, database_name, connector, executor))
         )
     }
+
+    /// Returns a session for the existing graph with the given name.
+    pub fn use_graph(&self, graph_name: &str) -> FutureResult<GraphSession<C>> {
+        let connector = self.connector.clone();
+        let executor = self.executor.clone();
+        let database_name = self.database_name.clone();
+        Box::new(self.connector.connection(&self.database_name)
+            .execute(GetGraph::with_name(graph_name))
+                .map(|graph| GraphSession::new(graph, database_name, connector, executor))
+        )
+    }
+
+    /// Returns the AQL user functions registered in the database represented
+    /// by this `DatabaseSession`.
+    pub fn aql_functions(&self) -> FutureResult<Vec<AqlFunction>> {
+        Box::new(self.connector.connection(&self.database_name)
+            .execute(ListAqlFunctions::new())
+        )
+    }
+
+    /// Registers the given AQL user functions in the database represented by
+    /// this `DatabaseSession` and returns the number of functions that have
+    /// been created or replaced.
+    ///
+    /// Functions that are already registered with the same code are left
+    /// untouched. Thus this function can be called on every start of an
+    /// application.
+    pub fn register_aql_functions<F>(&self, functions: F) -> FutureResult<usize>
+        where F: IntoIterator<Item=NewAqlFunction>
+    {
+        let functions = Vec::from_iter(functions);
+        let connector = self.connector.clone();
+        let database_name = self.database_name.clone();
+        Box::new(self.aql_functions()
+            .and_then(move |registered| {
+                let created = functions.into_iter()
+                    .filter(|function| !registered.iter().any(|existing| function.is_registered_as(existing)))
+                    .map(|function| connector.connection(&database_name)
+                        .execute(CreateAqlFunction::new(function)))
+                    .collect::<Vec<_>>();
+                future::join_all(created).map(|created| created.len())
+            })
+        )
+    }
+}
+
+/// A stream of all results of a cursor.
+///
+/// The stream fetches the next batch of results from the server whenever
+/// the results of the current batch are consumed. If the stream is dropped
+/// before all results are fetched the cursor is deleted on the server,
+/// provided the session has been created with an executor.
+///
+/// After a batch could not be fetched the stream ends. Fetching the next
+/// batch again would skip the results of the failed batch.
+pub struct CursorStream<T, C>
+    where C: 'static + Connector
+{
+    batch: vec::IntoIter<T>,
+    next_batch: Option<FutureResult<Cursor<T>>>,
+    cursor_id: Option<String>,
+    has_more: bool,
+    failed: bool,
+    database_name: String,
+    connector: Rc<C>,
+    executor: Option<TaskExecutor>,
+}
+
+impl<T, C> CursorStream<T, C>
+    where T: 'static + DeserializeOwned, C: 'static + Connector
+{
+    fn new(cursor: Cursor<T>, database_name: String, connector: Rc<C>, executor: Option<TaskExecutor>) -> Self {
+        let cursor_id = cursor.id().cloned();
+        let has_more = cursor.has_more();
+        CursorStream {
+            batch: cursor.into_result().into_iter(),
+            next_batch: None,
+            cursor_id,
+            has_more,
+            failed: false,
+            database_name,
+            connector,
+            executor,
+        }
+    }
+
+    /// Returns the id of the cursor on the server if there are more results
+    /// to be fetched.
+    pub fn cursor_id(&self) -> Option<&String> {
+        self.cursor_id.as_ref()
+    }
+
+    /// Returns whether there are more results to be fetched from the server.
+    pub fn has_more(&self) -> bool {
+        self.has_more
+    }
+}
+
+impl<T, C> Debug for CursorStream<T, C>
+    where T: Debug, C: 'static + Connector + Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CursorStream")
+            .field("batch", &self.batch)
+            .field("fetching", &self.next_batch.is_some())
+            .field("cursor_id", &self.cursor_id)
+            .field("has_more", &self.has_more)
+            .field("failed", &self.failed)
+            .field("database_name", &self.database_name)
+            .field("connector", &self.connector)
+            .field("executor", &self.executor)
+            .finish()
+    }
+}
+
+impl<T, C> Stream for CursorStream<T, C>
+    where T: 'static + DeserializeOwned, C: 'static + Connector
+{
+    type Item = T;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        loop {
+            if let Some(result) = self.batch.next() {
+                return Ok(Async::Ready(Some(result)));
+            }
+            if let Some(mut next_batch) = self.next_batch.take() {
+                match next_batch.poll() {
+                    Ok(Async::Ready(cursor)) => {
+                        self.cursor_id = cursor.id().cloned();
+                        self.has_more = cursor.has_more();
+                        self.batch = cursor.into_result().into_iter();
+                        continue;
+                    },
+                    Ok(Async::NotReady) => {
+                        self.next_batch = Some(next_batch);
+                        return Ok(Async::NotReady);
+                    },
+                    Err(error) => {
+                        self.failed = true;
+                        return Err(error);
+                    },
+                }
+            }
+            if !self.has_more || self.failed {
+                return Ok(Async::Ready(None));
+            }
+            match self.cursor_id.clone() {
+                Some(cursor_id) => {
+                    self.next_batch = Some(Box::new(self.connector.connection(&self.database_name)
+                        .execute(ReadNextBatchFromCursor::new(cursor_id))));
+                },
+                None => return Ok(Async::Ready(None)),
+            }
+        }
+    }
+}
+
+impl<T, C> Drop for CursorStream<T, C>
+    where C: 'static + Connector
+{
+    fn drop(&mut self) {
+        if !self.has_more {
+            return;
+        }
+        if let (Some(cursor_id), Some(executor)) = (self.cursor_id.take(), self.executor.as_ref()) {
+            executor.spawn(Box::new(self.connector.connection(&self.database_name)
+                .execute(DeleteCursor::new(cursor_id))
+                    .map(|_| ()).map_err(|_| ())));
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -139,20 +470,261 @@ pub struct GraphSession<C> {
     graph: Graph,
     database_name: String,
     connector: Rc<C>,
+    executor: Option<TaskExecutor>,
 }
 
 impl<C> GraphSession<C>
     where C: 'static + Connector
 {
-    fn new(graph: Graph, database_name: String, connector: Rc<C>) -> Self {
+    fn new(graph: Graph, database_name: String, connector: Rc<C>, executor: Option<TaskExecutor>) -> Self {
         GraphSession {
             graph,
             database_name,
             connector,
+            executor,
         }
     }
 
+    pub fn name(&self) -> &str {
+        self.graph.name()
+    }
+
     pub fn graph(&self) -> &Graph {
         &self.graph
     }
+
+    /// Returns a session for the vertex collection with the given name that
+    /// stores vertices with content of type `T`.
+    ///
+    /// The collection is not checked for being part of the graph.
+    pub fn vertex_collection<T>(&self, collection_name: &str) -> VertexCollectionSession<T, C>
+        where T: 'static + Serialize + DeserializeOwned + Debug
+    {
+        VertexCollectionSession::new(self.graph.name().to_owned(), collection_name.to_owned(),
+            self.database_name.clone(), self.connector.clone())
+    }
+
+    /// Returns a session for the edge collection with the given name that
+    /// stores edges with content of type `T`.
+    ///
+    /// The collection is not checked for being part of the graph.
+    pub fn edge_collection<T>(&self, collection_name: &str) -> EdgeCollectionSession<T, C>
+        where T: 'static + Serialize + DeserializeOwned + Debug
+    {
+        EdgeCollectionSession::new(self.graph.name().to_owned(), collection_name.to_owned(),
+            self.database_name.clone(), self.connector.clone())
+    }
+
+    /// Adds a vertex collection to the graph and returns a session for the
+    /// updated graph.
+    ///
+    /// The collection is created if it does not exist.
+    pub fn add_vertex_collection(self, collection_name: &str) -> FutureResult<GraphSession<C>> {
+        let method = AddVertexCollection::new(self.graph.name(), VertexCollection::new(collection_name));
+        self.update_graph(method)
+    }
+
+    /// Removes a vertex collection from the graph and returns a session for
+    /// the updated graph.
+    ///
+    /// Only vertex collections that are not used in an edge definition can
+    /// be removed. The collection itself is not dropped.
+    pub fn remove_vertex_collection(self, collection_name: &str) -> FutureResult<GraphSession<C>> {
+        let method = RemoveVertexCollection::new(self.graph.name(), collection_name);
+        self.update_graph(method)
+    }
+
+    /// Adds an edge definition to the graph and returns a session for the
+    /// updated graph.
+    ///
+    /// Collections of the edge definition that do not exist are created.
+    pub fn add_edge_definition(self, edge_definition: EdgeDefinition) -> FutureResult<GraphSession<C>> {
+        let method = AddEdgeDefinition::new(self.graph.name(), edge_definition);
+        self.update_graph(method)
+    }
+
+    /// Removes the edge definition for the given edge collection from the
+    /// graph and returns a session for the updated graph.
+    ///
+    /// The collections of the edge definition are not dropped.
+    pub fn remove_edge_definition(self, edge_definition_name: &str) -> FutureResult<GraphSession<C>> {
+        let method = RemoveEdgeDefinition::new(self.graph.name(), edge_definition_name);
+        self.update_graph(method)
+    }
+
+    /// Drops the graph that is used in this session.
+    ///
+    /// The collections of the graph are not dropped. After calling this
+    /// function the associated `GraphSession` is no longer valid.
+    pub fn drop(self) -> FutureResult<bool> {
+        Box::new(self.connector.connection(&self.database_name)
+            .execute(DropGraph::with_name(self.graph.name()))
+        )
+    }
+
+    fn update_graph<M>(self, method: M) -> FutureResult<GraphSession<C>>
+        where M: 'static + Method<Result=Graph> + Prepare
+    {
+        let GraphSession { database_name, connector, executor, .. } = self;
+        Box::new(connector.connection(&database_name)
+            .execute(method)
+                .map(move |graph| GraphSession::new(graph, database_name, connector, executor))
+        )
+    }
+}
+
+/// A session for the vertices of one vertex collection of a graph.
+///
+/// The content of the vertices is of type `T`.
+#[derive(Debug)]
+pub struct VertexCollectionSession<T, C> {
+    graph_name: String,
+    collection_name: String,
+    database_name: String,
+    connector: Rc<C>,
+    content: PhantomData<T>,
+}
+
+impl<T, C> VertexCollectionSession<T, C>
+    where T: 'static + Serialize + DeserializeOwned + Debug, C: 'static + Connector
+{
+    fn new(graph_name: String, collection_name: String, database_name: String, connector: Rc<C>) -> Self {
+        VertexCollectionSession {
+            graph_name,
+            collection_name,
+            database_name,
+            connector,
+            content: PhantomData,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.collection_name
+    }
+
+    pub fn graph_name(&self) -> &str {
+        &self.graph_name
+    }
+
+    /// Fetches the vertex with the given key.
+    pub fn get(&self, key: DocumentKey) -> FutureResult<Document<T>> {
+        Box::new(self.connector.connection(&self.database_name)
+            .execute(GetVertex::with_key(self.graph_name.clone(), self.collection_name.clone(), key))
+        )
+    }
+
+    /// Inserts a new vertex and returns its header.
+    pub fn insert<D>(&self, vertex: D) -> FutureResult<DocumentHeader>
+        where D: Into<NewDocument<T>>
+    {
+        Box::new(self.connector.connection(&self.database_name)
+            .execute(InsertVertex::new(self.graph_name.clone(), self.collection_name.clone(), vertex.into()))
+        )
+    }
+
+    /// Replaces the vertex with the given key.
+    pub fn replace<D>(&self, key: DocumentKey, vertex: D) -> FutureResult<UpdatedDocumentHeader>
+        where D: Into<NewDocument<T>>
+    {
+        let vertex_id = DocumentId::new(self.collection_name.clone(), key.as_str());
+        Box::new(self.connector.connection(&self.database_name)
+            .execute(ReplaceVertex::new(self.graph_name.clone(), vertex_id, vertex.into()))
+        )
+    }
+
+    /// Partially updates the vertex with the given key.
+    pub fn update<Upd>(&self, key: DocumentKey, update: Upd) -> FutureResult<UpdatedDocumentHeader>
+        where Upd: 'static + Serialize + Debug
+    {
+        let vertex_id = DocumentId::new(self.collection_name.clone(), key.as_str());
+        Box::new(self.connector.connection(&self.database_name)
+            .execute(ModifyVertex::new(self.graph_name.clone(), vertex_id, update))
+        )
+    }
+
+    /// Removes the vertex with the given key.
+    ///
+    /// All edges of the graph that are connected to the vertex are removed
+    /// as well.
+    pub fn remove(&self, key: DocumentKey) -> FutureResult<bool> {
+        Box::new(self.connector.connection(&self.database_name)
+            .execute(RemoveVertex::with_key(self.graph_name.clone(), self.collection_name.clone(), key))
+        )
+    }
+}
+
+/// A session for the edges of one edge collection of a graph.
+///
+/// The content of the edges is of type `T`.
+#[derive(Debug)]
+pub struct EdgeCollectionSession<T, C> {
+    graph_name: String,
+    collection_name: String,
+    database_name: String,
+    connector: Rc<C>,
+    content: PhantomData<T>,
+}
+
+impl<T, C> EdgeCollectionSession<T, C>
+    where T: 'static + Serialize + DeserializeOwned + Debug, C: 'static + Connector
+{
+    fn new(graph_name: String, collection_name: String, database_name: String, connector: Rc<C>) -> Self {
+        EdgeCollectionSession {
+            graph_name,
+            collection_name,
+            database_name,
+            connector,
+            content: PhantomData,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.collection_name
+    }
+
+    pub fn graph_name(&self) -> &str {
+        &self.graph_name
+    }
+
+    /// Fetches the edge with the given key.
+    pub fn get(&self, key: DocumentKey) -> FutureResult<Edge<T>> {
+        Box::new(self.connector.connection(&self.database_name)
+            .execute(GetEdge::with_key(self.graph_name.clone(), self.collection_name.clone(), key))
+        )
+    }
+
+    /// Inserts a new edge and returns its header.
+    ///
+    /// The vertices connected by the edge must conform to the edge
+    /// definition of this collection.
+    pub fn insert(&self, edge: NewEdge<T>) -> FutureResult<DocumentHeader> {
+        Box::new(self.connector.connection(&self.database_name)
+            .execute(InsertEdge::new(self.graph_name.clone(), self.collection_name.clone(), edge))
+        )
+    }
+
+    /// Replaces the edge with the given key.
+    pub fn replace(&self, key: DocumentKey, edge: NewEdge<T>) -> FutureResult<UpdatedDocumentHeader> {
+        let edge_id = DocumentId::new(self.collection_name.clone(), key.as_str());
+        Box::new(self.connector.connection(&self.database_name)
+            .execute(ReplaceEdge::new(self.graph_name.clone(), edge_id, edge))
+        )
+    }
+
+    /// Partially updates the edge with the given key.
+    pub fn update<Upd>(&self, key: DocumentKey, update: Upd) -> FutureResult<UpdatedDocumentHeader>
+        where Upd: 'static + Serialize + Debug
+    {
+        let edge_id = DocumentId::new(self.collection_name.clone(), key.as_str());
+        Box::new(self.connector.connection(&self.database_name)
+            .execute(ModifyEdge::new(self.graph_name.clone(), edge_id, update))
+        )
+    }
+
+    /// Removes the edge with the given key.
+    pub fn remove(&self, key: DocumentKey) -> FutureResult<bool> {
+        Box::new(self.connector.connection(&self.database_name)
+            .execute(RemoveEdge::with_key(self.graph_name.clone(), self.collection_name.clone(), key))
+        )
+    }
 }
@@ -50,3 +50,24 @@ fn use_database() {
 
     assert_that!(database.name(), is(equal_to("the_social_network")));
 }
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn detect_server_when_connecting() {
+    arango_session_test(|connector, mut core| {
+
+        let arango = ArangoSession::new(connector);
+        assert_that!(arango.server().is_some(), is(equal_to(false)));
+
+        let arango = core.run(arango.connect()).unwrap();
+
+        let server = arango.server().unwrap();
+        assert_that!(server.role(), is(equal_to(ServerRole::Single)));
+        assert_that!(server.is_cluster(), is(equal_to(false)));
+        assert_that!(server.id(), is(equal_to(None)));
+        assert_that!(server.version().server(), is(equal_to("arango")));
+        assert_that!(server.is_enterprise(), is(equal_to(false)));
+    },
+    |_, _| {
+    });
+}
@@ -1,15 +1,23 @@
 
 #[macro_use] extern crate hamcrest;
-
+#[macro_use] extern crate serde_json;
+extern crate futures;
 extern crate tokio_core;
 
 extern crate rincon_client;
 extern crate rincon_connector;
+extern crate rincon_core;
 extern crate rincon_session_async;
 extern crate rincon_test_helper;
 
+use std::time::Duration;
+
+use futures::{Future, Stream};
 use hamcrest::prelude::*;
+use tokio_core::reactor::Core;
 
+use rincon_core::api::method::Operation;
+use rincon_connector::mock::{Expectation, MockConnector};
 use rincon_session_async::*;
 
 use rincon_test_helper::*;
@@ -31,3 +39,153 @@ fn create_graph() {
         assert_that!(graph_session.graph().name(), is(equal_to("social")));
     });
 }
+
+#[test]
+fn stream_query_results_in_batches() {
+    arango_session_test_with_user_db("socius12", "the_social_network12", |connector, mut core| {
+
+        let arango = ArangoSession::with_executor(connector, core.handle());
+        let database = arango.use_database("the_social_network12");
+
+        let mut new_cursor = NewCursor::from(Query::new("FOR i IN 1..10 RETURN i"));
+        new_cursor.set_batch_size(3);
+
+        let results: Vec<i32> = core.run(database.query_opt_stream(new_cursor)
+            .and_then(|stream| stream.collect())).unwrap();
+
+        assert_that!(results, is(equal_to(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10])));
+    });
+}
+
+#[test]
+fn register_aql_functions_only_once() {
+    arango_session_test_with_user_db("socius14", "the_social_network14", |connector, mut core| {
+
+        let arango = ArangoSession::new(connector);
+        let database = arango.use_database("the_social_network14");
+        let functions = vec![
+            NewAqlFunction::new("scoring::bm25", "function (tf, idf) { return tf * idf; }")
+                .with_deterministic(true),
+            NewAqlFunction::new("scoring::tfidf", "function (tf) { return tf; }"),
+        ];
+
+        let registered = core.run(database.register_aql_functions(functions.clone())).unwrap();
+
+        assert_that!(registered, is(equal_to(2)));
+
+        let registered = core.run(database.register_aql_functions(functions)).unwrap();
+
+        assert_that!(registered, is(equal_to(0)));
+    });
+}
+
+#[test]
+#[cfg_attr(feature = "fake_server", ignore)]
+fn register_aql_functions_twice_on_live_server() {
+    arango_session_test_with_user_db("socius15", "the_social_network15", |connector, mut core| {
+
+        let arango = ArangoSession::new(connector);
+        let database = arango.use_database("the_social_network15");
+        let functions = vec![
+            NewAqlFunction::new("scoring::bm25", "function (tf, idf) {\n  return tf * idf;\n}\n")
+                .with_deterministic(true),
+            NewAqlFunction::new("scoring::tfidf", "  function (tf) { return tf; }"),
+        ];
+
+        let registered = core.run(database.register_aql_functions(functions.clone())).unwrap();
+
+        assert_that!(registered, is(equal_to(2)));
+
+        let registered = core.run(database.register_aql_functions(functions)).unwrap();
+
+        assert_that!(registered, is(equal_to(0)));
+    });
+}
+
+fn mock_cursor_server() -> MockConnector {
+    let connector = MockConnector::new();
+    connector.expect(Expectation::new(Operation::Create, "/_api/cursor")
+        .in_database("shop")
+        .returns_with_status(201, json!({
+            "id": "4711",
+            "result": [1, 2, 3],
+            "hasMore": true,
+            "error": false,
+            "code": 201,
+        })));
+    connector
+}
+
+#[test]
+fn dropping_query_stream_early_deletes_the_cursor() {
+    let connector = mock_cursor_server();
+    connector.expect(Expectation::new(Operation::Delete, "/_api/cursor/4711")
+        .in_database("shop")
+        .returns_with_status(202, json!({ "id": "4711", "error": false, "code": 202 })));
+    let mut core = Core::new().unwrap();
+
+    let arango = ArangoSession::with_executor(connector.clone(), core.handle());
+    let database = arango.use_database("shop");
+    let results: Vec<i32> = core.run(database.query_stream(Query::new("FOR i IN 1..10 RETURN i"))
+        .and_then(|stream| stream.take(2).collect())).unwrap();
+    core.turn(Some(Duration::from_millis(10)));
+
+    assert_that!(results, is(equal_to(vec![1, 2])));
+    connector.verify();
+}
+
+#[test]
+fn dropping_query_stream_after_failed_fetch_deletes_the_cursor() {
+    let connector = mock_cursor_server();
+    connector.expect(Expectation::new(Operation::Replace, "/_api/cursor/4711")
+        .in_database("shop")
+        .fails_with(Error::Timeout("no response within 30s".into())));
+    connector.expect(Expectation::new(Operation::Delete, "/_api/cursor/4711")
+        .in_database("shop")
+        .returns_with_status(202, json!({ "id": "4711", "error": false, "code": 202 })));
+    let mut core = Core::new().unwrap();
+
+    let arango = ArangoSession::with_executor(connector.clone(), core.handle());
+    let database = arango.use_database("shop");
+    let results: Result<Vec<i32>, Error> = core.run(database
+        .query_stream(Query::new("FOR i IN 1..10 RETURN i"))
+        .and_then(|stream| stream.collect()));
+    core.turn(Some(Duration::from_millis(10)));
+
+    assert_that!(results, is(equal_to(Err(Error::Timeout("no response within 30s".into())))));
+    connector.verify();
+}
+
+#[test]
+fn query_stream_ends_after_failed_fetch() {
+    let connector = mock_cursor_server();
+    connector.expect(Expectation::new(Operation::Replace, "/_api/cursor/4711")
+        .in_database("shop")
+        .returns_with_status(404, json!({
+            "error": true,
+            "code": 404,
+            "errorNum": 1600,
+            "errorMessage": "cursor not found",
+        })));
+    connector.expect(Expectation::new(Operation::Delete, "/_api/cursor/4711")
+        .in_database("shop")
+        .returns_with_status(404, json!({
+            "error": true,
+            "code": 404,
+            "errorNum": 1600,
+            "errorMessage": "cursor not found",
+        })));
+    let mut core = Core::new().unwrap();
+
+    let arango = ArangoSession::with_executor(connector.clone(), core.handle());
+    let database = arango.use_database("shop");
+    let results: Vec<Result<i32, Error>> = core.run(database
+        .query_stream(Query::new("FOR i IN 1..10 RETURN i"))
+        .and_then(|stream| stream.then(Ok::<_, Error>).take(10).collect())).unwrap();
+    core.turn(Some(Duration::from_millis(10)));
+
+    assert_that!(results.iter().filter_map(|result| result.as_ref().ok()).cloned().collect::<Vec<_>>(),
+        is(equal_to(vec![1, 2, 3])));
+    assert_that!(results.iter().filter(|result| result.is_err()).count(), is(equal_to(1)));
+    connector.verify();
+}
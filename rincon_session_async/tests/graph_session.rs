@@ -0,0 +1,80 @@
+#[macro_use] extern crate hamcrest;
+#[macro_use] extern crate serde_derive;
+
+extern crate futures;
+extern crate tokio_core;
+
+extern crate rincon_client;
+extern crate rincon_connector;
+extern crate rincon_session_async;
+extern crate rincon_test_helper;
+
+use futures::Future;
+use hamcrest::prelude::*;
+
+use rincon_session_async::*;
+
+use rincon_test_helper::*;
+
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Person {
+    name: String,
+    age: u16,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Knows {
+    since: u16,
+}
+
+fn knows_graph() -> NewGraph {
+    NewGraph::with_name("social")
+        .with_edge_definitions(vec![
+            EdgeDefinition::new("knows", vec!["persons".to_owned()], vec!["persons".to_owned()]),
+        ])
+}
+
+#[test]
+fn insert_and_get_vertices_and_edge() {
+    arango_session_test_with_user_db("socius30", "the_social_network30", |connector, mut core| {
+
+        let arango = ArangoSession::new(connector);
+        let database = arango.use_database("the_social_network30");
+        let graph = core.run(database.create_graph(knows_graph())).unwrap();
+        let persons = graph.vertex_collection::<Person>("persons");
+        let knows = graph.edge_collection::<Knows>("knows");
+
+        let alice = core.run(persons.insert(Person { name: "Alice".to_owned(), age: 42 })).unwrap();
+        let bob = core.run(persons.insert(Person { name: "Bob".to_owned(), age: 39 })).unwrap();
+        let header = core.run(knows.insert(NewEdge::new(alice.id().clone(), bob.id().clone(),
+            Knows { since: 2010 }))).unwrap();
+
+        let (vertex, edge) = core.run(persons.get(alice.key().clone())
+            .join(knows.get(header.key().clone()))).unwrap();
+
+        assert_that!(vertex.content().name.as_str(), is(equal_to("Alice")));
+        assert_that!(edge.from(), is(equal_to(alice.id())));
+        assert_that!(edge.to(), is(equal_to(bob.id())));
+        assert_that!(edge.content(), is(equal_to(&Knows { since: 2010 })));
+    });
+}
+
+#[test]
+fn add_edge_definition_and_drop_graph() {
+    arango_session_test_with_user_db("socius31", "the_social_network31", |connector, mut core| {
+
+        let arango = ArangoSession::new(connector);
+        let database = arango.use_database("the_social_network31");
+        let graph = core.run(database.create_graph(knows_graph())).unwrap();
+
+        let graph = core.run(graph.add_edge_definition(EdgeDefinition::new("lives_in",
+            vec!["persons".to_owned()], vec!["cities".to_owned()]))).unwrap();
+
+        assert_that!(graph.graph().edge_definitions().len(), is(equal_to(2)));
+
+        let dropped = core.run(graph.drop()).unwrap();
+
+        assert_that!(dropped, is(equal_to(true)));
+    });
+}
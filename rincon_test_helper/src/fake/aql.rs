@@ -0,0 +1,1134 @@
+//! A small interpreter for the subset of AQL the fake server supports.
+//!
+//! Supported are the operations `FOR ... IN`, `FILTER`, `SORT`, `LIMIT`,
+//! `LET`, `INSERT ... IN` and `RETURN [DISTINCT]`, subqueries, bind
+//! parameters, the usual operators and a selection of functions.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Number, Value};
+
+use rincon_core::arango::ErrorCode;
+
+/// Provides access to the collections of a database for queries.
+pub(crate) trait DataAccess {
+    /// Returns all documents of the given collection.
+    fn documents(&self, collection: &str) -> Result<Vec<Value>, QueryError>;
+
+    /// Returns the document with the given id if it exists.
+    fn document(&self, id: &str) -> Option<Value>;
+
+    /// Inserts the given document and returns the stored document.
+    fn insert(&mut self, collection: &str, document: Value) -> Result<Value, QueryError>;
+}
+
+/// An error that aborts the execution of a query.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct QueryError {
+    pub(crate) status_code: u16,
+    pub(crate) error_code: ErrorCode,
+    pub(crate) message: String,
+}
+
+impl QueryError {
+    pub(crate) fn new<M>(status_code: u16, error_code: ErrorCode, message: M) -> Self
+        where M: Into<String>
+    {
+        QueryError {
+            status_code,
+            error_code,
+            message: message.into(),
+        }
+    }
+
+    fn syntax<M>(message: M) -> Self
+        where M: Into<String>
+    {
+        QueryError::new(400, ErrorCode::QueryParse, message)
+    }
+}
+
+/// The statistics of an executed query.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct Statistics {
+    pub(crate) writes_executed: u64,
+    pub(crate) scanned_full: u64,
+    pub(crate) filtered: u64,
+    pub(crate) full_count: Option<u64>,
+}
+
+/// The result of an executed query.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct QueryResult {
+    pub(crate) values: Vec<Value>,
+    pub(crate) stats: Statistics,
+}
+
+/// Parses and executes the given query.
+pub(crate) fn execute<D>(query: &str, bind_vars: &Map<String, Value>, data: &mut D)
+    -> Result<QueryResult, QueryError>
+    where D: DataAccess
+{
+    let tokens = tokenize(query)?;
+    let operations = Parser::new(&tokens).parse_query()?;
+    let mut context = Context {
+        data,
+        bind_vars,
+        stats: Statistics::default(),
+    };
+    let values = context.run(&operations, Row::new(), true)?;
+    Ok(QueryResult {
+        values,
+        stats: context.stats,
+    })
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(f64),
+    Str(String),
+    Bind(String),
+    CollectionBind(String),
+    Punct(&'static str),
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match *self {
+            Token::Ident(ref name) => name.to_owned(),
+            Token::Number(number) => number.to_string(),
+            Token::Str(ref value) => format!("'{}'", value),
+            Token::Bind(ref name) => format!("@{}", name),
+            Token::CollectionBind(ref name) => format!("@@{}", name),
+            Token::Punct(punct) => punct.to_owned(),
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        match *self {
+            Token::Ident(ref name) => name.eq_ignore_ascii_case(keyword),
+            _ => false,
+        }
+    }
+}
+
+const PUNCTUATION: &[&str] = &["..", "==", "!=", "<=", ">=", "&&", "||",
+    "(", ")", "[", "]", "{", "}", ",", ":", ".", "<", ">", "=", "+", "-", "*", "/", "%",
+    "!", "?"];
+
+fn tokenize(query: &str) -> Result<Vec<Token>, QueryError> {
+    let chars = query.chars().collect::<Vec<_>>();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+    while index < chars.len() {
+        let current = chars[index];
+        if current.is_whitespace() {
+            index += 1;
+        } else if current == '/' && chars.get(index + 1) == Some(&'/') {
+            while index < chars.len() && chars[index] != '\n' {
+                index += 1;
+            }
+        } else if current == '/' && chars.get(index + 1) == Some(&'*') {
+            index += 2;
+            while index < chars.len() && !(chars[index] == '*' && chars.get(index + 1) == Some(&'/')) {
+                index += 1;
+            }
+            index += 2;
+        } else if current.is_ascii_digit() {
+            let start = index;
+            while index < chars.len() && (chars[index].is_ascii_digit()
+                || chars[index] == '.' && chars.get(index + 1).is_some_and(char::is_ascii_digit))
+            {
+                index += 1;
+            }
+            let literal = chars[start..index].iter().collect::<String>();
+            let number = literal.parse()
+                .map_err(|_| QueryError::syntax(format!("invalid number '{}'", literal)))?;
+            tokens.push(Token::Number(number));
+        } else if current == '\'' || current == '"' {
+            let mut value = String::new();
+            index += 1;
+            loop {
+                match chars.get(index) {
+                    None => return Err(QueryError::syntax("unterminated string literal")),
+                    Some(&'\\') => {
+                        match chars.get(index + 1) {
+                            Some(&'n') => value.push('\n'),
+                            Some(&'r') => value.push('\r'),
+                            Some(&'t') => value.push('\t'),
+                            Some(&'u') => {
+                                let code = chars.get(index + 2..index + 6)
+                                    .map(|digits| digits.iter().collect::<String>())
+                                    .and_then(|digits| u32::from_str_radix(&digits, 16).ok())
+                                    .ok_or_else(|| QueryError::syntax("invalid unicode escape sequence"))?;
+                                value.push(::std::char::from_u32(code).unwrap_or('\u{fffd}'));
+                                index += 4;
+                            },
+                            Some(&escaped) => value.push(escaped),
+                            None => return Err(QueryError::syntax("unterminated string literal")),
+                        }
+                        index += 2;
+                    },
+                    Some(&quote) if quote == current => {
+                        index += 1;
+                        break;
+                    },
+                    Some(&other) => {
+                        value.push(other);
+                        index += 1;
+                    },
+                }
+            }
+            tokens.push(Token::Str(value));
+        } else if current == '@' {
+            let collection = chars.get(index + 1) == Some(&'@');
+            index += if collection { 2 } else { 1 };
+            let start = index;
+            while index < chars.len() && (chars[index].is_alphanumeric() || chars[index] == '_') {
+                index += 1;
+            }
+            let name = chars[start..index].iter().collect::<String>();
+            if name.is_empty() {
+                return Err(QueryError::syntax("invalid bind parameter"));
+            }
+            tokens.push(if collection { Token::CollectionBind(name) } else { Token::Bind(name) });
+        } else if current.is_alphabetic() || current == '_' || current == '`' {
+            if current == '`' {
+                let start = index + 1;
+                index = start;
+                while index < chars.len() && chars[index] != '`' {
+                    index += 1;
+                }
+                tokens.push(Token::Ident(chars[start..index].iter().collect()));
+                index += 1;
+            } else {
+                let start = index;
+                while index < chars.len() && (chars[index].is_alphanumeric() || chars[index] == '_') {
+                    index += 1;
+                }
+                tokens.push(Token::Ident(chars[start..index].iter().collect()));
+            }
+        } else {
+            let rest = chars[index..].iter().take(2).collect::<String>();
+            match PUNCTUATION.iter().find(|punct| rest.starts_with(*punct)) {
+                Some(punct) => {
+                    index += punct.len();
+                    tokens.push(Token::Punct(punct));
+                },
+                None => return Err(QueryError::syntax(
+                    format!("syntax error, unexpected character '{}'", current))),
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Operation {
+    For(String, Expr),
+    Filter(Expr),
+    Sort(Vec<(Expr, bool)>),
+    Limit(Expr, Expr),
+    Let(String, Expr),
+    Insert(Expr, Expr),
+    Return(bool, Expr),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Expr {
+    Literal(Value),
+    Variable(String),
+    Bind(String),
+    CollectionBind(String),
+    Array(Vec<Expr>),
+    Object(Vec<(String, Expr)>),
+    Attribute(Box<Expr>, String),
+    Index(Box<Expr>, Box<Expr>),
+    Call(String, Vec<Expr>),
+    Unary(&'static str, Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+    Ternary(Box<Expr>, Box<Expr>, Box<Expr>),
+    Subquery(Vec<Operation>),
+}
+
+struct Parser<'t> {
+    tokens: &'t [Token],
+    position: usize,
+    in_operator: bool,
+}
+
+impl<'t> Parser<'t> {
+    fn new(tokens: &'t [Token]) -> Self {
+        Parser {
+            tokens,
+            position: 0,
+            in_operator: true,
+        }
+    }
+
+    fn peek(&self) -> Option<&'t Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<&'t Token> {
+        let token = self.tokens.get(self.position);
+        self.position += 1;
+        token
+    }
+
+    fn unexpected(&self) -> QueryError {
+        match self.peek() {
+            Some(token) => QueryError::syntax(format!("syntax error, unexpected {} near '{}'",
+                token.describe(),
+                self.tokens[self.position..].iter().map(Token::describe).collect::<Vec<_>>().join(" "))),
+            None => QueryError::syntax("syntax error, unexpected end of query"),
+        }
+    }
+
+    fn at_keyword(&self, keyword: &str) -> bool {
+        self.peek().is_some_and(|token| token.is_keyword(keyword))
+    }
+
+    fn at_punct(&self, punct: &str) -> bool {
+        self.peek() == Some(&Token::Punct(PUNCTUATION.iter().find(|p| **p == punct).unwrap()))
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        if self.at_keyword(keyword) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_punct(&mut self, punct: &str) -> bool {
+        if self.at_punct(punct) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), QueryError> {
+        if self.eat_keyword(keyword) { Ok(()) } else { Err(self.unexpected()) }
+    }
+
+    fn expect_punct(&mut self, punct: &str) -> Result<(), QueryError> {
+        if self.eat_punct(punct) { Ok(()) } else { Err(self.unexpected()) }
+    }
+
+    fn expect_ident(&mut self) -> Result<String, QueryError> {
+        match self.peek() {
+            Some(Token::Ident(name)) => {
+                self.position += 1;
+                Ok(name.to_owned())
+            },
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn parse_query(&mut self) -> Result<Vec<Operation>, QueryError> {
+        let operations = self.parse_operations()?;
+        if self.peek().is_some() {
+            return Err(self.unexpected());
+        }
+        Ok(operations)
+    }
+
+    fn parse_operations(&mut self) -> Result<Vec<Operation>, QueryError> {
+        let mut operations = Vec::new();
+        loop {
+            let operation = if self.eat_keyword("FOR") {
+                let variable = self.expect_ident()?;
+                self.expect_keyword("IN")?;
+                Operation::For(variable, self.parse_expr()?)
+            } else if self.eat_keyword("FILTER") {
+                Operation::Filter(self.parse_expr()?)
+            } else if self.eat_keyword("SORT") {
+                let mut criteria = Vec::new();
+                loop {
+                    let expr = self.parse_expr()?;
+                    let ascending = if self.eat_keyword("DESC") {
+                        false
+                    } else {
+                        self.eat_keyword("ASC");
+                        true
+                    };
+                    criteria.push((expr, ascending));
+                    if !self.eat_punct(",") {
+                        break;
+                    }
+                }
+                Operation::Sort(criteria)
+            } else if self.eat_keyword("LIMIT") {
+                let first = self.parse_expr()?;
+                if self.eat_punct(",") {
+                    Operation::Limit(first, self.parse_expr()?)
+                } else {
+                    Operation::Limit(Expr::Literal(Value::from(0)), first)
+                }
+            } else if self.eat_keyword("LET") {
+                let variable = self.expect_ident()?;
+                self.expect_punct("=")?;
+                Operation::Let(variable, self.parse_expr()?)
+            } else if self.eat_keyword("INSERT") {
+                self.in_operator = false;
+                let document = self.parse_expr();
+                self.in_operator = true;
+                let document = document?;
+                if !self.eat_keyword("IN") {
+                    self.expect_keyword("INTO")?;
+                }
+                let collection = match self.next() {
+                    Some(Token::Ident(name)) => Expr::Literal(Value::String(name.to_owned())),
+                    Some(Token::CollectionBind(name)) => Expr::CollectionBind(name.to_owned()),
+                    _ => {
+                        self.position -= 1;
+                        return Err(self.unexpected());
+                    },
+                };
+                Operation::Insert(document, collection)
+            } else if self.eat_keyword("RETURN") {
+                let distinct = self.eat_keyword("DISTINCT");
+                operations.push(Operation::Return(distinct, self.parse_expr()?));
+                return Ok(operations);
+            } else if operations.is_empty() {
+                return Err(self.unexpected());
+            } else {
+                return Ok(operations);
+            };
+            operations.push(operation);
+        }
+    }
+
+    fn parse_expr(&mut self) -> Result<Expr, QueryError> {
+        let condition = self.parse_binary(0)?;
+        if self.eat_punct("?") {
+            let then = self.parse_expr()?;
+            self.expect_punct(":")?;
+            let otherwise = self.parse_expr()?;
+            Ok(Expr::Ternary(Box::new(condition), Box::new(then), Box::new(otherwise)))
+        } else {
+            Ok(condition)
+        }
+    }
+
+    fn binary_operator(&self, level: usize) -> Option<(&'static str, usize)> {
+        let token = self.peek()?;
+        let operator = match *token {
+            Token::Punct("||") => "||",
+            Token::Punct("&&") => "&&",
+            Token::Punct(punct @ "==") | Token::Punct(punct @ "!=") | Token::Punct(punct @ "<")
+                | Token::Punct(punct @ "<=") | Token::Punct(punct @ ">")
+                | Token::Punct(punct @ ">=") => punct,
+            Token::Punct("..") => "..",
+            Token::Punct(punct @ "+") | Token::Punct(punct @ "-") => punct,
+            Token::Punct(punct @ "*") | Token::Punct(punct @ "/") | Token::Punct(punct @ "%") => punct,
+            ref token if token.is_keyword("OR") => "||",
+            ref token if token.is_keyword("AND") => "&&",
+            ref token if token.is_keyword("IN") && self.in_operator => "IN",
+            ref token if token.is_keyword("NOT")
+                && self.tokens.get(self.position + 1).is_some_and(|next| next.is_keyword("IN")) => "NOT IN",
+            _ => return None,
+        };
+        let precedence = match operator {
+            "||" => 0,
+            "&&" => 1,
+            "==" | "!=" | "<" | "<=" | ">" | ">=" | "IN" | "NOT IN" => 2,
+            ".." => 3,
+            "+" | "-" => 4,
+            _ => 5,
+        };
+        if precedence >= level { Some((operator, precedence)) } else { None }
+    }
+
+    fn parse_binary(&mut self, level: usize) -> Result<Expr, QueryError> {
+        let mut left = self.parse_unary()?;
+        while let Some((operator, precedence)) = self.binary_operator(level) {
+            self.position += if operator == "NOT IN" { 2 } else { 1 };
+            let right = self.parse_binary(precedence + 1)?;
+            left = Expr::Binary(operator, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, QueryError> {
+        if self.eat_punct("!") || self.eat_keyword("NOT") {
+            Ok(Expr::Unary("!", Box::new(self.parse_unary()?)))
+        } else if self.eat_punct("-") {
+            Ok(Expr::Unary("-", Box::new(self.parse_unary()?)))
+        } else if self.eat_punct("+") {
+            Ok(Expr::Unary("+", Box::new(self.parse_unary()?)))
+        } else {
+            self.parse_postfix()
+        }
+    }
+
+    fn parse_postfix(&mut self) -> Result<Expr, QueryError> {
+        let mut expr = self.parse_primary()?;
+        loop {
+            if self.eat_punct(".") {
+                let attribute = match self.next() {
+                    Some(Token::Ident(name)) => name.to_owned(),
+                    Some(Token::Str(name)) => name.to_owned(),
+                    _ => {
+                        self.position -= 1;
+                        return Err(self.unexpected());
+                    },
+                };
+                expr = Expr::Attribute(Box::new(expr), attribute);
+            } else if self.eat_punct("[") {
+                let index = self.parse_expr()?;
+                self.expect_punct("]")?;
+                expr = Expr::Index(Box::new(expr), Box::new(index));
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, QueryError> {
+        let token = match self.next() {
+            Some(token) => token,
+            None => return Err(self.unexpected()),
+        };
+        match *token {
+            Token::Number(number) => Ok(Expr::Literal(number_value(number))),
+            Token::Str(ref value) => Ok(Expr::Literal(Value::String(value.to_owned()))),
+            Token::Bind(ref name) => Ok(Expr::Bind(name.to_owned())),
+            Token::CollectionBind(ref name) => Ok(Expr::CollectionBind(name.to_owned())),
+            Token::Punct("(") => {
+                let expr = if self.at_keyword("FOR") || self.at_keyword("LET") {
+                    Expr::Subquery(self.parse_operations()?)
+                } else {
+                    self.parse_expr()?
+                };
+                self.expect_punct(")")?;
+                Ok(expr)
+            },
+            Token::Punct("[") => {
+                let mut elements = Vec::new();
+                while !self.eat_punct("]") {
+                    elements.push(self.parse_expr()?);
+                    if !self.eat_punct(",") {
+                        self.expect_punct("]")?;
+                        break;
+                    }
+                }
+                Ok(Expr::Array(elements))
+            },
+            Token::Punct("{") => {
+                let mut attributes = Vec::new();
+                while !self.eat_punct("}") {
+                    let name = match self.next() {
+                        Some(Token::Ident(name)) => name.to_owned(),
+                        Some(Token::Str(name)) => name.to_owned(),
+                        _ => {
+                            self.position -= 1;
+                            return Err(self.unexpected());
+                        },
+                    };
+                    let value = if self.eat_punct(":") {
+                        self.parse_expr()?
+                    } else {
+                        Expr::Variable(name.clone())
+                    };
+                    attributes.push((name, value));
+                    if !self.eat_punct(",") {
+                        self.expect_punct("}")?;
+                        break;
+                    }
+                }
+                Ok(Expr::Object(attributes))
+            },
+            Token::Ident(ref name) => {
+                if name.eq_ignore_ascii_case("true") {
+                    Ok(Expr::Literal(Value::Bool(true)))
+                } else if name.eq_ignore_ascii_case("false") {
+                    Ok(Expr::Literal(Value::Bool(false)))
+                } else if name.eq_ignore_ascii_case("null") {
+                    Ok(Expr::Literal(Value::Null))
+                } else if self.eat_punct("(") {
+                    let mut arguments = Vec::new();
+                    while !self.eat_punct(")") {
+                        arguments.push(self.parse_expr()?);
+                        if !self.eat_punct(",") {
+                            self.expect_punct(")")?;
+                            break;
+                        }
+                    }
+                    Ok(Expr::Call(name.to_uppercase(), arguments))
+                } else {
+                    Ok(Expr::Variable(name.to_owned()))
+                }
+            },
+            Token::Punct(_) => {
+                self.position -= 1;
+                Err(self.unexpected())
+            },
+        }
+    }
+}
+
+type Row = BTreeMap<String, Value>;
+
+struct Context<'a, D: 'a> {
+    data: &'a mut D,
+    bind_vars: &'a Map<String, Value>,
+    stats: Statistics,
+}
+
+impl<'a, D> Context<'a, D>
+    where D: DataAccess
+{
+    fn run(&mut self, operations: &[Operation], initial: Row, top_level: bool)
+        -> Result<Vec<Value>, QueryError>
+    {
+        let mut rows = vec![initial];
+        for operation in operations {
+            match *operation {
+                Operation::For(ref variable, ref source) => {
+                    let mut expanded = Vec::new();
+                    for row in rows {
+                        for value in self.evaluate_source(source, &row)? {
+                            let mut row = row.clone();
+                            row.insert(variable.to_owned(), value);
+                            expanded.push(row);
+                        }
+                    }
+                    rows = expanded;
+                },
+                Operation::Filter(ref condition) => {
+                    let mut retained = Vec::with_capacity(rows.len());
+                    for row in rows {
+                        if to_bool(&self.evaluate(condition, &row)?) {
+                            retained.push(row);
+                        } else {
+                            self.stats.filtered += 1;
+                        }
+                    }
+                    rows = retained;
+                },
+                Operation::Sort(ref criteria) => {
+                    let mut keyed = Vec::with_capacity(rows.len());
+                    for row in rows {
+                        let keys = criteria.iter()
+                            .map(|(expr, _)| self.evaluate(expr, &row))
+                            .collect::<Result<Vec<_>, _>>()?;
+                        keyed.push((keys, row));
+                    }
+                    keyed.sort_by(|(left, _), (right, _)| {
+                        for (index, &(_, ascending)) in criteria.iter().enumerate() {
+                            let ordering = compare(&left[index], &right[index]);
+                            if ordering != Ordering::Equal {
+                                return if ascending { ordering } else { ordering.reverse() };
+                            }
+                        }
+                        Ordering::Equal
+                    });
+                    rows = keyed.into_iter().map(|(_, row)| row).collect();
+                },
+                Operation::Limit(ref offset, ref count) => {
+                    let offset = to_number(&self.evaluate(offset, &Row::new())?).max(0.) as usize;
+                    let count = to_number(&self.evaluate(count, &Row::new())?).max(0.) as usize;
+                    if top_level {
+                        self.stats.full_count = Some(rows.len() as u64);
+                    }
+                    rows = rows.into_iter().skip(offset).take(count).collect();
+                },
+                Operation::Let(ref variable, ref expr) => {
+                    for row in &mut rows {
+                        let value = self.evaluate(expr, row)?;
+                        row.insert(variable.to_owned(), value);
+                    }
+                },
+                Operation::Insert(ref document, ref collection) => {
+                    for row in &mut rows {
+                        let document = self.evaluate(document, row)?;
+                        let collection = to_string(&self.evaluate(collection, row)?);
+                        let new = self.data.insert(&collection, document)?;
+                        self.stats.writes_executed += 1;
+                        row.insert("NEW".to_owned(), new);
+                    }
+                },
+                Operation::Return(distinct, ref expr) => {
+                    let mut values: Vec<Value> = Vec::with_capacity(rows.len());
+                    for row in &rows {
+                        let value = self.evaluate(expr, row)?;
+                        if !distinct || !values.contains(&value) {
+                            values.push(value);
+                        }
+                    }
+                    return Ok(values);
+                },
+            }
+        }
+        Ok(Vec::new())
+    }
+
+    fn evaluate_source(&mut self, source: &Expr, row: &Row) -> Result<Vec<Value>, QueryError> {
+        let collection = match *source {
+            Expr::Variable(ref name) if !row.contains_key(name) => Some(name.to_owned()),
+            Expr::CollectionBind(_) => Some(to_string(&self.evaluate(source, row)?)),
+            _ => None,
+        };
+        match collection {
+            Some(collection) => {
+                let documents = self.data.documents(&collection)?;
+                self.stats.scanned_full += documents.len() as u64;
+                Ok(documents)
+            },
+            None => match self.evaluate(source, row)? {
+                Value::Array(values) => Ok(values),
+                Value::Null => Ok(Vec::new()),
+                value => Ok(vec![value]),
+            },
+        }
+    }
+
+    fn evaluate(&mut self, expr: &Expr, row: &Row) -> Result<Value, QueryError> {
+        Ok(match *expr {
+            Expr::Literal(ref value) => value.clone(),
+            Expr::Variable(ref name) => match row.get(name) {
+                Some(value) => value.clone(),
+                None => return Err(QueryError::new(400, ErrorCode::QueryVariableNameUnknown,
+                    format!("unknown variable '{}'", name))),
+            },
+            Expr::Bind(ref name) | Expr::CollectionBind(ref name) => {
+                let key = match *expr {
+                    Expr::CollectionBind(_) => format!("@{}", name),
+                    _ => name.to_owned(),
+                };
+                match self.bind_vars.get(&key) {
+                    Some(value) => value.clone(),
+                    None => return Err(QueryError::new(400, ErrorCode::QueryBindParameterMissing,
+                        format!("no value specified for declared bind parameter '{}'", key))),
+                }
+            },
+            Expr::Array(ref elements) => Value::Array(elements.iter()
+                .map(|element| self.evaluate(element, row))
+                .collect::<Result<_, _>>()?),
+            Expr::Object(ref attributes) => {
+                let mut object = Map::new();
+                for (name, value) in attributes {
+                    let value = self.evaluate(value, row)?;
+                    object.insert(name.to_owned(), value);
+                }
+                Value::Object(object)
+            },
+            Expr::Attribute(ref object, ref attribute) => match self.evaluate(object, row)? {
+                Value::Object(mut object) => object.remove(attribute).unwrap_or(Value::Null),
+                _ => Value::Null,
+            },
+            Expr::Index(ref container, ref index) => {
+                let container = self.evaluate(container, row)?;
+                let index = self.evaluate(index, row)?;
+                match (container, index) {
+                    (Value::Object(mut object), Value::String(ref name)) =>
+                        object.remove(name).unwrap_or(Value::Null),
+                    (Value::Array(mut array), ref index) => {
+                        let index = to_number(index) as i64;
+                        let index = if index < 0 { array.len() as i64 + index } else { index };
+                        if index >= 0 && (index as usize) < array.len() {
+                            array.swap_remove(index as usize)
+                        } else {
+                            Value::Null
+                        }
+                    },
+                    _ => Value::Null,
+                }
+            },
+            Expr::Call(ref name, ref arguments) => {
+                let arguments = arguments.iter()
+                    .map(|argument| self.evaluate(argument, row))
+                    .collect::<Result<Vec<_>, _>>()?;
+                self.call(name, arguments)?
+            },
+            Expr::Unary(operator, ref operand) => {
+                let operand = self.evaluate(operand, row)?;
+                match operator {
+                    "!" => Value::Bool(!to_bool(&operand)),
+                    "-" => number_value(-to_number(&operand)),
+                    _ => number_value(to_number(&operand)),
+                }
+            },
+            Expr::Binary("&&", ref left, ref right) => {
+                let left = self.evaluate(left, row)?;
+                if to_bool(&left) { self.evaluate(right, row)? } else { left }
+            },
+            Expr::Binary("||", ref left, ref right) => {
+                let left = self.evaluate(left, row)?;
+                if to_bool(&left) { left } else { self.evaluate(right, row)? }
+            },
+            Expr::Binary(operator, ref left, ref right) => {
+                let left = self.evaluate(left, row)?;
+                let right = self.evaluate(right, row)?;
+                binary(operator, &left, &right)
+            },
+            Expr::Ternary(ref condition, ref then, ref otherwise) => {
+                if to_bool(&self.evaluate(condition, row)?) {
+                    self.evaluate(then, row)?
+                } else {
+                    self.evaluate(otherwise, row)?
+                }
+            },
+            Expr::Subquery(ref operations) => Value::Array(self.run(operations, row.clone(), false)?),
+        })
+    }
+
+    fn call(&mut self, name: &str, arguments: Vec<Value>) -> Result<Value, QueryError> {
+        let argument = |index: usize| arguments.get(index).cloned().unwrap_or(Value::Null);
+        let numbers = |value: Value| match value {
+            Value::Array(values) => values.iter()
+                .filter(|value| !value.is_null())
+                .map(to_number)
+                .collect::<Vec<_>>(),
+            _ => Vec::new(),
+        };
+        Ok(match name {
+            "CONCAT" => Value::String(arguments.iter()
+                .flat_map(|argument| match *argument {
+                    Value::Array(ref values) => values.clone(),
+                    ref value => vec![value.clone()],
+                })
+                .map(|value| to_string(&value))
+                .collect()),
+            "CONCAT_SEPARATOR" => Value::String(arguments.iter().skip(1)
+                .flat_map(|argument| match *argument {
+                    Value::Array(ref values) => values.clone(),
+                    ref value => vec![value.clone()],
+                })
+                .filter(|value| !value.is_null())
+                .map(|value| to_string(&value))
+                .collect::<Vec<_>>()
+                .join(&to_string(&argument(0)))),
+            "LENGTH" | "COUNT" => Value::from(match argument(0) {
+                Value::Null => 0,
+                Value::Bool(value) => if value { 1 } else { 0 },
+                Value::Number(ref number) => number.to_string().len(),
+                Value::String(ref value) => value.chars().count(),
+                Value::Array(ref values) => values.len(),
+                Value::Object(ref object) => object.len(),
+            }),
+            "UPPER" => Value::String(to_string(&argument(0)).to_uppercase()),
+            "LOWER" => Value::String(to_string(&argument(0)).to_lowercase()),
+            "CONTAINS" => Value::Bool(to_string(&argument(0)).contains(&to_string(&argument(1)))),
+            "SUBSTRING" => {
+                let value = to_string(&argument(0));
+                let offset = to_number(&argument(1)).max(0.) as usize;
+                let length = match arguments.get(2) {
+                    Some(length) => to_number(length).max(0.) as usize,
+                    None => value.len(),
+                };
+                Value::String(value.chars().skip(offset).take(length).collect())
+            },
+            "TO_STRING" => Value::String(to_string(&argument(0))),
+            "TO_NUMBER" => number_value(to_number(&argument(0))),
+            "TO_BOOL" => Value::Bool(to_bool(&argument(0))),
+            "ABS" => number_value(to_number(&argument(0)).abs()),
+            "FLOOR" => number_value(to_number(&argument(0)).floor()),
+            "CEIL" => number_value(to_number(&argument(0)).ceil()),
+            "ROUND" => number_value(to_number(&argument(0)).round()),
+            "SUM" => number_value(numbers(argument(0)).iter().sum()),
+            "AVERAGE" | "AVG" => {
+                let numbers = numbers(argument(0));
+                if numbers.is_empty() {
+                    Value::Null
+                } else {
+                    number_value(numbers.iter().sum::<f64>() / numbers.len() as f64)
+                }
+            },
+            "MIN" | "MAX" => match argument(0) {
+                Value::Array(values) => {
+                    let values = values.into_iter().filter(|value| !value.is_null());
+                    if name == "MIN" {
+                        values.min_by(compare)
+                    } else {
+                        values.max_by(compare)
+                    }.unwrap_or(Value::Null)
+                },
+                _ => Value::Null,
+            },
+            "FIRST" => match argument(0) {
+                Value::Array(mut values) if !values.is_empty() => values.swap_remove(0),
+                _ => Value::Null,
+            },
+            "LAST" => match argument(0) {
+                Value::Array(mut values) => values.pop().unwrap_or(Value::Null),
+                _ => Value::Null,
+            },
+            "KEYS" | "ATTRIBUTES" => match argument(0) {
+                Value::Object(object) => Value::Array(object.keys().cloned().map(Value::String).collect()),
+                _ => Value::Null,
+            },
+            "VALUES" => match argument(0) {
+                Value::Object(object) => Value::Array(object.values().cloned().collect()),
+                _ => Value::Null,
+            },
+            "HAS" => match argument(0) {
+                Value::Object(object) => Value::Bool(object.contains_key(&to_string(&argument(1)))),
+                _ => Value::Bool(false),
+            },
+            "MERGE" => {
+                let mut merged = Map::new();
+                for argument in arguments {
+                    if let Value::Object(object) = argument {
+                        merged.extend(object);
+                    }
+                }
+                Value::Object(merged)
+            },
+            "MATCHES" => match (argument(0), argument(1)) {
+                (Value::Object(document), Value::Object(example)) => Value::Bool(example.iter()
+                    .all(|(name, value)| compare(document.get(name).unwrap_or(&Value::Null), value)
+                        == Ordering::Equal)),
+                _ => Value::Bool(false),
+            },
+            "IS_NULL" => Value::Bool(argument(0).is_null()),
+            "NOT_NULL" => arguments.into_iter().find(|value| !value.is_null()).unwrap_or(Value::Null),
+            "DOCUMENT" => match argument(0) {
+                Value::String(ref id) => self.data.document(id).unwrap_or(Value::Null),
+                Value::Array(ids) => Value::Array(ids.iter()
+                    .filter_map(|id| self.data.document(&to_string(id)))
+                    .collect()),
+                _ => Value::Null,
+            },
+            _ => return Err(QueryError::new(400, ErrorCode::QueryFunctionNameUnknown,
+                format!("usage of unknown function '{}()'", name))),
+        })
+    }
+}
+
+fn binary(operator: &str, left: &Value, right: &Value) -> Value {
+    match operator {
+        "==" => Value::Bool(compare(left, right) == Ordering::Equal),
+        "!=" => Value::Bool(compare(left, right) != Ordering::Equal),
+        "<" => Value::Bool(compare(left, right) == Ordering::Less),
+        "<=" => Value::Bool(compare(left, right) != Ordering::Greater),
+        ">" => Value::Bool(compare(left, right) == Ordering::Greater),
+        ">=" => Value::Bool(compare(left, right) != Ordering::Less),
+        "IN" | "NOT IN" => {
+            let contained = match *right {
+                Value::Array(ref values) => values.iter()
+                    .any(|value| compare(left, value) == Ordering::Equal),
+                _ => false,
+            };
+            Value::Bool(contained == (operator == "IN"))
+        },
+        ".." => {
+            let from = to_number(left) as i64;
+            let to = to_number(right) as i64;
+            Value::Array(if from <= to {
+                (from..=to).map(Value::from).collect()
+            } else {
+                (to..=from).rev().map(Value::from).collect()
+            })
+        },
+        "+" => number_value(to_number(left) + to_number(right)),
+        "-" => number_value(to_number(left) - to_number(right)),
+        "*" => number_value(to_number(left) * to_number(right)),
+        "/" => {
+            let divisor = to_number(right);
+            if divisor == 0. { Value::Null } else { number_value(to_number(left) / divisor) }
+        },
+        _ => {
+            let divisor = to_number(right);
+            if divisor == 0. { Value::Null } else { number_value(to_number(left) % divisor) }
+        },
+    }
+}
+
+/// Returns a JSON number for the given value, using an integer if the value
+/// has no fractional part.
+fn number_value(number: f64) -> Value {
+    if !number.is_finite() {
+        Value::Null
+    } else if number.fract() == 0. && number.abs() < 9_007_199_254_740_992. {
+        Value::from(number as i64)
+    } else {
+        Number::from_f64(number).map_or(Value::Null, Value::Number)
+    }
+}
+
+fn to_number(value: &Value) -> f64 {
+    match *value {
+        Value::Null => 0.,
+        Value::Bool(value) => if value { 1. } else { 0. },
+        Value::Number(ref number) => number.as_f64().unwrap_or(0.),
+        Value::String(ref value) => value.trim().parse().unwrap_or(0.),
+        Value::Array(ref values) => match values.len() {
+            0 => 0.,
+            1 => to_number(&values[0]),
+            _ => 0.,
+        },
+        Value::Object(_) => 0.,
+    }
+}
+
+fn to_bool(value: &Value) -> bool {
+    match *value {
+        Value::Null => false,
+        Value::Bool(value) => value,
+        Value::Number(ref number) => number.as_f64().is_some_and(|number| number != 0.),
+        Value::String(ref value) => !value.is_empty(),
+        Value::Array(_) | Value::Object(_) => true,
+    }
+}
+
+fn to_string(value: &Value) -> String {
+    match *value {
+        Value::Null => String::new(),
+        Value::String(ref value) => value.to_owned(),
+        ref value => value.to_string(),
+    }
+}
+
+fn type_rank(value: &Value) -> u8 {
+    match *value {
+        Value::Null => 0,
+        Value::Bool(_) => 1,
+        Value::Number(_) => 2,
+        Value::String(_) => 3,
+        Value::Array(_) => 4,
+        Value::Object(_) => 5,
+    }
+}
+
+/// Compares two values in the order defined by AQL.
+pub(crate) fn compare(left: &Value, right: &Value) -> Ordering {
+    match (left, right) {
+        (&Value::Bool(left), &Value::Bool(right)) => left.cmp(&right),
+        (Value::Number(left), Value::Number(right)) =>
+            left.as_f64().partial_cmp(&right.as_f64()).unwrap_or(Ordering::Equal),
+        (Value::String(left), Value::String(right)) => left.cmp(right),
+        (Value::Array(left), Value::Array(right)) => {
+            for (left, right) in left.iter().zip(right.iter()) {
+                let ordering = compare(left, right);
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            left.len().cmp(&right.len())
+        },
+        (Value::Object(left), Value::Object(right)) => {
+            let mut keys = left.keys().chain(right.keys()).collect::<Vec<_>>();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let ordering = compare(left.get(key).unwrap_or(&Value::Null),
+                    right.get(key).unwrap_or(&Value::Null));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            Ordering::Equal
+        },
+        _ => type_rank(left).cmp(&type_rank(right)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Collections {
+        customers: Vec<Value>,
+    }
+
+    impl DataAccess for Collections {
+        fn documents(&self, collection: &str) -> Result<Vec<Value>, QueryError> {
+            if collection == "customers" {
+                Ok(self.customers.clone())
+            } else {
+                Err(QueryError::new(404, ErrorCode::ArangoCollectionNotFound,
+                    format!("collection not found: {}", collection)))
+            }
+        }
+
+        fn document(&self, id: &str) -> Option<Value> {
+            self.customers.iter().find(|customer| customer["_id"] == id).cloned()
+        }
+
+        fn insert(&mut self, collection: &str, mut document: Value) -> Result<Value, QueryError> {
+            self.documents(collection)?;
+            let key = (self.customers.len() + 1).to_string();
+            document["_id"] = Value::String(format!("{}/{}", collection, key));
+            document["_key"] = Value::String(key);
+            self.customers.push(document.clone());
+            Ok(document)
+        }
+    }
+
+    fn run(query: &str, data: &mut Collections) -> Result<QueryResult, QueryError> {
+        execute(query, &Map::new(), data)
+    }
+
+    #[test]
+    fn return_range_of_numbers() {
+        let result = run("FOR i IN 1..5 FILTER i % 2 == 1 RETURN i * 10", &mut Collections::default()).unwrap();
+
+        assert_eq!(vec![json!(10), json!(30), json!(50)], result.values);
+        assert_eq!(2, result.stats.filtered);
+    }
+
+    #[test]
+    fn insert_documents_and_return_new() {
+        let mut data = Collections::default();
+
+        let result = run("FOR i IN 1..3 \
+            INSERT { name: CONCAT('No.', i), age: i + 21 } IN customers \
+            RETURN NEW.name", &mut data).unwrap();
+
+        assert_eq!(vec![json!("No.1"), json!("No.2"), json!("No.3")], result.values);
+        assert_eq!(3, result.stats.writes_executed);
+        assert_eq!(json!({ "_id": "customers/2", "_key": "2", "name": "No.2", "age": 23 }),
+            data.customers[1]);
+    }
+
+    #[test]
+    fn filter_sort_and_limit_documents_of_collection() {
+        let mut data = Collections::default();
+        run("FOR i IN 1..21 INSERT { name: CONCAT('No.', i), age: i + 21 } IN customers", &mut data).unwrap();
+
+        let result = run("FOR c IN customers FILTER c.age <= 37 SORT c.name DESC LIMIT 1, 3 RETURN c.name",
+            &mut data).unwrap();
+
+        assert_eq!(vec![json!("No.8"), json!("No.7"), json!("No.6")], result.values);
+        assert_eq!(21, result.stats.scanned_full);
+        assert_eq!(5, result.stats.filtered);
+        assert_eq!(Some(16), result.stats.full_count);
+    }
+
+    #[test]
+    fn use_bind_parameters_let_and_subqueries() {
+        let mut bind_vars = Map::new();
+        bind_vars.insert("names".to_owned(), json!(["anna", "bert", "carl"]));
+        bind_vars.insert("min".to_owned(), json!(2));
+
+        let result = execute("LET upper = (FOR name IN @names FILTER LENGTH(name) >= 4 RETURN UPPER(name)) \
+            RETURN { count: LENGTH(upper), names: upper, ok: LENGTH(upper) >= @min }",
+            &bind_vars, &mut Collections::default()).unwrap();
+
+        assert_eq!(vec![json!({ "count": 3, "names": ["ANNA", "BERT", "CARL"], "ok": true })], result.values);
+    }
+
+    #[test]
+    fn query_on_unknown_collection_fails() {
+        let result = run("FOR c IN suppliers RETURN c", &mut Collections::default());
+
+        assert_eq!(Err(QueryError::new(404, ErrorCode::ArangoCollectionNotFound,
+            "collection not found: suppliers")), result);
+    }
+
+    #[test]
+    fn invalid_query_is_a_syntax_error() {
+        let result = run("FOR c IN customers RETURN", &mut Collections::default());
+
+        assert_eq!(Some(ErrorCode::QueryParse), result.err().map(|error| error.error_code));
+    }
+}
@@ -0,0 +1,2397 @@
+//! The in-memory state of the fake server and the handlers of the supported
+//! REST endpoints.
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use base64;
+use hyper::Method;
+use serde_json::{self, Map, Value};
+
+use rincon_core::arango::ErrorCode;
+use rincon_core::arango::protocol::SYSTEM_DATABASE;
+
+use super::aql::{self, DataAccess, QueryError};
+
+const ROOT_USERNAME: &str = "root";
+
+const COLLECTION_TYPE_DOCUMENTS: u64 = 2;
+const COLLECTION_TYPE_EDGES: u64 = 3;
+const COLLECTION_STATUS_UNLOADED: u64 = 2;
+const COLLECTION_STATUS_LOADED: u64 = 3;
+
+const DEFAULT_BATCH_SIZE: usize = 1000;
+const SERVER_VERSION: &str = "3.2.10";
+const TARGET_VERSION: &str = "30210";
+const DEFAULT_MAX_ITERATIONS: u64 = 10_000_000;
+const TOKEN_LIFETIME_SECS: u64 = 3600;
+
+/// The credentials sent with a request.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Credentials {
+    None,
+    Basic(String, String),
+    Bearer(String),
+}
+
+/// A request to the fake server with the body already parsed.
+#[derive(Clone, Debug)]
+pub(crate) struct Request {
+    pub(crate) method: Method,
+    pub(crate) database: String,
+    pub(crate) path: Vec<String>,
+    pub(crate) parameters: HashMap<String, String>,
+    pub(crate) credentials: Credentials,
+    pub(crate) if_match: Option<String>,
+    pub(crate) if_none_match: Option<String>,
+    pub(crate) body: Option<Value>,
+}
+
+impl Request {
+    fn flag(&self, name: &str, default: bool) -> bool {
+        self.parameters.get(name).map_or(default, |value| value == "true")
+    }
+
+    fn parameter(&self, name: &str) -> Option<&str> {
+        self.parameters.get(name).map(String::as_str)
+    }
+
+    fn object(&self) -> Result<Map<String, Value>, Response> {
+        match self.body {
+            Some(Value::Object(ref object)) => Ok(object.clone()),
+            None => Ok(Map::new()),
+            Some(_) => Err(Response::error(400, ErrorCode::HttpBadParameter,
+                "expecting a JSON object as body")),
+        }
+    }
+}
+
+/// A response of the fake server.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Response {
+    pub(crate) status: u16,
+    pub(crate) etag: Option<String>,
+    pub(crate) body: Option<Value>,
+}
+
+impl Response {
+    fn new(status: u16, body: Value) -> Self {
+        Response {
+            status,
+            etag: None,
+            body: Some(body),
+        }
+    }
+
+    fn empty(status: u16) -> Self {
+        Response {
+            status,
+            etag: None,
+            body: None,
+        }
+    }
+
+    /// Creates a response with the given fields and the `code` and `error`
+    /// fields added.
+    fn fields(status: u16, mut fields: Map<String, Value>) -> Self {
+        fields.insert("error".to_owned(), Value::Bool(false));
+        fields.insert("code".to_owned(), Value::from(status));
+        Response::new(status, Value::Object(fields))
+    }
+
+    /// Creates a response with a single field and the `code` and `error`
+    /// fields added.
+    fn field(status: u16, name: &str, value: Value) -> Self {
+        let mut fields = Map::new();
+        fields.insert(name.to_owned(), value);
+        Response::fields(status, fields)
+    }
+
+    fn result(status: u16, result: Value) -> Self {
+        Response::new(status, json!({ "error": false, "code": status, "result": result }))
+    }
+
+    pub(crate) fn error<M>(status: u16, error_code: ErrorCode, message: M) -> Self
+        where M: Into<String>
+    {
+        Response::new(status, json!({
+            "error": true,
+            "code": status,
+            "errorNum": error_code.as_u16(),
+            "errorMessage": message.into(),
+        }))
+    }
+
+    pub(crate) fn unauthorized() -> Self {
+        Response::empty(401)
+    }
+
+    fn not_found(request: &Request) -> Self {
+        Response::error(404, ErrorCode::HttpNotFound,
+            format!("unknown path '/{}'", request.path.join("/")))
+    }
+
+    pub(crate) fn method_not_allowed() -> Self {
+        Response::error(405, ErrorCode::HttpMethodNotAllowed, "method not supported")
+    }
+}
+
+impl From<QueryError> for Response {
+    fn from(error: QueryError) -> Self {
+        Response::error(error.status_code, error.error_code, error.message)
+    }
+}
+
+type Handled = Result<Response, Response>;
+
+type StoredDocument = Map<String, Value>;
+
+/// The access level of a user to a database or collection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Level {
+    None,
+    ReadOnly,
+    ReadWrite,
+}
+
+impl Level {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "none" => Some(Level::None),
+            "ro" => Some(Level::ReadOnly),
+            "rw" => Some(Level::ReadWrite),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match *self {
+            Level::None => "none",
+            Level::ReadOnly => "ro",
+            Level::ReadWrite => "rw",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct User {
+    name: String,
+    password: String,
+    active: bool,
+    extra: Value,
+    databases: BTreeMap<String, Level>,
+    collections: BTreeMap<(String, String), Level>,
+}
+
+impl User {
+    fn new(name: String, password: String, active: bool, extra: Value) -> Self {
+        User {
+            name,
+            password,
+            active,
+            extra,
+            databases: BTreeMap::new(),
+            collections: BTreeMap::new(),
+        }
+    }
+
+    fn is_superuser(&self) -> bool {
+        self.name == ROOT_USERNAME
+    }
+
+    fn database_level(&self, database: &str) -> Level {
+        if self.is_superuser() {
+            Level::ReadWrite
+        } else {
+            self.databases.get(database).or_else(|| self.databases.get("*"))
+                .cloned().unwrap_or(Level::None)
+        }
+    }
+
+    fn collection_level(&self, database: &str, collection: &str) -> Level {
+        self.collections.get(&(database.to_owned(), collection.to_owned())).cloned()
+            .unwrap_or_else(|| self.database_level(database))
+    }
+
+    fn to_json(&self) -> Map<String, Value> {
+        let mut fields = Map::new();
+        fields.insert("user".to_owned(), Value::String(self.name.clone()));
+        fields.insert("active".to_owned(), Value::Bool(self.active));
+        fields.insert("extra".to_owned(), self.extra.clone());
+        fields
+    }
+}
+
+/// Generates the ids, keys and revisions of the fake server.
+#[derive(Debug, Default)]
+struct Clock(u64);
+
+impl Clock {
+    fn tick(&mut self) -> u64 {
+        self.0 += 1;
+        self.0
+    }
+
+    fn revision(&mut self) -> String {
+        format!("_{:x}", 0x1000_0000 + self.tick())
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Collection {
+    id: String,
+    name: String,
+    kind: u64,
+    is_system: bool,
+    is_loaded: bool,
+    wait_for_sync: bool,
+    is_volatile: bool,
+    do_compact: bool,
+    journal_size: u64,
+    index_buckets: u64,
+    allow_user_keys: bool,
+    last_value: u64,
+    documents: Vec<Map<String, Value>>,
+    indexes: Vec<Map<String, Value>>,
+}
+
+impl Collection {
+    fn new(id: String, name: String, kind: u64) -> Self {
+        Collection {
+            id,
+            name,
+            kind,
+            is_system: false,
+            is_loaded: true,
+            wait_for_sync: false,
+            is_volatile: false,
+            do_compact: true,
+            journal_size: 33_554_432,
+            index_buckets: 8,
+            allow_user_keys: true,
+            last_value: 0,
+            documents: Vec::new(),
+            indexes: Vec::new(),
+        }
+    }
+
+    fn summary(&self) -> Map<String, Value> {
+        let mut fields = Map::new();
+        fields.insert("id".to_owned(), Value::String(self.id.clone()));
+        fields.insert("name".to_owned(), Value::String(self.name.clone()));
+        fields.insert("type".to_owned(), Value::from(self.kind));
+        let status = if self.is_loaded { COLLECTION_STATUS_LOADED } else { COLLECTION_STATUS_UNLOADED };
+        fields.insert("status".to_owned(), Value::from(status));
+        fields.insert("isSystem".to_owned(), Value::Bool(self.is_system));
+        fields
+    }
+
+    fn properties(&self) -> Map<String, Value> {
+        let mut fields = self.summary();
+        fields.insert("keyOptions".to_owned(), json!({
+            "allowUserKeys": self.allow_user_keys,
+            "type": "traditional",
+            "lastValue": self.last_value,
+        }));
+        fields.insert("waitForSync".to_owned(), Value::Bool(self.wait_for_sync));
+        fields.insert("isVolatile".to_owned(), Value::Bool(self.is_volatile));
+        fields.insert("doCompact".to_owned(), Value::Bool(self.do_compact));
+        fields.insert("journalSize".to_owned(), Value::from(self.journal_size));
+        fields.insert("indexBuckets".to_owned(), Value::from(self.index_buckets));
+        fields
+    }
+
+    fn is_edges(&self) -> bool {
+        self.kind == COLLECTION_TYPE_EDGES
+    }
+
+    /// Returns the revision of the most recently written document, which
+    /// stands in for the revision of the collection.
+    fn revision(&self) -> String {
+        self.documents.iter()
+            .filter_map(|document| document["_rev"].as_str())
+            .max().unwrap_or("0").to_owned()
+    }
+
+    /// Calculates the checksum as xor of the hashes of the single documents,
+    /// so that it does not depend on the order of the documents.
+    fn checksum(&self, with_revisions: bool, with_data: bool) -> u64 {
+        self.documents.iter().fold(0, |checksum, document| {
+            let mut hasher = DefaultHasher::new();
+            document["_key"].as_str().unwrap_or_default().hash(&mut hasher);
+            if with_revisions {
+                document["_rev"].as_str().unwrap_or_default().hash(&mut hasher);
+            }
+            if with_data {
+                for (name, value) in document.iter().filter(|&(name, _)| !name.starts_with('_')) {
+                    name.hash(&mut hasher);
+                    value.to_string().hash(&mut hasher);
+                }
+            }
+            checksum ^ hasher.finish()
+        })
+    }
+
+    fn figures(&self) -> Value {
+        let documents_size = self.documents.iter()
+            .map(|document| Value::Object(document.clone()).to_string().len())
+            .sum::<usize>();
+        let journals = if self.documents.is_empty() { 0 } else { 1 };
+        json!({
+            "alive": { "count": self.documents.len(), "size": documents_size },
+            "dead": { "count": 0, "size": 0, "deletion": 0 },
+            "datafiles": { "count": 0, "fileSize": 0 },
+            "journals": { "count": journals, "fileSize": journals * self.journal_size },
+            "compactors": { "count": 0, "fileSize": 0 },
+            "indexes": { "count": self.all_indexes().len(), "size": 0 },
+            "uncollectedLogfileEntries": 0,
+        })
+    }
+
+    fn position(&self, key: &str) -> Option<usize> {
+        self.documents.iter().position(|document| document["_key"] == key)
+    }
+
+    fn builtin_indexes(&self) -> Vec<Map<String, Value>> {
+        let mut indexes = Vec::new();
+        if let Value::Object(primary) = json!({
+            "id": format!("{}/0", self.name),
+            "type": "primary",
+            "fields": ["_key"],
+            "selectivityEstimate": 1,
+            "unique": true,
+            "sparse": false,
+        }) {
+            indexes.push(primary);
+        }
+        if self.is_edges() {
+            if let Value::Object(edge) = json!({
+                "id": format!("{}/1", self.name),
+                "type": "edge",
+                "fields": ["_from", "_to"],
+                "unique": false,
+                "sparse": false,
+            }) {
+                indexes.push(edge);
+            }
+        }
+        indexes
+    }
+
+    fn all_indexes(&self) -> Vec<Map<String, Value>> {
+        let mut indexes = self.builtin_indexes();
+        indexes.extend(self.indexes.iter().cloned());
+        indexes
+    }
+
+    /// Builds a stored document from the given content, keeping the system
+    /// attributes of the given key and revision.
+    fn stored(&self, key: &str, revision: &str, mut content: Map<String, Value>)
+        -> Result<Map<String, Value>, ItemError>
+    {
+        content.remove("_id");
+        content.remove("_key");
+        content.remove("_rev");
+        content.insert("_key".to_owned(), Value::String(key.to_owned()));
+        content.insert("_id".to_owned(), Value::String(format!("{}/{}", self.name, key)));
+        content.insert("_rev".to_owned(), Value::String(revision.to_owned()));
+        if self.is_edges() {
+            for attribute in &["_from", "_to"] {
+                let valid = content.get(*attribute).and_then(Value::as_str)
+                    .is_some_and(|handle| handle.contains('/'));
+                if !valid {
+                    return Err(ItemError::new(400, ErrorCode::ArangoInvalidEdgeAttribute,
+                        "invalid edge attribute"));
+                }
+            }
+        }
+        Ok(content)
+    }
+
+    /// Inserts the given document and returns the new document together
+    /// with the document it has overwritten if any.
+    fn insert(&mut self, clock: &mut Clock, content: Value, overwrite: bool)
+        -> Result<(StoredDocument, Option<StoredDocument>), ItemError>
+    {
+        let content = match content {
+            Value::Object(content) => content,
+            _ => return Err(ItemError::new(400, ErrorCode::ArangoDocumentTypeInvalid,
+                "invalid document type")),
+        };
+        let key = match content.get("_key") {
+            Some(Value::String(key)) => {
+                if !self.allow_user_keys {
+                    return Err(ItemError::new(400, ErrorCode::ArangoDocumentKeyBad,
+                        "illegal document key"));
+                }
+                if !is_valid_key(key) {
+                    return Err(ItemError::new(400, ErrorCode::ArangoDocumentKeyBad,
+                        "illegal document key"));
+                }
+                key.to_owned()
+            },
+            Some(_) => return Err(ItemError::new(400, ErrorCode::ArangoDocumentKeyBad,
+                "illegal document key")),
+            None => {
+                let key = clock.tick();
+                self.last_value = key;
+                key.to_string()
+            },
+        };
+        let revision = clock.revision();
+        let document = self.stored(&key, &revision, content)?;
+        match self.position(&key) {
+            Some(position) if overwrite => {
+                let old = ::std::mem::replace(&mut self.documents[position], document.clone());
+                Ok((document, Some(old)))
+            },
+            Some(_) => Err(ItemError::new(409, ErrorCode::ArangoUniqueConstraintViolated,
+                "unique constraint violated - in index 0 of type primary over [\"_key\"]")),
+            None => {
+                self.documents.push(document.clone());
+                Ok((document, None))
+            },
+        }
+    }
+}
+
+/// The error of a single document operation.
+#[derive(Clone, Debug, PartialEq)]
+struct ItemError {
+    status: u16,
+    error_code: ErrorCode,
+    message: String,
+}
+
+impl ItemError {
+    fn new<M>(status: u16, error_code: ErrorCode, message: M) -> Self
+        where M: Into<String>
+    {
+        ItemError {
+            status,
+            error_code,
+            message: message.into(),
+        }
+    }
+
+    fn to_item(&self) -> Value {
+        json!({
+            "error": true,
+            "errorNum": self.error_code.as_u16(),
+            "errorMessage": self.message,
+        })
+    }
+
+    fn into_response(self) -> Response {
+        Response::error(self.status, self.error_code, self.message)
+    }
+}
+
+impl From<ItemError> for QueryError {
+    fn from(error: ItemError) -> Self {
+        QueryError::new(error.status, error.error_code, error.message)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct EdgeDefinition {
+    collection: String,
+    from: Vec<String>,
+    to: Vec<String>,
+}
+
+impl EdgeDefinition {
+    fn parse(value: &Value) -> Result<Self, Response> {
+        let names = |attribute: &str| value.get(attribute).and_then(Value::as_array).map(|names| {
+            let mut names = names.iter().filter_map(Value::as_str).map(str::to_owned).collect::<Vec<_>>();
+            names.sort();
+            names.dedup();
+            names
+        });
+        match (value.get("collection").and_then(Value::as_str), names("from"), names("to")) {
+            (Some(collection), Some(from), Some(to)) if !collection.is_empty() => Ok(EdgeDefinition {
+                collection: collection.to_owned(),
+                from,
+                to,
+            }),
+            _ => Err(Response::error(400, ErrorCode::GraphCreateMalformedEdgeDefinition,
+                "edge definition is malformed")),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "collection": self.collection,
+            "from": self.from,
+            "to": self.to,
+        })
+    }
+
+    fn vertex_collections(&self) -> Vec<String> {
+        self.from.iter().chain(self.to.iter()).cloned().collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Graph {
+    name: String,
+    revision: String,
+    edge_definitions: Vec<EdgeDefinition>,
+    orphan_collections: Vec<String>,
+}
+
+impl Graph {
+    fn to_json(&self) -> Value {
+        json!({
+            "_id": format!("_graphs/{}", self.name),
+            "_key": self.name,
+            "_rev": self.revision,
+            "name": self.name,
+            "edgeDefinitions": self.edge_definitions.iter()
+                .map(EdgeDefinition::to_json).collect::<Vec<_>>(),
+            "orphanCollections": self.orphan_collections,
+            "isSmart": false,
+            "smartGraphAttribute": "",
+            "numberOfShards": 1,
+            "replicationFactor": 1,
+        })
+    }
+
+    fn edge_definition(&self, collection: &str) -> Option<&EdgeDefinition> {
+        self.edge_definitions.iter().find(|definition| definition.collection == collection)
+    }
+
+    fn edge_collections(&self) -> Vec<String> {
+        let mut collections = self.edge_definitions.iter()
+            .map(|definition| definition.collection.clone()).collect::<Vec<_>>();
+        collections.sort();
+        collections
+    }
+
+    /// Returns the vertex collections used in the edge definitions.
+    fn defined_vertex_collections(&self) -> Vec<String> {
+        let mut collections = self.edge_definitions.iter()
+            .flat_map(EdgeDefinition::vertex_collections).collect::<Vec<_>>();
+        collections.sort();
+        collections.dedup();
+        collections
+    }
+
+    fn vertex_collections(&self) -> Vec<String> {
+        let mut collections = self.defined_vertex_collections();
+        collections.extend(self.orphan_collections.iter().cloned());
+        collections.sort();
+        collections.dedup();
+        collections
+    }
+
+    /// Removes the orphan collections that are now used in an edge
+    /// definition and adds those of the given collections that are no
+    /// longer used in any edge definition.
+    fn update_orphans(&mut self, released: Vec<String>) {
+        let defined = self.defined_vertex_collections();
+        self.orphan_collections.retain(|collection| !defined.contains(collection));
+        for collection in released {
+            if !defined.contains(&collection) && !self.orphan_collections.contains(&collection) {
+                self.orphan_collections.push(collection);
+            }
+        }
+    }
+
+    /// Checks that the given collection holds the vertices or edges of this
+    /// graph.
+    fn check_collection(&self, kind: &str, collection: &str) -> Result<(), Response> {
+        if kind == "edge" {
+            self.edge_definition(collection).map(|_| ()).ok_or_else(|| Response::error(404,
+                ErrorCode::GraphEdgeColDoesNotExist, "edge collection not used in graph"))
+        } else if self.vertex_collections().iter().any(|name| name == collection) {
+            Ok(())
+        } else {
+            Err(Response::error(404, ErrorCode::GraphVertexColDoesNotExist,
+                "vertex collection not used in graph"))
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Cursor {
+    remaining: VecDeque<Value>,
+    batch_size: usize,
+    count: Option<usize>,
+    extra: Value,
+}
+
+#[derive(Clone, Debug)]
+struct Database {
+    id: String,
+    name: String,
+    collections: BTreeMap<String, Collection>,
+    cursors: BTreeMap<String, Cursor>,
+    graphs: BTreeMap<String, Graph>,
+    functions: BTreeMap<String, Value>,
+    query_tracking: Map<String, Value>,
+}
+
+impl Database {
+    fn new(id: String, name: String) -> Self {
+        Database {
+            id,
+            name,
+            collections: BTreeMap::new(),
+            cursors: BTreeMap::new(),
+            graphs: BTreeMap::new(),
+            functions: BTreeMap::new(),
+            query_tracking: default_query_tracking(),
+        }
+    }
+
+    /// Returns the document with the given id.
+    fn document(&self, id: &str) -> Option<&StoredDocument> {
+        let mut parts = id.splitn(2, '/');
+        let collection = self.collections.get(parts.next()?)?;
+        let position = collection.position(parts.next()?)?;
+        Some(&collection.documents[position])
+    }
+
+    fn graph(&self, name: &str) -> Result<Graph, Response> {
+        self.graphs.get(name).cloned().ok_or_else(|| Response::error(404,
+            ErrorCode::GraphNotFound, "graph not found"))
+    }
+
+    /// Creates the collection with the given name and type unless it exists
+    /// already.
+    fn ensure_collection(&mut self, clock: &mut Clock, name: &str, kind: u64) -> Result<(), Response> {
+        match self.collections.get(name) {
+            Some(collection) if collection.kind != kind => Err(if kind == COLLECTION_TYPE_EDGES {
+                Response::error(400, ErrorCode::ArangoCollectionTypeInvalid, "invalid collection type")
+            } else {
+                Response::error(400, ErrorCode::GraphWrongCollectionTypeVertex, "not a vertex collection")
+            }),
+            Some(_) => Ok(()),
+            None if !is_valid_name(name, false) =>
+                Err(Response::error(400, ErrorCode::ArangoIllegalName, "illegal name")),
+            None => {
+                let collection = Collection::new(clock.tick().to_string(), name.to_owned(), kind);
+                self.collections.insert(name.to_owned(), collection);
+                Ok(())
+            },
+        }
+    }
+
+    fn add_edge_definition(&mut self, clock: &mut Clock, graph: &mut Graph, definition: EdgeDefinition)
+        -> Result<(), Response>
+    {
+        if graph.edge_definition(&definition.collection).is_some() {
+            return Err(Response::error(400, ErrorCode::GraphCollectionMultiUse,
+                "multi use of edge collection in edge def"));
+        }
+        self.ensure_collection(clock, &definition.collection, COLLECTION_TYPE_EDGES)?;
+        for collection in definition.vertex_collections() {
+            self.ensure_collection(clock, &collection, COLLECTION_TYPE_DOCUMENTS)?;
+        }
+        graph.edge_definitions.push(definition);
+        graph.update_orphans(Vec::new());
+        Ok(())
+    }
+
+    /// Drops the given collections unless they are used by another graph.
+    fn drop_graph_collections(&mut self, collections: Vec<String>) {
+        for collection in collections {
+            let used = self.graphs.values().any(|graph| graph.vertex_collections().contains(&collection)
+                || graph.edge_definition(&collection).is_some());
+            if !used {
+                self.collections.remove(&collection);
+            }
+        }
+    }
+
+    /// Checks that the given vertex exists and is stored in one of the
+    /// given collections.
+    fn check_vertex(&self, vertex: Option<&Value>, collections: &[String]) -> Result<(), Response> {
+        let mut parts = vertex.and_then(Value::as_str).unwrap_or_default().splitn(2, '/');
+        let (collection, key) = (parts.next().unwrap_or_default(), parts.next().unwrap_or_default());
+        if !collections.iter().any(|name| name == collection) {
+            return Err(Response::error(400, ErrorCode::GraphInvalidEdge,
+                "edge does not conform to any edge definition"));
+        }
+        match self.collections.get(collection).and_then(|collection| collection.position(key)) {
+            Some(_) => Ok(()),
+            None => Err(Response::error(404, ErrorCode::ArangoDocumentNotFound, "vertex not found")),
+        }
+    }
+
+    fn collection(&self, name: &str) -> Result<&Collection, Response> {
+        self.collections.get(name).ok_or_else(|| Response::error(404,
+            ErrorCode::ArangoCollectionNotFound, format!("unknown collection '{}'", name)))
+    }
+
+    fn collection_mut(&mut self, name: &str) -> Result<&mut Collection, Response> {
+        self.collections.get_mut(name).ok_or_else(|| Response::error(404,
+            ErrorCode::ArangoCollectionNotFound, format!("unknown collection '{}'", name)))
+    }
+
+    fn documents_mut(&mut self, name: &str) -> Result<&mut Collection, Response> {
+        self.collections.get_mut(name).ok_or_else(|| Response::error(404,
+            ErrorCode::ArangoCollectionNotFound, format!("collection not found: {}", name)))
+    }
+}
+
+/// Gives queries access to the collections of a database.
+struct QueryData<'a> {
+    database: &'a mut Database,
+    clock: &'a mut Clock,
+}
+
+impl<'a> DataAccess for QueryData<'a> {
+    fn documents(&self, collection: &str) -> Result<Vec<Value>, QueryError> {
+        match self.database.collections.get(collection) {
+            Some(collection) => Ok(collection.documents.iter().cloned().map(Value::Object).collect()),
+            None => Err(QueryError::new(404, ErrorCode::ArangoCollectionNotFound,
+                format!("collection not found: {}", collection))),
+        }
+    }
+
+    fn document(&self, id: &str) -> Option<Value> {
+        self.database.document(id).cloned().map(Value::Object)
+    }
+
+    fn insert(&mut self, collection: &str, document: Value) -> Result<Value, QueryError> {
+        let collection = match self.database.collections.get_mut(collection) {
+            Some(collection) => collection,
+            None => return Err(QueryError::new(404, ErrorCode::ArangoCollectionNotFound,
+                format!("collection not found: {}", collection))),
+        };
+        let (new, _) = collection.insert(self.clock, document, false)?;
+        Ok(Value::Object(new))
+    }
+}
+
+/// A traversal as far as supported by the fake server, which does not
+/// execute any JavaScript functions.
+struct Traversal<'a> {
+    database: &'a Database,
+    edge_collections: Vec<String>,
+    direction: String,
+    min_depth: u64,
+    max_depth: Option<u64>,
+    vertex_uniqueness: String,
+    edge_uniqueness: String,
+    postorder: bool,
+    backward: bool,
+    max_iterations: u64,
+    iterations: u64,
+    visited_vertices: HashSet<String>,
+    visited_edges: HashSet<String>,
+    vertices: Vec<Value>,
+    paths: Vec<Value>,
+}
+
+impl<'a> Traversal<'a> {
+    /// Returns the edges connected to the given vertex together with the id
+    /// of the vertex on the other side.
+    fn connected(&self, vertex_id: &str) -> Vec<(Value, String)> {
+        let mut connected = Vec::new();
+        for name in &self.edge_collections {
+            let edges = match self.database.collections.get(name) {
+                Some(edges) => edges,
+                None => continue,
+            };
+            for edge in &edges.documents {
+                let from = edge["_from"].as_str().unwrap_or_default();
+                let to = edge["_to"].as_str().unwrap_or_default();
+                let next = if from == vertex_id && self.direction != "inbound" {
+                    to
+                } else if to == vertex_id && self.direction != "outbound" {
+                    from
+                } else {
+                    continue;
+                };
+                connected.push((Value::Object(edge.clone()), next.to_owned()));
+            }
+        }
+        if self.backward {
+            connected.reverse();
+        }
+        connected
+    }
+
+    /// Checks the uniqueness rules for following the given edge and marks
+    /// the edge and vertex as visited.
+    fn admit(&mut self, edge: &Value, next: &str, vertices: &[Value], edges: &[Value]) -> bool {
+        let edge_id = edge["_id"].as_str().unwrap_or_default();
+        let admitted = match &self.edge_uniqueness[..] {
+            "global" => !self.visited_edges.contains(edge_id),
+            "none" => true,
+            _ => !edges.iter().any(|visited| visited["_id"] == edge_id),
+        } && match &self.vertex_uniqueness[..] {
+            "global" => !self.visited_vertices.contains(next),
+            "path" => !vertices.iter().any(|visited| visited["_id"] == next),
+            _ => true,
+        };
+        if admitted {
+            self.visited_edges.insert(edge_id.to_owned());
+            self.visited_vertices.insert(next.to_owned());
+        }
+        admitted
+    }
+
+    fn iterate(&mut self) -> Result<(), Response> {
+        self.iterations += 1;
+        if self.iterations > self.max_iterations {
+            Err(Response::error(500, ErrorCode::GraphTooManyIterations, "too many iterations"))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn record(&mut self, vertices: &[Value], edges: &[Value]) {
+        if edges.len() as u64 >= self.min_depth {
+            self.vertices.push(vertices[vertices.len() - 1].clone());
+            self.paths.push(json!({ "edges": edges, "vertices": vertices }));
+        }
+    }
+
+    /// Returns the vertices that can be reached from the end of the given
+    /// path.
+    fn expand(&mut self, vertices: &[Value], edges: &[Value]) -> Vec<(Value, Value)> {
+        if let Some(max_depth) = self.max_depth {
+            if edges.len() as u64 >= max_depth {
+                return Vec::new();
+            }
+        }
+        let vertex_id = vertices[vertices.len() - 1]["_id"].as_str().unwrap_or_default().to_owned();
+        let mut expanded = Vec::new();
+        for (edge, next) in self.connected(&vertex_id) {
+            let vertex = match self.database.document(&next) {
+                Some(vertex) => Value::Object(vertex.clone()),
+                None => continue,
+            };
+            if self.admit(&edge, &next, vertices, edges) {
+                expanded.push((edge, vertex));
+            }
+        }
+        expanded
+    }
+
+    fn depth_first(&mut self, vertices: &mut Vec<Value>, edges: &mut Vec<Value>) -> Result<(), Response> {
+        self.iterate()?;
+        if !self.postorder {
+            self.record(vertices, edges);
+        }
+        for (edge, vertex) in self.expand(vertices, edges) {
+            edges.push(edge);
+            vertices.push(vertex);
+            self.depth_first(vertices, edges)?;
+            edges.pop();
+            vertices.pop();
+        }
+        if self.postorder {
+            self.record(vertices, edges);
+        }
+        Ok(())
+    }
+
+    fn breadth_first(&mut self, start: Value) -> Result<(), Response> {
+        let mut queue = VecDeque::new();
+        queue.push_back((vec![start], Vec::new()));
+        while let Some((vertices, edges)) = queue.pop_front() {
+            self.iterate()?;
+            self.record(&vertices, &edges);
+            for (edge, vertex) in self.expand(&vertices, &edges) {
+                let mut next_vertices = vertices.clone();
+                next_vertices.push(vertex);
+                let mut next_edges = edges.clone();
+                next_edges.push(edge);
+                queue.push_back((next_vertices, next_edges));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The state of the fake server.
+#[derive(Debug)]
+pub(crate) struct Backend {
+    clock: Clock,
+    users: BTreeMap<String, User>,
+    tokens: HashMap<String, String>,
+    databases: BTreeMap<String, Database>,
+}
+
+impl Backend {
+    /// Creates a new backend with the `_system` database and a `root` user
+    /// with the given password.
+    pub(crate) fn new(root_password: &str) -> Self {
+        let mut clock = Clock::default();
+        let mut users = BTreeMap::new();
+        users.insert(ROOT_USERNAME.to_owned(), User::new(ROOT_USERNAME.to_owned(),
+            root_password.to_owned(), true, Value::Object(Map::new())));
+        let mut databases = BTreeMap::new();
+        databases.insert(SYSTEM_DATABASE.to_owned(),
+            Database::new(clock.tick().to_string(), SYSTEM_DATABASE.to_owned()));
+        Backend {
+            clock,
+            users,
+            tokens: HashMap::new(),
+            databases,
+        }
+    }
+
+    /// Handles the given request and returns the response.
+    pub(crate) fn handle(&mut self, request: &Request) -> Response {
+        match self.dispatch(request) {
+            Ok(response) | Err(response) => response,
+        }
+    }
+
+    fn dispatch(&mut self, request: &Request) -> Handled {
+        let path = request.path.iter().map(String::as_str).collect::<Vec<_>>();
+        if path == ["_open", "auth"] {
+            return match request.method {
+                Method::Post => self.login(request),
+                _ => Err(Response::method_not_allowed()),
+            };
+        }
+        let username = self.authenticate(&request.credentials).ok_or_else(Response::unauthorized)?;
+        if !self.databases.contains_key(&request.database) {
+            return Err(Response::error(404, ErrorCode::ArangoDatabaseNotFound, "database not found"));
+        }
+        let self_service = request.method == Method::Get && match path[..] {
+            ["_api", "database", "user"] | ["_api", "version"] | ["_admin", "server", _] => true,
+            _ => path.len() >= 3 && path[0] == "_api" && path[1] == "user" && path[2] == username,
+        };
+        if !self_service {
+            self.authorize(&username, request, &path)?;
+        }
+        match path.get(1).cloned() {
+            Some("database") if path[0] == "_api" => self.handle_database(&username, request, &path[2..]),
+            Some("user") if path[0] == "_api" => self.handle_user(request, &path[2..]),
+            Some("collection") if path[0] == "_api" => self.handle_collection(request, &path[2..]),
+            Some("document") if path[0] == "_api" => self.handle_document(request, &path[2..]),
+            Some("cursor") if path[0] == "_api" => self.handle_cursor(request, &path[2..]),
+            Some("index") if path[0] == "_api" => self.handle_index(request, &path[2..]),
+            Some("gharial") if path[0] == "_api" => self.handle_graph(request, &path[2..]),
+            Some("edges") if path[0] == "_api" => self.handle_edges(request, &path[2..]),
+            Some("traversal") if path[0] == "_api" => self.handle_traversal(request, &path[2..]),
+            Some("aqlfunction") if path[0] == "_api" => self.handle_aql_function(request, &path[2..]),
+            Some("query") if path[0] == "_api" => self.handle_query(request, &path[2..]),
+            Some("engine") if path[0] == "_api" => self.handle_engine(request, &path[2..]),
+            Some("export") if path[0] == "_api" => self.handle_export(request, &path[2..]),
+            Some("version") if path[0] == "_api" => self.handle_version(request, &path[2..]),
+            Some("database") if path[0] == "_admin" => self.handle_admin_database(request, &path[2..]),
+            Some("server") if path[0] == "_admin" => self.handle_server(request, &path[2..]),
+            _ => Err(Response::not_found(request)),
+        }
+    }
+
+    pub(crate) fn authenticate(&self, credentials: &Credentials) -> Option<String> {
+        let username = match *credentials {
+            Credentials::None => return None,
+            Credentials::Basic(ref username, ref password) => {
+                let user = self.users.get(username)?;
+                if &user.password != password {
+                    return None;
+                }
+                username.to_owned()
+            },
+            Credentials::Bearer(ref token) => self.tokens.get(token)?.to_owned(),
+        };
+        match self.users.get(&username) {
+            Some(user) if user.active => Some(username),
+            _ => None,
+        }
+    }
+
+    /// Checks whether the user may access the database of the request.
+    ///
+    /// Requests that modify data require read-write access, managing
+    /// databases and users requires read-write access to `_system`.
+    fn authorize(&self, username: &str, request: &Request, path: &[&str]) -> Result<(), Response> {
+        let user = &self.users[username];
+        let administrative = match path.get(1).cloned() {
+            Some("user") => true,
+            Some("database") => path.get(2) != Some(&"current"),
+            _ => false,
+        };
+        let database = if administrative { SYSTEM_DATABASE } else { &request.database };
+        let required = match request.method {
+            Method::Get | Method::Head => Level::ReadOnly,
+            _ if path.get(1) == Some(&"cursor") => Level::ReadOnly,
+            _ => Level::ReadWrite,
+        };
+        let level = user.database_level(database);
+        if level == Level::None {
+            Err(Response::unauthorized())
+        } else if level < required {
+            Err(Response::error(403, ErrorCode::Forbidden, "forbidden"))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn login(&mut self, request: &Request) -> Handled {
+        let body = request.object()?;
+        let username = body.get("username").and_then(Value::as_str).unwrap_or_default().to_owned();
+        let password = body.get("password").and_then(Value::as_str).unwrap_or_default().to_owned();
+        if self.authenticate(&Credentials::Basic(username.clone(), password)).is_none() {
+            return Err(Response::error(401, ErrorCode::HttpUnauthorized, "Wrong credentials"));
+        }
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|now| now.as_secs()).unwrap_or(0);
+        let header = json!({ "alg": "HS256", "typ": "JWT" });
+        let payload = json!({
+            "preferred_username": username,
+            "iss": "arangodb",
+            "iat": now,
+            "exp": now + TOKEN_LIFETIME_SECS,
+        });
+        let jwt = format!("{}.{}.{}",
+            base64::encode_config(header.to_string().as_bytes(), base64::URL_SAFE_NO_PAD),
+            base64::encode_config(payload.to_string().as_bytes(), base64::URL_SAFE_NO_PAD),
+            base64::encode_config(format!("signature-{}", self.clock.tick()).as_bytes(),
+                base64::URL_SAFE_NO_PAD));
+        self.tokens.insert(jwt.clone(), username);
+        Ok(Response::new(200, json!({ "jwt": jwt })))
+    }
+
+    fn handle_database(&mut self, username: &str, request: &Request, path: &[&str]) -> Handled {
+        match (&request.method, path) {
+            (&Method::Get, &[]) => {
+                require_system_database(request)?;
+                Ok(Response::result(200, Value::Array(self.databases.keys()
+                    .cloned().map(Value::String).collect())))
+            },
+            (&Method::Get, &["user"]) => {
+                let user = &self.users[username];
+                Ok(Response::result(200, Value::Array(self.databases.keys()
+                    .filter(|database| user.database_level(database) != Level::None)
+                    .cloned().map(Value::String).collect())))
+            },
+            (&Method::Get, &["current"]) => {
+                let database = &self.databases[&request.database];
+                Ok(Response::result(200, json!({
+                    "id": database.id,
+                    "name": database.name,
+                    "path": format!("/var/lib/arangodb3/databases/database-{}", database.id),
+                    "isSystem": database.name == SYSTEM_DATABASE,
+                })))
+            },
+            (&Method::Post, &[]) => {
+                require_system_database(request)?;
+                let body = request.object()?;
+                let name = body.get("name").and_then(Value::as_str).unwrap_or_default().to_owned();
+                if !is_valid_name(&name, false) {
+                    return Err(Response::error(400, ErrorCode::ArangoDatabaseNameInvalid,
+                        "database name invalid"));
+                }
+                if self.databases.contains_key(&name) {
+                    return Err(Response::error(409, ErrorCode::ArangoDuplicateName, "duplicate name"));
+                }
+                let mut new_users = Vec::new();
+                for user in body.get("users").and_then(Value::as_array).cloned().unwrap_or_default() {
+                    let user_name = user.get("user").or_else(|| user.get("username"))
+                        .and_then(Value::as_str).unwrap_or_default().to_owned();
+                    if user_name.is_empty() {
+                        return Err(Response::error(400, ErrorCode::HttpBadParameter,
+                            "user name must not be empty"));
+                    }
+                    new_users.push(User::new(user_name,
+                        user.get("passwd").and_then(Value::as_str).unwrap_or_default().to_owned(),
+                        user.get("active").and_then(Value::as_bool).unwrap_or(true),
+                        user.get("extra").cloned().unwrap_or_else(|| Value::Object(Map::new()))));
+                }
+                let id = self.clock.tick().to_string();
+                self.databases.insert(name.clone(), Database::new(id, name.clone()));
+                for new_user in new_users {
+                    let user = self.users.entry(new_user.name.clone()).or_insert(new_user);
+                    user.databases.insert(name.clone(), Level::ReadWrite);
+                }
+                Ok(Response::result(201, Value::Bool(true)))
+            },
+            (&Method::Delete, &[name]) => {
+                require_system_database(request)?;
+                if name == SYSTEM_DATABASE {
+                    return Err(Response::error(403, ErrorCode::Forbidden, "forbidden"));
+                }
+                if self.databases.remove(name).is_none() {
+                    return Err(Response::error(404, ErrorCode::ArangoDatabaseNotFound,
+                        "database not found"));
+                }
+                for user in self.users.values_mut() {
+                    user.databases.remove(name);
+                    user.collections.retain(|(database, _), _| database != name);
+                }
+                Ok(Response::result(200, Value::Bool(true)))
+            },
+            (_, &[]) | (_, &[_]) => Err(Response::method_not_allowed()),
+            _ => Err(Response::not_found(request)),
+        }
+    }
+
+    fn user_mut(&mut self, name: &str) -> Result<&mut User, Response> {
+        self.users.get_mut(name)
+            .ok_or_else(|| Response::error(404, ErrorCode::UserNotFound, "user not found"))
+    }
+
+    fn handle_user(&mut self, request: &Request, path: &[&str]) -> Handled {
+        match (&request.method, path) {
+            (&Method::Get, &[]) => Ok(Response::result(200, Value::Array(self.users.values()
+                .map(|user| Value::Object(user.to_json())).collect()))),
+            (&Method::Post, &[]) => {
+                let body = request.object()?;
+                let name = body.get("user").and_then(Value::as_str).unwrap_or_default().to_owned();
+                if name.is_empty() {
+                    return Err(Response::error(400, ErrorCode::HttpBadParameter,
+                        "invalid user name"));
+                }
+                if self.users.contains_key(&name) {
+                    return Err(Response::error(409, ErrorCode::UserDuplicate, "duplicate user"));
+                }
+                let user = User::new(name.clone(),
+                    body.get("passwd").and_then(Value::as_str).unwrap_or_default().to_owned(),
+                    body.get("active").and_then(Value::as_bool).unwrap_or(true),
+                    body.get("extra").cloned().unwrap_or_else(|| Value::Object(Map::new())));
+                let fields = user.to_json();
+                self.users.insert(name, user);
+                Ok(Response::fields(201, fields))
+            },
+            (&Method::Get, &[name]) => {
+                let user = self.user_mut(name)?;
+                Ok(Response::fields(200, user.to_json()))
+            },
+            (&Method::Patch, &[name]) | (&Method::Put, &[name]) => {
+                let body = request.object()?;
+                let replace = request.method == Method::Put;
+                let user = self.user_mut(name)?;
+                match body.get("passwd").and_then(Value::as_str) {
+                    Some(password) => user.password = password.to_owned(),
+                    None if replace => user.password = String::new(),
+                    None => {},
+                }
+                match body.get("active").and_then(Value::as_bool) {
+                    Some(active) => user.active = active,
+                    None if replace => user.active = true,
+                    None => {},
+                }
+                match body.get("extra") {
+                    Some(extra) => user.extra = extra.clone(),
+                    None if replace => user.extra = Value::Object(Map::new()),
+                    None => {},
+                }
+                Ok(Response::fields(200, user.to_json()))
+            },
+            (&Method::Delete, &[name]) => {
+                if self.users.remove(name).is_none() {
+                    return Err(Response::error(404, ErrorCode::UserNotFound, "user not found"));
+                }
+                self.tokens.retain(|_, user| user != name);
+                Ok(Response::fields(202, Map::new()))
+            },
+            (&Method::Get, &[name, "database"]) => {
+                let databases = self.databases.keys().cloned().collect::<Vec<_>>();
+                let user = self.user_mut(name)?;
+                let mut levels = Map::new();
+                for database in databases {
+                    let level = user.database_level(&database);
+                    if level != Level::None {
+                        levels.insert(database, Value::String(level.as_str().to_owned()));
+                    }
+                }
+                Ok(Response::result(200, Value::Object(levels)))
+            },
+            (&Method::Get, &[name, "database", database]) => {
+                let user = self.user_mut(name)?;
+                Ok(Response::result(200, Value::String(user.database_level(database).as_str().to_owned())))
+            },
+            (&Method::Get, &[name, "database", database, collection]) => {
+                let user = self.user_mut(name)?;
+                Ok(Response::result(200, Value::String(
+                    user.collection_level(database, collection).as_str().to_owned())))
+            },
+            (&Method::Put, &[name, "database", database]) |
+            (&Method::Put, &[name, "database", database, _]) => {
+                let level = request.object()?.get("grant").and_then(Value::as_str)
+                    .and_then(Level::parse)
+                    .ok_or_else(|| Response::error(400, ErrorCode::HttpBadParameter,
+                        "invalid grant"))?;
+                let user = self.user_mut(name)?;
+                let mut fields = Map::new();
+                match path.get(3) {
+                    Some(collection) => {
+                        user.collections.insert((database.to_owned(), collection.to_string()), level);
+                        fields.insert(format!("{}/{}", database, collection),
+                            Value::String(level.as_str().to_owned()));
+                    },
+                    None => {
+                        user.databases.insert(database.to_owned(), level);
+                        fields.insert(database.to_owned(), Value::String(level.as_str().to_owned()));
+                    },
+                }
+                Ok(Response::fields(200, fields))
+            },
+            (&Method::Delete, &[name, "database", database]) |
+            (&Method::Delete, &[name, "database", database, _]) => {
+                let user = self.user_mut(name)?;
+                match path.get(3) {
+                    Some(collection) => {
+                        user.collections.remove(&(database.to_owned(), collection.to_string()));
+                    },
+                    None => {
+                        user.databases.remove(database);
+                    },
+                }
+                Ok(Response::fields(202, Map::new()))
+            },
+            (_, &[]) | (_, &[_]) => Err(Response::method_not_allowed()),
+            _ => Err(Response::not_found(request)),
+        }
+    }
+
+    fn handle_collection(&mut self, request: &Request, path: &[&str]) -> Handled {
+        let database = self.databases.get_mut(&request.database).expect("database exists");
+        match (&request.method, path) {
+            (&Method::Get, &[]) => {
+                let exclude_system = request.flag("excludeSystem", false);
+                Ok(Response::result(200, Value::Array(database.collections.values()
+                    .filter(|collection| !exclude_system || !collection.is_system)
+                    .map(|collection| Value::Object(collection.summary()))
+                    .collect())))
+            },
+            (&Method::Post, &[]) => {
+                let body = request.object()?;
+                let name = body.get("name").and_then(Value::as_str).unwrap_or_default().to_owned();
+                let is_system = body.get("isSystem").and_then(Value::as_bool).unwrap_or(false);
+                if !is_valid_name(&name, is_system) {
+                    return Err(Response::error(400, ErrorCode::ArangoIllegalName, "illegal name"));
+                }
+                if database.collections.contains_key(&name) {
+                    return Err(Response::error(409, ErrorCode::ArangoDuplicateName, "duplicate name"));
+                }
+                let kind = body.get("type").and_then(Value::as_u64).unwrap_or(COLLECTION_TYPE_DOCUMENTS);
+                if kind != COLLECTION_TYPE_DOCUMENTS && kind != COLLECTION_TYPE_EDGES {
+                    return Err(Response::error(400, ErrorCode::ArangoCollectionTypeInvalid,
+                        "invalid collection type"));
+                }
+                let defaults = Collection::new(self.clock.tick().to_string(), name.clone(), kind);
+                let collection = Collection {
+                    is_system,
+                    wait_for_sync: body.get("waitForSync").and_then(Value::as_bool)
+                        .unwrap_or(defaults.wait_for_sync),
+                    is_volatile: body.get("isVolatile").and_then(Value::as_bool)
+                        .unwrap_or(defaults.is_volatile),
+                    do_compact: body.get("doCompact").and_then(Value::as_bool)
+                        .unwrap_or(defaults.do_compact),
+                    journal_size: body.get("journalSize").and_then(Value::as_u64)
+                        .unwrap_or(defaults.journal_size),
+                    index_buckets: body.get("indexBuckets").and_then(Value::as_u64)
+                        .unwrap_or(defaults.index_buckets),
+                    allow_user_keys: body.get("keyOptions")
+                        .and_then(|options| options.get("allowUserKeys"))
+                        .and_then(Value::as_bool).unwrap_or(defaults.allow_user_keys),
+                    ..defaults
+                };
+                let properties = collection.properties();
+                database.collections.insert(name, collection);
+                Ok(Response::fields(200, properties))
+            },
+            (&Method::Get, &[name]) => Ok(Response::fields(200, database.collection(name)?.summary())),
+            (&Method::Delete, &[name]) => {
+                database.collection(name)?;
+                let collection = database.collections.remove(name).expect("collection exists");
+                let mut fields = Map::new();
+                fields.insert("id".to_owned(), Value::String(collection.id));
+                Ok(Response::fields(200, fields))
+            },
+            (&Method::Get, &[name, "properties"]) =>
+                Ok(Response::fields(200, database.collection(name)?.properties())),
+            (&Method::Put, &[name, "properties"]) => {
+                let body = request.object()?;
+                let collection = database.collection_mut(name)?;
+                if let Some(wait_for_sync) = body.get("waitForSync").and_then(Value::as_bool) {
+                    collection.wait_for_sync = wait_for_sync;
+                }
+                if let Some(journal_size) = body.get("journalSize").and_then(Value::as_u64) {
+                    collection.journal_size = journal_size;
+                }
+                Ok(Response::fields(200, collection.properties()))
+            },
+            (&Method::Put, &[name, "rename"]) => {
+                let new_name = request.object()?.get("name").and_then(Value::as_str)
+                    .unwrap_or_default().to_owned();
+                let is_system = database.collection(name)?.is_system;
+                if new_name.is_empty() {
+                    return Err(Response::error(403, ErrorCode::Forbidden, "forbidden"));
+                }
+                if !is_valid_name(&new_name, is_system) {
+                    return Err(Response::error(400, ErrorCode::ArangoIllegalName, "illegal name"));
+                }
+                if database.collections.contains_key(&new_name) {
+                    return Err(Response::error(409, ErrorCode::ArangoDuplicateName, "duplicate name"));
+                }
+                let mut collection = database.collections.remove(name).expect("collection exists");
+                collection.name = new_name.clone();
+                for document in &mut collection.documents {
+                    let id = format!("{}/{}", new_name, document["_key"].as_str().unwrap_or_default());
+                    document.insert("_id".to_owned(), Value::String(id));
+                }
+                let summary = collection.summary();
+                database.collections.insert(new_name, collection);
+                Ok(Response::fields(200, summary))
+            },
+            (&Method::Get, &[name, "count"]) => {
+                let collection = database.collection(name)?;
+                let mut properties = collection.properties();
+                properties.insert("count".to_owned(), Value::from(collection.documents.len()));
+                Ok(Response::fields(200, properties))
+            },
+            (&Method::Put, &[name, "truncate"]) => {
+                let collection = database.collection_mut(name)?;
+                collection.documents.clear();
+                Ok(Response::fields(200, collection.summary()))
+            },
+            (&Method::Get, &[name, "figures"]) => {
+                let collection = database.collection(name)?;
+                let mut properties = collection.properties();
+                properties.insert("count".to_owned(), Value::from(collection.documents.len()));
+                properties.insert("figures".to_owned(), collection.figures());
+                Ok(Response::fields(200, properties))
+            },
+            (&Method::Get, &[name, "checksum"]) => {
+                let collection = database.collection(name)?;
+                let checksum = collection.checksum(request.flag("withRevisions", false),
+                    request.flag("withData", false));
+                let mut summary = collection.summary();
+                summary.insert("checksum".to_owned(), Value::String(checksum.to_string()));
+                summary.insert("revision".to_owned(), Value::String(collection.revision()));
+                Ok(Response::fields(200, summary))
+            },
+            (&Method::Get, &[name, "revision"]) => {
+                let collection = database.collection(name)?;
+                let mut properties = collection.properties();
+                properties.insert("revision".to_owned(), Value::String(collection.revision()));
+                Ok(Response::fields(200, properties))
+            },
+            (&Method::Put, &[name, "load"]) => {
+                let collection = database.collection_mut(name)?;
+                collection.is_loaded = true;
+                let mut summary = collection.summary();
+                summary.insert("count".to_owned(), Value::from(collection.documents.len()));
+                Ok(Response::fields(200, summary))
+            },
+            (&Method::Put, &[name, "unload"]) => {
+                let collection = database.collection_mut(name)?;
+                collection.is_loaded = false;
+                Ok(Response::fields(200, collection.summary()))
+            },
+            (&Method::Put, &[name, "loadIndexesIntoMemory"]) => {
+                database.collection(name)?;
+                Ok(Response::result(200, Value::Bool(true)))
+            },
+            (&Method::Put, &[name, "rotate"]) => {
+                if database.collection(name)?.documents.is_empty() {
+                    return Err(Response::error(400, ErrorCode::ArangoNoJournal, "no journal"));
+                }
+                Ok(Response::result(200, Value::Bool(true)))
+            },
+            (_, &[]) | (_, &[_]) | (_, &[_, "properties"]) | (_, &[_, "rename"])
+                | (_, &[_, "count"]) | (_, &[_, "truncate"]) | (_, &[_, "figures"])
+                | (_, &[_, "checksum"]) | (_, &[_, "revision"]) | (_, &[_, "load"])
+                | (_, &[_, "unload"]) | (_, &[_, "loadIndexesIntoMemory"]) | (_, &[_, "rotate"]) =>
+                Err(Response::method_not_allowed()),
+            _ => Err(Response::not_found(request)),
+        }
+    }
+
+    fn handle_document(&mut self, request: &Request, path: &[&str]) -> Handled {
+        let clock = &mut self.clock;
+        let database = self.databases.get_mut(&request.database).expect("database exists");
+        let return_old = request.flag("returnOld", false);
+        let return_new = request.flag("returnNew", false);
+        let status = if request.flag("waitForSync", false) { 201 } else { 202 };
+        match (&request.method, path) {
+            (&Method::Get, &[name, key]) | (&Method::Head, &[name, key]) => {
+                let collection = database.documents_mut(name)?;
+                let document = match collection.position(key) {
+                    Some(position) => &collection.documents[position],
+                    None => return Err(Response::error(404, ErrorCode::ArangoDocumentNotFound,
+                        "document not found")),
+                };
+                let revision = document["_rev"].as_str().unwrap_or_default().to_owned();
+                if let Some(ref if_none_match) = request.if_none_match {
+                    if unquote(if_none_match) == revision {
+                        return Ok(Response::empty(304));
+                    }
+                }
+                check_if_match(request, document)?;
+                let mut response = if request.method == Method::Head {
+                    Response::empty(200)
+                } else {
+                    Response::new(200, Value::Object(document.clone()))
+                };
+                response.etag = Some(revision);
+                Ok(response)
+            },
+            (&Method::Post, &[name]) => {
+                let overwrite = request.flag("overwrite", false);
+                let collection = database.documents_mut(name)?;
+                let mut insert = |content: Value| -> Result<Value, ItemError> {
+                    let (new, old) = collection.insert(clock, content, overwrite)?;
+                    let mut header = header_of(&new);
+                    if let Some(old) = old {
+                        header.insert("_oldRev".to_owned(), old["_rev"].clone());
+                        if return_old {
+                            header.insert("old".to_owned(), Value::Object(old));
+                        }
+                    }
+                    if return_new {
+                        header.insert("new".to_owned(), Value::Object(new));
+                    }
+                    Ok(Value::Object(header))
+                };
+                match request.body {
+                    Some(Value::Array(ref contents)) => Ok(Response::new(status, Value::Array(
+                        contents.iter().cloned()
+                            .map(|content| insert(content).unwrap_or_else(|error: ItemError| error.to_item()))
+                            .collect()))),
+                    Some(ref content) => insert(content.clone())
+                        .map(|header| Response::new(status, header))
+                        .map_err(ItemError::into_response),
+                    None => Err(Response::error(400, ErrorCode::ArangoDocumentTypeInvalid,
+                        "invalid document type")),
+                }
+            },
+            (&Method::Put, &[name, key]) | (&Method::Patch, &[name, key]) => {
+                let collection = database.documents_mut(name)?;
+                let content = request.object()?;
+                let position = collection.position(key).ok_or_else(|| Response::error(404,
+                    ErrorCode::ArangoDocumentNotFound, "document not found"))?;
+                check_if_match(request, &collection.documents[position])?;
+                if !request.flag("ignoreRevs", true) {
+                    check_revision(&content, &collection.documents[position])
+                        .map_err(|_| Response::error(412, ErrorCode::ArangoConflict, "precondition failed"))?;
+                }
+                modify(collection, clock, request, position, content, return_old, return_new)
+                    .map(|header| Response::new(status, header))
+                    .map_err(ItemError::into_response)
+            },
+            (&Method::Put, &[name]) | (&Method::Patch, &[name]) => {
+                let collection = database.documents_mut(name)?;
+                let contents = match request.body {
+                    Some(Value::Array(ref contents)) => contents.clone(),
+                    _ => return Err(Response::error(400, ErrorCode::HttpBadParameter,
+                        "expecting an array of documents")),
+                };
+                let ignore_revisions = request.flag("ignoreRevs", true);
+                Ok(Response::new(status, Value::Array(contents.into_iter().map(|content| {
+                    let content = match content {
+                        Value::Object(content) => content,
+                        _ => return ItemError::new(400, ErrorCode::ArangoDocumentTypeInvalid,
+                            "invalid document type").to_item(),
+                    };
+                    let key = content.get("_key").and_then(Value::as_str).map(str::to_owned)
+                        .or_else(|| content.get("_id").and_then(Value::as_str)
+                            .and_then(|id| id.split_once('/').map(|(_, key)| key)).map(str::to_owned))
+                        .unwrap_or_default();
+                    let position = match collection.position(&key) {
+                        Some(position) => position,
+                        None => return ItemError::new(404, ErrorCode::ArangoDocumentNotFound,
+                            "document not found").to_item(),
+                    };
+                    if !ignore_revisions && check_revision(&content, &collection.documents[position]).is_err() {
+                        return ItemError::new(412, ErrorCode::ArangoConflict, "conflict").to_item();
+                    }
+                    modify(collection, clock, request, position, content, return_old, return_new)
+                        .unwrap_or_else(|error| error.to_item())
+                }).collect())))
+            },
+            (&Method::Delete, &[name, key]) => {
+                let collection = database.documents_mut(name)?;
+                let position = collection.position(key).ok_or_else(|| Response::error(404,
+                    ErrorCode::ArangoDocumentNotFound, "document not found"))?;
+                check_if_match(request, &collection.documents[position])?;
+                let old = collection.documents.remove(position);
+                let mut header = header_of(&old);
+                if return_old {
+                    header.insert("old".to_owned(), Value::Object(old));
+                }
+                Ok(Response::new(status, Value::Object(header)))
+            },
+            (&Method::Delete, &[name]) => {
+                let collection = database.documents_mut(name)?;
+                let selectors = match request.body {
+                    Some(Value::Array(ref selectors)) => selectors.clone(),
+                    _ => return Err(Response::error(400, ErrorCode::HttpBadParameter,
+                        "expecting an array of document keys")),
+                };
+                let ignore_revisions = request.flag("ignoreRevs", true);
+                Ok(Response::new(status, Value::Array(selectors.into_iter().map(|selector| {
+                    let (handle, revision) = match selector {
+                        Value::String(handle) => (handle, None),
+                        Value::Object(ref object) => (
+                            object.get("_key").or_else(|| object.get("_id"))
+                                .and_then(Value::as_str).unwrap_or_default().to_owned(),
+                            object.get("_rev").cloned(),
+                        ),
+                        _ => return ItemError::new(400, ErrorCode::ArangoDocumentHandleBad,
+                            "illegal document handle").to_item(),
+                    };
+                    let key = handle.rsplit('/').next().unwrap_or_default().to_owned();
+                    let position = match collection.position(&key) {
+                        Some(position) => position,
+                        None => return ItemError::new(404, ErrorCode::ArangoDocumentNotFound,
+                            "document not found").to_item(),
+                    };
+                    if !ignore_revisions && revision.is_some_and(|revision|
+                        revision != collection.documents[position]["_rev"])
+                    {
+                        return ItemError::new(412, ErrorCode::ArangoConflict, "conflict").to_item();
+                    }
+                    let old = collection.documents.remove(position);
+                    let mut header = header_of(&old);
+                    if return_old {
+                        header.insert("old".to_owned(), Value::Object(old));
+                    }
+                    Value::Object(header)
+                }).collect())))
+            },
+            (_, &[_]) | (_, &[_, _]) => Err(Response::method_not_allowed()),
+            _ => Err(Response::not_found(request)),
+        }
+    }
+
+    fn handle_cursor(&mut self, request: &Request, path: &[&str]) -> Handled {
+        let clock = &mut self.clock;
+        let database = self.databases.get_mut(&request.database).expect("database exists");
+        match (&request.method, path) {
+            (&Method::Post, &[]) => {
+                let body = request.object()?;
+                let query = body.get("query").and_then(Value::as_str).unwrap_or_default().to_owned();
+                let bind_vars = match body.get("bindVars") {
+                    Some(Value::Object(bind_vars)) => bind_vars.clone(),
+                    _ => Map::new(),
+                };
+                let full_count = body.get("options").and_then(|options| options.get("fullCount"))
+                    .and_then(Value::as_bool).unwrap_or(false);
+                let started = Instant::now();
+                let result = aql::execute(&query, &bind_vars, &mut QueryData { database: &mut *database, clock: &mut *clock })?;
+                let elapsed = started.elapsed();
+                let mut stats = json!({
+                    "writesExecuted": result.stats.writes_executed,
+                    "writesIgnored": 0,
+                    "scannedFull": result.stats.scanned_full,
+                    "scannedIndex": 0,
+                    "filtered": result.stats.filtered,
+                    "httpRequests": 0,
+                    "executionTime": elapsed.as_secs() as f64 + f64::from(elapsed.subsec_nanos()) * 1e-9,
+                });
+                if full_count {
+                    stats["fullCount"] = Value::from(result.stats.full_count
+                        .unwrap_or(result.values.len() as u64));
+                }
+                let count = if body.get("count").and_then(Value::as_bool).unwrap_or(false) {
+                    Some(result.values.len())
+                } else {
+                    None
+                };
+                let cursor = Cursor {
+                    remaining: result.values.into_iter().collect(),
+                    batch_size: body.get("batchSize").and_then(Value::as_u64)
+                        .map_or(DEFAULT_BATCH_SIZE, |batch_size| batch_size.max(1) as usize),
+                    count,
+                    extra: json!({ "stats": stats, "warnings": [] }),
+                };
+                let id = clock.tick().to_string();
+                Ok(next_batch(database, id, cursor, 201))
+            },
+            (&Method::Put, &[id]) => {
+                let cursor = database.cursors.remove(id).ok_or_else(|| Response::error(404,
+                    ErrorCode::CursorNotFound, "cursor not found"))?;
+                Ok(next_batch(database, id.to_owned(), cursor, 200))
+            },
+            (&Method::Delete, &[id]) => {
+                database.cursors.remove(id).ok_or_else(|| Response::error(404,
+                    ErrorCode::CursorNotFound, "cursor not found"))?;
+                let mut fields = Map::new();
+                fields.insert("id".to_owned(), Value::String(id.to_owned()));
+                Ok(Response::fields(202, fields))
+            },
+            (_, &[]) | (_, &[_]) => Err(Response::method_not_allowed()),
+            _ => Err(Response::not_found(request)),
+        }
+    }
+
+    fn handle_export(&mut self, request: &Request, path: &[&str]) -> Handled {
+        let clock = &mut self.clock;
+        let database = self.databases.get_mut(&request.database).expect("database exists");
+        match (&request.method, path) {
+            (&Method::Post, &[]) => {
+                let body = request.object()?;
+                let name = request.parameter("collection").unwrap_or_default();
+                let documents = &database.collection(name)?.documents;
+                let limit = match body.get("limit").and_then(Value::as_u64) {
+                    Some(limit) if limit > 0 => limit as usize,
+                    _ => documents.len(),
+                };
+                let restrict = match body.get("restrict") {
+                    Some(restrict) => {
+                        let fields = restrict.get("fields").and_then(Value::as_array)
+                            .map(|fields| fields.iter().filter_map(Value::as_str).map(str::to_owned).collect::<Vec<_>>())
+                            .unwrap_or_default();
+                        match restrict.get("type").and_then(Value::as_str) {
+                            Some("include") => Some((true, fields)),
+                            Some("exclude") => Some((false, fields)),
+                            _ => return Err(Response::error(400, ErrorCode::BadParameter,
+                                "expecting either 'include' or 'exclude' for 'restrict.type'")),
+                        }
+                    },
+                    None => None,
+                };
+                let remaining = documents.iter().take(limit)
+                    .map(|document| match restrict {
+                        Some((include, ref fields)) => Value::Object(document.iter()
+                            .filter(|&(name, _)| fields.contains(name) == include)
+                            .map(|(name, value)| (name.clone(), value.clone()))
+                            .collect()),
+                        None => Value::Object(document.clone()),
+                    })
+                    .collect::<VecDeque<_>>();
+                let count = if body.get("count").and_then(Value::as_bool).unwrap_or(false) {
+                    Some(remaining.len())
+                } else {
+                    None
+                };
+                let cursor = Cursor {
+                    remaining,
+                    batch_size: body.get("batchSize").and_then(Value::as_u64)
+                        .map_or(DEFAULT_BATCH_SIZE, |batch_size| batch_size.max(1) as usize),
+                    count,
+                    extra: Value::Null,
+                };
+                let id = clock.tick().to_string();
+                Ok(next_batch(database, id, cursor, 201))
+            },
+            (_, &[]) => Err(Response::method_not_allowed()),
+            _ => Err(Response::not_found(request)),
+        }
+    }
+
+    fn handle_index(&mut self, request: &Request, path: &[&str]) -> Handled {
+        let clock = &mut self.clock;
+        let database = self.databases.get_mut(&request.database).expect("database exists");
+        match (&request.method, path) {
+            (&Method::Get, &[]) => {
+                let name = request.parameter("collection").unwrap_or_default();
+                let indexes = database.collection(name)?.all_indexes();
+                let identifiers = indexes.iter()
+                    .map(|index| (index["id"].as_str().unwrap_or_default().to_owned(),
+                        Value::Object(index.clone())))
+                    .collect::<Map<_, _>>();
+                let mut fields = Map::new();
+                fields.insert("indexes".to_owned(), Value::Array(indexes.into_iter().map(Value::Object).collect()));
+                fields.insert("identifiers".to_owned(), Value::Object(identifiers));
+                Ok(Response::fields(200, fields))
+            },
+            (&Method::Post, &[]) => {
+                let name = request.parameter("collection").unwrap_or_default().to_owned();
+                let body = request.object()?;
+                let collection = database.collection_mut(&name)?;
+                let mut index = new_index(&body)?;
+                let existing = collection.all_indexes().into_iter().find(|existing|
+                    ["type", "fields", "unique", "sparse"].iter()
+                        .all(|attribute| existing.get(*attribute) == index.get(*attribute)));
+                match existing {
+                    Some(mut existing) => {
+                        existing.insert("isNewlyCreated".to_owned(), Value::Bool(false));
+                        Ok(Response::fields(200, existing))
+                    },
+                    None => {
+                        index.insert("id".to_owned(), Value::String(format!("{}/{}", name, clock.tick())));
+                        collection.indexes.push(index.clone());
+                        index.insert("isNewlyCreated".to_owned(), Value::Bool(true));
+                        Ok(Response::fields(201, index))
+                    },
+                }
+            },
+            (&Method::Get, &[name, key]) => {
+                let id = format!("{}/{}", name, key);
+                database.collection(name)?.all_indexes().into_iter()
+                    .find(|index| index["id"] == Value::String(id.clone()))
+                    .map(|index| Response::fields(200, index))
+                    .ok_or_else(|| Response::error(404, ErrorCode::ArangoIndexNotFound, "index not found"))
+            },
+            (&Method::Delete, &[name, key]) => {
+                let id = format!("{}/{}", name, key);
+                let collection = database.collection_mut(name)?;
+                if collection.builtin_indexes().iter().any(|index| index["id"] == Value::String(id.clone())) {
+                    return Err(Response::error(403, ErrorCode::Forbidden, "forbidden"));
+                }
+                let position = collection.indexes.iter()
+                    .position(|index| index["id"] == Value::String(id.clone()))
+                    .ok_or_else(|| Response::error(404, ErrorCode::ArangoIndexNotFound, "index not found"))?;
+                collection.indexes.remove(position);
+                let mut fields = Map::new();
+                fields.insert("id".to_owned(), Value::String(id));
+                Ok(Response::fields(200, fields))
+            },
+            (_, &[]) | (_, &[_, _]) => Err(Response::method_not_allowed()),
+            _ => Err(Response::not_found(request)),
+        }
+    }
+
+    fn handle_graph(&mut self, request: &Request, path: &[&str]) -> Handled {
+        let clock = &mut self.clock;
+        let database = self.databases.get_mut(&request.database).expect("database exists");
+        let status = if request.flag("waitForSync", false) { 201 } else { 202 };
+        match (&request.method, path) {
+            (&Method::Get, &[]) => Ok(Response::field(200, "graphs", Value::Array(database.graphs.values()
+                .map(Graph::to_json).collect()))),
+            (&Method::Post, &[]) => {
+                let body = request.object()?;
+                let name = body.get("name").and_then(Value::as_str).unwrap_or_default().to_owned();
+                if name.is_empty() {
+                    return Err(Response::error(400, ErrorCode::GraphCreateMissingName,
+                        "graph name is missing"));
+                }
+                if database.graphs.contains_key(&name) {
+                    return Err(Response::error(409, ErrorCode::GraphDuplicate, "graph already exists"));
+                }
+                let mut graph = Graph {
+                    name: name.clone(),
+                    revision: clock.revision(),
+                    edge_definitions: Vec::new(),
+                    orphan_collections: Vec::new(),
+                };
+                for definition in body.get("edgeDefinitions").and_then(Value::as_array).cloned().unwrap_or_default() {
+                    let definition = EdgeDefinition::parse(&definition)?;
+                    database.add_edge_definition(clock, &mut graph, definition)?;
+                }
+                for orphan in body.get("orphanCollections").and_then(Value::as_array).cloned().unwrap_or_default() {
+                    let orphan = orphan.as_str().unwrap_or_default();
+                    database.ensure_collection(clock, orphan, COLLECTION_TYPE_DOCUMENTS)?;
+                    graph.update_orphans(vec![orphan.to_owned()]);
+                }
+                let json = graph.to_json();
+                database.graphs.insert(name, graph);
+                Ok(Response::field(status, "graph", json))
+            },
+            (&Method::Get, &[name]) => Ok(Response::field(200, "graph", database.graph(name)?.to_json())),
+            (&Method::Delete, &[name]) => {
+                let graph = database.graph(name)?;
+                database.graphs.remove(name);
+                if request.flag("dropCollections", false) {
+                    let mut collections = graph.vertex_collections();
+                    collections.extend(graph.edge_collections());
+                    database.drop_graph_collections(collections);
+                }
+                Ok(Response::field(status, "removed", Value::Bool(true)))
+            },
+            (&Method::Get, &[name, "vertex"]) => Ok(Response::field(200, "collections",
+                json!(database.graph(name)?.vertex_collections()))),
+            (&Method::Post, &[name, "vertex"]) => {
+                let mut graph = database.graph(name)?;
+                let collection = request.object()?.get("collection").and_then(Value::as_str)
+                    .unwrap_or_default().to_owned();
+                if graph.defined_vertex_collections().contains(&collection) {
+                    return Err(Response::error(400, ErrorCode::GraphCollectionUsedInEdgeDef,
+                        "collection already used in edge def"));
+                }
+                if graph.orphan_collections.contains(&collection) {
+                    return Err(Response::error(400, ErrorCode::GraphCollectionUsedInOrphans,
+                        "collection already used in orphans"));
+                }
+                database.ensure_collection(clock, &collection, COLLECTION_TYPE_DOCUMENTS)?;
+                graph.update_orphans(vec![collection]);
+                graph.revision = clock.revision();
+                let json = graph.to_json();
+                database.graphs.insert(name.to_owned(), graph);
+                Ok(Response::field(status, "graph", json))
+            },
+            (&Method::Delete, &[name, "vertex", collection]) => {
+                let mut graph = database.graph(name)?;
+                if !graph.orphan_collections.iter().any(|orphan| orphan == collection) {
+                    return Err(Response::error(400, ErrorCode::GraphNotInOrphanCollection,
+                        "not in orphan collection"));
+                }
+                graph.orphan_collections.retain(|orphan| orphan != collection);
+                graph.revision = clock.revision();
+                let json = graph.to_json();
+                database.graphs.insert(name.to_owned(), graph);
+                if request.flag("dropCollection", false) {
+                    database.drop_graph_collections(vec![collection.to_owned()]);
+                }
+                Ok(Response::field(status, "graph", json))
+            },
+            (&Method::Get, &[name, "edge"]) => Ok(Response::field(200, "collections",
+                json!(database.graph(name)?.edge_collections()))),
+            (&Method::Post, &[name, "edge"]) => {
+                let mut graph = database.graph(name)?;
+                let definition = EdgeDefinition::parse(request.body.as_ref().unwrap_or(&Value::Null))?;
+                database.add_edge_definition(clock, &mut graph, definition)?;
+                graph.revision = clock.revision();
+                let json = graph.to_json();
+                database.graphs.insert(name.to_owned(), graph);
+                Ok(Response::field(status, "graph", json))
+            },
+            (&Method::Put, &[name, "edge", collection]) | (&Method::Delete, &[name, "edge", collection]) => {
+                let mut graph = database.graph(name)?;
+                let position = graph.edge_definitions.iter()
+                    .position(|definition| definition.collection == collection)
+                    .ok_or_else(|| Response::error(404, ErrorCode::GraphEdgeCollectionNotUsed,
+                        "edge collection not used in graph"))?;
+                let removed = graph.edge_definitions.remove(position);
+                if request.method == Method::Put {
+                    let definition = EdgeDefinition::parse(request.body.as_ref().unwrap_or(&Value::Null))?;
+                    database.add_edge_definition(clock, &mut graph, definition)?;
+                }
+                graph.update_orphans(removed.vertex_collections());
+                graph.revision = clock.revision();
+                let json = graph.to_json();
+                database.graphs.insert(name.to_owned(), graph);
+                if request.method == Method::Delete && request.flag("dropCollections", false) {
+                    database.drop_graph_collections(vec![removed.collection]);
+                }
+                Ok(Response::field(status, "graph", json))
+            },
+            (&Method::Post, &[name, kind, collection]) if kind == "vertex" || kind == "edge" => {
+                let graph = database.graph(name)?;
+                graph.check_collection(kind, collection)?;
+                let content = request.object()?;
+                if let Some(definition) = graph.edge_definition(collection).filter(|_| kind == "edge") {
+                    database.check_vertex(content.get("_from"), &definition.from)?;
+                    database.check_vertex(content.get("_to"), &definition.to)?;
+                }
+                let (new, _) = database.documents_mut(collection)?
+                    .insert(clock, Value::Object(content), false)
+                    .map_err(ItemError::into_response)?;
+                Ok(Response::field(status, kind, Value::Object(header_of(&new))))
+            },
+            (&Method::Get, &[name, kind, collection, key]) if kind == "vertex" || kind == "edge" => {
+                database.graph(name)?.check_collection(kind, collection)?;
+                let collection = database.documents_mut(collection)?;
+                let position = collection.position(key).ok_or_else(|| Response::error(404,
+                    ErrorCode::ArangoDocumentNotFound, "document not found"))?;
+                check_if_match(request, &collection.documents[position])?;
+                Ok(Response::field(200, kind, Value::Object(collection.documents[position].clone())))
+            },
+            (&Method::Put, &[name, kind, collection, key]) | (&Method::Patch, &[name, kind, collection, key])
+                if kind == "vertex" || kind == "edge" =>
+            {
+                let graph = database.graph(name)?;
+                graph.check_collection(kind, collection)?;
+                let content = request.object()?;
+                if let Some(definition) = graph.edge_definition(collection).filter(|_| kind == "edge") {
+                    if content.contains_key("_from") {
+                        database.check_vertex(content.get("_from"), &definition.from)?;
+                    }
+                    if content.contains_key("_to") {
+                        database.check_vertex(content.get("_to"), &definition.to)?;
+                    }
+                }
+                let collection = database.documents_mut(collection)?;
+                let position = collection.position(key).ok_or_else(|| Response::error(404,
+                    ErrorCode::ArangoDocumentNotFound, "document not found"))?;
+                check_if_match(request, &collection.documents[position])?;
+                modify(collection, clock, request, position, content, false, false)
+                    .map(|header| Response::field(status, kind, header))
+                    .map_err(ItemError::into_response)
+            },
+            (&Method::Delete, &[name, kind, collection, key]) if kind == "vertex" || kind == "edge" => {
+                let graph = database.graph(name)?;
+                graph.check_collection(kind, collection)?;
+                let removed = {
+                    let collection = database.documents_mut(collection)?;
+                    let position = collection.position(key).ok_or_else(|| Response::error(404,
+                        ErrorCode::ArangoDocumentNotFound, "document not found"))?;
+                    check_if_match(request, &collection.documents[position])?;
+                    collection.documents.remove(position)
+                };
+                if kind == "vertex" {
+                    let id = removed["_id"].clone();
+                    for edges in graph.edge_collections() {
+                        if let Some(edges) = database.collections.get_mut(&edges) {
+                            edges.documents.retain(|edge| edge["_from"] != id && edge["_to"] != id);
+                        }
+                    }
+                }
+                Ok(Response::field(status, "removed", Value::Bool(true)))
+            },
+            (_, &[]) | (_, &[_]) | (_, &[_, "vertex"]) | (_, &[_, "edge"]) => Err(Response::method_not_allowed()),
+            (_, &[_, kind, _]) | (_, &[_, kind, _, _]) if kind == "vertex" || kind == "edge" =>
+                Err(Response::method_not_allowed()),
+            _ => Err(Response::not_found(request)),
+        }
+    }
+
+    fn handle_edges(&mut self, request: &Request, path: &[&str]) -> Handled {
+        let database = &self.databases[&request.database];
+        match (&request.method, path) {
+            (&Method::Get, &[name]) => {
+                let collection = database.collection(name)?;
+                if !collection.is_edges() {
+                    return Err(Response::error(400, ErrorCode::ArangoCollectionTypeInvalid,
+                        "invalid collection type"));
+                }
+                let vertex = request.parameter("vertex").ok_or_else(|| Response::error(400,
+                    ErrorCode::HttpBadParameter, "illegal document handle"))?;
+                let direction = request.parameter("direction").unwrap_or("any");
+                let edges = collection.documents.iter()
+                    .filter(|edge| (direction != "in" && edge["_from"] == vertex)
+                        || (direction != "out" && edge["_to"] == vertex))
+                    .cloned().map(Value::Object).collect::<Vec<_>>();
+                let mut fields = Map::new();
+                fields.insert("stats".to_owned(), json!({
+                    "scannedIndex": edges.len(),
+                    "filtered": 0,
+                }));
+                fields.insert("edges".to_owned(), Value::Array(edges));
+                Ok(Response::fields(200, fields))
+            },
+            (_, &[_]) => Err(Response::method_not_allowed()),
+            _ => Err(Response::not_found(request)),
+        }
+    }
+
+    fn handle_traversal(&mut self, request: &Request, path: &[&str]) -> Handled {
+        let database = &self.databases[&request.database];
+        match (&request.method, path) {
+            (&Method::Post, &[]) => {
+                let body = request.object()?;
+                let string = |name: &str| body.get(name).and_then(Value::as_str);
+                let number = |name: &str| body.get(name).and_then(Value::as_u64);
+                if ["filter", "visitor", "init", "expander", "sort"].iter().any(|name| body.contains_key(*name)) {
+                    return Err(Response::error(501, ErrorCode::NotImplemented,
+                        "JavaScript functions are not supported by the fake server"));
+                }
+                let start_vertex = string("startVertex").unwrap_or_default().to_owned();
+                let start = database.document(&start_vertex).cloned().ok_or_else(|| Response::error(404,
+                    ErrorCode::ArangoDocumentNotFound, "invalid startVertex"))?;
+                let edge_collections = match (string("graphName"), string("edgeCollection")) {
+                    (Some(graph_name), _) => database.graph(graph_name)?.edge_collections(),
+                    (None, Some(edge_collection)) => {
+                        database.collection(edge_collection)?;
+                        vec![edge_collection.to_owned()]
+                    },
+                    (None, None) => return Err(Response::error(400, ErrorCode::HttpBadParameter,
+                        "missing graphName or edgeCollection")),
+                };
+                let uniqueness = |name: &str, default: &str| body.get("uniqueness")
+                    .and_then(|uniqueness| uniqueness.get(name)).and_then(Value::as_str)
+                    .unwrap_or(default).to_owned();
+                let postorder = string("order") == Some("postorder");
+                let breadth_first = string("strategy") == Some("breadthfirst");
+                if postorder && breadth_first {
+                    return Err(Response::error(400, ErrorCode::HttpBadParameter,
+                        "cannot use post-order with breadth-first strategy"));
+                }
+                let mut traversal = Traversal {
+                    database,
+                    edge_collections,
+                    direction: string("direction").unwrap_or("outbound").to_owned(),
+                    min_depth: number("minDepth").unwrap_or(0),
+                    max_depth: number("maxDepth"),
+                    vertex_uniqueness: uniqueness("vertices", "none"),
+                    edge_uniqueness: uniqueness("edges", "path"),
+                    postorder,
+                    backward: string("itemOrder") == Some("backward"),
+                    max_iterations: number("maxIterations").unwrap_or(DEFAULT_MAX_ITERATIONS),
+                    iterations: 0,
+                    visited_vertices: HashSet::new(),
+                    visited_edges: HashSet::new(),
+                    vertices: Vec::new(),
+                    paths: Vec::new(),
+                };
+                traversal.visited_vertices.insert(start_vertex);
+                let start = Value::Object(start);
+                if breadth_first {
+                    traversal.breadth_first(start)?;
+                } else {
+                    traversal.depth_first(&mut vec![start], &mut Vec::new())?;
+                }
+                Ok(Response::result(200, json!({
+                    "visited": {
+                        "vertices": traversal.vertices,
+                        "paths": traversal.paths,
+                    },
+                })))
+            },
+            (_, &[]) => Err(Response::method_not_allowed()),
+            _ => Err(Response::not_found(request)),
+        }
+    }
+
+    /// The fake server reports to use the MMFiles storage engine, which is
+    /// what the figures and properties of its collections look like.
+    fn handle_engine(&mut self, request: &Request, path: &[&str]) -> Handled {
+        match (&request.method, path) {
+            (&Method::Get, &[]) => Ok(Response::new(200, json!({
+                "name": "mmfiles",
+                "supports": {
+                    "dfdb": true,
+                    "indexes": ["primary", "edge", "hash", "skiplist", "persistent", "geo", "fulltext"],
+                },
+            }))),
+            (_, &[]) => Err(Response::method_not_allowed()),
+            _ => Err(Response::not_found(request)),
+        }
+    }
+
+    fn handle_version(&mut self, request: &Request, path: &[&str]) -> Handled {
+        match (&request.method, path) {
+            (&Method::Get, &[]) => {
+                let mut version = json!({
+                    "server": "arango",
+                    "version": SERVER_VERSION,
+                    "license": "community",
+                });
+                if request.parameter("details") == Some("true") {
+                    version["details"] = json!({ "mode": "server", "role": "SINGLE" });
+                }
+                Ok(Response::new(200, version))
+            },
+            (_, &[]) => Err(Response::method_not_allowed()),
+            _ => Err(Response::not_found(request)),
+        }
+    }
+
+    fn handle_admin_database(&mut self, request: &Request, path: &[&str]) -> Handled {
+        match (&request.method, path) {
+            (&Method::Get, &["target-version"]) => Ok(Response::new(200, json!({
+                "version": TARGET_VERSION,
+                "error": false,
+                "code": 200,
+            }))),
+            (_, &["target-version"]) => Err(Response::method_not_allowed()),
+            _ => Err(Response::not_found(request)),
+        }
+    }
+
+    fn handle_server(&mut self, request: &Request, path: &[&str]) -> Handled {
+        match (&request.method, path) {
+            (&Method::Get, &["role"]) => Ok(Response::new(200, json!({
+                "role": "SINGLE",
+                "error": false,
+                "code": 200,
+            }))),
+            (&Method::Get, &["id"]) => Err(Response::error(500, ErrorCode::HttpServerError,
+                "ServerId not available")),
+            (_, &["role"]) | (_, &["id"]) => Err(Response::method_not_allowed()),
+            _ => Err(Response::not_found(request)),
+        }
+    }
+
+    fn handle_aql_function(&mut self, request: &Request, path: &[&str]) -> Handled {
+        let database = self.databases.get_mut(&request.database).expect("database exists");
+        match (&request.method, path) {
+            (&Method::Get, &[]) => {
+                let prefix = request.parameter("namespace")
+                    .map(|namespace| namespace.to_lowercase() + "::");
+                let functions = database.functions.iter()
+                    .filter(|&(name, _)| prefix.as_ref().is_none_or(|prefix| name.starts_with(prefix)))
+                    .map(|(_, function)| function.clone())
+                    .collect();
+                Ok(Response::result(200, Value::Array(functions)))
+            },
+            (&Method::Post, &[]) => {
+                let body = request.object()?;
+                let name = body.get("name").and_then(Value::as_str).unwrap_or_default();
+                if !is_valid_function_name(name) {
+                    return Err(Response::error(400, ErrorCode::QueryFunctionInvalidName,
+                        "invalid user function name"));
+                }
+                let code = body.get("code").and_then(Value::as_str).unwrap_or_default();
+                if code.trim().is_empty() {
+                    return Err(Response::error(400, ErrorCode::QueryFunctionInvalidCode,
+                        "invalid user function code"));
+                }
+                let function = json!({
+                    "name": name,
+                    "code": code,
+                    "isDeterministic": body.get("isDeterministic").and_then(Value::as_bool).unwrap_or(false),
+                });
+                let replaced = database.functions.insert(name.to_lowercase(), function).is_some();
+                let status = if replaced { 200 } else { 201 };
+                Ok(Response::field(status, "isNewlyCreated", Value::Bool(!replaced)))
+            },
+            (&Method::Delete, &[name]) => {
+                let name = name.to_lowercase();
+                let deleted = if request.flag("group", false) {
+                    let prefix = name + "::";
+                    let before = database.functions.len();
+                    database.functions.retain(|name, _| !name.starts_with(&prefix));
+                    before - database.functions.len()
+                } else if database.functions.remove(&name).is_some() {
+                    1
+                } else {
+                    return Err(Response::error(404, ErrorCode::QueryFunctionNotFound,
+                        "user function not found"));
+                };
+                Ok(Response::field(200, "deletedCount", Value::from(deleted)))
+            },
+            (_, &[]) | (_, &[_]) => Err(Response::method_not_allowed()),
+            _ => Err(Response::not_found(request)),
+        }
+    }
+
+    /// Handles the query management endpoints. As all queries are executed
+    /// synchronously there are never any running or slow queries.
+    fn handle_query(&mut self, request: &Request, path: &[&str]) -> Handled {
+        let database = self.databases.get_mut(&request.database).expect("database exists");
+        match (&request.method, path) {
+            (&Method::Get, &["current"]) | (&Method::Get, &["slow"]) =>
+                Ok(Response::new(200, Value::Array(Vec::new()))),
+            (&Method::Delete, &["slow"]) =>
+                Ok(Response::fields(200, Map::new())),
+            (&Method::Get, &["properties"]) =>
+                Ok(Response::new(200, Value::Object(database.query_tracking.clone()))),
+            (&Method::Put, &["properties"]) => {
+                let body = request.object()?;
+                for (name, value) in body {
+                    if database.query_tracking.contains_key(&name) {
+                        database.query_tracking.insert(name, value);
+                    }
+                }
+                Ok(Response::new(200, Value::Object(database.query_tracking.clone())))
+            },
+            (&Method::Delete, &[_]) =>
+                Err(Response::error(404, ErrorCode::QueryNotFound, "query not found")),
+            (&Method::Post, &[]) =>
+                Err(Response::error(501, ErrorCode::NotImplemented,
+                    "parsing queries is not supported by the fake server")),
+            (_, &["current"]) | (_, &["slow"]) | (_, &["properties"]) =>
+                Err(Response::method_not_allowed()),
+            _ => Err(Response::not_found(request)),
+        }
+    }
+}
+
+/// Returns the default configuration of the query tracking.
+fn default_query_tracking() -> Map<String, Value> {
+    let mut properties = Map::new();
+    properties.insert("enabled".to_owned(), Value::Bool(true));
+    properties.insert("trackSlowQueries".to_owned(), Value::Bool(true));
+    properties.insert("trackBindVars".to_owned(), Value::Bool(true));
+    properties.insert("maxSlowQueries".to_owned(), Value::from(64));
+    properties.insert("slowQueryThreshold".to_owned(), Value::from(10.0));
+    properties.insert("maxQueryStringLength".to_owned(), Value::from(4096));
+    properties
+}
+
+/// Checks that the name of a user function consists of at least two parts
+/// separated by `::`.
+fn is_valid_function_name(name: &str) -> bool {
+    let parts = name.split("::").collect::<Vec<_>>();
+    parts.len() > 1 && parts.iter().all(|part|
+        !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+}
+
+fn require_system_database(request: &Request) -> Result<(), Response> {
+    if request.database == SYSTEM_DATABASE {
+        Ok(())
+    } else {
+        Err(Response::error(403, ErrorCode::ArangoUseSystemDatabase,
+            "operation only allowed in system database"))
+    }
+}
+
+fn is_valid_name(name: &str, is_system: bool) -> bool {
+    let mut chars = name.chars();
+    let valid_start = match chars.next() {
+        Some('_') => is_system,
+        Some(first) => first.is_ascii_alphabetic(),
+        None => false,
+    };
+    valid_start && name.len() <= 64
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty() && key.len() <= 254 && key.chars().all(|c|
+        c.is_ascii_alphanumeric() || "_-:.@()+,=;$!*'%".contains(c))
+}
+
+fn unquote(value: &str) -> &str {
+    value.trim_matches('"')
+}
+
+fn header_of(document: &Map<String, Value>) -> Map<String, Value> {
+    ["_id", "_key", "_rev"].iter()
+        .map(|attribute| ((*attribute).to_owned(), document[*attribute].clone()))
+        .collect()
+}
+
+fn check_if_match(request: &Request, document: &Map<String, Value>) -> Result<(), Response> {
+    match request.if_match {
+        Some(ref if_match) if document["_rev"] != unquote(if_match) => {
+            let mut response = Response::error(412, ErrorCode::ArangoConflict, "precondition failed");
+            if let Some(Value::Object(ref mut fields)) = response.body {
+                fields.extend(header_of(document));
+            }
+            Err(response)
+        },
+        _ => Ok(()),
+    }
+}
+
+fn check_revision(content: &Map<String, Value>, document: &Map<String, Value>) -> Result<(), ()> {
+    match content.get("_rev") {
+        Some(revision) if *revision != document["_rev"] => Err(()),
+        _ => Ok(()),
+    }
+}
+
+/// Merges the patch into the target as specified by the `keepNull` and
+/// `mergeObjects` options of update operations.
+fn merge(target: &mut Map<String, Value>, patch: Map<String, Value>, keep_null: bool, merge_objects: bool) {
+    for (name, value) in patch {
+        match value {
+            Value::Null if !keep_null => {
+                target.remove(&name);
+            },
+            Value::Object(patch) if merge_objects => {
+                if let Some(&mut Value::Object(ref mut nested)) = target.get_mut(&name) {
+                    merge(nested, patch, keep_null, merge_objects);
+                    continue;
+                }
+                let mut nested = Map::new();
+                merge(&mut nested, patch, keep_null, merge_objects);
+                target.insert(name, Value::Object(nested));
+            },
+            value => {
+                target.insert(name, value);
+            },
+        }
+    }
+}
+
+/// Replaces or updates the document at the given position.
+fn modify(
+    collection: &mut Collection,
+    clock: &mut Clock,
+    request: &Request,
+    position: usize,
+    content: Map<String, Value>,
+    return_old: bool,
+    return_new: bool,
+) -> Result<Value, ItemError> {
+    let old = collection.documents[position].clone();
+    let content = if request.method == Method::Patch {
+        let mut merged = old.clone();
+        merge(&mut merged, content, request.flag("keepNull", true), request.flag("mergeObjects", true));
+        merged
+    } else {
+        let mut replaced = content;
+        if collection.is_edges() {
+            for attribute in &["_from", "_to"] {
+                if !replaced.contains_key(*attribute) {
+                    if let Some(value) = old.get(*attribute) {
+                        replaced.insert(attribute.to_string(), value.clone());
+                    }
+                }
+            }
+        }
+        replaced
+    };
+    let key = old["_key"].as_str().unwrap_or_default().to_owned();
+    let revision = clock.revision();
+    let new = collection.stored(&key, &revision, content)?;
+    collection.documents[position] = new.clone();
+    let mut header = header_of(&new);
+    header.insert("_oldRev".to_owned(), old["_rev"].clone());
+    if return_old {
+        header.insert("old".to_owned(), Value::Object(old));
+    }
+    if return_new {
+        header.insert("new".to_owned(), Value::Object(new));
+    }
+    Ok(Value::Object(header))
+}
+
+/// Takes the next batch from the cursor and keeps the cursor if there are
+/// more results.
+fn next_batch(database: &mut Database, id: String, mut cursor: Cursor, status: u16) -> Response {
+    let size = cursor.batch_size.min(cursor.remaining.len());
+    let batch = cursor.remaining.drain(..size).collect::<Vec<_>>();
+    let has_more = !cursor.remaining.is_empty();
+    let mut fields = Map::new();
+    fields.insert("result".to_owned(), Value::Array(batch));
+    fields.insert("hasMore".to_owned(), Value::Bool(has_more));
+    if let Some(count) = cursor.count {
+        fields.insert("count".to_owned(), Value::from(count));
+    }
+    if !cursor.extra.is_null() {
+        fields.insert("cached".to_owned(), Value::Bool(false));
+        fields.insert("extra".to_owned(), cursor.extra.clone());
+    }
+    if has_more {
+        fields.insert("id".to_owned(), Value::String(id.clone()));
+        database.cursors.insert(id, cursor);
+    }
+    Response::fields(status, fields)
+}
+
+/// Builds the description of a new index from the body of a create index
+/// request.
+fn new_index(body: &Map<String, Value>) -> Result<Map<String, Value>, Response> {
+    let fields = body.get("fields").cloned().unwrap_or_else(|| Value::Array(Vec::new()));
+    let field_count = fields.as_array().map_or(0, Vec::len);
+    if field_count == 0 {
+        return Err(Response::error(400, ErrorCode::BadParameter, "fields must be non-empty"));
+    }
+    let flag = |name: &str, default: bool| body.get(name).and_then(Value::as_bool).unwrap_or(default);
+    let kind = body.get("type").and_then(Value::as_str).unwrap_or_default();
+    let index = match kind {
+        "hash" => json!({
+            "type": "hash",
+            "fields": fields,
+            "unique": flag("unique", false),
+            "sparse": flag("sparse", false),
+            "deduplicate": flag("deduplicate", true),
+            "selectivityEstimate": 1,
+        }),
+        "skiplist" | "persistent" => json!({
+            "type": kind,
+            "fields": fields,
+            "unique": flag("unique", false),
+            "sparse": flag("sparse", false),
+            "deduplicate": flag("deduplicate", true),
+        }),
+        "geo" | "geo1" | "geo2" if field_count == 1 => json!({
+            "type": "geo1",
+            "fields": fields,
+            "geoJson": flag("geoJson", false),
+            "constraint": false,
+            "unique": false,
+            "sparse": true,
+        }),
+        "geo" | "geo1" | "geo2" if field_count == 2 => json!({
+            "type": "geo2",
+            "fields": fields,
+            "constraint": false,
+            "unique": false,
+            "sparse": true,
+        }),
+        "fulltext" => json!({
+            "type": "fulltext",
+            "fields": fields,
+            "minLength": body.get("minLength").and_then(Value::as_u64).unwrap_or(2),
+            "unique": false,
+            "sparse": true,
+        }),
+        _ => return Err(Response::error(400, ErrorCode::BadParameter, "invalid index type")),
+    };
+    match index {
+        Value::Object(index) => Ok(index),
+        _ => unreachable!(),
+    }
+}
+
+/// Parses the given body as JSON.
+pub(crate) fn parse_body(body: &[u8]) -> Result<Option<Value>, Response> {
+    if body.is_empty() {
+        Ok(None)
+    } else {
+        serde_json::from_slice(body).map(Some).map_err(|cause|
+            Response::error(400, ErrorCode::HttpCorruptedJson, cause.to_string()))
+    }
+}
@@ -0,0 +1,174 @@
+//! Batch requests (`/_api/batch`) of the fake server.
+//!
+//! The parts of a batch are executed one after the other by the backend with
+//! the credentials of the batch request. The responses are returned as parts
+//! of a multipart body in the same order and with the same content ids as
+//! the requests.
+
+use hyper::{Method, StatusCode};
+use url::form_urlencoded;
+
+use rincon_core::arango::ErrorCode;
+use rincon_core::arango::protocol::{CONTENT_TYPE_BATCH_PART, HEADER_CONTENT_ID,
+    HEADER_CONTENT_TYPE, HEADER_IF_MATCH, HEADER_IF_NON_MATCH, PATH_DB};
+
+use super::backend::{self, parse_body, Backend, Credentials, Response};
+use super::split_path;
+
+/// A batch request with the parts already split.
+#[derive(Debug)]
+pub(crate) struct Batch {
+    boundary: String,
+    parts: Vec<Part>,
+}
+
+#[derive(Debug)]
+struct Part {
+    content_id: Option<String>,
+    request: Result<backend::Request, Response>,
+}
+
+impl Batch {
+    /// Parses the multipart body of a batch request.
+    ///
+    /// The requests of the parts are executed in the given database unless
+    /// their path selects another database.
+    pub(crate) fn parse(body: &[u8], database: &str, credentials: &Credentials)
+        -> Result<Self, Response>
+    {
+        let body = String::from_utf8_lossy(body);
+        let boundary = body.lines().find(|line| line.starts_with("--"))
+            .map(|line| line.trim_end()[2..].to_owned())
+            .ok_or_else(|| Response::error(400, ErrorCode::HttpBadParameter,
+                "invalid multipart message received"))?;
+        let delimiter = format!("--{}", boundary);
+        let start = body.find(&delimiter[..]).unwrap_or_default() + delimiter.len();
+        let mut parts = Vec::new();
+        for chunk in body[start..].split(&delimiter[..]) {
+            if chunk.starts_with("--") {
+                break;
+            }
+            if chunk.trim().is_empty() {
+                continue;
+            }
+            let chunk = chunk.trim_start_matches(&['\r', '\n'][..]);
+            let (part_header, request) = split_head(chunk);
+            parts.push(Part {
+                content_id: header_value(part_header, HEADER_CONTENT_ID),
+                request: parse_request(request, database, credentials),
+            });
+        }
+        Ok(Batch {
+            boundary,
+            parts,
+        })
+    }
+
+    /// Returns the boundary of the multipart body.
+    pub(crate) fn boundary(&self) -> &str {
+        &self.boundary
+    }
+
+    /// Executes the requests of all parts and returns the multipart body of
+    /// the response and the number of failed parts.
+    pub(crate) fn execute(&self, backend: &mut Backend) -> (String, usize) {
+        let mut body = String::new();
+        let mut errors = 0;
+        for part in &self.parts {
+            let response = match part.request {
+                Ok(ref request) => backend.handle(request),
+                Err(ref response) => response.clone(),
+            };
+            if response.status >= 400 {
+                errors += 1;
+            }
+            body.push_str(&format!("--{}\r\n", self.boundary));
+            body.push_str(&format!("{}: {}\r\n", HEADER_CONTENT_TYPE, CONTENT_TYPE_BATCH_PART));
+            if let Some(ref content_id) = part.content_id {
+                body.push_str(&format!("{}: {}\r\n", HEADER_CONTENT_ID, content_id));
+            }
+            body.push_str("\r\n");
+            body.push_str(&render_response(&response));
+            body.push_str("\r\n");
+        }
+        body.push_str(&format!("--{}--\r\n", self.boundary));
+        (body, errors)
+    }
+}
+
+fn parse_request(text: &str, database: &str, credentials: &Credentials)
+    -> Result<backend::Request, Response>
+{
+    let (head, body) = split_head(text);
+    let bad_request = || Response::error(400, ErrorCode::HttpBadParameter,
+        "invalid request line in batch part");
+    let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().and_then(|method| method.parse::<Method>().ok())
+        .ok_or_else(bad_request)?;
+    let uri = request_line.next().ok_or_else(bad_request)?;
+    let (path, query) = match uri.find('?') {
+        Some(index) => (&uri[..index], Some(&uri[index + 1..])),
+        None => (uri, None),
+    };
+    let (path_database, segments) = split_path(path);
+    let database = if path.starts_with(PATH_DB) { path_database } else { database.to_owned() };
+    let content_length = header_value(head, "Content-Length")
+        .and_then(|length| length.parse::<usize>().ok());
+    let body = match content_length {
+        Some(length) if length <= body.len() && body.is_char_boundary(length) => &body[..length],
+        _ => body.trim_end_matches('\n').trim_end_matches('\r'),
+    };
+    Ok(backend::Request {
+        method,
+        database,
+        path: segments,
+        parameters: query
+            .map(|query| form_urlencoded::parse(query.as_bytes()).into_owned().collect())
+            .unwrap_or_default(),
+        credentials: credentials.clone(),
+        if_match: header_value(head, HEADER_IF_MATCH),
+        if_none_match: header_value(head, HEADER_IF_NON_MATCH),
+        body: parse_body(body.as_bytes())?,
+    })
+}
+
+fn render_response(response: &Response) -> String {
+    let status = StatusCode::try_from(response.status).unwrap_or(StatusCode::InternalServerError);
+    let mut text = format!("HTTP/1.1 {} {}\r\n", response.status,
+        status.canonical_reason().unwrap_or_default());
+    if let Some(ref etag) = response.etag {
+        text.push_str(&format!("Etag: \"{}\"\r\n", etag));
+    }
+    let body = response.body.as_ref().map(|body| body.to_string()).unwrap_or_default();
+    if response.body.is_some() {
+        text.push_str("Content-Type: application/json; charset=utf-8\r\n");
+    }
+    text.push_str(&format!("Content-Length: {}\r\n\r\n", body.len()));
+    text.push_str(&body);
+    text
+}
+
+/// Splits the given text at the first empty line into the header and the
+/// remaining body.
+fn split_head(text: &str) -> (&str, &str) {
+    match text.find("\r\n\r\n") {
+        Some(index) => (&text[..index], &text[index + 4..]),
+        None => match text.find("\n\n") {
+            Some(index) => (&text[..index], &text[index + 2..]),
+            None => (text, ""),
+        },
+    }
+}
+
+fn header_value(head: &str, name: &str) -> Option<String> {
+    head.lines()
+        .filter_map(|line| {
+            let mut split = line.splitn(2, ':');
+            match (split.next(), split.next()) {
+                (Some(key), Some(value)) if key.trim().eq_ignore_ascii_case(name) =>
+                    Some(value.trim().to_owned()),
+                _ => None,
+            }
+        })
+        .next()
+}
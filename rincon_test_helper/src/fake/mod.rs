@@ -0,0 +1,304 @@
+//! An embedded fake of an ArangoDB server for running integration tests
+//! without an external server process.
+//!
+//! The fake server keeps all data in memory and implements the core parts of
+//! the REST API:
+//!
+//! * databases (`/_api/database`)
+//! * users and permissions (`/_api/user`)
+//! * authentication with JWT (`/_open/auth`)
+//! * collections (`/_api/collection`)
+//! * documents (`/_api/document`)
+//! * indexes (`/_api/index`)
+//! * cursors (`/_api/cursor`) for a subset of AQL
+//! * batch requests (`/_api/batch`)
+//!
+//! Queries may use the operations `FOR`, `FILTER`, `SORT`, `LIMIT`, `LET`,
+//! `INSERT` and `RETURN` together with bind parameters and the most common
+//! operators and functions. Indexes are listed but not used for queries.
+//! Request and response bodies are always JSON.
+
+mod aql;
+mod backend;
+mod batch;
+
+use std::io;
+use std::net::SocketAddr;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+
+use futures::{Future, Stream};
+use hyper::{self, StatusCode};
+use hyper::header::{Authorization, Basic, Bearer, ContentLength, ContentType, ETag, EntityTag};
+use hyper::server::{Http, Request, Response, Service};
+use url::form_urlencoded;
+use url::percent_encoding::percent_decode;
+
+use rincon_core::arango::protocol::{HEADER_IF_MATCH, HEADER_IF_NON_MATCH, PATH_DB,
+    SYSTEM_DATABASE};
+
+use self::backend::{parse_body, Backend, Credentials};
+use self::batch::Batch;
+
+/// A running fake server.
+///
+/// The server runs in a background thread until the process exits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FakeServer {
+    address: SocketAddr,
+}
+
+impl FakeServer {
+    /// Starts a new fake server on a free port of the loopback interface.
+    ///
+    /// The server is set up with the `_system` database and a `root` user
+    /// with the given password.
+    pub fn start(root_password: &str) -> io::Result<Self> {
+        let backend = Arc::new(Mutex::new(Backend::new(root_password)));
+        let (sender, receiver) = mpsc::channel();
+        thread::Builder::new()
+            .name("fake-arangodb".to_owned())
+            .spawn(move || {
+                let address = "127.0.0.1:0".parse().expect("valid socket address");
+                let server = Http::new().bind(&address, move || Ok(FakeService {
+                    backend: backend.clone(),
+                }));
+                let server = match server {
+                    Ok(server) => server,
+                    Err(error) => {
+                        let _ = sender.send(Err(io::Error::other(error.to_string())));
+                        return;
+                    },
+                };
+                let _ = sender.send(server.local_addr()
+                    .map_err(|error| io::Error::other(error.to_string())));
+                let _ = server.run();
+            })?;
+        let address = receiver.recv()
+            .map_err(|error| io::Error::other(error.to_string()))??;
+        Ok(FakeServer {
+            address,
+        })
+    }
+
+    /// Returns the socket address the server is listening on.
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Returns the URL of the server to be used for a `DataSource`.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.address)
+    }
+}
+
+struct FakeService {
+    backend: Arc<Mutex<Backend>>,
+}
+
+impl Service for FakeService {
+    type Request = Request;
+    type Response = Response;
+    type Error = hyper::Error;
+    type Future = Box<dyn Future<Item=Response, Error=hyper::Error>>;
+
+    fn call(&self, request: Request) -> Self::Future {
+        let backend = self.backend.clone();
+        let (method, uri, _, headers, body) = request.deconstruct();
+        Box::new(body.concat2().map(move |body| {
+            let (database, path) = split_path(uri.path());
+            let parameters = uri.query()
+                .map(|query| form_urlencoded::parse(query.as_bytes()).into_owned().collect())
+                .unwrap_or_default();
+            let credentials = if let Some(basic) = headers.get::<Authorization<Basic>>() {
+                Credentials::Basic(basic.username.clone(), basic.password.clone().unwrap_or_default())
+            } else if let Some(bearer) = headers.get::<Authorization<Bearer>>() {
+                Credentials::Bearer(bearer.token.clone())
+            } else {
+                Credentials::None
+            };
+            if path == ["_api", "batch"] {
+                let mut backend = backend.lock().expect("fake server state");
+                return to_http_response_of_batch(&mut backend, &method, &database, &credentials,
+                    &body);
+            }
+            let body = match parse_body(&body) {
+                Ok(body) => body,
+                Err(response) => return to_http_response(response),
+            };
+            let header = |name: &str| headers.get_raw(name)
+                .and_then(|raw| raw.one())
+                .map(|value| String::from_utf8_lossy(value).into_owned());
+            let request = backend::Request {
+                method,
+                database,
+                path,
+                parameters,
+                credentials,
+                if_match: header(HEADER_IF_MATCH),
+                if_none_match: header(HEADER_IF_NON_MATCH),
+                body,
+            };
+            let response = backend.lock().expect("fake server state").handle(&request);
+            to_http_response(response)
+        }))
+    }
+}
+
+/// Splits the path of a request URI into the name of the database and the
+/// decoded segments of the remaining path.
+fn split_path(path: &str) -> (String, Vec<String>) {
+    let decode = |segment: &str| percent_decode(segment.as_bytes()).decode_utf8_lossy().into_owned();
+    if let Some(path) = path.strip_prefix(PATH_DB) {
+        let mut parts = path.splitn(2, '/');
+        let database = decode(parts.next().unwrap_or_default());
+        let rest = parts.next().unwrap_or_default();
+        (database, rest.split('/').filter(|segment| !segment.is_empty()).map(decode).collect())
+    } else {
+        (SYSTEM_DATABASE.to_owned(),
+            path.split('/').filter(|segment| !segment.is_empty()).map(decode).collect())
+    }
+}
+
+/// Executes a batch request and returns the responses to its parts as a
+/// multipart body.
+fn to_http_response_of_batch(
+    backend: &mut Backend,
+    method: &hyper::Method,
+    database: &str,
+    credentials: &Credentials,
+    body: &[u8],
+) -> Response {
+    if *method != hyper::Method::Post {
+        return to_http_response(backend::Response::method_not_allowed());
+    }
+    if backend.authenticate(credentials).is_none() {
+        return to_http_response(backend::Response::unauthorized());
+    }
+    let batch = match Batch::parse(body, database, credentials) {
+        Ok(batch) => batch,
+        Err(response) => return to_http_response(response),
+    };
+    let (payload, errors) = batch.execute(backend);
+    let mut http_response = Response::new();
+    http_response.headers_mut().set_raw("Content-Type",
+        format!("multipart/form-data; boundary={}", batch.boundary()));
+    http_response.headers_mut().set_raw("x-arango-errors", errors.to_string());
+    http_response.with_header(ContentLength(payload.len() as u64))
+        .with_body(payload.into_bytes())
+}
+
+fn to_http_response(response: backend::Response) -> Response {
+    let status = StatusCode::try_from(response.status).unwrap_or(StatusCode::InternalServerError);
+    let mut http_response = Response::new().with_status(status);
+    if let Some(etag) = response.etag {
+        http_response.headers_mut().set(ETag(EntityTag::strong(etag)));
+    }
+    match response.body {
+        Some(body) => {
+            let payload = body.to_string().into_bytes();
+            http_response.with_header(ContentType::json())
+                .with_header(ContentLength(payload.len() as u64))
+                .with_body(payload)
+        },
+        None => http_response.with_header(ContentLength(0)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tokio_core::reactor::Core;
+
+    use rincon_core::api::connector::{Connector, Error, Execute};
+    use rincon_core::api::datasource::DataSource;
+    use rincon_core::api::query::Query;
+    use rincon_core::api::types::Empty;
+    use rincon_core::arango::ErrorCode;
+    use rincon_connector::http::BasicConnector;
+    use rincon_client::collection::methods::{CreateCollection, ListCollections};
+    use rincon_client::cursor::methods::{CreateCursor, ReadNextBatchFromCursor};
+    use rincon_client::cursor::types::NewCursor;
+    use rincon_client::database::methods::{CreateDatabase, ListAccessibleDatabases};
+    use rincon_client::database::types::NewDatabase;
+    use rincon_client::document::methods::{GetDocument, InsertDocument};
+    use rincon_client::document::types::{DocumentId, NewDocument};
+    use rincon_client::user::types::NewUser;
+    use serde_json::Value;
+
+    use MyUserAgent;
+
+    #[test]
+    fn split_path_of_request_to_database() {
+        assert_eq!(("my_db".to_owned(), vec!["_api".to_owned(), "document".to_owned(), "a b".to_owned()]),
+            split_path("/_db/my_db/_api/document/a%20b"));
+        assert_eq!(("_system".to_owned(), vec!["_api".to_owned(), "version".to_owned()]),
+            split_path("/_api/version"));
+    }
+
+    #[test]
+    fn store_and_query_documents_of_a_new_database() {
+        let server = FakeServer::start("secret").unwrap();
+        let mut core = Core::new().unwrap();
+        let root_ds = DataSource::from_url(&server.url()).unwrap()
+            .with_basic_authentication("root", "secret");
+        let connector = BasicConnector::new(&MyUserAgent, root_ds, &core.handle()).unwrap();
+        let sys_conn = connector.system_connection();
+
+        let new_database = NewDatabase::new("shop", vec![NewUser::<Empty>::with_name("clerk", "s3cr3t")]);
+        let created = core.run(sys_conn.execute(CreateDatabase::new(new_database))).unwrap();
+        assert!(created);
+
+        let user_ds = DataSource::from_url(&server.url()).unwrap()
+            .with_basic_authentication("clerk", "s3cr3t");
+        let connector = BasicConnector::new(&MyUserAgent, user_ds, &core.handle()).unwrap();
+        let conn = connector.connection("shop");
+
+        let databases = core.run(conn.execute(ListAccessibleDatabases::new())).unwrap();
+        assert_eq!(vec!["shop".to_owned()], databases);
+
+        core.run(conn.execute(CreateCollection::documents_with_name("customers"))).unwrap();
+        let collections = core.run(conn.execute(ListCollections::new())).unwrap();
+        assert_eq!(1, collections.len());
+        assert_eq!("customers", collections[0].name());
+
+        let header = core.run(conn.execute(InsertDocument::new("customers",
+            NewDocument::from_content(json!({ "name": "Jane Doe", "age": 42 }))))).unwrap();
+        let document = core.run(conn.execute(GetDocument::<Value>::with_id(header.id().clone()))).unwrap();
+        assert_eq!(&json!({ "name": "Jane Doe", "age": 42 }), document.content());
+
+        let missing = core.run(conn.execute(GetDocument::<Value>::with_id(
+            DocumentId::new("customers", "unknown"))));
+        match missing {
+            Err(Error::Method(ref error)) => assert_eq!(ErrorCode::ArangoDocumentNotFound, error.error_code()),
+            ref result => panic!("unexpected result: {:?}", result),
+        }
+
+        core.run(conn.execute(CreateCursor::<Value>::from_query(Query::new(
+            "FOR i IN 1..5 INSERT { name: CONCAT('No.', i), age: i + 20 } IN customers")))).unwrap();
+        let mut new_cursor = NewCursor::from(Query::new(
+            "FOR c IN customers FILTER c.age < 30 SORT c.age DESC RETURN c.name"));
+        new_cursor.set_batch_size(4);
+        let cursor = core.run(conn.execute(CreateCursor::<String>::new(new_cursor))).unwrap();
+        assert_eq!(&["No.5", "No.4", "No.3", "No.2"], cursor.result());
+        assert!(cursor.has_more());
+        let stats = cursor.extra().unwrap().stats();
+        assert_eq!(6, stats.scanned_full());
+        assert_eq!(1, stats.filtered());
+
+        let next = core.run(conn.execute(ReadNextBatchFromCursor::<String>::with_id(
+            cursor.id().unwrap().clone()))).unwrap();
+        assert_eq!(&["No.1"], next.result());
+        assert!(!next.has_more());
+
+        let anonymous_ds = DataSource::from_url(&server.url()).unwrap()
+            .with_basic_authentication("clerk", "wrong");
+        let connector = BasicConnector::new(&MyUserAgent, anonymous_ds, &core.handle()).unwrap();
+        let denied = core.run(connector.connection("shop").execute(ListCollections::new()));
+        match denied {
+            Err(Error::Method(ref error)) => assert_eq!(ErrorCode::HttpUnauthorized, error.error_code()),
+            ref result => panic!("unexpected result: {:?}", result),
+        }
+    }
+}
@@ -13,9 +13,15 @@
     unused_qualifications,
 )]
 
+extern crate base64;
 extern crate dotenv;
+extern crate futures;
+extern crate hyper;
+#[macro_use] extern crate lazy_static;
 extern crate log4rs;
+#[macro_use] extern crate serde_json;
 extern crate tokio_core;
+extern crate url;
 
 extern crate rincon_core;
 extern crate rincon_connector;
@@ -41,6 +47,10 @@ use rincon_client::database::types::NewDatabase;
 use rincon_client::user::methods::RemoveUser;
 use rincon_client::user::types::NewUser;
 
+use fake::FakeServer;
+
+pub mod fake;
+
 pub const ENV_ARANGO_DB_URL: &str = "ARANGO_DB_URL";
 #[allow(dead_code)]
 pub const ENV_ARANGO_ROOT_USERNAME: &str = "ARANGO_ROOT_USERNAME";
@@ -55,6 +65,35 @@ pub const ENV_ARANGO_TEST_PASSWORD: &str = "ARANGO_TEST_PASSWORD";
 
 const LOCK_FILE: &str = "db_test.lock";
 
+lazy_static! {
+    static ref FAKE_SERVER: FakeServer = {
+        let root_password = env::var(ENV_ARANGO_ROOT_PASSWORD).unwrap_or_default();
+        FakeServer::start(&root_password).expect("Could not start fake ArangoDB server")
+    };
+}
+
+/// Returns whether the integration tests run against the embedded fake
+/// server.
+#[allow(dead_code)]
+pub fn is_fake_server() -> bool {
+    cfg!(feature = "fake_server")
+}
+
+/// Returns the URL of the server the integration tests run against.
+///
+/// If the feature `fake_server` is enabled the tests run against an embedded
+/// fake server that is started on first use. Otherwise the URL is taken from
+/// the environment variable `ARANGO_DB_URL`.
+#[allow(dead_code)]
+pub fn db_url() -> String {
+    if is_fake_server() {
+        FAKE_SERVER.url()
+    } else {
+        dotenv().ok();
+        env::var(ENV_ARANGO_DB_URL).unwrap()
+    }
+}
+
 #[allow(dead_code)]
 pub fn root_user() -> (String, String) {
     dotenv().ok();
@@ -71,7 +110,7 @@ pub fn init_logging() {
 #[allow(dead_code)]
 pub fn system_datasource() -> DataSource {
     dotenv().ok();
-    let db_url = env::var(ENV_ARANGO_DB_URL).unwrap();
+    let db_url = db_url();
 
     DataSource::from_url(&db_url).unwrap()
 }
@@ -79,7 +118,7 @@ pub fn system_datasource() -> DataSource {
 #[allow(dead_code)]
 pub fn test_datasource() -> (DataSource, String) {
     dotenv().ok();
-    let db_url = env::var(ENV_ARANGO_DB_URL).unwrap();
+    let db_url = db_url();
     let database = env::var(ENV_ARANGO_TEST_DATABASE).unwrap();
     let username = env::var(ENV_ARANGO_TEST_USERNAME).unwrap();
     let password = env::var(ENV_ARANGO_TEST_PASSWORD).unwrap();
@@ -96,7 +135,7 @@ pub fn arango_session_test<Test, CleanUp>(test: Test, clean_up: CleanUp) -> ()
         CleanUp: FnOnce(BasicConnection, &mut Core) -> (),
 {
     dotenv().ok();
-    let db_url = env::var(ENV_ARANGO_DB_URL).unwrap();
+    let db_url = db_url();
 
     let system_ds = DataSource::from_url(&db_url).unwrap();
 
@@ -120,7 +159,7 @@ pub fn arango_session_test_with_user_db<Test>(user: &str, database: &str, test:
         Test: FnOnce(BasicConnector, Core) -> () + panic::UnwindSafe,
 {
     dotenv().ok();
-    let db_url = env::var(ENV_ARANGO_DB_URL).unwrap();
+    let db_url = db_url();
 
     let mut core = Core::new().unwrap();
 
@@ -150,7 +189,7 @@ pub fn arango_system_db_test<Test, CleanUp>(test: Test, clean_up: CleanUp) -> ()
         CleanUp: FnOnce(BasicConnection, &mut Core) -> (),
 {
     dotenv().ok();
-    let db_url = env::var(ENV_ARANGO_DB_URL).unwrap();
+    let db_url = db_url();
 
     let system_ds = DataSource::from_url(&db_url).unwrap();
 
@@ -176,7 +215,7 @@ pub fn arango_user_db_test<Test, CleanUp>(test: Test, clean_up: CleanUp) -> ()
         CleanUp: FnOnce(BasicConnection, &mut Core) -> (),
 {
     dotenv().ok();
-    let db_url = env::var(ENV_ARANGO_DB_URL).unwrap();
+    let db_url = db_url();
     let database = env::var(ENV_ARANGO_TEST_DATABASE).unwrap();
     let username = env::var(ENV_ARANGO_TEST_USERNAME).unwrap();
     let password = env::var(ENV_ARANGO_TEST_PASSWORD).unwrap();
@@ -208,7 +247,7 @@ pub fn arango_test_with_user_db<Test>(user: &str, database: &str, test: Test) ->
         Test: FnOnce(BasicConnection, &mut Core) -> () + panic::UnwindSafe,
 {
     dotenv().ok();
-    let db_url = env::var(ENV_ARANGO_DB_URL).unwrap();
+    let db_url = db_url();
 
     let mut core = Core::new().unwrap();
 
@@ -238,7 +277,7 @@ pub fn arango_test_with_document_collection<Test>(collection: &str, test: Test)
         Test: FnOnce(BasicConnection, &mut Core) -> () + panic::UnwindSafe,
 {
     dotenv().ok();
-    let db_url = env::var(ENV_ARANGO_DB_URL).unwrap();
+    let db_url = db_url();
     let database = env::var(ENV_ARANGO_TEST_DATABASE).unwrap();
     let username = env::var(ENV_ARANGO_TEST_USERNAME).unwrap();
     let password = env::var(ENV_ARANGO_TEST_PASSWORD).unwrap();
@@ -275,7 +314,7 @@ pub fn arango_test_with_edge_collection<Test>(collection: &str, test: Test) -> (
         Test: FnOnce(BasicConnection, &mut Core) -> () + panic::UnwindSafe,
 {
     dotenv().ok();
-    let db_url = env::var(ENV_ARANGO_DB_URL).unwrap();
+    let db_url = db_url();
     let database = env::var(ENV_ARANGO_TEST_DATABASE).unwrap();
     let username = env::var(ENV_ARANGO_TEST_USERNAME).unwrap();
     let password = env::var(ENV_ARANGO_TEST_PASSWORD).unwrap();